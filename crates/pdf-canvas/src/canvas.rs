@@ -1,4 +1,4 @@
-use pdf_graphics::{PathFillType, pdf_path::PdfPath, transform::Transform};
+use pdf_graphics::{pdf_path::PdfPath, transform::Transform};
 
 use crate::error::PdfCanvasError;
 
@@ -24,11 +24,21 @@ pub(crate) trait Canvas {
     /// This sets the complete transformation from user space to device space.
     fn set_matrix(&mut self, matrix: Transform) -> Result<(), PdfCanvasError>;
 
-    /// Fills the given path with using a currently set color.
+    /// Paints a glyph outline according to the current text rendering mode.
+    ///
+    /// The outline is filled and/or stroked with the current colours, and in the
+    /// clipping modes it is also accumulated into the clip applied at the end of
+    /// the text object.
     ///
     /// # Parameters
     ///
-    /// - `path`: The path to fill. The coordinates are in the backend's device space.
-    /// - `fill_type`: The rule (winding or even-odd) to determine what is "inside" the path.
-    fn fill_path(&mut self, path: &PdfPath, fill_type: PathFillType) -> Result<(), PdfCanvasError>;
+    /// - `path`: The glyph outline. The coordinates are in the backend's device space.
+    fn paint_glyph(&mut self, path: &PdfPath) -> Result<(), PdfCanvasError>;
+
+    /// Marks the current graphics state as running a Type 3 glyph description.
+    ///
+    /// Paths painted until the state is restored follow the text rendering mode:
+    /// they are suppressed in the invisible and clip-only modes, and added to the
+    /// text clip in the clipping modes.
+    fn begin_glyph_description(&mut self) -> Result<(), PdfCanvasError>;
 }
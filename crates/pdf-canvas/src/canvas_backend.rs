@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 
 use pdf_graphics::{
-    BlendMode, ImageEncoding, MaskMode, PathFillType, color::Color, pdf_path::PdfPath,
+    BlendMode, ImageEncoding, MaskMode, PathFillType, StrokeStyle, color::Color, pdf_path::PdfPath,
     transform::Transform,
 };
 
@@ -26,9 +26,9 @@ pub enum Shader<'a> {
         /// The ending y-coordinate of the gradient line.
         y1: f32,
         /// The array of colors to be used in the gradient.
-        colors: &'a [Color],
+        colors: Cow<'a, [Color]>,
         /// The positions of each color stop, specified as values between 0.0 and 1.0.
-        positions: &'a [f32],
+        positions: Cow<'a, [f32]>,
     },
     /// Represents a tiling pattern image shader for filling or stroking paths with a repeated image.
     ///
@@ -58,9 +58,9 @@ pub enum Shader<'a> {
         /// The radius of the end circle.
         end_r: f32,
         /// The array of colors to be used in the gradient.
-        colors: &'a [Color],
+        colors: Cow<'a, [Color]>,
         /// The positions of each color stop, specified as values between 0.0 and 1.0.
-        positions: &'a [f32],
+        positions: Cow<'a, [f32]>,
         /// An optional transformation to apply to the gradient.
         transform: Option<Transform>,
     },
@@ -71,22 +71,37 @@ pub enum Shader<'a> {
 /// The `Image` struct encapsulates raw image data, dimensions, encoding, and optional
 /// transformation or masking information.
 pub struct Image<'a> {
-    /// The raw image data.
+    /// The image data: encoded as given by `encoding`, or uncompressed pixels
+    /// row by row from the top.
     pub data: Cow<'a, [u8]>,
     /// The width of the image in pixels.
     pub width: u32,
     /// The height of the image in pixels.
     pub height: u32,
-    /// The bits per pixel (color depth) of the image.
+    /// The number of bytes per pixel of uncompressed data: 4 for RGBA, not
+    /// premultiplied, or 1 for alpha only. `None` for encoded data.
     pub bytes_per_pixel: Option<u32>,
     /// The image encoding (e.g., "jpeg", "png").
     pub encoding: ImageEncoding,
     /// A transformation matrix to apply to the image.
     pub transform: Transform,
-    /// An optional alpha mask to apply to the image.
+    /// An optional alpha mask to apply to the image, one byte per pixel.
     pub mask: Option<Cow<'a, [u8]>>,
 }
 
+/// How the offscreen layer of a transparency group is set up and composited.
+#[derive(Clone, Copy)]
+pub struct GroupLayer {
+    /// Whether the layer starts transparent rather than from the backdrop.
+    pub isolated: bool,
+    /// Whether objects in the group knock out the ones painted before them.
+    pub knockout: bool,
+    /// The alpha with which the layer is composited onto the backdrop.
+    pub alpha: f32,
+    /// The blend mode with which the layer is composited onto the backdrop.
+    pub blend_mode: Option<BlendMode>,
+}
+
 /// A low-level drawing backend for rendering PDF graphics.
 ///
 /// This trait defines the fundamental drawing operations that a `PdfCanvas` uses
@@ -114,33 +129,35 @@ pub trait CanvasBackend {
         blend_mode: Option<BlendMode>,
     ) -> Result<(), Self::ErrorType>;
 
-    /// Strokes the given path with the specified color and line width.
+    /// Strokes the given path with the specified color and stroke style.
     ///
     /// # Parameters
     ///
     /// - `path`: The path to stroke. The coordinates are in the backend's device space.
     /// - `color`: The color of the stroke.
-    /// - `line_width`: The width of the stroke in device units.
+    /// - `style`: The width, caps, joins, miter limit and dash pattern, in device units.
     /// - `shader`: An optional shader to use for the stroke.
     /// - `blend_mode`: An optional blend mode to use when stroking the path.
     fn stroke_path(
         &mut self,
         path: &PdfPath,
         color: Color,
-        line_width: f32,
+        style: &StrokeStyle,
         shader: &Option<Shader>,
         blend_mode: Option<BlendMode>,
     ) -> Result<(), Self::ErrorType>;
 
-    /// Sets the clipping region by intersecting the current clip path with the given path.
+    /// Intersects the current clipping region with the given path.
     ///
-    /// All subsequent drawing operations will be constrained to this new region.
+    /// All subsequent drawing operations will be constrained to this new region,
+    /// until the matching `pop_clip_region` restores the region in effect before
+    /// this call. Pushes and pops nest like a stack.
     ///
     /// # Parameters
     ///
     /// - `path`: The path to use for clipping.
     /// - `mode`: The fill type to determine the clipping region.
-    fn set_clip_region(
+    fn push_clip_region(
         &mut self,
         path: &PdfPath,
         mode: PathFillType,
@@ -152,8 +169,9 @@ pub trait CanvasBackend {
     /// Returns the height of the canvas in device units.
     fn height(&self) -> f32;
 
-    /// Resets the clipping region to the entire canvas area.
-    fn reset_clip(&mut self) -> Result<(), Self::ErrorType>;
+    /// Restores the clipping region in effect before the most recent
+    /// `push_clip_region` that has not been popped yet.
+    fn pop_clip_region(&mut self) -> Result<(), Self::ErrorType>;
 
     /// Draws an image onto the canvas at the current transformation.
     ///
@@ -187,10 +205,28 @@ pub trait CanvasBackend {
     ///
     /// - `mask`: The mask layer to end and apply.
     /// - `transform`: The transformation to apply to the mask when compositing.
+    /// - `mask_mode`: Whether mask values come from the mask's luminosity or alpha.
+    /// - `transfer`: An optional table of 256 entries mapping each computed mask
+    ///   value to the value applied.
     fn end_mask_layer(
         &mut self,
         mask: &RecordingCanvas,
         transform: &Transform,
         mask_mode: MaskMode,
+        transfer: Option<&[u8]>,
     ) -> Result<(), Self::ErrorType>;
+
+    /// Begins a transparency group.
+    ///
+    /// Subsequent drawing goes into an offscreen layer until the matching
+    /// `end_transparency_group`, which composites the layer onto the backdrop.
+    /// Groups nest, and clip pushes made inside a group are popped before it ends.
+    ///
+    /// # Parameters
+    ///
+    /// - `layer`: How the layer starts and how it is composited.
+    fn begin_transparency_group(&mut self, layer: &GroupLayer) -> Result<(), Self::ErrorType>;
+
+    /// Ends the innermost transparency group, compositing its layer onto the backdrop.
+    fn end_transparency_group(&mut self) -> Result<(), Self::ErrorType>;
 }
@@ -1,20 +1,18 @@
 use pdf_content_stream::pdf_operator_backend::ClippingPathOps;
 use pdf_graphics::PathFillType;
 
-use crate::{error::PdfCanvasError, pdf_canvas::PdfCanvas};
+use crate::pdf_canvas::PdfCanvas;
 
+// `W` and `W*` only mark the current path: it becomes part of the clipping path
+// once the next path-painting operator has painted it (ISO 32000-1, 8.5.4).
 impl<T: std::error::Error> ClippingPathOps for PdfCanvas<'_, T> {
     fn clip_path_nonzero_winding(&mut self) -> Result<(), Self::ErrorType> {
-        let Some(path) = self.current_path.take() else {
-            return Err(PdfCanvasError::NoActivePath);
-        };
-        self.set_clip_path(path, PathFillType::Winding)
+        self.pending_clip = Some(PathFillType::Winding);
+        Ok(())
     }
 
     fn clip_path_even_odd(&mut self) -> Result<(), Self::ErrorType> {
-        let Some(path) = self.current_path.take() else {
-            return Err(PdfCanvasError::NoActivePath);
-        };
-        self.set_clip_path(path, PathFillType::EvenOdd)
+        self.pending_clip = Some(PathFillType::EvenOdd);
+        Ok(())
     }
 }
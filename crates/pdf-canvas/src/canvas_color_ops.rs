@@ -1,21 +1,86 @@
 use pdf_content_stream::pdf_operator_backend::ColorOps;
+use pdf_page::color_space::ColorSpace;
 
 use crate::{error::PdfCanvasError, pdf_canvas::PdfCanvas};
 use pdf_graphics::color::Color;
 
-impl<T: std::error::Error> ColorOps for PdfCanvas<'_, T> {
-    fn set_stroking_color_space(&mut self, _name: &str) -> Result<(), Self::ErrorType> {
-        self.current_state_mut()?.pattern = None;
+impl<'a, T: std::error::Error> PdfCanvas<'a, T> {
+    /// Sets the stroking colour space and the colour given by `components` in it.
+    ///
+    /// The colour keeps the current stroking alpha, which is set separately by `/CA`.
+    fn apply_stroke_color(
+        &mut self,
+        space: &'a ColorSpace,
+        components: &[f32],
+    ) -> Result<(), PdfCanvasError> {
+        let color = space.to_color(components, &self.color_conversion()?)?;
+        let state = self.current_state_mut()?;
+        state.stroke_color = Color {
+            a: state.stroke_color.a,
+            ..color
+        };
+        state.stroke_color_space = space;
+        state.stroke_pattern = None;
         Ok(())
     }
 
-    fn set_non_stroking_color_space(&mut self, _name: &str) -> Result<(), Self::ErrorType> {
-        self.current_state_mut()?.pattern = None;
+    /// Sets the non-stroking colour space and the colour given by `components` in it.
+    ///
+    /// The colour keeps the current non-stroking alpha, which is set separately by `/ca`.
+    fn apply_fill_color(
+        &mut self,
+        space: &'a ColorSpace,
+        components: &[f32],
+    ) -> Result<(), PdfCanvasError> {
+        let color = space.to_color(components, &self.color_conversion()?)?;
+        let state = self.current_state_mut()?;
+        state.fill_color = Color {
+            a: state.fill_color.a,
+            ..color
+        };
+        state.fill_color_space = space;
+        state.fill_pattern = None;
         Ok(())
     }
+}
 
-    fn set_stroking_color(&mut self, _components: &[f32]) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented("set_stroking_color".into()))
+// Colour operators are no-ops inside an uncoloured Type 3 glyph description (after `d1`).
+impl<T: std::error::Error> ColorOps for PdfCanvas<'_, T> {
+    fn set_stroking_color_space(&mut self, name: &str) -> Result<(), Self::ErrorType> {
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.find_color_space(name)?;
+        if let ColorSpace::Pattern { .. } = space {
+            // The initial pattern colour paints nothing until `SCN` selects a pattern.
+            let state = self.current_state_mut()?;
+            state.stroke_color_space = space;
+            state.stroke_pattern = None;
+            return Ok(());
+        }
+        self.apply_stroke_color(space, &space.initial_components())
+    }
+
+    fn set_non_stroking_color_space(&mut self, name: &str) -> Result<(), Self::ErrorType> {
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.find_color_space(name)?;
+        if let ColorSpace::Pattern { .. } = space {
+            let state = self.current_state_mut()?;
+            state.fill_color_space = space;
+            state.fill_pattern = None;
+            return Ok(());
+        }
+        self.apply_fill_color(space, &space.initial_components())
+    }
+
+    fn set_stroking_color(&mut self, components: &[f32]) -> Result<(), Self::ErrorType> {
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.current_state()?.stroke_color_space;
+        self.apply_stroke_color(space, components)
     }
 
     fn set_non_stroking_color_extended(
@@ -23,13 +88,18 @@ impl<T: std::error::Error> ColorOps for PdfCanvas<'_, T> {
         components: &[f32],
         pattern_name: &str,
     ) -> Result<(), Self::ErrorType> {
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let pattern = self.find_pattern(pattern_name)?;
+        // Uncoloured patterns are painted in a colour of the underlying space.
         if !components.is_empty() {
-            return Err(PdfCanvasError::NotImplemented(
-                "set_non_stroking_color_extended with components".into(),
-            ));
+            let space = self.current_state()?.fill_color_space;
+            self.apply_fill_color(space, components)?;
         }
 
-        self.set_pattern(pattern_name)
+        self.current_state_mut()?.fill_pattern = Some(pattern);
+        Ok(())
     }
 
     fn set_stroking_color_extended(
@@ -37,54 +107,65 @@ impl<T: std::error::Error> ColorOps for PdfCanvas<'_, T> {
         components: &[f32],
         pattern_name: &str,
     ) -> Result<(), Self::ErrorType> {
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let pattern = self.find_pattern(pattern_name)?;
         if !components.is_empty() {
-            return Err(PdfCanvasError::NotImplemented(
-                "set_stroking_color_extended with components".into(),
-            ));
+            let space = self.current_state()?.stroke_color_space;
+            self.apply_stroke_color(space, components)?;
         }
 
-        self.set_pattern(pattern_name)
+        self.current_state_mut()?.stroke_pattern = Some(pattern);
+        Ok(())
     }
 
-    fn set_non_stroking_color(&mut self, _components: &[f32]) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented(
-            "set_non_stroking_color".into(),
-        ))
+    fn set_non_stroking_color(&mut self, components: &[f32]) -> Result<(), Self::ErrorType> {
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.current_state()?.fill_color_space;
+        self.apply_fill_color(space, components)
     }
 
     fn set_stroking_gray(&mut self, gray: f32) -> Result<(), Self::ErrorType> {
-        let state = self.current_state_mut()?;
-        state.stroke_color = Color::from_gray(gray);
-        state.pattern = None;
-        Ok(())
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.find_color_space("DeviceGray")?;
+        self.apply_stroke_color(space, &[gray])
     }
 
     fn set_non_stroking_gray(&mut self, gray: f32) -> Result<(), Self::ErrorType> {
-        let state = self.current_state_mut()?;
-        state.fill_color = Color::from_gray(gray);
-        state.pattern = None;
-        Ok(())
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.find_color_space("DeviceGray")?;
+        self.apply_fill_color(space, &[gray])
     }
 
     fn set_stroking_rgb(&mut self, r: f32, g: f32, b: f32) -> Result<(), Self::ErrorType> {
-        let state = self.current_state_mut()?;
-        state.stroke_color = Color::from_rgb(r, g, b);
-        state.pattern = None;
-        Ok(())
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.find_color_space("DeviceRGB")?;
+        self.apply_stroke_color(space, &[r, g, b])
     }
 
     fn set_non_stroking_rgb(&mut self, r: f32, g: f32, b: f32) -> Result<(), Self::ErrorType> {
-        let state = self.current_state_mut()?;
-        state.fill_color = Color::from_rgb(r, g, b);
-        state.pattern = None;
-        Ok(())
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.find_color_space("DeviceRGB")?;
+        self.apply_fill_color(space, &[r, g, b])
     }
 
     fn set_stroking_cmyk(&mut self, c: f32, m: f32, y: f32, k: f32) -> Result<(), Self::ErrorType> {
-        let state = self.current_state_mut()?;
-        state.stroke_color = Color::from_cmyk(c, m, y, k);
-        state.pattern = None;
-        Ok(())
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.find_color_space("DeviceCMYK")?;
+        self.apply_stroke_color(space, &[c, m, y, k])
     }
 
     fn set_non_stroking_cmyk(
@@ -94,9 +175,10 @@ impl<T: std::error::Error> ColorOps for PdfCanvas<'_, T> {
         y: f32,
         k: f32,
     ) -> Result<(), Self::ErrorType> {
-        let state = self.current_state_mut()?;
-        state.fill_color = Color::from_cmyk(c, m, y, k);
-        state.pattern = None;
-        Ok(())
+        if self.current_state()?.ignore_color_ops {
+            return Ok(());
+        }
+        let space = self.find_color_space("DeviceCMYK")?;
+        self.apply_fill_color(space, &[c, m, y, k])
     }
 }
@@ -1,60 +1,244 @@
 use std::borrow::Cow;
 
+use num_traits::{FromPrimitive, ToPrimitive};
 use pdf_content_stream::pdf_operator_backend::XObjectOps;
-use pdf_graphics::ImageEncoding;
-use pdf_page::{image::ImageFilter, xobject::XObject};
+use pdf_graphics::{
+    ImageEncoding, MaskMode, PaintMode, PathFillType, pdf_path::PdfPath, transform::Transform,
+};
+use pdf_object::{ObjectVariant, dictionary::Dictionary};
+use pdf_page::{
+    color_space::ColorSpace,
+    image::{ImageFilter, ImageMask, ImageXObject},
+    xobject::XObject,
+};
 
-use crate::{canvas_backend::Image, error::PdfCanvasError, pdf_canvas::PdfCanvas};
+use crate::{
+    canvas_backend::{CanvasBackend, Image},
+    error::PdfCanvasError,
+    pdf_canvas::PdfCanvas,
+    recording_canvas::RecordingCanvas,
+};
 
 impl<T: std::error::Error> XObjectOps for PdfCanvas<'_, T> {
     fn invoke_xobject(&mut self, xobject_name: &str) -> Result<(), Self::ErrorType> {
         let resources = self.get_resources()?;
 
-        if let Some(XObject::Image(image)) = resources.xobjects.get(xobject_name) {
-            let mask = image
-                .smask
-                .as_ref()
-                .map(|m| Cow::Borrowed(m.data.as_slice()));
+        // XObjects in hidden layers are skipped, along with their side effects.
+        match resources.xobjects.get(xobject_name) {
+            _ if self.is_content_hidden() => {}
+            Some(XObject::Image(image)) => {
+                if !self.is_optional_content_hidden(image.optional_content.as_ref()) {
+                    self.draw_image_xobject(image, None)?;
+                }
+            }
+            Some(XObject::Form(form)) => {
+                if !self.is_optional_content_hidden(form.optional_content.as_ref()) {
+                    self.render_form(form)?;
+                }
+            }
+            None => return Err(PdfCanvasError::XObjectNotFound(xobject_name.to_string())),
+        }
+        Ok(())
+    }
+
+    fn draw_inline_image(
+        &mut self,
+        dictionary: &Dictionary,
+        data: &[u8],
+    ) -> Result<(), Self::ErrorType> {
+        // An inline image's colour space may name a `/ColorSpace` resource, which
+        // the image itself cannot resolve.
+        let named_space = match dictionary.get("ColorSpace") {
+            Some(ObjectVariant::Name(name)) => Some(name.as_str()),
+            _ => None,
+        };
+        let image = match named_space {
+            Some(_) => {
+                let mut dictionary = dictionary.clone();
+                dictionary.dictionary.remove("ColorSpace");
+                ImageXObject::from_inline(&dictionary, data)?
+            }
+            None => ImageXObject::from_inline(dictionary, data)?,
+        };
+        let color_space = match named_space {
+            Some(name) if !image.image_mask => Some(self.find_color_space(name)?),
+            _ => None,
+        };
+        self.draw_image_xobject(&image, color_space)
+    }
+}
+
+/// Returns what the backend's JPEG decoding would get wrong about an image, if
+/// anything. The backend decodes the data as gray or RGB, without the image's
+/// `/Decode` array, colour key mask or colour space conversion; the profile of an
+/// ICC-based gray or RGB space is not applied.
+fn unsupported_jpeg_feature(
+    image: &ImageXObject,
+    color_space: Option<&ColorSpace>,
+) -> Option<&'static str> {
+    let inverted = image
+        .decode
+        .as_deref()
+        .is_some_and(|decode| decode.chunks_exact(2).any(|pair| pair != [0.0, 1.0]));
+    if inverted {
+        return Some("a /Decode array");
+    }
+    if matches!(image.mask, Some(ImageMask::ColorKey(_))) {
+        return Some("a colour key mask");
+    }
+    match color_space {
+        Some(ColorSpace::DeviceGray | ColorSpace::DeviceRGB) | None => None,
+        Some(ColorSpace::IccBased {
+            components: 1 | 3, ..
+        }) => None,
+        Some(ColorSpace::DeviceCMYK) => Some("a CMYK colour space"),
+        Some(ColorSpace::IccBased { .. }) => Some("a CMYK ICC-based colour space"),
+        Some(_) => Some("a colour space other than gray or RGB"),
+    }
+}
 
-            let transform = self.current_state()?.transform;
+impl<T: std::error::Error> PdfCanvas<'_, T> {
+    /// Draws an image XObject into the unit square of the current user space.
+    ///
+    /// `color_space` overrides the image's own colour space, for inline images
+    /// whose colour space is a named resource.
+    pub(crate) fn draw_image_xobject(
+        &mut self,
+        image: &ImageXObject,
+        color_space: Option<&ColorSpace>,
+    ) -> Result<(), PdfCanvasError> {
+        if self.is_content_hidden() {
+            return Ok(());
+        }
+        if image.image_mask {
+            return self.draw_stencil_mask(image);
+        }
+        let transform = self.current_state()?.transform;
 
-            // Determine image encoding based on the filter applied.
-            // Currently, only DCTDecode (JPEG) is supported.
-            let encoding = match &image.filter {
-                Some(ImageFilter::DCTDecode) => ImageEncoding::Jpeg,
-                Some(ImageFilter::FlateDecode) => ImageEncoding::Uncompressed,
-                Some(ImageFilter::Unsupported(other)) => {
+        // JPEG data is decoded by the backend, so only the alpha is decoded here.
+        // Other images are decoded to RGBA with their alpha.
+        let (data, encoding, bytes_per_pixel, mask) = match &image.filter {
+            Some(ImageFilter::DCTDecode) => {
+                if let Some(feature) =
+                    unsupported_jpeg_feature(image, color_space.or(image.color_space.as_ref()))
+                {
                     return Err(PdfCanvasError::NotImplemented(format!(
-                        "{} image filter",
-                        other
+                        "DCTDecode image with {feature}"
                     )));
                 }
-                None => ImageEncoding::Uncompressed,
-            };
+                (
+                    Cow::Borrowed(image.data.as_slice()),
+                    ImageEncoding::Jpeg,
+                    None,
+                    image.decode_alpha()?.map(Cow::Owned),
+                )
+            }
+            Some(ImageFilter::Unsupported(other)) => {
+                return Err(PdfCanvasError::NotImplemented(format!(
+                    "{} image filter",
+                    other
+                )));
+            }
+            Some(ImageFilter::FlateDecode) | None => {
+                let conversion = self.color_conversion()?;
+                let rgba = match color_space {
+                    Some(space) => image.decode_rgba_in(space, &conversion)?,
+                    None => image.decode_rgba(&conversion)?,
+                };
+                (Cow::Owned(rgba), ImageEncoding::Uncompressed, Some(4), None)
+            }
+        };
+
+        let image = Image {
+            data,
+            width: image.width,
+            height: image.height,
+            bytes_per_pixel,
+            encoding,
+            transform,
+            mask,
+        };
+
+        let blend_mode = self.shape_blend_mode()?;
+        self.canvas
+            .draw_image(&image, blend_mode)
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        Ok(())
+    }
 
+    /// Paints the current fill colour or pattern through a stencil image mask
+    /// covering the unit square of the current user space.
+    fn draw_stencil_mask(&mut self, image: &ImageXObject) -> Result<(), PdfCanvasError> {
+        let stencil = image.decode_stencil()?;
+        let blend_mode = self.shape_blend_mode()?;
+        let state = self.current_state()?;
+        let transform = state.transform;
+
+        if state.fill_pattern.is_none() {
+            // A solid colour is drawn as an image of that colour with the stencil
+            // as its alpha.
+            let color = state.fill_color;
+            let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round().to_u8().unwrap_or(0);
+            let [r, g, b] = [color.r, color.g, color.b].map(channel);
+            let rgba: Vec<u8> = stencil
+                .iter()
+                .flat_map(|&painted| [r, g, b, channel(f32::from(painted) / 255.0 * color.a)])
+                .collect();
             let image = Image {
-                data: Cow::Borrowed(image.data.as_slice()),
+                data: Cow::Owned(rgba),
                 width: image.width,
                 height: image.height,
-                bytes_per_pixel: Some(image.bits_per_component),
-                encoding,
+                bytes_per_pixel: Some(4),
+                encoding: ImageEncoding::Uncompressed,
                 transform,
-                mask,
+                mask: None,
             };
-
-            let blend_mode = self.current_state()?.blend_mode;
-            self.canvas
+            return self
+                .canvas
                 .draw_image(&image, blend_mode)
-                .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
-        } else if let Some(XObject::Form(form)) = resources.xobjects.get(xobject_name) {
-            self.render_content_stream(
-                &form.content_stream.operations,
-                form.matrix,
-                form.resources.as_ref(),
-            )?;
-        } else {
-            return Err(PdfCanvasError::XObjectNotFound(xobject_name.to_string()));
+                .map_err(|e| PdfCanvasError::BackendError(e.to_string()));
         }
-        Ok(())
+
+        // A pattern fills the unit square through the stencil, applied as an alpha
+        // mask with one mask pixel per sample.
+        let width = f32::from_u32(image.width)
+            .ok_or(PdfCanvasError::NumericConversionError("u32 to f32 width"))?;
+        let height = f32::from_u32(image.height)
+            .ok_or(PdfCanvasError::NumericConversionError("u32 to f32 height"))?;
+        let mut content = RecordingCanvas::new(width, height);
+        let stencil = Image {
+            data: Cow::Owned(stencil),
+            width: image.width,
+            height: image.height,
+            bytes_per_pixel: Some(1),
+            encoding: ImageEncoding::Uncompressed,
+            transform: Transform::from_row(width, 0.0, 0.0, -height, 0.0, height),
+            mask: None,
+        };
+        content
+            .draw_image(&stencil, None)
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        let mut mask_transform =
+            Transform::from_row(width.recip(), 0.0, 0.0, height.recip(), 0.0, 0.0);
+        mask_transform.concat(&transform);
+
+        let mut square = PdfPath::default();
+        square.move_to(0.0, 0.0);
+        square.line_to(1.0, 0.0);
+        square.line_to(1.0, 1.0);
+        square.line_to(0.0, 1.0);
+        square.close();
+        square.transform(&transform);
+
+        self.canvas
+            .begin_mask_layer(&content, &mask_transform, MaskMode::Alpha)
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        // The mask layer is ended even if the fill fails, so the backend's layers
+        // stay balanced.
+        let drawn = self.draw_path(&square, PaintMode::Fill, PathFillType::Winding);
+        self.canvas
+            .end_mask_layer(&content, &mask_transform, MaskMode::Alpha, None)
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        drawn
     }
 }
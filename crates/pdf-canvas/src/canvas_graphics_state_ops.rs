@@ -1,9 +1,23 @@
+use num_traits::{FromPrimitive, ToPrimitive};
 use pdf_content_stream::pdf_operator_backend::GraphicsStateOps;
-use pdf_graphics::{LineCap, LineJoin, transform::Transform};
-use pdf_page::{external_graphics_state::ExternalGraphicsStateKey, xobject::XObject};
+use pdf_graphics::{
+    LineCap, LineJoin, MaskMode, PathFillType, color::Color, color_management::RenderingIntent,
+    pdf_path::PdfPath, transform::Transform,
+};
+use pdf_page::{
+    color_space::ColorSpace,
+    external_graphics_state::{ExternalGraphicsStateKey, SoftMask},
+    form::FormXObject,
+    xobject::XObject,
+};
 
 use crate::{
-    canvas::Canvas, error::PdfCanvasError, pdf_canvas::PdfCanvas, recording_canvas::RecordingCanvas,
+    canvas::Canvas,
+    canvas_backend::CanvasBackend,
+    canvas_state::{LiftedLayer, SoftMaskLayer, StateLayer},
+    error::PdfCanvasError,
+    pdf_canvas::PdfCanvas,
+    recording_canvas::RecordingCanvas,
 };
 
 impl<T: std::error::Error> GraphicsStateOps for PdfCanvas<'_, T> {
@@ -12,6 +26,10 @@ impl<T: std::error::Error> GraphicsStateOps for PdfCanvas<'_, T> {
     }
 
     fn restore_graphics_state(&mut self) -> Result<(), Self::ErrorType> {
+        // A `Q` without a matching `q` in the same content stream is ignored.
+        if self.canvas_stack.len() <= self.stream_base {
+            return Ok(());
+        }
         self.restore()
     }
 
@@ -63,16 +81,18 @@ impl<T: std::error::Error> GraphicsStateOps for PdfCanvas<'_, T> {
 
     fn set_dash_pattern(
         &mut self,
-        _dash_array: &[f32],
-        _dash_phase: f32,
+        dash_array: &[f32],
+        dash_phase: f32,
     ) -> Result<(), Self::ErrorType> {
+        let state = self.current_state_mut()?;
+        state.dash_array = dash_array.to_vec();
+        state.dash_phase = dash_phase;
         Ok(())
     }
 
-    fn set_rendering_intent(&mut self, _intent: &str) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented(
-            "set_rendering_intent".into(),
-        ))
+    fn set_rendering_intent(&mut self, intent: &str) -> Result<(), Self::ErrorType> {
+        self.current_state_mut()?.rendering_intent = RenderingIntent::from_name(intent);
+        Ok(())
     }
 
     fn set_flatness_tolerance(&mut self, _tolerance: f32) -> Result<(), Self::ErrorType> {
@@ -101,15 +121,11 @@ impl<T: std::error::Error> GraphicsStateOps for PdfCanvas<'_, T> {
                 ExternalGraphicsStateKey::MiterLimit(miter) => {
                     self.current_state_mut()?.miter_limit = *miter;
                 }
-                ExternalGraphicsStateKey::DashPattern(..) => {
-                    return Err(PdfCanvasError::NotImplemented(
-                        "ExtGState: DashPattern".into(),
-                    ));
+                ExternalGraphicsStateKey::DashPattern(dash_array, dash_phase) => {
+                    self.set_dash_pattern(dash_array, *dash_phase)?;
                 }
-                ExternalGraphicsStateKey::RenderingIntent(_) => {
-                    return Err(PdfCanvasError::NotImplemented(
-                        "ExtGState: RenderingIntent".into(),
-                    ));
+                ExternalGraphicsStateKey::RenderingIntent(intent) => {
+                    self.set_rendering_intent(intent)?;
                 }
                 ExternalGraphicsStateKey::OverprintStroke(_) => {}
                 ExternalGraphicsStateKey::OverprintFill(_) => {}
@@ -131,54 +147,7 @@ impl<T: std::error::Error> GraphicsStateOps for PdfCanvas<'_, T> {
                     }
                 }
                 ExternalGraphicsStateKey::SoftMask(smask) => {
-                    // Handle the `/SMask` entry from an `ExtGState` dictionary.
-                    if let Some(smask) = smask {
-                        if let XObject::Image(_) = &smask.shape {
-                            return Err(PdfCanvasError::NotImplemented(
-                                "SoftMask with Image shape".into(),
-                            ));
-                        } else if let XObject::Form(form) = &smask.shape {
-                            // The soft mask is defined by a Form XObject.
-                            // We need to render this form's content into a separate mask surface.
-
-                            // Create a recording canvas to act as the mask layer.
-                            let mut recording_canvas = RecordingCanvas::new(
-                                form.bbox[2] - form.bbox[0],
-                                form.bbox[3] - form.bbox[1],
-                            );
-
-                            // Create a temporary `PdfCanvas` that draws into our new mask surface.
-                            // This allows us to reuse the rendering logic for the form's content stream.
-                            let mut other =
-                                PdfCanvas::new(&mut recording_canvas, self.page, Some(&form.bbox))?;
-
-                            // 3. Render the form's content stream into the mask canvas.
-                            other.render_content_stream(
-                                &form.content_stream.operations,
-                                form.matrix,
-                                form.resources.as_ref(),
-                            )?;
-
-                            let transform = self.current_state()?.transform;
-                            // 4. Enable the mask on the main canvas. Subsequent drawing operations
-                            // will be modulated by this mask.
-                            self.canvas
-                                .begin_mask_layer(&recording_canvas, &transform, smask.mask_type)
-                                .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
-
-                            // 5. Store the mask in the current canvas state to be used until it's finished.
-                            self.mask = Some((Box::new(recording_canvas), smask.mask_type));
-                        }
-                    } else if let Some((mask, mask_type)) = self.mask.take() {
-                        // This branch handles the case where `/SMask` is set to `/None` in the `ExtGState`,
-                        // which signals the end of the current soft mask application.
-                        let transform = self.current_state()?.transform;
-                        // Finalize the masking operation on the backend, which typically involves
-                        // compositing the masked content.
-                        self.canvas
-                            .end_mask_layer(mask.as_ref(), &transform, mask_type)
-                            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
-                    }
+                    self.set_soft_mask(smask.as_deref())?;
                 }
                 ExternalGraphicsStateKey::StrokingAlpha(alpha) => {
                     self.current_state_mut()?.stroke_color.a = *alpha
@@ -192,3 +161,163 @@ impl<T: std::error::Error> GraphicsStateOps for PdfCanvas<'_, T> {
         Ok(())
     }
 }
+
+impl<T: std::error::Error> PdfCanvas<'_, T> {
+    /// Sets the soft mask of the current graphics state, or removes it for
+    /// `/SMask /None`.
+    ///
+    /// The mask stays in effect until the state it was set in is restored, or until
+    /// another `gs` replaces it. A mask inherited from an enclosing state is lifted
+    /// until the current state is restored, when it is pushed again with the
+    /// enclosing states' clips that were taken down to reach it.
+    fn set_soft_mask(&mut self, smask: Option<&SoftMask>) -> Result<(), PdfCanvasError> {
+        // The state owning each layer, as the states push their layers in order.
+        let owners: Vec<usize> = self
+            .canvas_stack
+            .iter()
+            .enumerate()
+            .flat_map(|(index, state)| std::iter::repeat_n(index, state.layer_depth))
+            .collect();
+        let current = self.canvas_stack.len().saturating_sub(1);
+        let mask_index = self
+            .layers
+            .iter()
+            .rposition(|layer| matches!(layer, StateLayer::SoftMask(_)))
+            .filter(|_| owners.len() == self.layers.len());
+
+        // The layers from the mask up are popped so that the mask can be applied,
+        // then the clips among them are pushed again inside the new one.
+        let mut clips = Vec::new();
+        while let Some(mask_index) = mask_index
+            && self.layers.len() > mask_index
+        {
+            let owner = owners.get(self.layers.len().saturating_sub(1)).copied();
+            let Some(layer) = self.pop_layer()? else {
+                break;
+            };
+            if let Some(state) = owner.and_then(|owner| self.canvas_stack.get_mut(owner)) {
+                state.layer_depth = state.layer_depth.saturating_sub(1);
+            }
+            if let StateLayer::Clip { path, mode } = &layer {
+                clips.push((path.clone(), *mode));
+            }
+            if let Some(owner) = owner.filter(|&owner| owner != current) {
+                self.lifted_layers.push(LiftedLayer {
+                    lifted_by: current,
+                    owner,
+                    layer,
+                });
+            }
+        }
+
+        if let Some(smask) = smask {
+            let mask = self.render_soft_mask(smask)?;
+            self.canvas
+                .begin_mask_layer(&mask.content, &mask.transform, mask.mode)
+                .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+            self.push_layer(StateLayer::SoftMask(mask))?;
+        }
+
+        for (path, mode) in clips.into_iter().rev() {
+            self.set_device_clip_path(path, mode)?;
+        }
+        Ok(())
+    }
+
+    /// Renders the content of a soft mask into a recording, positioned by the
+    /// current transformation matrix.
+    ///
+    /// A luminosity mask's group is composited over its backdrop colour `/BC`, so
+    /// that areas it leaves unpainted take the backdrop's luminosity.
+    fn render_soft_mask(&mut self, smask: &SoftMask) -> Result<SoftMaskLayer, PdfCanvasError> {
+        let ctm = self.current_state()?.transform;
+        let (content, transform) = match &smask.shape {
+            XObject::Form(form) => {
+                let width = form.bbox[2] - form.bbox[0];
+                let height = form.bbox[3] - form.bbox[1];
+                let mut content = RecordingCanvas::new(width, height);
+                if smask.mask_type == MaskMode::Luminosity {
+                    let backdrop = self.soft_mask_backdrop(smask, form)?;
+                    let mut area = PdfPath::default();
+                    area.move_to(0.0, 0.0);
+                    area.line_to(width, 0.0);
+                    area.line_to(width, height);
+                    area.line_to(0.0, height);
+                    area.close();
+                    content
+                        .fill_path(&area, PathFillType::Winding, backdrop, &None, None)
+                        .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+                }
+
+                // Draw the form into the mask surface with a canvas of its own, which
+                // reuses the rendering logic for the form's content stream.
+                PdfCanvas::new(&mut content, self.page, Some(&form.bbox))?
+                    .with_color_management(self.color_management, self.output_intent)
+                    .with_optional_content(self.optional_content)
+                    .render_form(form)?;
+                (content, ctm)
+            }
+            XObject::Image(image) => {
+                // `/G` should be a transparency group, but an image is taken as a
+                // group that paints it into the unit square, one mask pixel per sample.
+                let width = f32::from_u32(image.width)
+                    .ok_or(PdfCanvasError::NumericConversionError("u32 to f32 width"))?;
+                let height = f32::from_u32(image.height)
+                    .ok_or(PdfCanvasError::NumericConversionError("u32 to f32 height"))?;
+                let mut content = RecordingCanvas::new(width, height);
+                PdfCanvas::new(&mut content, self.page, Some(&[0.0, 0.0, 1.0, 1.0]))?
+                    .with_color_management(self.color_management, self.output_intent)
+                    .with_optional_content(self.optional_content)
+                    .draw_image_xobject(image, None)?;
+
+                let mut transform =
+                    Transform::from_row(width.recip(), 0.0, 0.0, height.recip(), 0.0, 0.0);
+                transform.concat(&ctm);
+                (content, transform)
+            }
+        };
+
+        let transfer = smask
+            .transfer
+            .as_ref()
+            .map(|function| {
+                (0..=u8::MAX)
+                    .map(|value| {
+                        let x = f32::from(value) / 255.0;
+                        let y = function.interpolate(&[x])?.first().copied().unwrap_or(x);
+                        Ok((y.clamp(0.0, 1.0) * 255.0).round().to_u8().unwrap_or(value))
+                    })
+                    .collect::<Result<Vec<u8>, PdfCanvasError>>()
+            })
+            .transpose()?;
+
+        Ok(SoftMaskLayer {
+            content: Box::new(content),
+            transform,
+            mode: smask.mask_type,
+            transfer,
+        })
+    }
+
+    /// Returns the backdrop colour of a luminosity mask: `/BC` in the group's
+    /// colour space, or black.
+    fn soft_mask_backdrop(
+        &self,
+        smask: &SoftMask,
+        form: &FormXObject,
+    ) -> Result<Color, PdfCanvasError> {
+        let Some(components) = &smask.backdrop else {
+            return Ok(Color::from_rgb(0.0, 0.0, 0.0));
+        };
+        let family = match components.len() {
+            3 => "DeviceRGB",
+            4 => "DeviceCMYK",
+            _ => "DeviceGray",
+        };
+        let color_space = match form.group.as_ref().and_then(|g| g.color_space.as_ref()) {
+            Some(color_space) => color_space,
+            None => ColorSpace::from_family_name(family).unwrap_or_else(ColorSpace::device_gray),
+        };
+        Ok(color_space.to_color(components, &self.color_conversion()?)?)
+    }
+}
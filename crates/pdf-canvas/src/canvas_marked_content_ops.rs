@@ -1,7 +1,6 @@
-use std::rc::Rc;
-
-use pdf_content_stream::pdf_operator_backend::MarkedContentOps;
-use pdf_object::dictionary::Dictionary;
+use pdf_content_stream::{
+    marked_content_operators::PropertyList, pdf_operator_backend::MarkedContentOps,
+};
 
 use crate::pdf_canvas::PdfCanvas;
 
@@ -19,18 +18,33 @@ impl<T: std::error::Error> MarkedContentOps for PdfCanvas<'_, T> {
     }
 
     fn begin_marked_content(&mut self, _tag: &str) -> Result<(), Self::ErrorType> {
+        self.marked_content.push(false);
         Ok(())
     }
 
     fn begin_marked_content_with_properties(
         &mut self,
-        _tag: &str,
-        _properties: &Rc<Dictionary>,
+        tag: &str,
+        properties: &PropertyList,
     ) -> Result<(), Self::ErrorType> {
+        // Optional content is named in the `/Properties` resources; other property
+        // lists do not affect rendering.
+        let hidden = match properties {
+            PropertyList::Named(name) if tag == "OC" => {
+                let content = self
+                    .get_resources()
+                    .ok()
+                    .and_then(|resources| resources.optional_content.get(name));
+                self.is_optional_content_hidden(content)
+            }
+            _ => false,
+        };
+        self.marked_content.push(hidden);
         Ok(())
     }
 
     fn end_marked_content(&mut self) -> Result<(), Self::ErrorType> {
+        self.marked_content.pop();
         Ok(())
     }
 }
@@ -112,11 +112,10 @@ impl<T: std::error::Error> PathPaintingOps for PdfCanvas<'_, T> {
     }
 
     fn end_path_no_op(&mut self) -> Result<(), Self::ErrorType> {
-        self.current_path.take();
-        if self.current_state_mut()?.clip_path.take().is_some() {
-            self.canvas
-                .reset_clip()
-                .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        let path = self.current_path.take();
+        if let (Some(mut path), Some(mode)) = (path, self.pending_clip.take()) {
+            path.transform(&self.current_state()?.transform);
+            self.set_device_clip_path(path, mode)?;
         }
         Ok(())
     }
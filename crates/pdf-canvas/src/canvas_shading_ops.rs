@@ -0,0 +1,922 @@
+use std::f32::consts::TAU;
+
+use num_traits::ToPrimitive;
+use pdf_content_stream::pdf_operator_backend::ShadingOps;
+use pdf_graphics::{
+    BlendMode, PathFillType, color::Color, pdf_path::PdfPath, transform::Transform,
+};
+use pdf_page::{
+    color_space::ColorSpace,
+    function::{Function, FunctionInterpolationError},
+    mesh::{MeshVertex, Patch},
+    shading::Shading,
+};
+
+use crate::{error::PdfCanvasError, pdf_canvas::PdfCanvas};
+
+/// The approximate width of a band of an axial or radial shading, in device pixels.
+const GRADIENT_STEP_SIZE: f32 = 2.0;
+/// The most bands an axial or radial shading is divided into.
+const MAX_GRADIENT_STEPS: u16 = 256;
+/// The approximate size of a cell of a function-based shading, in device pixels.
+const FUNCTION_CELL_SIZE: f32 = 4.0;
+/// The most cells along each side of a function-based shading's domain.
+const MAX_FUNCTION_CELLS: u16 = 128;
+/// The number of points approximating each circle of a radial shading.
+const CIRCLE_SEGMENTS: u16 = 64;
+/// The approximate size of a cell of a patch, in device pixels.
+const PATCH_CELL_SIZE: f32 = 4.0;
+/// The most cells along each side of a patch.
+const MAX_PATCH_CELLS: u16 = 64;
+/// Gouraud-shaded triangles are split until their edges are at most this long, in
+/// device pixels, or their corners differ by at most `GOURAUD_COLOR_TOLERANCE`.
+const GOURAUD_EDGE_SIZE: f32 = 2.0;
+/// The largest difference of a colour channel across a triangle painted in one colour.
+const GOURAUD_COLOR_TOLERANCE: f32 = 2.0 / 255.0;
+/// The most times a Gouraud-shaded triangle is split in four.
+const MAX_GOURAUD_DEPTH: u8 = 7;
+/// How far the corners of a mesh triangle are pushed outwards, in device pixels,
+/// so that neighbouring triangles overlap.
+const TRIANGLE_OVERLAP: f32 = 0.35;
+/// The number of samples along each dimension of a shading's domain averaged into
+/// the colour that stands in for the shading.
+const AVERAGE_SAMPLES: u16 = 8;
+/// The furthest an extended radial shading is followed, in multiples of the
+/// distance between its circles.
+const MAX_EXTENSION: f32 = 1.0e4;
+
+/// A circle of a radial shading: centre x, centre y and radius.
+type Circle = (f32, f32, f32);
+
+/// Returns how many steps of about `step_size` cover `device_length`, between 1 and `max`.
+fn step_count(device_length: f32, step_size: f32, max: u16) -> u16 {
+    (device_length / step_size)
+        .ceil()
+        .to_u16()
+        .unwrap_or(max)
+        .clamp(1, max)
+}
+
+/// Returns the device-space length of the vector `(dx, dy)` under `transform`.
+fn device_length(transform: &Transform, dx: f32, dy: f32) -> f32 {
+    let x = transform.sx * dx + transform.kx * dy;
+    let y = transform.ky * dx + transform.sy * dy;
+    x.hypot(y)
+}
+
+/// Returns the smallest and the largest of `values`.
+fn min_max(values: impl Iterator<Item = f32>) -> (f32, f32) {
+    values.fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    })
+}
+
+/// Builds a closed polygon and maps it to device space.
+fn polygon(points: &[(f32, f32)], transform: &Transform) -> PdfPath {
+    let mut path = PdfPath::default();
+    add_polygon(&mut path, points);
+    path.transform(transform);
+    path
+}
+
+/// Adds `points` to `path` as a closed subpath.
+fn add_polygon(path: &mut PdfPath, points: &[(f32, f32)]) {
+    if let Some((&(x, y), rest)) = points.split_first() {
+        path.move_to(x, y);
+        for &(x, y) in rest {
+            path.line_to(x, y);
+        }
+        path.close();
+    }
+}
+
+/// Returns the convex hull of `points` in counter-clockwise order.
+fn convex_hull(mut points: Vec<(f32, f32)>) -> Vec<(f32, f32)> {
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    let cross = |o: (f32, f32), a: (f32, f32), b: (f32, f32)| {
+        (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+    };
+
+    // Andrew's monotone chain: the lower hull left to right, then the upper hull
+    // right to left. Each chain ends where the next one starts.
+    let mut hull: Vec<(f32, f32)> = Vec::new();
+    for chain in [
+        points.iter().collect::<Vec<_>>(),
+        points.iter().rev().collect(),
+    ] {
+        let start = hull.len();
+        for &p in chain {
+            while hull.len().saturating_sub(start) >= 2
+                && matches!(hull.as_slice(), [.., o, a] if cross(*o, *a, p) <= 0.0)
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop();
+    }
+    hull
+}
+
+/// Returns the points of a polygon approximating a circle, in counter-clockwise
+/// order. A negative radius gives a single point at the centre.
+fn circle_points((cx, cy, r): Circle) -> Vec<(f32, f32)> {
+    let r = r.max(0.0);
+    (0..CIRCLE_SEGMENTS)
+        .map(|i| {
+            let angle = f32::from(i) * TAU / f32::from(CIRCLE_SEGMENTS);
+            (cx + r * angle.cos(), cy + r * angle.sin())
+        })
+        .collect()
+}
+
+/// Returns the intersection of two convex polygons given in counter-clockwise
+/// order, by clipping `subject` against each edge of `clip` (Sutherland-Hodgman).
+fn convex_intersection(subject: Vec<(f32, f32)>, clip: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let edges = clip.iter().zip(clip.iter().cycle().skip(1));
+    edges.fold(subject, |points, (&a, &b)| {
+        // Positive on the left of the edge, which is the inside.
+        let side = |p: (f32, f32)| (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+        let mut clipped = Vec::with_capacity(points.len());
+        for (&p, &q) in points.iter().zip(points.iter().cycle().skip(1)) {
+            let (sp, sq) = (side(p), side(q));
+            if sp >= 0.0 {
+                clipped.push(p);
+            }
+            if (sp >= 0.0) != (sq >= 0.0) {
+                let t = sp / (sp - sq);
+                clipped.push((p.0 + t * (q.0 - p.0), p.1 + t * (q.1 - p.1)));
+            }
+        }
+        clipped
+    })
+}
+
+/// Builds the area swept by the outline of a circle moving linearly from `a` to `b`,
+/// mapped to device space, to be filled with the even-odd rule.
+///
+/// The area is the convex hull of the two circles less the part inside both, so
+/// the interior of a growing or shrinking circle is left for the other bands.
+fn ring(a: Circle, b: Circle, transform: &Transform) -> PdfPath {
+    // A circle shrunk to a point has no inside to leave out.
+    let has_inside = a.2 > 0.0 && b.2 > 0.0;
+    let (a, b) = (circle_points(a), circle_points(b));
+    let lens = if has_inside {
+        convex_intersection(a.clone(), &b)
+    } else {
+        Vec::new()
+    };
+    let mut path = PdfPath::default();
+    add_polygon(&mut path, &convex_hull([a, b].concat()));
+    if lens.len() >= 3 {
+        add_polygon(&mut path, &lens);
+    }
+    path.transform(transform);
+    path
+}
+
+/// Returns how far, in units of `t`, a radial shading extended beyond the circle
+/// `end` must be followed to cover `corners`. `other` is the circle at the opposite
+/// end of the shading, so the extension moves away from it.
+fn extension_length(end: Circle, other: Circle, corners: &[(f32, f32); 4]) -> f32 {
+    let (cx, cy, r) = end;
+    let center_step = (cx - other.0).hypot(cy - other.1);
+    let radius_step = r - other.2;
+    let length = if radius_step < 0.0 {
+        // The circles shrink until their radius reaches zero.
+        r / -radius_step
+    } else {
+        let reach = corners
+            .iter()
+            .map(|&(x, y)| (x - cx).hypot(y - cy))
+            .fold(0.0, f32::max);
+        if radius_step > center_step {
+            // The circles grow faster than they move, until one contains the corners.
+            (reach - r).max(0.0) / (radius_step - center_step)
+        } else if center_step > 0.0 {
+            // The circles sweep a cone or a cylinder; follow it past the corners.
+            2.0 * (reach + r) / center_step
+        } else {
+            0.0
+        }
+    };
+    length.clamp(0.0, MAX_EXTENSION)
+}
+
+/// A vertex of a Gouraud-shaded triangle in device space, with its colour values and
+/// the colour they give.
+#[derive(Clone)]
+struct DeviceVertex {
+    x: f32,
+    y: f32,
+    values: Vec<f32>,
+    color: Color,
+}
+
+/// Returns the point of a cubic Bézier patch at `(u, v)`.
+fn patch_point(points: &[[(f32, f32); 4]; 4], u: f32, v: f32) -> (f32, f32) {
+    let bernstein = |t: f32| {
+        let s = 1.0 - t;
+        [s * s * s, 3.0 * t * s * s, 3.0 * t * t * s, t * t * t]
+    };
+    let (bu, bv) = (bernstein(u), bernstein(v));
+    let mut point = (0.0, 0.0);
+    for (row, wu) in points.iter().zip(bu) {
+        for (&(x, y), wv) in row.iter().zip(bv) {
+            point.0 += wu * wv * x;
+            point.1 += wu * wv * y;
+        }
+    }
+    point
+}
+
+/// Returns the colour values of a patch at `(u, v)`, interpolated bilinearly
+/// between its corners.
+fn patch_values(colors: &[Vec<f32>; 4], u: f32, v: f32) -> Vec<f32> {
+    let [c00, c03, c33, c30] = colors;
+    let weights = [(1.0 - u) * (1.0 - v), (1.0 - u) * v, u * v, u * (1.0 - v)];
+    (0..c00.len())
+        .map(|k| {
+            [c00, c03, c33, c30]
+                .iter()
+                .zip(weights)
+                .map(|(c, w)| w * c.get(k).copied().unwrap_or(0.0))
+                .sum()
+        })
+        .collect()
+}
+
+/// Returns the values halfway between `a` and `b`.
+fn midpoint_values(a: &[f32], b: &[f32]) -> Vec<f32> {
+    a.iter().zip(b).map(|(a, b)| 0.5 * (a + b)).collect()
+}
+
+/// Evaluates the functions of a shading, either a single n-out function or n
+/// 1-out functions, whose outputs are the colour components.
+fn function_components(
+    functions: &[Function],
+    inputs: &[f32],
+) -> Result<Vec<f32>, FunctionInterpolationError> {
+    match functions {
+        [function] => function.interpolate(inputs),
+        _ => functions
+            .iter()
+            .map(|function| {
+                Ok(function
+                    .interpolate(inputs)?
+                    .first()
+                    .copied()
+                    .unwrap_or(0.0))
+            })
+            .collect(),
+    }
+}
+
+/// Returns the colour components of a mesh vertex, whose values are either the
+/// components themselves or a parametric value passed through the shading's functions.
+fn mesh_components(
+    functions: &[Function],
+    values: &[f32],
+) -> Result<Vec<f32>, FunctionInterpolationError> {
+    if functions.is_empty() {
+        Ok(values.to_vec())
+    } else {
+        function_components(functions, values)
+    }
+}
+
+impl<T: std::error::Error> ShadingOps for PdfCanvas<'_, T> {
+    fn paint_shading(&mut self, shading_name: &str) -> Result<(), Self::ErrorType> {
+        if self.is_content_hidden() {
+            return Ok(());
+        }
+        let shading = self.find_shading(shading_name)?;
+        let transform = self.current_state()?.transform;
+        self.paint_shading_in_clip(shading, &transform, false)
+    }
+}
+
+impl<T: std::error::Error> PdfCanvas<'_, T> {
+    /// Fills a device-space path with a shading pattern.
+    ///
+    /// The path clips the shading, which is painted in pattern space: the page's
+    /// default coordinate space, mapped by the pattern matrix.
+    pub(crate) fn fill_with_shading_pattern(
+        &mut self,
+        path: &PdfPath,
+        fill_type: PathFillType,
+        shading: &Shading,
+        matrix: &Option<Transform>,
+    ) -> Result<(), PdfCanvasError> {
+        let base = self
+            .canvas_stack
+            .first()
+            .ok_or(PdfCanvasError::EmptyGraphicsStateStack)?
+            .transform;
+        let mut to_device = matrix.unwrap_or_default();
+        to_device.concat(&base);
+
+        self.canvas
+            .push_clip_region(path, fill_type)
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        let painted = self.paint_shading_in_clip(shading, &to_device, true);
+        self.canvas
+            .pop_clip_region()
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        painted
+    }
+
+    /// Paints a shading over the whole of the current clipping region.
+    ///
+    /// Bands of axial and radial shadings, cells of function-based shadings and
+    /// small triangles of mesh shadings are filled one after another in solid
+    /// colours, overlapping where possible so that no seams show.
+    ///
+    /// # Parameters
+    ///
+    /// - `shading`: The shading to paint.
+    /// - `to_device`: Maps the shading's coordinate space to device space.
+    /// - `paint_background`: Whether `/Background` fills the area outside the
+    ///   shading. Shading patterns use it; the `sh` operator ignores it.
+    pub(crate) fn paint_shading_in_clip(
+        &mut self,
+        shading: &Shading,
+        to_device: &Transform,
+        paint_background: bool,
+    ) -> Result<(), PdfCanvasError> {
+        // The canvas corners, in shading space, bound the area worth painting.
+        let Some(to_shading) = to_device.invert() else {
+            return Ok(());
+        };
+        let (width, height) = (self.canvas.width(), self.canvas.height());
+        let corners = [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)]
+            .map(|(x, y)| to_shading.transform_point(x, y));
+
+        let bbox = shading.bbox();
+        if let Some([x0, y0, x1, y1]) = bbox {
+            let clip = polygon(&[(x0, y0), (x1, y0), (x1, y1), (x0, y1)], to_device);
+            self.canvas
+                .push_clip_region(&clip, PathFillType::Winding)
+                .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        }
+        let painted = self.paint_shading_bands(shading, to_device, &corners, paint_background);
+        if bbox.is_some() {
+            self.canvas
+                .pop_clip_region()
+                .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        }
+        painted
+    }
+
+    fn paint_shading_bands(
+        &mut self,
+        shading: &Shading,
+        to_device: &Transform,
+        corners: &[(f32, f32); 4],
+        paint_background: bool,
+    ) -> Result<(), PdfCanvasError> {
+        let color_space = shading.color_space();
+        if paint_background && let Some(background) = shading.background() {
+            let color = self.shading_color(color_space, background)?;
+            self.fill_shading_path(&polygon(corners, to_device), PathFillType::Winding, color)?;
+        }
+
+        match shading {
+            Shading::Axial {
+                coords,
+                function,
+                domain,
+                extend,
+                ..
+            } => self.paint_axial(
+                *coords,
+                function,
+                *domain,
+                *extend,
+                color_space,
+                to_device,
+                corners,
+            ),
+            Shading::Radial {
+                coords,
+                function,
+                domain,
+                extend,
+                ..
+            } => self.paint_radial(
+                *coords,
+                function,
+                *domain,
+                *extend,
+                color_space,
+                to_device,
+                corners,
+            ),
+            Shading::FunctionBased {
+                domain,
+                matrix,
+                functions,
+                ..
+            } => self.paint_function_based(
+                domain.as_deref(),
+                matrix,
+                functions,
+                color_space,
+                to_device,
+            ),
+            Shading::TriangleMesh {
+                functions,
+                triangles,
+                ..
+            } => {
+                for triangle in triangles {
+                    let [a, b, c] = triangle.each_ref().map(|vertex| {
+                        self.device_vertex(vertex, functions, color_space, to_device)
+                    });
+                    self.paint_gouraud_triangle([a?, b?, c?], functions, color_space, 0)?;
+                }
+                Ok(())
+            }
+            Shading::PatchMesh {
+                functions, patches, ..
+            } => {
+                for patch in patches {
+                    self.paint_patch(patch, functions, color_space, to_device)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Maps a mesh vertex to device space and computes its colour.
+    fn device_vertex(
+        &self,
+        vertex: &MeshVertex,
+        functions: &[Function],
+        color_space: &ColorSpace,
+        to_device: &Transform,
+    ) -> Result<DeviceVertex, PdfCanvasError> {
+        let (x, y) = to_device.transform_point(vertex.x, vertex.y);
+        Ok(DeviceVertex {
+            x,
+            y,
+            color: self.mesh_color(functions, color_space, &vertex.values)?,
+            values: vertex.values.clone(),
+        })
+    }
+
+    /// Paints a Gouraud-shaded triangle, splitting it in four until each part is
+    /// small or nearly uniform in colour, and filling the parts in their mean colour.
+    fn paint_gouraud_triangle(
+        &mut self,
+        vertices: [DeviceVertex; 3],
+        functions: &[Function],
+        color_space: &ColorSpace,
+        depth: u8,
+    ) -> Result<(), PdfCanvasError> {
+        let [a, b, c] = &vertices;
+        let longest_edge = [(a, b), (b, c), (c, a)]
+            .iter()
+            .map(|(p, q)| (p.x - q.x).hypot(p.y - q.y))
+            .fold(0.0, f32::max);
+        let channels = |v: &DeviceVertex| [v.color.r, v.color.g, v.color.b, v.color.a];
+        let color_spread = (0..4)
+            .map(|k| min_max([a, b, c].iter().map(|v| channels(v)[k])))
+            .map(|(min, max)| max - min)
+            .fold(0.0, f32::max);
+
+        if depth >= MAX_GOURAUD_DEPTH
+            || longest_edge <= GOURAUD_EDGE_SIZE
+            || color_spread <= GOURAUD_COLOR_TOLERANCE
+        {
+            let mean = |f: fn(&Color) -> f32| (f(&a.color) + f(&b.color) + f(&c.color)) / 3.0;
+            let color = Color {
+                r: mean(|c| c.r),
+                g: mean(|c| c.g),
+                b: mean(|c| c.b),
+                a: mean(|c| c.a),
+            };
+            let mut corners = [(a.x, a.y), (b.x, b.y), (c.x, c.y)];
+            if self.bands_overlap()? {
+                // Push the corners away from the centroid so that no seams show
+                // between neighbouring triangles.
+                let (cx, cy) = ((a.x + b.x + c.x) / 3.0, (a.y + b.y + c.y) / 3.0);
+                for (x, y) in &mut corners {
+                    let distance = (*x - cx).hypot(*y - cy);
+                    if distance > 0.0 {
+                        let scale = 1.0 + TRIANGLE_OVERLAP / distance;
+                        (*x, *y) = (cx + (*x - cx) * scale, cy + (*y - cy) * scale);
+                    }
+                }
+            }
+            return self.fill_shading_path(
+                &polygon(&corners, &Transform::identity()),
+                PathFillType::Winding,
+                color,
+            );
+        }
+
+        let midpoint = |p: &DeviceVertex, q: &DeviceVertex| {
+            let values = midpoint_values(&p.values, &q.values);
+            Ok::<_, PdfCanvasError>(DeviceVertex {
+                x: 0.5 * (p.x + q.x),
+                y: 0.5 * (p.y + q.y),
+                color: self.mesh_color(functions, color_space, &values)?,
+                values,
+            })
+        };
+        let (ab, bc, ca) = (midpoint(a, b)?, midpoint(b, c)?, midpoint(c, a)?);
+        let depth = depth.saturating_add(1);
+        for part in [
+            [a.clone(), ab.clone(), ca.clone()],
+            [ab.clone(), b.clone(), bc.clone()],
+            [ca.clone(), bc.clone(), c.clone()],
+            [ab, bc, ca],
+        ] {
+            self.paint_gouraud_triangle(part, functions, color_space, depth)?;
+        }
+        Ok(())
+    }
+
+    /// Paints a tensor-product patch as a grid of Gouraud-shaded triangles.
+    ///
+    /// Cells are painted in order of increasing `v`, then `u`, so that where the
+    /// patch folds over itself the parts with larger parameters are on top.
+    fn paint_patch(
+        &mut self,
+        patch: &Patch,
+        functions: &[Function],
+        color_space: &ColorSpace,
+        to_device: &Transform,
+    ) -> Result<(), PdfCanvasError> {
+        let device_points = patch
+            .points
+            .map(|row| row.map(|(x, y)| to_device.transform_point(x, y)));
+        let (x_min, x_max) = min_max(device_points.iter().flatten().map(|p| p.0));
+        let (y_min, y_max) = min_max(device_points.iter().flatten().map(|p| p.1));
+        let cells = step_count(
+            (x_max - x_min).hypot(y_max - y_min),
+            PATCH_CELL_SIZE,
+            MAX_PATCH_CELLS,
+        );
+        let step = 1.0 / f32::from(cells);
+
+        let vertex = |canvas: &Self, i: u16, j: u16| {
+            let (u, v) = (f32::from(i) * step, f32::from(j) * step);
+            let (x, y) = patch_point(&device_points, u, v);
+            let values = patch_values(&patch.colors, u, v);
+            Ok::<_, PdfCanvasError>(DeviceVertex {
+                x,
+                y,
+                color: canvas.mesh_color(functions, color_space, &values)?,
+                values,
+            })
+        };
+        let mut previous_row: Vec<DeviceVertex> = (0..=cells)
+            .map(|i| vertex(self, i, 0))
+            .collect::<Result<_, _>>()?;
+        for j in 1..=cells {
+            let row: Vec<DeviceVertex> = (0..=cells)
+                .map(|i| vertex(self, i, j))
+                .collect::<Result<_, _>>()?;
+            for (below, above) in previous_row.windows(2).zip(row.windows(2)) {
+                if let ([p00, p10], [p01, p11]) = (below, above) {
+                    for triangle in [
+                        [p00.clone(), p10.clone(), p01.clone()],
+                        [p10.clone(), p11.clone(), p01.clone()],
+                    ] {
+                        self.paint_gouraud_triangle(triangle, functions, color_space, 0)?;
+                    }
+                }
+            }
+            previous_row = row;
+        }
+        Ok(())
+    }
+
+    /// Converts the colour values of a mesh vertex, either colour components or a
+    /// parametric value passed through the shading's functions, to a colour.
+    fn mesh_color(
+        &self,
+        functions: &[Function],
+        color_space: &ColorSpace,
+        values: &[f32],
+    ) -> Result<Color, PdfCanvasError> {
+        self.shading_color(color_space, &mesh_components(functions, values)?)
+    }
+
+    /// Returns whether neighbouring bands, cells and triangles of a shading may
+    /// overlap to hide seams. They may only when painting one over another leaves
+    /// no trace: with an opaque colour and normal blending.
+    fn bands_overlap(&self) -> Result<bool, PdfCanvasError> {
+        let state = self.current_state()?;
+        Ok(state.fill_color.a >= 1.0 && matches!(state.blend_mode, None | Some(BlendMode::Normal)))
+    }
+
+    /// Paints an axial shading as bands perpendicular to its axis, each spanning the
+    /// canvas. `/Extend` adds a band of the end colour before the start and after
+    /// the end of the axis.
+    #[allow(clippy::too_many_arguments)]
+    fn paint_axial(
+        &mut self,
+        [x0, y0, x1, y1]: [f32; 4],
+        function: &Function,
+        domain: [f32; 2],
+        extend: [bool; 2],
+        color_space: &ColorSpace,
+        to_device: &Transform,
+        corners: &[(f32, f32); 4],
+    ) -> Result<(), PdfCanvasError> {
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let length_squared = dx * dx + dy * dy;
+        if length_squared == 0.0 {
+            return Ok(());
+        }
+
+        // A point is `p0 + t * d + s * n`, where `n` is the axis turned by 90 degrees.
+        let (t_min, t_max) = min_max(
+            corners
+                .iter()
+                .map(|&(x, y)| ((x - x0) * dx + (y - y0) * dy) / length_squared),
+        );
+        let (s_min, s_max) = min_max(
+            corners
+                .iter()
+                .map(|&(x, y)| ((y - y0) * dx - (x - x0) * dy) / length_squared),
+        );
+        let band = |from: f32, to: f32| {
+            let point = |t: f32, s: f32| (x0 + t * dx - s * dy, y0 + t * dy + s * dx);
+            polygon(
+                &[
+                    point(from, s_min),
+                    point(to, s_min),
+                    point(to, s_max),
+                    point(from, s_max),
+                ],
+                to_device,
+            )
+        };
+        let color_at = |canvas: &Self, t: f32| {
+            let components = function.interpolate(&[domain[0] + t * (domain[1] - domain[0])])?;
+            canvas.shading_color(color_space, &components)
+        };
+
+        if extend[0] && t_min < 0.0 {
+            let color = color_at(self, 0.0)?;
+            self.fill_shading_path(&band(t_min, 0.0), PathFillType::Winding, color)?;
+        }
+
+        let steps = step_count(
+            device_length(to_device, dx, dy),
+            GRADIENT_STEP_SIZE,
+            MAX_GRADIENT_STEPS,
+        );
+        let step = 1.0 / f32::from(steps);
+        let last = steps.saturating_sub(1);
+        let overlap = if self.bands_overlap()? {
+            0.5 * step
+        } else {
+            0.0
+        };
+        for i in 0..steps {
+            let from = f32::from(i) * step;
+            let to = if i == last {
+                1.0
+            } else {
+                from + step + overlap
+            };
+            if to < t_min || from > t_max {
+                continue;
+            }
+            let color = color_at(self, from + 0.5 * step)?;
+            self.fill_shading_path(&band(from, to), PathFillType::Winding, color)?;
+        }
+
+        if extend[1] && t_max > 1.0 {
+            let color = color_at(self, 1.0)?;
+            self.fill_shading_path(&band(1.0, t_max), PathFillType::Winding, color)?;
+        }
+        Ok(())
+    }
+
+    /// Paints a radial shading as the rings swept between successive circles, in
+    /// order of increasing `t` so that later circles cover earlier ones.
+    #[allow(clippy::too_many_arguments)]
+    fn paint_radial(
+        &mut self,
+        [x0, y0, r0, x1, y1, r1]: [f32; 6],
+        function: &Function,
+        domain: [f32; 2],
+        extend: [bool; 2],
+        color_space: &ColorSpace,
+        to_device: &Transform,
+        corners: &[(f32, f32); 4],
+    ) -> Result<(), PdfCanvasError> {
+        let circle = |t: f32| (x0 + t * (x1 - x0), y0 + t * (y1 - y0), r0 + t * (r1 - r0));
+        let color_at = |canvas: &Self, t: f32| {
+            let components = function.interpolate(&[domain[0] + t * (domain[1] - domain[0])])?;
+            canvas.shading_color(color_space, &components)
+        };
+
+        if extend[0] {
+            let length = extension_length(circle(0.0), circle(1.0), corners);
+            if length > 0.0 {
+                let color = color_at(self, 0.0)?;
+                let outline = ring(circle(-length), circle(0.0), to_device);
+                self.fill_shading_path(&outline, PathFillType::EvenOdd, color)?;
+            }
+        }
+
+        let sweep =
+            device_length(to_device, x1 - x0, y1 - y0) + (r1 - r0).abs() * to_device.mean_scale();
+        let steps = step_count(sweep, GRADIENT_STEP_SIZE, MAX_GRADIENT_STEPS);
+        let step = 1.0 / f32::from(steps);
+        let last = steps.saturating_sub(1);
+        let overlap = if self.bands_overlap()? {
+            0.5 * step
+        } else {
+            0.0
+        };
+        for i in 0..steps {
+            let from = f32::from(i) * step;
+            let to = if i == last {
+                1.0
+            } else {
+                from + step + overlap
+            };
+            let (start, end) = (circle(from), circle(to));
+            // Circles with a negative radius are not painted.
+            if start.2 < 0.0 && end.2 < 0.0 {
+                continue;
+            }
+            let color = color_at(self, from + 0.5 * step)?;
+            self.fill_shading_path(&ring(start, end, to_device), PathFillType::EvenOdd, color)?;
+        }
+
+        if extend[1] {
+            let length = extension_length(circle(1.0), circle(0.0), corners);
+            if length > 0.0 {
+                let color = color_at(self, 1.0)?;
+                let outline = ring(circle(1.0), circle(1.0 + length), to_device);
+                self.fill_shading_path(&outline, PathFillType::EvenOdd, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Paints a function-based shading as a grid of cells over its domain, each in
+    /// the colour the function gives at the cell's centre.
+    fn paint_function_based(
+        &mut self,
+        domain: Option<&[f32]>,
+        matrix: &Option<Transform>,
+        functions: &[Function],
+        color_space: &ColorSpace,
+        to_device: &Transform,
+    ) -> Result<(), PdfCanvasError> {
+        let [x0, x1, y0, y1] = match domain {
+            Some(&[x0, x1, y0, y1]) => [x0, x1, y0, y1],
+            _ => [0.0, 1.0, 0.0, 1.0],
+        };
+        // The shading matrix maps the domain into the shading's coordinate space.
+        let mut transform = matrix.unwrap_or_default();
+        transform.concat(to_device);
+
+        let columns = step_count(
+            device_length(&transform, x1 - x0, 0.0),
+            FUNCTION_CELL_SIZE,
+            MAX_FUNCTION_CELLS,
+        );
+        let rows = step_count(
+            device_length(&transform, 0.0, y1 - y0),
+            FUNCTION_CELL_SIZE,
+            MAX_FUNCTION_CELLS,
+        );
+        let cell_width = (x1 - x0) / f32::from(columns);
+        let cell_height = (y1 - y0) / f32::from(rows);
+        let (last_column, last_row) = (columns.saturating_sub(1), rows.saturating_sub(1));
+        let overlap = if self.bands_overlap()? { 0.5 } else { 0.0 };
+
+        for row in 0..rows {
+            let v0 = y0 + f32::from(row) * cell_height;
+            let v1 = v0 + cell_height * if row == last_row { 1.0 } else { 1.0 + overlap };
+            for column in 0..columns {
+                let u0 = x0 + f32::from(column) * cell_width;
+                let u1 = u0
+                    + cell_width
+                        * if column == last_column {
+                            1.0
+                        } else {
+                            1.0 + overlap
+                        };
+                let components = function_components(
+                    functions,
+                    &[u0 + 0.5 * cell_width, v0 + 0.5 * cell_height],
+                )?;
+                let color = self.shading_color(color_space, &components)?;
+                let cell = polygon(&[(u0, v0), (u1, v0), (u1, v1), (u0, v1)], &transform);
+                self.fill_shading_path(&cell, PathFillType::Winding, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the mean colour of a shading with the given alpha, averaged over its
+    /// colour components. It stands in for shadings that backends cannot paint along
+    /// a stroke.
+    pub(crate) fn shading_average_color(
+        &self,
+        shading: &Shading,
+        alpha: f32,
+    ) -> Result<Color, PdfCanvasError> {
+        let samples: Vec<Vec<f32>> = match shading {
+            Shading::FunctionBased {
+                domain, functions, ..
+            } => {
+                let [x0, x1, y0, y1] = match domain.as_deref() {
+                    Some(&[x0, x1, y0, y1]) => [x0, x1, y0, y1],
+                    _ => [0.0, 1.0, 0.0, 1.0],
+                };
+                let centre = |i: u16, from: f32, to: f32| {
+                    from + (f32::from(i) + 0.5) / f32::from(AVERAGE_SAMPLES) * (to - from)
+                };
+                (0..AVERAGE_SAMPLES)
+                    .flat_map(|row| (0..AVERAGE_SAMPLES).map(move |column| (column, row)))
+                    .map(|(column, row)| {
+                        function_components(
+                            functions,
+                            &[centre(column, x0, x1), centre(row, y0, y1)],
+                        )
+                    })
+                    .collect::<Result<_, _>>()?
+            }
+            Shading::Axial {
+                function, domain, ..
+            }
+            | Shading::Radial {
+                function, domain, ..
+            } => (0..AVERAGE_SAMPLES)
+                .map(|i| {
+                    function.interpolate(&[domain[0]
+                        + (f32::from(i) + 0.5) / f32::from(AVERAGE_SAMPLES)
+                            * (domain[1] - domain[0])])
+                })
+                .collect::<Result<_, _>>()?,
+            Shading::TriangleMesh {
+                functions,
+                triangles,
+                ..
+            } => triangles
+                .iter()
+                .flatten()
+                .map(|vertex| mesh_components(functions, &vertex.values))
+                .collect::<Result<_, _>>()?,
+            Shading::PatchMesh {
+                functions, patches, ..
+            } => patches
+                .iter()
+                .flat_map(|patch| &patch.colors)
+                .map(|values| mesh_components(functions, values))
+                .collect::<Result<_, _>>()?,
+        };
+
+        let count = samples.len().to_f32().unwrap_or(1.0).max(1.0);
+        let mut mean = Vec::new();
+        for components in &samples {
+            mean.resize(mean.len().max(components.len()), 0.0);
+            for (sum, component) in mean.iter_mut().zip(components) {
+                *sum += component / count;
+            }
+        }
+        let color = shading
+            .color_space()
+            .to_color(&mean, &self.color_conversion()?)?;
+        Ok(Color { a: alpha, ..color })
+    }
+
+    /// Converts shading colour components to a colour with the current fill alpha.
+    fn shading_color(
+        &self,
+        color_space: &ColorSpace,
+        components: &[f32],
+    ) -> Result<Color, PdfCanvasError> {
+        let color = color_space.to_color(components, &self.color_conversion()?)?;
+        Ok(Color {
+            a: self.current_state()?.fill_color.a,
+            ..color
+        })
+    }
+
+    /// Fills a device-space path with a solid colour and the current blend mode.
+    fn fill_shading_path(
+        &mut self,
+        path: &PdfPath,
+        fill_type: PathFillType,
+        color: Color,
+    ) -> Result<(), PdfCanvasError> {
+        let blend_mode = self.shape_blend_mode()?;
+        self.canvas
+            .fill_path(path, fill_type, color, &None, blend_mode)
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))
+    }
+}
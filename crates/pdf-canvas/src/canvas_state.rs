@@ -1,9 +1,41 @@
 use pdf_graphics::{
-    BlendMode, LineCap, LineJoin, color::Color, pdf_path::PdfPath, transform::Transform,
+    BlendMode, LineCap, LineJoin, MaskMode, PathFillType, StrokeStyle, color::Color,
+    color_management::RenderingIntent, pdf_path::PdfPath, transform::Transform,
 };
-use pdf_page::{pattern::Pattern, resources::Resources};
+use pdf_page::{color_space::ColorSpace, pattern::Pattern, resources::Resources};
 
-use crate::text_state::TextState;
+use crate::{recording_canvas::RecordingCanvas, text_state::TextState};
+
+/// A layer pushed onto the backend by the graphics state, which stays in effect
+/// until the state that pushed it is restored.
+pub(crate) enum StateLayer {
+    /// A clipping path in device space, kept so that it can be pushed again.
+    Clip { path: PdfPath, mode: PathFillType },
+    /// A soft mask, applied to what was drawn while it was in effect once it ends.
+    SoftMask(SoftMaskLayer),
+}
+
+/// A layer of an enclosing state taken down while a nested state lifted the soft
+/// mask it inherited. It is pushed again when the nested state is restored.
+pub(crate) struct LiftedLayer {
+    /// The index on the state stack of the state that lifted the layer.
+    pub lifted_by: usize,
+    /// The index on the state stack of the state that pushed the layer.
+    pub owner: usize,
+    pub layer: StateLayer,
+}
+
+/// A soft mask rendered when an `ExtGState` set it.
+pub(crate) struct SoftMaskLayer {
+    /// The mask's content, in the mask group's form space.
+    pub content: Box<RecordingCanvas>,
+    /// Maps the mask's form space to device space, fixed by the CTM at the `gs`.
+    pub transform: Transform,
+    /// Whether mask values come from the content's luminosity or alpha.
+    pub mode: MaskMode,
+    /// The transfer function sampled at the 256 mask values, if any.
+    pub transfer: Option<Vec<u8>>,
+}
 
 /// Represents the complete graphics state for a PDF canvas, including
 /// transformation, color, stroke, text, and pattern information.
@@ -15,24 +47,46 @@ pub(crate) struct CanvasState<'a> {
     pub stroke_color: Color,
     /// The current fill color used for filling paths and text.
     pub fill_color: Color,
+    /// The colour space in which stroking colour components are interpreted.
+    pub stroke_color_space: &'a ColorSpace,
+    /// The colour space in which non-stroking colour components are interpreted.
+    pub fill_color_space: &'a ColorSpace,
     /// The current line width for stroking paths, in user space units.
     pub line_width: f32,
     /// The current miter limit for joins, controlling how sharp corners are rendered.
     pub miter_limit: f32,
     /// The current text state, encapsulating font, size, and text matrix.
     pub text_state: TextState<'a>,
-    /// The current clipping path, if any, restricting drawing to a region.
-    pub clip_path: Option<PdfPath>,
+    /// The number of backend layers (clipping paths and soft masks) pushed since
+    /// this state was saved. Restoring the previous state pops them all.
+    pub layer_depth: usize,
     /// The current line cap style (butt, round, or projecting square).
     pub line_cap: LineCap,
     /// The current line join style (miter, round, or bevel).
     pub line_join: LineJoin,
+    /// The current dash array, alternating dash and gap lengths in user space units.
+    pub dash_array: Vec<f32>,
+    /// The current dash phase, in user space units.
+    pub dash_phase: f32,
     /// The current resource dictionary, overriding the page's resources if set.
     pub resources: Option<&'a Resources>,
-    /// The current pattern (shading or tiling) used for filling or stroking.
-    pub pattern: Option<&'a Pattern>,
+    /// The current pattern (shading or tiling) used for filling.
+    pub fill_pattern: Option<&'a Pattern>,
+    /// The current pattern (shading or tiling) used for stroking.
+    pub stroke_pattern: Option<&'a Pattern>,
     /// The current blend mode, controlling compositing behavior.
     pub blend_mode: Option<BlendMode>,
+    /// Set inside a knockout transparency group, where each object painted replaces
+    /// what the group's earlier objects painted beneath it.
+    pub knockout: bool,
+    /// The rendering intent used when colours are converted with ICC profiles.
+    pub rendering_intent: RenderingIntent,
+    /// Set by `d1` while a Type 3 glyph description runs: the glyph is a shape
+    /// painted in the current colour, so colour operators are ignored.
+    pub ignore_color_ops: bool,
+    /// Set while a Type 3 glyph description runs, so that the paths it paints
+    /// follow the text rendering mode.
+    pub in_glyph_description: bool,
 }
 
 impl CanvasState<'_> {
@@ -43,7 +97,20 @@ impl CanvasState<'_> {
     /// Default stroke color.
     const DEFAULT_STROKE_COLOR: Color = Color::from_rgb(0.0, 0.0, 0.0);
     /// Default miter limit.
-    const DEFAULT_MITER_LIMIT: f32 = 0.0;
+    const DEFAULT_MITER_LIMIT: f32 = 10.0;
+
+    /// Returns the stroke parameters with lengths mapped to device space by the CTM.
+    pub fn device_stroke_style(&self) -> StrokeStyle {
+        let scale = self.transform.mean_scale();
+        StrokeStyle {
+            line_width: self.line_width * scale,
+            line_cap: self.line_cap,
+            line_join: self.line_join,
+            miter_limit: self.miter_limit,
+            dash_array: self.dash_array.iter().map(|len| len * scale).collect(),
+            dash_phase: self.dash_phase * scale,
+        }
+    }
 }
 
 impl Default for CanvasState<'_> {
@@ -52,15 +119,24 @@ impl Default for CanvasState<'_> {
             transform: Transform::identity(),
             stroke_color: Self::DEFAULT_STROKE_COLOR,
             fill_color: Self::DEFAULT_FILL_COLOR,
+            stroke_color_space: ColorSpace::device_gray(),
+            fill_color_space: ColorSpace::device_gray(),
             line_width: Self::DEFAULT_LINE_WIDTH,
             miter_limit: Self::DEFAULT_MITER_LIMIT,
             text_state: TextState::default(),
-            clip_path: None,
+            layer_depth: 0,
             resources: None,
-            pattern: None,
+            fill_pattern: None,
+            stroke_pattern: None,
             line_cap: LineCap::Butt,
             line_join: LineJoin::Miter,
+            dash_array: Vec::new(),
+            dash_phase: 0.0,
             blend_mode: None,
+            knockout: false,
+            rendering_intent: RenderingIntent::default(),
+            ignore_color_ops: false,
+            in_glyph_description: false,
         }
     }
 }
@@ -7,20 +7,21 @@ use crate::type1_font_renderer::Type1FontRenderer;
 use crate::type3_font_renderer::Type3FontRenderer;
 use pdf_content_stream::TextElement;
 use pdf_content_stream::pdf_operator_backend::{
-    TextObjectOps, TextPositioningOps, TextShowingOps, TextStateOps,
+    TextObjectOps, TextPositioningOps, TextShowingOps, TextStateOps, Type3FontOps,
 };
 use pdf_font::font::Font;
-use pdf_graphics::TextRenderingMode;
 use pdf_graphics::transform::Transform;
+use pdf_graphics::{PathFillType, TextRenderingMode};
+use std::rc::Rc;
 
 impl<T: std::error::Error> TextPositioningOps for PdfCanvas<'_, T> {
     fn move_text_position(&mut self, tx: f32, ty: f32) -> Result<(), Self::ErrorType> {
-        let mat = Transform::from_translate(tx, ty);
-        self.current_state_mut()?
-            .text_state
-            .line_matrix
-            .concat(&mat);
-        self.current_state_mut()?.text_state.matrix = self.current_state()?.text_state.line_matrix;
+        // The offset is in text space, so it is applied before the current line matrix.
+        let text_state = &mut self.current_state_mut()?.text_state;
+        let mut line_matrix = Transform::from_translate(tx, ty);
+        line_matrix.concat(&text_state.line_matrix);
+        text_state.line_matrix = line_matrix;
+        text_state.matrix = line_matrix;
         Ok(())
     }
 
@@ -29,10 +30,8 @@ impl<T: std::error::Error> TextPositioningOps for PdfCanvas<'_, T> {
         tx: f32,
         ty: f32,
     ) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented(format!(
-            "move_text_position_and_set_leading TD: tx={}, ty={}",
-            tx, ty
-        )))
+        self.current_state_mut()?.text_state.leading = -ty;
+        self.move_text_position(tx, ty)
     }
 
     fn set_text_matrix(
@@ -51,9 +50,8 @@ impl<T: std::error::Error> TextPositioningOps for PdfCanvas<'_, T> {
     }
 
     fn move_to_start_of_next_line(&mut self) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented(
-            "move_to_start_of_next_line T*".into(),
-        ))
+        let leading = self.current_state()?.text_state.leading;
+        self.move_text_position(0.0, -leading)
     }
 }
 
@@ -61,10 +59,16 @@ impl<T: std::error::Error> TextObjectOps for PdfCanvas<'_, T> {
     fn begin_text_object(&mut self) -> Result<(), Self::ErrorType> {
         self.current_state_mut()?.text_state.matrix = Transform::identity();
         self.current_state_mut()?.text_state.line_matrix = Transform::identity();
+        self.text_clip = None;
         Ok(())
     }
 
     fn end_text_object(&mut self) -> Result<(), Self::ErrorType> {
+        // Glyphs shown in a clipping mode form a single clip, applied once the text
+        // object ends.
+        if let Some(path) = self.text_clip.take() {
+            self.set_device_clip_path(path, PathFillType::Winding)?;
+        }
         Ok(())
     }
 }
@@ -86,10 +90,8 @@ impl<T: std::error::Error> TextStateOps for PdfCanvas<'_, T> {
     }
 
     fn set_text_leading(&mut self, leading: f32) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented(format!(
-            "set_text_leading TL: {}",
-            leading
-        )))
+        self.current_state_mut()?.text_state.leading = leading;
+        Ok(())
     }
 
     fn set_font_and_size(&mut self, font_name: &str, size: f32) -> Result<(), Self::ErrorType> {
@@ -102,24 +104,27 @@ impl<T: std::error::Error> TextStateOps for PdfCanvas<'_, T> {
             .ok_or(PdfCanvasError::MissingPageResources)?;
 
         if let Some(font) = resources.fonts.get(font_name) {
-            self.current_state_mut()?.text_state.font = Some(font);
+            let text_state = &mut self.current_state_mut()?.text_state;
+            text_state.font = Some(font);
+            text_state.font_resources = resources.font_resources.get(font_name).map(Rc::as_ref);
             return Ok(());
         }
 
         if let Some(resources) = self.current_state()?.resources
             && let Some(font) = resources.fonts.get(font_name)
         {
-            self.current_state_mut()?.text_state.font = Some(font);
+            let text_state = &mut self.current_state_mut()?.text_state;
+            text_state.font = Some(font);
+            text_state.font_resources = resources.font_resources.get(font_name).map(Rc::as_ref);
             return Ok(());
         }
 
         Err(PdfCanvasError::FontNotFound(font_name.to_string()))
     }
 
-    fn set_text_rendering_mode(&mut self, _mode: TextRenderingMode) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented(
-            "set_text_rendering_mode".into(),
-        ))
+    fn set_text_rendering_mode(&mut self, mode: TextRenderingMode) -> Result<(), Self::ErrorType> {
+        self.current_state_mut()?.text_state.rendering_mode = mode;
+        Ok(())
     }
 
     fn set_text_rise(&mut self, rise: f32) -> Result<(), Self::ErrorType> {
@@ -139,26 +144,37 @@ impl<T: std::error::Error> TextShowingOps for PdfCanvas<'_, T> {
             word_spacing,
             rise,
             font: ref current_font,
+            font_resources,
             ..
         } = self.current_state()?.text_state.clone();
 
         let current_transform = self.current_state()?.transform;
         let current_font = current_font.ok_or(PdfCanvasError::NoCurrentFont)?;
 
-        match current_font {
+        let text_matrix = match current_font {
             Font::Type3(type3_font) => {
+                // Glyph procedures run with the font's own resources when it declares them.
+                let previous_resources = self.current_state()?.resources;
+                if font_resources.is_some() {
+                    self.current_state_mut()?.resources = font_resources;
+                }
                 let mut renderer = Type3FontRenderer::new(
                     self,
                     font_size,
                     horizontal_scaling,
                     rise,
+                    word_spacing,
+                    character_spacing,
                     current_transform,
                     matrix,
                     type3_font,
                 )?;
-                renderer.render_text(text)
+                let result = renderer.render_text(text).map(|()| renderer.text_matrix());
+                self.current_state_mut()?.resources = previous_resources;
+                result?
             }
-            Font::Type1(type1_font) => {
+            // Type1 fonts embedding TrueType outlines are drawn like TrueType fonts.
+            Font::Type1(type1_font) if !type1_font.has_truetype_outlines() => {
                 let mut renderer = Type1FontRenderer::new(
                     self,
                     type1_font,
@@ -170,9 +186,10 @@ impl<T: std::error::Error> TextShowingOps for PdfCanvas<'_, T> {
                     word_spacing,
                     character_spacing,
                 );
-                renderer.render_text(text)
+                renderer.render_text(text)?;
+                renderer.text_matrix()
             }
-            Font::TrueType(_) | Font::Type0(_) => {
+            Font::Type1(_) | Font::TrueType(_) | Font::Type0(_) => {
                 let mut renderer = TrueTypeFontRenderer::new(
                     self,
                     current_font,
@@ -184,9 +201,14 @@ impl<T: std::error::Error> TextShowingOps for PdfCanvas<'_, T> {
                     word_spacing,
                     character_spacing,
                 )?;
-                renderer.render_text(text)
+                renderer.render_text(text)?;
+                renderer.text_matrix()
             }
-        }
+        };
+
+        // Later text continues where the shown glyphs ended.
+        self.current_state_mut()?.text_state.matrix = text_matrix;
+        Ok(())
     }
 
     fn show_text_with_glyph_positioning(
@@ -201,9 +223,20 @@ impl<T: std::error::Error> TextShowingOps for PdfCanvas<'_, T> {
                 TextElement::Adjustment { amount } => {
                     let amount = (*amount) / 1000.0;
                     let state = self.current_state_mut()?;
-                    let tx =
-                        -amount * state.text_state.font_size * state.text_state.horizontal_scaling;
-                    state.text_state.matrix.translate(tx, 0.0);
+                    let text_state = &mut state.text_state;
+                    // The adjustment moves the next glyph against the writing direction,
+                    // in text space, so it applies before the text matrix.
+                    let (tx, ty) = if text_state.font.is_some_and(Font::is_vertical) {
+                        (0.0, -amount * text_state.font_size)
+                    } else {
+                        let tx = -amount
+                            * text_state.font_size
+                            * (text_state.horizontal_scaling / 100.0);
+                        (tx, 0.0)
+                    };
+                    let mut advanced = Transform::from_translate(tx, ty);
+                    advanced.concat(&text_state.matrix);
+                    text_state.matrix = advanced;
                 }
                 TextElement::HexString { value } => {
                     self.show_text(value)?;
@@ -214,10 +247,8 @@ impl<T: std::error::Error> TextShowingOps for PdfCanvas<'_, T> {
     }
 
     fn move_to_next_line_and_show_text(&mut self, text: &[u8]) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented(format!(
-            "move_to_next_line_and_show_text ' (text_len={})",
-            text.len()
-        )))
+        self.move_to_start_of_next_line()?;
+        self.show_text(text)
     }
 
     fn set_spacing_and_show_text(
@@ -226,11 +257,30 @@ impl<T: std::error::Error> TextShowingOps for PdfCanvas<'_, T> {
         char_spacing: f32,
         text: &[u8],
     ) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented(format!(
-            "set_spacing_and_show_text \" : word_spacing={}, char_spacing={}, text_len={}",
-            word_spacing,
-            char_spacing,
-            text.len()
-        )))
+        self.set_word_spacing(word_spacing)?;
+        self.set_character_spacing(char_spacing)?;
+        self.move_to_next_line_and_show_text(text)
+    }
+}
+
+impl<T: std::error::Error> Type3FontOps for PdfCanvas<'_, T> {
+    fn set_char_width(&mut self, _wx: f32, _wy: f32) -> Result<(), Self::ErrorType> {
+        // The glyph description may set colours; advances come from the font's `/Widths`.
+        Ok(())
+    }
+
+    fn set_char_width_and_bounding_box(
+        &mut self,
+        _wx: f32,
+        _wy: f32,
+        _llx: f32,
+        _lly: f32,
+        _urx: f32,
+        _ury: f32,
+    ) -> Result<(), Self::ErrorType> {
+        // The flag lives in the graphics state saved around the glyph, so it is
+        // cleared when the glyph description ends.
+        self.current_state_mut()?.ignore_color_ops = true;
+        Ok(())
     }
 }
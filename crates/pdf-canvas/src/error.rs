@@ -1,4 +1,10 @@
+use pdf_content_stream::error::PdfOperatorError;
 use pdf_font::cff::error::CompactFontFormatError;
+use pdf_page::{
+    color_space::ColorSpaceError,
+    function::FunctionInterpolationError,
+    image::{ImageDecodeError, ImageXObjectError},
+};
 use thiserror::Error;
 
 use crate::truetype_font_renderer::TrueTypeFontRendererError;
@@ -21,6 +27,18 @@ pub enum PdfCanvasError {
     FontNotFound(String),
     #[error("Pattern '{0}' not found")]
     PatternNotFound(String),
+    #[error("Shading '{0}' not found")]
+    ShadingNotFound(String),
+    #[error("Shading function evaluation error: {0}")]
+    FunctionError(#[from] FunctionInterpolationError),
+    #[error("Colour space '{0}' not found")]
+    ColorSpaceNotFound(String),
+    #[error("Colour conversion error: {0}")]
+    ColorSpaceError(#[from] ColorSpaceError),
+    #[error("Image decoding error: {0}")]
+    ImageDecodeError(#[from] ImageDecodeError),
+    #[error("Inline image error: {0}")]
+    InlineImageError(#[from] ImageXObjectError),
     #[error("Graphics state dictionary '{0}' not found in resources")]
     GraphicsStateNotFound(String),
     #[error("Font '{0}' is a Type3 font but is missing its definition data")]
@@ -37,14 +55,14 @@ pub enum PdfCanvasError {
     Type3FontError(#[from] Type3FontRendererError),
     #[error("Extrenal object '{0}' not found in resources")]
     XObjectNotFound(String),
-    #[error("Page missing media box")]
-    MissingMediaBox,
     #[error("Failed numeric conversion: {0}")]
     NumericConversionError(&'static str),
     #[error("Not implemented: {0}")]
     NotImplemented(String),
     #[error("Compact font format parsing error: {0}")]
     CompactFontFormatError(#[from] CompactFontFormatError),
+    #[error("Content stream operator error: {0}")]
+    OperatorError(#[from] PdfOperatorError),
     #[error("Canvas backend error: {0}")]
     BackendError(String),
 }
@@ -1,8 +1,6 @@
 use error::PdfCanvasError;
 use pdf_canvas::PdfCanvas;
-use pdf_content_stream::pdf_operator_backend::{
-    PdfOperatorBackend, PdfOperatorBackendError, ShadingOps,
-};
+use pdf_content_stream::pdf_operator_backend::{PdfOperatorBackend, PdfOperatorBackendError};
 
 mod canvas;
 pub mod canvas_backend;
@@ -12,6 +10,7 @@ mod canvas_external_object_ops;
 mod canvas_graphics_state_ops;
 mod canvas_marked_content_ops;
 mod canvas_path_ops;
+mod canvas_shading_ops;
 mod canvas_state;
 mod canvas_text_ops;
 pub mod error;
@@ -25,12 +24,6 @@ mod type3_font_renderer;
 
 impl<T: std::error::Error> PdfOperatorBackend for PdfCanvas<'_, T> {}
 
-impl<T: std::error::Error> ShadingOps for PdfCanvas<'_, T> {
-    fn paint_shading(&mut self, _shading_name: &str) -> Result<(), Self::ErrorType> {
-        Err(PdfCanvasError::NotImplemented("paint_shading".into()))
-    }
-}
-
 impl<T> PdfOperatorBackendError for PdfCanvas<'_, T> {
     type ErrorType = PdfCanvasError;
 }
@@ -1,12 +1,25 @@
-use num_traits::FromPrimitive;
+use std::borrow::Cow;
+
 use pdf_content_stream::pdf_operator::PdfOperatorVariant;
-use pdf_graphics::{MaskMode, PaintMode, PathFillType, pdf_path::PdfPath, transform::Transform};
-use pdf_page::{page::PdfPage, pattern::Pattern, resources::Resources, shading::Shading};
+use pdf_graphics::color_management::IccProfile;
+use pdf_graphics::{BlendMode, PaintMode, PathFillType, pdf_path::PdfPath, transform::Transform};
+use pdf_page::{
+    annotation::AppearanceKind,
+    color_space::{ColorConversion, ColorSpace},
+    form::FormXObject,
+    optional_content::{OptionalContent, OptionalContentProperties},
+    page::PdfPage,
+    page_box::PageBox,
+    pattern::Pattern,
+    resources::Resources,
+    shading::{ColorStops, Shading},
+    transparency_group::TransparencyGroup,
+};
 
 use crate::{
     canvas::Canvas,
-    canvas_backend::{CanvasBackend, Shader},
-    canvas_state::CanvasState,
+    canvas_backend::{CanvasBackend, GroupLayer, Shader},
+    canvas_state::{CanvasState, LiftedLayer, StateLayer},
     error::PdfCanvasError,
     recording_canvas::RecordingCanvas,
     text_state::TextState,
@@ -17,31 +30,73 @@ pub struct PdfCanvas<'a, T> {
     pub(crate) current_path: Option<PdfPath>,
     /// The drawing backend implementing `CanvasBackend` for rendering operations.
     pub(crate) canvas: &'a mut dyn CanvasBackend<ErrorType = T>,
-    /// An optional mask surface for advanced compositing or clipping.
-    pub(crate) mask: Option<(Box<RecordingCanvas>, MaskMode)>,
+    /// The clipping paths and soft masks pushed onto the backend by the states on
+    /// the stack, innermost last.
+    pub(crate) layers: Vec<StateLayer>,
+    /// Layers of enclosing states taken down to lift an inherited soft mask, in
+    /// the order they were popped.
+    pub(crate) lifted_layers: Vec<LiftedLayer>,
     /// The PDF page associated with this canvas.
     pub(crate) page: &'a PdfPage,
     /// The stack of graphics states, supporting save/restore semantics.
     pub(crate) canvas_stack: Vec<CanvasState<'a>>,
+    /// Glyph outlines shown in a clipping text rendering mode since `BT`, in device
+    /// space. They become the clipping path at `ET`.
+    pub(crate) text_clip: Option<PdfPath>,
+    /// The fill rule of a `W` or `W*` awaiting the next path-painting operator,
+    /// which turns the current path into a clip after painting it.
+    pub(crate) pending_clip: Option<PathFillType>,
+    /// Whether colours are converted with ICC profiles.
+    pub(crate) color_management: bool,
+    /// The document's output intent profile, characterising the device colour spaces.
+    pub(crate) output_intent: Option<&'a IccProfile>,
+    /// The document's optional content, deciding which layers are drawn.
+    pub(crate) optional_content: Option<&'a OptionalContentProperties>,
+    /// For each marked-content sequence entered, innermost last, whether it is
+    /// optional content that is hidden. Nothing is painted inside a hidden one.
+    pub(crate) marked_content: Vec<bool>,
+    /// The region of the page that is drawn, in default user space.
+    pub(crate) visible_box: PageBox,
+    /// The number of states on the stack when the content stream being rendered
+    /// began. Its `Q` operators cannot restore past them.
+    pub(crate) stream_base: usize,
 }
 
 impl<T: std::error::Error> Canvas for PdfCanvas<'_, T> {
     fn save(&mut self) -> Result<(), PdfCanvasError> {
         let mut state = self.current_state()?.clone();
-        state.clip_path = None;
+        state.layer_depth = 0;
 
         self.canvas_stack.push(state);
         Ok(())
     }
 
     fn restore(&mut self) -> Result<(), PdfCanvasError> {
+        // The backend returns to the exact clipping region of the restored state,
+        // and soft masks set since the save are applied.
         let prev = self.canvas_stack.pop();
-        if let Some(state) = prev
-            && state.clip_path.is_some()
+        for _ in 0..prev.map_or(0, |state| state.layer_depth) {
+            self.pop_layer()?;
+        }
+
+        // An inherited soft mask lifted by the restored state takes effect again.
+        let restored = self.canvas_stack.len();
+        while let Some(lifted) = self
+            .lifted_layers
+            .pop_if(|lifted| lifted.lifted_by >= restored)
         {
-            self.canvas
-                .reset_clip()
-                .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+            match &lifted.layer {
+                StateLayer::Clip { path, mode } => self.canvas.push_clip_region(path, *mode),
+                StateLayer::SoftMask(mask) => {
+                    self.canvas
+                        .begin_mask_layer(&mask.content, &mask.transform, mask.mode)
+                }
+            }
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+            if let Some(owner) = self.canvas_stack.get_mut(lifted.owner) {
+                owner.layer_depth = owner.layer_depth.saturating_add(1);
+            }
+            self.layers.push(lifted.layer);
         }
         Ok(())
     }
@@ -51,8 +106,22 @@ impl<T: std::error::Error> Canvas for PdfCanvas<'_, T> {
         Ok(())
     }
 
-    fn fill_path(&mut self, path: &PdfPath, fill_type: PathFillType) -> Result<(), PdfCanvasError> {
-        self.draw_path(path, PaintMode::Fill, fill_type)
+    fn paint_glyph(&mut self, path: &PdfPath) -> Result<(), PdfCanvasError> {
+        let rendering_mode = self.current_state()?.text_state.rendering_mode;
+        if rendering_mode.clips() {
+            self.text_clip
+                .get_or_insert_with(PdfPath::default)
+                .append(path);
+        }
+        match rendering_mode.paint_mode() {
+            Some(mode) => self.draw_path(path, mode, PathFillType::Winding),
+            None => Ok(()),
+        }
+    }
+
+    fn begin_glyph_description(&mut self) -> Result<(), PdfCanvasError> {
+        self.current_state_mut()?.in_glyph_description = true;
+        Ok(())
     }
 }
 
@@ -62,11 +131,16 @@ where
 {
     /// Creates a new `PdfCanvas` for rendering PDF graphics onto a backend surface.
     ///
+    /// The page's crop box is rotated by its `/Rotate` and scaled to fill the
+    /// backend, with its lower-left corner moved to the origin. Drawing is
+    /// clipped to that region. An offscreen canvas given `bb` instead scales
+    /// `bb` to the backend, without moving or rotating it.
+    ///
     /// # Parameters
     ///
     /// - `backend`: The drawing backend implementing `CanvasBackend`.
     /// - `page`: The PDF page to render.
-    /// - `bb`: Optional bounding box to override the page's media box.
+    /// - `bb`: The bounding box of an offscreen canvas, or `None` for the page.
     ///
     /// # Returns
     ///
@@ -76,50 +150,70 @@ where
         page: &'a PdfPage,
         bb: Option<&[f32; 4]>,
     ) -> Result<Self, PdfCanvasError> {
-        let media_box = &page.media_box;
+        // An offscreen canvas, for a pattern cell or a soft mask, draws `bb` in its
+        // own coordinates; only a page has its crop box moved and rotated.
+        let (visible_box, origin, rotate) = match bb {
+            Some(&[x0, y0, x1, y1]) => (PageBox::new(x0, y0, x1, y1), (0.0, 0.0), 0),
+            None => (
+                page.crop_box,
+                (page.crop_box.left, page.crop_box.bottom),
+                page.rotate,
+            ),
+        };
+        let (width, height) = (visible_box.width(), visible_box.height());
 
-        let (pdf_media_width, pdf_media_height) = if let Some(bb) = bb {
-            (bb[2] - bb[0], bb[3] - bb[1])
-        } else if let Some(mb) = media_box.as_ref() {
-            (
-                f32::from_u32(mb.width())
-                    .ok_or(PdfCanvasError::NumericConversionError("u32 to f32 width"))?,
-                f32::from_u32(mb.height())
-                    .ok_or(PdfCanvasError::NumericConversionError("u32 to f32 height"))?,
-            )
-        } else {
-            (0.0, 0.0)
+        // Rotates the visible box, moved to the origin, clockwise by `/Rotate`
+        // while keeping it in the positive quadrant.
+        let (rotation, rotated_width, rotated_height) = match rotate {
+            90 => (
+                Transform::from_row(0.0, -1.0, 1.0, 0.0, 0.0, width),
+                height,
+                width,
+            ),
+            180 => (
+                Transform::from_row(-1.0, 0.0, 0.0, -1.0, width, height),
+                width,
+                height,
+            ),
+            270 => (
+                Transform::from_row(0.0, 1.0, -1.0, 0.0, height, 0.0),
+                height,
+                width,
+            ),
+            _ => (Transform::default(), width, height),
         };
 
         let backend_canvas_width = backend.width();
         let backend_canvas_height = backend.height();
 
         // Calculate scale factors.
-        let scale_x = if pdf_media_width != 0.0 {
-            backend_canvas_width / pdf_media_width
+        let scale_x = if rotated_width != 0.0 {
+            backend_canvas_width / rotated_width
         } else {
             1.0
         };
 
-        let scale_y = if pdf_media_height != 0.0 {
-            backend_canvas_height / pdf_media_height
+        let scale_y = if rotated_height != 0.0 {
+            backend_canvas_height / rotated_height
         } else {
             1.0
         };
 
-        // Directly construct the userspace transformation matrix.
-        // This matrix performs the following operations on PDF coordinates (px, py):
-        // 1. Scales them: (px * scale_x, py * scale_y)
-        // 2. Flips the Y-axis and translates it: Y_canvas = backend_canvas_height - (py * scale_y)
-        // Resulting canvas coordinates: (px * scale_x, backend_canvas_height - py * scale_y)
-        let userspace_matrix = Transform::from_row(
+        // The userspace transformation matrix maps PDF coordinates (px, py) by:
+        // 1. Translating the crop box's lower-left corner to the origin.
+        // 2. Rotating the page clockwise by `/Rotate`.
+        // 3. Scaling, then flipping the Y-axis so that it points down, with the
+        //    origin at the top-left: (x * scale_x, backend_canvas_height - y * scale_y).
+        let mut userspace_matrix = Transform::from_row(1.0, 0.0, 0.0, 1.0, -origin.0, -origin.1);
+        userspace_matrix.concat(&rotation);
+        userspace_matrix.concat(&Transform::from_row(
             scale_x,               // sx: Scale X
             0.0,                   // ky: Skew Y (none)
             0.0,                   // kx: Skew X (none)
             -scale_y,              // sy: Scale Y and reflect (Y points down)
             0.0,                   // tx: Translate X (none)
             backend_canvas_height, // ty: Translate Y to move origin to top-left after reflection
-        );
+        ));
 
         let canvas_stack = vec![CanvasState {
             transform: userspace_matrix,
@@ -130,9 +224,69 @@ where
         Ok(Self {
             current_path: None,
             canvas: backend,
-            mask: None,
+            layers: Vec::new(),
+            lifted_layers: Vec::new(),
             page,
             canvas_stack,
+            text_clip: None,
+            pending_clip: None,
+            color_management: true,
+            output_intent: None,
+            optional_content: None,
+            marked_content: Vec::new(),
+            stream_base: 0,
+            visible_box,
+        })
+    }
+
+    /// Configures how colours are converted to sRGB.
+    ///
+    /// # Parameters
+    ///
+    /// - `enabled`: Whether ICC profiles are used. Turning this off is faster, and
+    ///   converts ICC-based colours through their alternate spaces.
+    /// - `output_intent`: The document's output intent profile, if any.
+    pub fn with_color_management(
+        mut self,
+        enabled: bool,
+        output_intent: Option<&'a IccProfile>,
+    ) -> Self {
+        self.color_management = enabled;
+        self.output_intent = output_intent;
+        self
+    }
+
+    /// Sets the document's optional content, whose hidden layers are not drawn.
+    ///
+    /// Without it, all optional content is drawn.
+    pub fn with_optional_content(
+        mut self,
+        optional_content: Option<&'a OptionalContentProperties>,
+    ) -> Self {
+        self.optional_content = optional_content;
+        self
+    }
+
+    /// Returns whether `content` belongs to a hidden layer.
+    pub(crate) fn is_optional_content_hidden(&self, content: Option<&OptionalContent>) -> bool {
+        match (self.optional_content, content) {
+            (Some(properties), Some(content)) => !properties.is_visible(content),
+            _ => false,
+        }
+    }
+
+    /// Returns whether painting is suppressed because a hidden optional content
+    /// sequence is open. Graphics state operators still take effect.
+    pub(crate) fn is_content_hidden(&self) -> bool {
+        self.marked_content.iter().any(|&hidden| hidden)
+    }
+
+    /// Returns the colour conversion options for the current graphics state.
+    pub(crate) fn color_conversion(&self) -> Result<ColorConversion<'a>, PdfCanvasError> {
+        Ok(ColorConversion {
+            color_management: self.color_management,
+            rendering_intent: self.current_state()?.rendering_intent,
+            output_intent: self.output_intent,
         })
     }
 
@@ -178,23 +332,29 @@ where
         match shading {
             Shading::Axial {
                 coords: [x0, y0, x1, y1],
-                positions,
-                colors,
+                color_space,
+                function,
                 ..
-            } => Ok(Shader::LinearGradient {
-                x0: *x0,
-                y0: *y0,
-                x1: *x1,
-                y1: *y1,
-                colors,
-                positions,
-            }),
+            } => {
+                let ColorStops { colors, positions } =
+                    ColorStops::from(function, color_space, &self.color_conversion()?);
+                Ok(Shader::LinearGradient {
+                    x0: *x0,
+                    y0: *y0,
+                    x1: *x1,
+                    y1: *y1,
+                    colors: Cow::Owned(colors),
+                    positions: Cow::Owned(positions),
+                })
+            }
             Shading::Radial {
                 coords: [start_x, start_y, start_r, end_x, end_y, end_r],
-                positions,
-                colors,
+                color_space,
+                function,
                 ..
             } => {
+                let ColorStops { colors, positions } =
+                    ColorStops::from(function, color_space, &self.color_conversion()?);
                 let transform = matrix.map(|mut mat| {
                     mat.ty = self.canvas.height() - mat.ty;
                     mat
@@ -207,23 +367,29 @@ where
                     end_y: *end_y,
                     end_r: *end_r,
                     transform,
-                    colors,
-                    positions,
+                    colors: Cow::Owned(colors),
+                    positions: Cow::Owned(positions),
                 })
             }
             Shading::FunctionBased { .. } => Err(PdfCanvasError::NotImplemented(
-                "FunctionBased shading not implemented".into(),
+                "stroking with a function-based shading pattern".into(),
             )),
+            Shading::TriangleMesh { .. } | Shading::PatchMesh { .. } => Err(
+                PdfCanvasError::NotImplemented("stroking with a mesh shading pattern".into()),
+            ),
         }
     }
 
-    /// Computes the current shader based on the active pattern.
+    /// Computes the shader for the given fill or stroke pattern.
     ///
     /// # Returns
     ///
     /// An optional `Shader` or an error if pattern rendering fails.
-    fn compute_shader(&mut self) -> Result<Option<Shader<'a>>, PdfCanvasError> {
-        let Some(pattern) = self.current_state()?.pattern else {
+    fn compute_shader(
+        &mut self,
+        pattern: Option<&'a Pattern>,
+    ) -> Result<Option<Shader<'a>>, PdfCanvasError> {
+        let Some(pattern) = pattern else {
             return Ok(None);
         };
 
@@ -245,7 +411,9 @@ where
                     RecordingCanvas::new(bbox[2] - bbox[0], bbox[3] - bbox[1]);
 
                 // Render the tiling content into a temporary canvas.
-                let mut other = PdfCanvas::new(&mut recording_canvas, self.page, Some(bbox))?;
+                let mut other = PdfCanvas::new(&mut recording_canvas, self.page, Some(bbox))?
+                    .with_color_management(self.color_management, self.output_intent)
+                    .with_optional_content(self.optional_content);
                 other.render_content_stream(&content_stream.operations, None, Some(resources))?;
                 let shader = Shader::TilingPatternImage {
                     image: Box::new(recording_canvas),
@@ -260,6 +428,12 @@ where
 
     /// Draws a path using the specified paint mode and fill type, applying any active shader or pattern.
     ///
+    /// For `FillAndStroke` the path is filled first and then stroked, so the stroke
+    /// covers the inner half of its width. Each half uses its own colour and pattern,
+    /// and both share the current blend mode. When either is translucent, both are
+    /// painted into an isolated knockout group so that the stroke replaces the fill
+    /// beneath it instead of compositing over it (ISO 32000-1, 11.7.4.4).
+    ///
     /// # Parameters
     ///
     /// - `path`: The path to draw.
@@ -268,43 +442,97 @@ where
     ///
     /// # Errors
     ///
-    /// Returns an error if the paint mode is not implemented or if pattern computation fails.
-    fn draw_path(
+    /// Returns an error if pattern computation or drawing fails.
+    pub(crate) fn draw_path(
         &mut self,
         path: &PdfPath,
         mode: PaintMode,
         fill_type: PathFillType,
     ) -> Result<(), PdfCanvasError> {
-        let shader = self.compute_shader()?;
+        if self.is_content_hidden() {
+            return Ok(());
+        }
+        let state = self.current_state()?;
+        if mode != PaintMode::FillAndStroke
+            || (state.fill_color.a >= 1.0 && state.stroke_color.a >= 1.0)
+        {
+            return self.paint_path(path, &mode, fill_type);
+        }
+
+        let layer = GroupLayer {
+            isolated: true,
+            knockout: true,
+            alpha: 1.0,
+            blend_mode: state.blend_mode,
+        };
+        self.canvas
+            .begin_transparency_group(&layer)
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
 
-        match mode {
-            PaintMode::Fill => {
+        self.save()?;
+        let state = self.current_state_mut()?;
+        state.blend_mode = None;
+        state.knockout = true;
+        let painted = self.paint_path(path, &mode, fill_type);
+        self.restore()?;
+
+        self.canvas
+            .end_transparency_group()
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        painted
+    }
+
+    /// Fills and/or strokes a path with the current colours, patterns and blend mode.
+    fn paint_path(
+        &mut self,
+        path: &PdfPath,
+        mode: &PaintMode,
+        fill_type: PathFillType,
+    ) -> Result<(), PdfCanvasError> {
+        if matches!(mode, PaintMode::Fill | PaintMode::FillAndStroke) {
+            // Backends paint axial and radial gradients with a shader; other shadings
+            // are painted piece by piece inside the path.
+            if let Some(Pattern::Shading {
+                shading, matrix, ..
+            }) = self.current_state()?.fill_pattern
+                && !matches!(shading, Shading::Axial { .. } | Shading::Radial { .. })
+            {
+                self.fill_with_shading_pattern(path, fill_type, shading, matrix)?;
+            } else {
+                let shader = self.compute_shader(self.current_state()?.fill_pattern)?;
                 self.canvas
                     .fill_path(
                         path,
                         fill_type,
                         self.current_state()?.fill_color,
                         &shader,
-                        self.current_state()?.blend_mode,
+                        self.shape_blend_mode()?,
                     )
                     .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
             }
-            PaintMode::Stroke => {
-                self.canvas
-                    .stroke_path(
-                        path,
-                        self.current_state()?.stroke_color,
-                        self.current_state()?.line_width,
-                        &shader,
-                        self.current_state()?.blend_mode,
-                    )
-                    .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
-            }
-            PaintMode::FillAndStroke => {
-                return Err(PdfCanvasError::NotImplemented(
-                    "FillAndStroke mode is not implemented".into(),
-                ));
-            }
+        }
+        if matches!(mode, PaintMode::Stroke | PaintMode::FillAndStroke) {
+            let state = self.current_state()?;
+            let (color, pattern) = (state.stroke_color, state.stroke_pattern);
+            let (color, shader) = match pattern {
+                // A stroke cannot be painted piece by piece, so shadings without a
+                // backend shader are approximated by their average colour.
+                Some(Pattern::Shading { shading, .. })
+                    if !matches!(shading, Shading::Axial { .. } | Shading::Radial { .. }) =>
+                {
+                    (self.shading_average_color(shading, color.a)?, None)
+                }
+                pattern => (color, self.compute_shader(pattern)?),
+            };
+            self.canvas
+                .stroke_path(
+                    path,
+                    color,
+                    &self.current_state()?.device_stroke_style(),
+                    &shader,
+                    self.shape_blend_mode()?,
+                )
+                .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
         }
         Ok(())
     }
@@ -328,67 +556,141 @@ where
             return Err(PdfCanvasError::NoActivePath);
         };
         path.transform(&self.current_state()?.transform);
-        self.draw_path(&path, mode, fill_type)
+
+        // Inside a Type 3 glyph the glyph description decides how to paint, but the
+        // text rendering mode still decides whether to paint and whether to clip.
+        let state = self.current_state()?;
+        let mut paint = true;
+        if state.in_glyph_description {
+            let rendering_mode = state.text_state.rendering_mode;
+            if rendering_mode.clips() {
+                self.text_clip
+                    .get_or_insert_with(PdfPath::default)
+                    .append(&path);
+            }
+            paint = rendering_mode.paint_mode().is_some();
+        }
+        if paint {
+            self.draw_path(&path, mode, fill_type)?;
+        }
+
+        // A preceding `W` or `W*` clips to the path once it has been painted.
+        if let Some(clip_mode) = self.pending_clip.take() {
+            self.set_device_clip_path(path, clip_mode)?;
+        }
+        Ok(())
     }
 
-    /// Sets the clipping path for subsequent drawing operations.
+    /// Intersects the clipping path with a path already in device space.
+    ///
+    /// The clip lasts until the graphics state in effect is restored.
     ///
     /// # Parameters
     ///
-    /// - `path`: The path to use as the new clipping region.
+    /// - `path`: The path to intersect the clipping region with, in device space.
     /// - `mode`: The fill rule for the clipping path.
     ///
     /// # Errors
     ///
-    /// Returns an error if the graphics state is invalid.
-    pub(crate) fn set_clip_path(
+    /// Returns an error if the graphics state is invalid or the backend fails.
+    pub(crate) fn set_device_clip_path(
         &mut self,
-        mut path: PdfPath,
+        path: PdfPath,
         mode: PathFillType,
     ) -> Result<(), PdfCanvasError> {
-        path.transform(&self.current_state()?.transform);
-        if self.current_state()?.clip_path.is_some() {
-            self.canvas
-                .reset_clip()
-                .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
-        }
-
         self.canvas
-            .set_clip_region(&path, mode)
+            .push_clip_region(&path, mode)
             .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
-        self.current_state_mut()?.clip_path = Some(path);
+        self.push_layer(StateLayer::Clip { path, mode })
+    }
+
+    /// Records a layer just pushed onto the backend as part of the current state.
+    pub(crate) fn push_layer(&mut self, layer: StateLayer) -> Result<(), PdfCanvasError> {
+        let state = self.current_state_mut()?;
+        state.layer_depth = state.layer_depth.saturating_add(1);
+        self.layers.push(layer);
         Ok(())
     }
 
-    /// Sets the current pattern by name from the page resources.
+    /// Pops the innermost backend layer and undoes it: a clipping path is removed,
+    /// and a soft mask is applied to what was drawn while it was in effect.
+    ///
+    /// The caller accounts for the layer in the depth of the state that pushed it.
+    pub(crate) fn pop_layer(&mut self) -> Result<Option<StateLayer>, PdfCanvasError> {
+        let layer = self.layers.pop();
+        match &layer {
+            Some(StateLayer::Clip { .. }) => self.canvas.pop_clip_region(),
+            Some(StateLayer::SoftMask(mask)) => self.canvas.end_mask_layer(
+                &mask.content,
+                &mask.transform,
+                mask.mode,
+                mask.transfer.as_deref(),
+            ),
+            None => Ok(()),
+        }
+        .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        Ok(layer)
+    }
+
+    /// Looks up a pattern by name, first in the page resources and then in the
+    /// current resource dictionary.
     ///
     /// # Parameters
     ///
-    /// - `pattern_name`: The name of the pattern to activate.
+    /// - `pattern_name`: The name of the pattern to look up.
     ///
     /// # Errors
     ///
     /// Returns an error if the pattern is not found in the resources.
-    pub(crate) fn set_pattern(&mut self, pattern_name: &str) -> Result<(), PdfCanvasError> {
-        let Some(pattern) = self
-            .page
+    pub(crate) fn find_pattern(&self, pattern_name: &str) -> Result<&'a Pattern, PdfCanvasError> {
+        self.page
             .resources
             .as_ref()
             .and_then(|r| r.patterns.get(pattern_name))
-        else {
-            if let Some(pattern) = self
-                .current_state()?
-                .resources
-                .and_then(|r| r.patterns.get(pattern_name))
-            {
-                self.current_state_mut()?.pattern = Some(pattern);
-                return Ok(());
-            }
-            return Err(PdfCanvasError::PatternNotFound(pattern_name.to_string()));
-        };
+            .or_else(|| {
+                self.current_state()
+                    .ok()?
+                    .resources
+                    .and_then(|r| r.patterns.get(pattern_name))
+            })
+            .ok_or_else(|| PdfCanvasError::PatternNotFound(pattern_name.to_string()))
+    }
 
-        self.current_state_mut()?.pattern = Some(pattern);
-        Ok(())
+    /// Looks up a shading by name in the current resource dictionary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the shading is not found in the resources.
+    pub(crate) fn find_shading(&self, shading_name: &str) -> Result<&'a Shading, PdfCanvasError> {
+        self.get_resources()
+            .ok()
+            .and_then(|r| r.shadings.get(shading_name))
+            .ok_or_else(|| PdfCanvasError::ShadingNotFound(shading_name.to_string()))
+    }
+
+    /// Resolves the operand of `CS` or `cs`: a colour space family name, or the name
+    /// of an entry in the current `/ColorSpace` resource dictionary.
+    ///
+    /// The device families are replaced by the resources' `DefaultGray`,
+    /// `DefaultRGB` or `DefaultCMYK` colour space when one is defined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is neither a family nor a named colour space.
+    pub(crate) fn find_color_space(&self, name: &str) -> Result<&'a ColorSpace, PdfCanvasError> {
+        let resources = self.get_resources().ok();
+        let named = |key: &str| resources.and_then(|r| r.color_spaces.get(key));
+        let default_name = match name {
+            "DeviceGray" => Some("DefaultGray"),
+            "DeviceRGB" => Some("DefaultRGB"),
+            "DeviceCMYK" => Some("DefaultCMYK"),
+            _ => None,
+        };
+        default_name
+            .and_then(named)
+            .or_else(|| ColorSpace::from_family_name(name))
+            .or_else(|| named(name))
+            .ok_or_else(|| PdfCanvasError::ColorSpaceNotFound(name.to_string()))
     }
 
     /// Returns the current resource dictionary, or the page's resources if not overridden.
@@ -424,16 +726,44 @@ where
         mat: Option<Transform>,
         resources: Option<&'a Resources>,
     ) -> Result<(), PdfCanvasError> {
+        let (depth, layer_count, outer_base) =
+            (self.canvas_stack.len(), self.layers.len(), self.stream_base);
+        // Marked-content sequences are scoped to the stream that opens them, but
+        // those open around it still apply to its content.
+        let outer_marked_content = self.marked_content.clone();
         self.save()?;
+        self.stream_base = self.canvas_stack.len();
+        let rendered = self.run_content_stream(operations, mat, resources);
 
+        // An error or an unbalanced `q` leaves states, and the clips and masks
+        // they pushed, on the stack.
+        let mut unwound = Ok(());
+        while self.canvas_stack.len() > depth && unwound.is_ok() {
+            unwound = self.restore();
+        }
+        while self.layers.len() > layer_count && unwound.is_ok() {
+            unwound = self.pop_layer().map(|_| ());
+        }
+        self.stream_base = outer_base;
+        self.marked_content = outer_marked_content;
+        rendered.and(unwound)
+    }
+
+    fn run_content_stream(
+        &mut self,
+        operations: &[PdfOperatorVariant],
+        mat: Option<Transform>,
+        resources: Option<&'a Resources>,
+    ) -> Result<(), PdfCanvasError> {
         if let Some(mat) = mat {
             // Concatenate the provided Form/XObject matrix with the current CTM.
             // PDF spec: invoking a form XObject with its /Matrix entry performs a
-            // concatenation like the 'cm' operator does. The operation is:
-            //   CTM' = FormMatrix * CTM
-            // Our Transform::concat(other) implements self = other * self (pre-multiply),
-            // so we just call concat with the form matrix.
-            self.current_state_mut()?.transform.concat(&mat);
+            // concatenation like the 'cm' operator does: the form matrix maps form
+            // space to user space, which the CTM then maps to device space.
+            let state = self.current_state_mut()?;
+            let mut transform = mat;
+            transform.concat(&state.transform);
+            state.transform = transform;
         }
 
         if let Some(resources) = resources {
@@ -443,7 +773,712 @@ where
         for op in operations {
             op.call(self)?;
         }
+        Ok(())
+    }
+
+    /// Renders the page's content stream, as a transparency group if the page
+    /// has a `/Group`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any operation fails or if the graphics state is invalid.
+    pub fn render_page(&mut self) -> Result<(), PdfCanvasError> {
+        let page = self.page;
+        let Some(contents) = &page.contents else {
+            return Ok(());
+        };
+        self.save()?;
+        let rendered = self.clip_to_visible_box().and_then(|()| match &page.group {
+            Some(group) => self.render_transparency_group(group, |canvas| {
+                canvas.render_content_stream(&contents.operations, None, None)
+            }),
+            None => self.render_content_stream(&contents.operations, None, None),
+        });
+        self.restore()?;
+        rendered
+    }
+
+    /// Draws the normal appearances of the page's annotations over its content.
+    ///
+    /// Each appearance is mapped from its `/BBox` and `/Matrix` onto the
+    /// annotation's `/Rect`. Hidden and no-view annotations, annotations in hidden
+    /// layers, and annotations without an appearance are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first appearance stream that fails to render. The
+    /// annotations after it are still drawn.
+    pub fn render_annotations(&mut self) -> Result<(), PdfCanvasError> {
+        // Appearances are drawn in the page's initial state, whatever the page
+        // content left behind.
+        self.unwind_to_base_state()?;
+        let base = self
+            .canvas_stack
+            .first()
+            .ok_or(PdfCanvasError::EmptyGraphicsStateStack)?
+            .transform;
+        let page = self.page;
+        let mut first_error = None;
+        for annotation in &page.annotations {
+            if annotation.is_hidden()
+                || self.is_optional_content_hidden(annotation.optional_content.as_ref())
+            {
+                continue;
+            }
+            let Some(form) = annotation.appearance(AppearanceKind::Normal) else {
+                continue;
+            };
+            if self.is_optional_content_hidden(form.optional_content.as_ref()) {
+                continue;
+            }
+            let Some(mut transform) = annotation.appearance_transform(form) else {
+                continue;
+            };
+            transform.concat(&base);
+
+            self.save()?;
+            self.current_state_mut()?.transform = transform;
+            let rendered = self
+                .clip_to_visible_box()
+                .and_then(|()| self.render_form(form));
+            self.restore()?;
+            if let Err(error) = rendered {
+                first_error.get_or_insert(error);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Returns to the canvas's initial graphics state, ending the clips, soft
+    /// masks and marked-content sequences left open.
+    fn unwind_to_base_state(&mut self) -> Result<(), PdfCanvasError> {
+        while self.canvas_stack.len() > 1 {
+            self.restore()?;
+        }
+        while self.pop_layer()?.is_some() {}
+        if let Some(base) = self.canvas_stack.first_mut() {
+            base.layer_depth = 0;
+        }
+        self.lifted_layers.clear();
+        self.marked_content.clear();
+        self.stream_base = 0;
+        self.current_path = None;
+        self.pending_clip = None;
+        self.text_clip = None;
+        Ok(())
+    }
+
+    /// Clips to the visible region of the page until the current state is restored.
+    fn clip_to_visible_box(&mut self) -> Result<(), PdfCanvasError> {
+        let base = self
+            .canvas_stack
+            .first()
+            .ok_or(PdfCanvasError::EmptyGraphicsStateStack)?
+            .transform;
+        let PageBox {
+            left,
+            bottom,
+            right,
+            top,
+        } = self.visible_box;
+        let mut path = PdfPath::default();
+        path.move_to(left, bottom);
+        path.line_to(right, bottom);
+        path.line_to(right, top);
+        path.line_to(left, top);
+        path.close();
+        path.transform(&base);
+        self.set_device_clip_path(path, PathFillType::Winding)
+    }
+
+    /// Renders a form XObject, as a transparency group if it has a `/Group`.
+    pub(crate) fn render_form(&mut self, form: &'a FormXObject) -> Result<(), PdfCanvasError> {
+        let operations = &form.content_stream.operations;
+        let resources = form.resources.as_ref();
+        match &form.group {
+            Some(group) => self.render_transparency_group(group, |canvas| {
+                canvas.render_content_stream(operations, form.matrix, resources)
+            }),
+            None => self.render_content_stream(operations, form.matrix, resources),
+        }
+    }
+
+    /// Renders content into the offscreen layer of a transparency group, which is
+    /// then composited with the current alpha constant, blend mode and soft mask.
+    ///
+    /// The group's own objects start from an alpha of 1 and the normal blend
+    /// mode (ISO 32000-1, 11.6.6), since the group as a whole carries the current
+    /// ones.
+    ///
+    /// Known limitation: the group colour space `/CS` is ignored, because backends
+    /// only composite in their own RGB space. Groups whose `/CS` is not RGB, such
+    /// as CMYK groups with separable blend modes, can therefore blend to slightly
+    /// different colours than the specification prescribes.
+    ///
+    /// # Parameters
+    ///
+    /// - `group`: The group's attributes.
+    /// - `render`: Renders the group's content.
+    fn render_transparency_group(
+        &mut self,
+        group: &TransparencyGroup,
+        render: impl FnOnce(&mut Self) -> Result<(), PdfCanvasError>,
+    ) -> Result<(), PdfCanvasError> {
+        // TODO: Blend in `group.color_space` once backends can composite in it.
+        let state = self.current_state()?;
+        let layer = GroupLayer {
+            isolated: group.isolated,
+            knockout: group.knockout,
+            alpha: state.fill_color.a,
+            blend_mode: state.blend_mode,
+        };
+        self.canvas
+            .begin_transparency_group(&layer)
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+
+        self.save()?;
+        let state = self.current_state_mut()?;
+        state.fill_color.a = 1.0;
+        state.stroke_color.a = 1.0;
+        state.blend_mode = None;
+        state.knockout = group.knockout;
+        let rendered = render(self);
+        self.restore()?;
+
+        self.canvas
+            .end_transparency_group()
+            .map_err(|e| PdfCanvasError::BackendError(e.to_string()))?;
+        rendered
+    }
+
+    /// Returns the blend mode for filling or stroking a shape, or drawing an image.
+    ///
+    /// Inside a knockout group, objects painted with the normal blend mode replace
+    /// what lies beneath them within the group. Other blend modes still blend
+    /// with it, which only approximates knockout for them.
+    pub(crate) fn shape_blend_mode(&self) -> Result<Option<BlendMode>, PdfCanvasError> {
+        let state = self.current_state()?;
+        Ok(match state.blend_mode {
+            None | Some(BlendMode::Normal) if state.knockout => Some(BlendMode::Source),
+            blend_mode => blend_mode,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use pdf_document::PdfDocument;
+    use pdf_graphics::{MaskMode, color::Color};
+
+    use super::*;
+    use crate::recording_canvas::RecordingCommand;
+
+    fn stream(dictionary: &str, data: &str) -> String {
+        format!(
+            "<< {dictionary} /Length {} >>\nstream\n{data}\nendstream",
+            data.len()
+        )
+    }
+
+    /// Builds a one-page document. The page dictionary gets the `page` entries and
+    /// `content` as its content stream, and `objects` are numbered from 5.
+    fn document(page: &str, content: &str, objects: &[String]) -> PdfDocument {
+        let mut pdf = String::from(
+            "%PDF-1.7\n\
+             1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n\
+             2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n",
+        );
+        pdf.push_str(&format!(
+            "3 0 obj\n<< /Type /Page /Parent 2 0 R /Contents 4 0 R {page} >>\nendobj\n"
+        ));
+        pdf.push_str(&format!("4 0 obj\n{}\nendobj\n", stream("", content)));
+        for (id, object) in (5..).zip(objects) {
+            pdf.push_str(&format!("{id} 0 obj\n{object}\nendobj\n"));
+        }
+        pdf.push_str("trailer\n<< /Root 1 0 R >>\nstartxref\n0\n%%EOF\n");
+        PdfDocument::from(pdf.as_bytes()).unwrap()
+    }
 
-        self.restore()
+    /// Renders the page of `document` onto a 100 by 100 recording.
+    fn render(document: &PdfDocument) -> (RecordingCanvas, Result<(), PdfCanvasError>) {
+        let mut recording = RecordingCanvas::new(100.0, 100.0);
+        let rendered = PdfCanvas::new(&mut recording, &document.pages[0], None)
+            .and_then(|mut canvas| canvas.render_page());
+        (recording, rendered)
+    }
+
+    /// Describes the recorded commands, with the device-space bounds of paths
+    /// rounded to whole units.
+    fn trace(recording: &RecordingCanvas) -> Vec<String> {
+        let bounds = |path: &PdfPath| {
+            let [x0, y0, x1, y1] = path
+                .bounds()
+                .unwrap_or_default()
+                .map(|value| value.round() + 0.0);
+            format!("[{x0} {y0} {x1} {y1}]")
+        };
+        recording
+            .commands()
+            .iter()
+            .map(|command| match command {
+                RecordingCommand::FillPath { path, .. } => format!("fill {}", bounds(path)),
+                RecordingCommand::StrokePath { path, .. } => format!("stroke {}", bounds(path)),
+                RecordingCommand::PushClipRegion { path, .. } => format!("clip {}", bounds(path)),
+                RecordingCommand::PopClipRegion => "pop clip".to_string(),
+                RecordingCommand::DrawImage { .. } => "image".to_string(),
+                RecordingCommand::BeginMaskLayer { .. } => "begin mask".to_string(),
+                RecordingCommand::EndMaskLayer { .. } => "end mask".to_string(),
+                RecordingCommand::BeginTransparencyGroup { .. } => "begin group".to_string(),
+                RecordingCommand::EndTransparencyGroup => "end group".to_string(),
+            })
+            .collect()
+    }
+
+    fn group_layer(command: &RecordingCommand) -> Option<&GroupLayer> {
+        match command {
+            RecordingCommand::BeginTransparencyGroup { layer } => Some(layer),
+            _ => None,
+        }
+    }
+
+    /// Returns the colour and blend mode of a fill.
+    fn fill(command: &RecordingCommand) -> Option<(Color, Option<BlendMode>)> {
+        match command {
+            RecordingCommand::FillPath {
+                color, blend_mode, ..
+            } => Some((*color, *blend_mode)),
+            _ => None,
+        }
+    }
+
+    const SQUARE_PAGE: &str = "/MediaBox [0 0 100 100]";
+
+    #[test]
+    fn nested_clips_are_removed_by_their_own_restore() {
+        let document = document(
+            SQUARE_PAGE,
+            "q 0 0 50 50 re W n q 10 10 20 20 re W n 0 0 100 100 re f Q \
+             0 0 100 100 re f Q 0 0 100 100 re f",
+            &[],
+        );
+        let (recording, rendered) = render(&document);
+        rendered.unwrap();
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "clip [0 50 50 100]",
+                "clip [10 70 30 90]",
+                "fill [0 0 100 100]",
+                "pop clip",
+                "fill [0 0 100 100]",
+                "pop clip",
+                "fill [0 0 100 100]",
+                "pop clip",
+            ]
+        );
+    }
+
+    #[test]
+    fn unbalanced_saves_are_unwound_at_the_end_of_the_stream() {
+        let document = document(SQUARE_PAGE, "q 0 0 50 50 re W n q 0 0 20 20 re W n", &[]);
+        let (recording, rendered) = render(&document);
+        rendered.unwrap();
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "clip [0 50 50 100]",
+                "clip [0 80 20 100]",
+                "pop clip",
+                "pop clip",
+                "pop clip",
+            ]
+        );
+    }
+
+    #[test]
+    fn unmatched_restores_keep_the_clip() {
+        let document = document(SQUARE_PAGE, "0 0 50 50 re W n Q Q 0 0 100 100 re f", &[]);
+        let (recording, rendered) = render(&document);
+        rendered.unwrap();
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "clip [0 50 50 100]",
+                "fill [0 0 100 100]",
+                "pop clip",
+                "pop clip",
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_unwind_the_clips_pushed_so_far() {
+        let document = document(SQUARE_PAGE, "q 0 0 50 50 re W n /Missing gs", &[]);
+        let (recording, rendered) = render(&document);
+        assert!(rendered.is_err());
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "clip [0 50 50 100]",
+                "pop clip",
+                "pop clip",
+            ]
+        );
+    }
+
+    #[test]
+    fn form_groups_composite_with_the_current_alpha_and_blend_mode() {
+        let document = document(
+            "/MediaBox [0 0 100 100] \
+             /Resources << /XObject << /Fm1 5 0 R >> /ExtGState << /GS1 6 0 R >> >>",
+            "/GS1 gs /Fm1 Do 0 0 10 10 re f",
+            &[
+                stream(
+                    "/Type /XObject /Subtype /Form /BBox [0 0 100 100] \
+                     /Group << /S /Transparency /I true /K true >>",
+                    "0 0 10 10 re f",
+                ),
+                "<< /Type /ExtGState /ca 0.5 /BM /Multiply >>".to_string(),
+            ],
+        );
+        let (recording, rendered) = render(&document);
+        rendered.unwrap();
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "begin group",
+                "fill [0 90 10 100]",
+                "end group",
+                "fill [0 90 10 100]",
+                "pop clip",
+            ]
+        );
+
+        let commands = recording.commands();
+        let layer = group_layer(&commands[1]).unwrap();
+        assert!(layer.isolated && layer.knockout);
+        assert_eq!(layer.alpha, 0.5);
+        assert!(layer.blend_mode == Some(BlendMode::Multiply));
+        // Objects inside the group start from an opaque normal state, and knock
+        // out what the group painted before them.
+        let (color, blend_mode) = fill(&commands[2]).unwrap();
+        assert_eq!(color.a, 1.0);
+        assert!(blend_mode == Some(BlendMode::Source));
+        // The state outside the group is untouched.
+        let (color, blend_mode) = fill(&commands[4]).unwrap();
+        assert_eq!(color.a, 0.5);
+        assert!(blend_mode == Some(BlendMode::Multiply));
+    }
+
+    #[test]
+    fn page_groups_enclose_the_page_content_inside_its_clip() {
+        let document = document(
+            "/MediaBox [0 0 100 100] /Group << /S /Transparency >>",
+            "q 0 0 50 50 re W n",
+            &[],
+        );
+        let (recording, rendered) = render(&document);
+        rendered.unwrap();
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "begin group",
+                "clip [0 50 50 100]",
+                "pop clip",
+                "end group",
+                "pop clip",
+            ]
+        );
+    }
+
+    /// A luminosity soft mask over a white backdrop, whose transfer function
+    /// inverts it, and an `ExtGState` removing the soft mask.
+    fn soft_mask_objects() -> Vec<String> {
+        vec![
+            "<< /Type /ExtGState /SMask << /S /Luminosity /G 7 0 R /BC [1] \
+             /TR << /FunctionType 2 /Domain [0 1] /C0 [1] /C1 [0] /N 1 >> >> >>"
+                .to_string(),
+            "<< /Type /ExtGState /SMask /None >>".to_string(),
+            stream(
+                "/Type /XObject /Subtype /Form /BBox [0 0 100 100] \
+                 /Group << /S /Transparency /CS /DeviceGray >>",
+                "0 g 0 0 50 50 re f",
+            ),
+        ]
+    }
+
+    const SOFT_MASK_PAGE: &str =
+        "/MediaBox [0 0 100 100] /Resources << /ExtGState << /GS1 5 0 R /GS2 6 0 R >> >>";
+
+    #[test]
+    fn soft_masks_end_with_the_state_that_set_them() {
+        let document = document(
+            SOFT_MASK_PAGE,
+            "q /GS1 gs 0 0 10 10 re f Q 0 0 10 10 re f",
+            &soft_mask_objects(),
+        );
+        let (recording, rendered) = render(&document);
+        rendered.unwrap();
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "begin mask",
+                "fill [0 90 10 100]",
+                "end mask",
+                "fill [0 90 10 100]",
+                "pop clip",
+            ]
+        );
+
+        let RecordingCommand::EndMaskLayer {
+            mask,
+            mask_mode,
+            transfer,
+            ..
+        } = &recording.commands()[3]
+        else {
+            unreachable!("the trace has an end mask here");
+        };
+        assert!(*mask_mode == MaskMode::Luminosity);
+        let transfer = transfer.as_deref().unwrap();
+        assert_eq!((transfer[0], transfer[255]), (255, 0));
+        // The group is painted over its white backdrop.
+        let (backdrop, _) = fill(&mask.commands()[0]).unwrap();
+        assert_eq!((backdrop.r, backdrop.g, backdrop.b), (1.0, 1.0, 1.0));
+        assert_eq!(trace(mask)[0], "fill [0 0 100 100]");
+    }
+
+    #[test]
+    fn inherited_soft_masks_are_lifted_until_restore() {
+        let document = document(
+            SOFT_MASK_PAGE,
+            "/GS1 gs q 0 0 50 50 re W n /GS2 gs 0 0 10 10 re f Q 0 0 10 10 re f",
+            &soft_mask_objects(),
+        );
+        let (recording, rendered) = render(&document);
+        rendered.unwrap();
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "begin mask",
+                "clip [0 50 50 100]",
+                // `/SMask /None` takes down the inherited mask, keeping the clip.
+                "pop clip",
+                "end mask",
+                "clip [0 50 50 100]",
+                "fill [0 90 10 100]",
+                // `Q` ends the clip and brings the inherited mask back.
+                "pop clip",
+                "begin mask",
+                "fill [0 90 10 100]",
+                "end mask",
+                "pop clip",
+            ]
+        );
+    }
+
+    /// Renders a 2 by 1 stencil mask whose samples are 0 and 1, given its
+    /// `/Decode` entry, and returns the recording.
+    fn render_stencil_mask(content: &str, decode: &str) -> RecordingCanvas {
+        let document = document(
+            "/MediaBox [0 0 100 100] \
+             /Resources << /XObject << /Im1 5 0 R >> /Pattern << /P1 6 0 R >> >>",
+            content,
+            &[
+                // `A` is 0b0100_0001, so the two samples are 0 and 1.
+                stream(
+                    &format!(
+                        "/Type /XObject /Subtype /Image /Width 2 /Height 1 /ImageMask true {decode}"
+                    ),
+                    "A",
+                ),
+                "<< /PatternType 2 /Shading << /ShadingType 2 /ColorSpace /DeviceGray \
+                 /Coords [0 0 1 0] /Function << /FunctionType 2 /Domain [0 1] \
+                 /C0 [0] /C1 [1] /N 1 >> >> >>"
+                    .to_string(),
+            ],
+        );
+        let (recording, rendered) = render(&document);
+        rendered.unwrap();
+        recording
+    }
+
+    fn image(command: &RecordingCommand) -> Option<&[u8]> {
+        match command {
+            RecordingCommand::DrawImage { image, .. } => Some(&image.data),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn stencil_masks_paint_the_fill_colour_through_their_samples() {
+        let content = "1 0 0 rg q 10 0 0 10 0 0 cm /Im1 Do Q";
+        let recording = render_stencil_mask(content, "");
+        assert_eq!(
+            trace(&recording),
+            ["clip [0 0 100 100]", "image", "pop clip"]
+        );
+        // Samples of 0 are painted.
+        assert_eq!(
+            image(&recording.commands()[1]).unwrap(),
+            [255, 0, 0, 255, 255, 0, 0, 0]
+        );
+
+        let recording = render_stencil_mask(content, "/Decode [1 0]");
+        assert_eq!(
+            image(&recording.commands()[1]).unwrap(),
+            [255, 0, 0, 0, 255, 0, 0, 255]
+        );
+    }
+
+    #[test]
+    fn stencil_masks_paint_patterns_through_an_alpha_mask() {
+        let recording = render_stencil_mask("/Pattern cs /P1 scn q 10 0 0 10 0 0 cm /Im1 Do Q", "");
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "begin mask",
+                "fill [0 90 10 100]",
+                "end mask",
+                "pop clip",
+            ]
+        );
+        let RecordingCommand::BeginMaskLayer {
+            mask, mask_mode, ..
+        } = &recording.commands()[1]
+        else {
+            unreachable!("the trace has a begin mask here");
+        };
+        assert!(*mask_mode == MaskMode::Alpha);
+        assert_eq!(image(&mask.commands()[0]).unwrap(), [255, 0]);
+    }
+
+    #[test]
+    fn pages_are_rotated_and_moved_to_the_origin() {
+        // A 100 by 50 crop box, scaled to fill the 100 by 100 recording once rotated,
+        // with a 40 by 10 rectangle at its lower-left corner.
+        let cases = [
+            ("/MediaBox [0 0 100 50]", "0 0 40 10", "[0 80 40 100]"),
+            (
+                "/MediaBox [0 0 100 50] /Rotate 90",
+                "0 0 40 10",
+                "[0 0 20 40]",
+            ),
+            (
+                "/MediaBox [0 0 100 50] /Rotate 180",
+                "0 0 40 10",
+                "[60 0 100 20]",
+            ),
+            (
+                "/MediaBox [0 0 100 50] /Rotate 270",
+                "0 0 40 10",
+                "[80 60 100 100]",
+            ),
+            (
+                "/MediaBox [0 0 200 200] /CropBox [50 50 150 100] /Rotate -270",
+                "50 50 40 10",
+                "[0 0 20 40]",
+            ),
+        ];
+        for (page, rectangle, bounds) in cases {
+            let content = format!("{rectangle} re f");
+            let (recording, rendered) = render(&document(page, &content, &[]));
+            rendered.unwrap();
+            assert_eq!(
+                trace(&recording),
+                [
+                    "clip [0 0 100 100]".to_string(),
+                    format!("fill {bounds}"),
+                    "pop clip".to_string(),
+                ],
+                "{page}"
+            );
+        }
+    }
+
+    #[test]
+    fn annotations_after_a_failed_appearance_are_drawn() {
+        let annotation = |appearance: i32| {
+            format!(
+                "<< /Type /Annot /Subtype /Square /Rect [0 0 10 10] /AP << /N {appearance} 0 R >> >>"
+            )
+        };
+        let form =
+            |content: &str| stream("/Type /XObject /Subtype /Form /BBox [0 0 10 10]", content);
+        let document = document(
+            "/MediaBox [0 0 100 100] /Annots [5 0 R 6 0 R]",
+            "",
+            &[
+                annotation(7),
+                annotation(8),
+                form("q 0 0 5 5 re W n /Missing gs"),
+                form("0 0 10 10 re f"),
+            ],
+        );
+        let mut recording = RecordingCanvas::new(100.0, 100.0);
+        let rendered = PdfCanvas::new(&mut recording, &document.pages[0], None)
+            .and_then(|mut canvas| canvas.render_annotations());
+        assert!(rendered.is_err());
+        assert_eq!(
+            trace(&recording),
+            [
+                "clip [0 0 100 100]",
+                "clip [0 95 5 100]",
+                "pop clip",
+                "pop clip",
+                "clip [0 0 100 100]",
+                "fill [0 90 10 100]",
+                "pop clip",
+            ]
+        );
+    }
+
+    #[test]
+    fn translucent_fill_and_stroke_paint_in_a_knockout_group() {
+        let document = document(
+            "/MediaBox [0 0 100 100] /Resources << /ExtGState << /GS1 5 0 R >> >>",
+            "/GS1 gs 10 10 20 20 re B 10 10 20 20 re f",
+            &["<< /Type /ExtGState /ca 0.5 /CA 0.25 /BM /Multiply >>".to_string()],
+        );
+        let (recording, rendered) = render(&document);
+        rendered.unwrap();
+        let trace = trace(&recording);
+        assert_eq!(trace[1], "begin group");
+        assert_eq!(trace[2], "fill [10 70 30 90]");
+        assert!(trace[3].starts_with("stroke"));
+        assert_eq!(trace[4..], ["end group", "fill [10 70 30 90]", "pop clip"]);
+
+        let commands = recording.commands();
+        let layer = group_layer(&commands[1]).unwrap();
+        assert!(layer.isolated && layer.knockout);
+        assert_eq!(layer.alpha, 1.0);
+        assert!(layer.blend_mode == Some(BlendMode::Multiply));
+        // The fill and stroke keep their own alphas, and the stroke replaces the
+        // fill beneath it.
+        let (color, blend_mode) = fill(&commands[2]).unwrap();
+        assert_eq!(color.a, 0.5);
+        assert!(blend_mode == Some(BlendMode::Source));
+        let RecordingCommand::StrokePath {
+            color, blend_mode, ..
+        } = &commands[3]
+        else {
+            unreachable!()
+        };
+        assert_eq!(color.a, 0.25);
+        assert!(*blend_mode == Some(BlendMode::Source));
+        // Painting fill or stroke alone does not need a group.
+        let (color, blend_mode) = fill(&commands[5]).unwrap();
+        assert_eq!(color.a, 0.5);
+        assert!(blend_mode == Some(BlendMode::Multiply));
     }
 }
@@ -1,8 +1,8 @@
 use std::borrow::Cow;
 
-use crate::canvas_backend::{CanvasBackend, Image as BackendImage, Shader};
+use crate::canvas_backend::{CanvasBackend, GroupLayer, Image as BackendImage, Shader};
 use pdf_graphics::{
-    BlendMode, ImageEncoding, MaskMode, PathFillType, color::Color, pdf_path::PdfPath,
+    BlendMode, ImageEncoding, MaskMode, PathFillType, StrokeStyle, color::Color, pdf_path::PdfPath,
     transform::Transform,
 };
 use thiserror::Error;
@@ -18,7 +18,7 @@ pub enum RecordingCanvasError {
 
 /// Owned representation of an image.
 #[derive(Clone)]
-struct RecordedImage {
+pub(crate) struct RecordedImage {
     pub data: Vec<u8>,
     pub width: u32,
     pub height: u32,
@@ -44,7 +44,7 @@ impl From<&BackendImage<'_>> for RecordedImage {
 
 /// Owned representation of a shader.
 #[derive(Clone)]
-enum RecordedShader {
+pub(crate) enum RecordedShader {
     LinearGradient {
         x0: f32,
         y0: f32,
@@ -87,8 +87,8 @@ impl From<&Shader<'_>> for RecordedShader {
                 y0: *y0,
                 x1: *x1,
                 y1: *y1,
-                colors: (*colors).to_vec(),
-                positions: (*positions).to_vec(),
+                colors: colors.to_vec(),
+                positions: positions.to_vec(),
             },
             Shader::TilingPatternImage {
                 image,
@@ -118,8 +118,8 @@ impl From<&Shader<'_>> for RecordedShader {
                 end_x: *end_x,
                 end_y: *end_y,
                 end_r: *end_r,
-                colors: (*colors).to_vec(),
-                positions: (*positions).to_vec(),
+                colors: colors.to_vec(),
+                positions: positions.to_vec(),
                 transform: *transform,
             },
         }
@@ -128,7 +128,7 @@ impl From<&Shader<'_>> for RecordedShader {
 
 /// Enum representing each drawing command that can be recorded.
 #[derive(Clone)]
-enum RecordingCommand {
+pub(crate) enum RecordingCommand {
     FillPath {
         path: PdfPath,
         fill_type: PathFillType,
@@ -139,15 +139,15 @@ enum RecordingCommand {
     StrokePath {
         path: PdfPath,
         color: Color,
-        line_width: f32,
+        style: StrokeStyle,
         shader: Option<RecordedShader>,
         blend_mode: Option<BlendMode>,
     },
-    SetClipRegion {
+    PushClipRegion {
         path: PdfPath,
         mode: PathFillType,
     },
-    ResetClip,
+    PopClipRegion,
     DrawImage {
         image: RecordedImage,
         blend_mode: Option<BlendMode>,
@@ -161,7 +161,12 @@ enum RecordingCommand {
         mask: Box<RecordingCanvas>,
         transform: Transform,
         mask_mode: MaskMode,
+        transfer: Option<Vec<u8>>,
     },
+    BeginTransparencyGroup {
+        layer: GroupLayer,
+    },
+    EndTransparencyGroup,
 }
 
 /// An in-memory, backend-agnostic canvas that records drawing commands.
@@ -189,6 +194,12 @@ impl RecordingCanvas {
         }
     }
 
+    /// Returns the recorded drawing commands, in the order they were issued.
+    #[cfg(test)]
+    pub(crate) fn commands(&self) -> &[RecordingCommand] {
+        &self.commands
+    }
+
     /// Replays all recorded drawing commands onto the provided backend.
     ///
     /// This method iterates over the internally stored sequence of drawing
@@ -229,8 +240,8 @@ impl RecordingCanvas {
                             y0: *y0,
                             x1: *x1,
                             y1: *y1,
-                            colors,
-                            positions,
+                            colors: Cow::Borrowed(colors),
+                            positions: Cow::Borrowed(positions),
                         },
                         RecordedShader::TilingPatternImage {
                             image,
@@ -260,8 +271,8 @@ impl RecordingCanvas {
                             end_x: *end_x,
                             end_y: *end_y,
                             end_r: *end_r,
-                            colors,
-                            positions,
+                            colors: Cow::Borrowed(colors),
+                            positions: Cow::Borrowed(positions),
                             transform: *transform,
                         },
                     });
@@ -270,7 +281,7 @@ impl RecordingCanvas {
                 StrokePath {
                     path,
                     color,
-                    line_width,
+                    style,
                     shader,
                     blend_mode,
                 } => {
@@ -287,8 +298,8 @@ impl RecordingCanvas {
                             y0: *y0,
                             x1: *x1,
                             y1: *y1,
-                            colors,
-                            positions,
+                            colors: Cow::Borrowed(colors),
+                            positions: Cow::Borrowed(positions),
                         },
                         RecordedShader::TilingPatternImage {
                             image,
@@ -318,15 +329,15 @@ impl RecordingCanvas {
                             end_x: *end_x,
                             end_y: *end_y,
                             end_r: *end_r,
-                            colors,
-                            positions,
+                            colors: Cow::Borrowed(colors),
+                            positions: Cow::Borrowed(positions),
                             transform: *transform,
                         },
                     });
-                    backend.stroke_path(path, *color, *line_width, &shader_ref, *blend_mode)?;
+                    backend.stroke_path(path, *color, style, &shader_ref, *blend_mode)?;
                 }
-                SetClipRegion { path, mode } => backend.set_clip_region(path, *mode)?,
-                ResetClip => backend.reset_clip()?,
+                PushClipRegion { path, mode } => backend.push_clip_region(path, *mode)?,
+                PopClipRegion => backend.pop_clip_region()?,
                 DrawImage { image, blend_mode } => {
                     let backend_img = BackendImage {
                         data: Cow::Owned(image.data.clone()),
@@ -350,9 +361,12 @@ impl RecordingCanvas {
                     mask,
                     transform,
                     mask_mode,
+                    transfer,
                 } => {
-                    backend.end_mask_layer(mask, transform, *mask_mode)?;
+                    backend.end_mask_layer(mask, transform, *mask_mode, transfer.as_deref())?;
                 }
+                BeginTransparencyGroup { layer } => backend.begin_transparency_group(layer)?,
+                EndTransparencyGroup => backend.end_transparency_group()?,
             }
         }
         Ok(())
@@ -384,26 +398,26 @@ impl CanvasBackend for RecordingCanvas {
         &mut self,
         path: &PdfPath,
         color: Color,
-        line_width: f32,
+        style: &StrokeStyle,
         shader: &Option<Shader>,
         blend_mode: Option<BlendMode>,
     ) -> Result<(), Self::ErrorType> {
         self.commands.push(RecordingCommand::StrokePath {
             path: path.clone(),
             color,
-            line_width,
+            style: style.clone(),
             shader: shader.as_ref().map(|s| s.into()),
             blend_mode,
         });
         Ok(())
     }
 
-    fn set_clip_region(
+    fn push_clip_region(
         &mut self,
         path: &PdfPath,
         mode: PathFillType,
     ) -> Result<(), Self::ErrorType> {
-        self.commands.push(RecordingCommand::SetClipRegion {
+        self.commands.push(RecordingCommand::PushClipRegion {
             path: path.clone(),
             mode,
         });
@@ -417,8 +431,8 @@ impl CanvasBackend for RecordingCanvas {
         self.height
     }
 
-    fn reset_clip(&mut self) -> Result<(), Self::ErrorType> {
-        self.commands.push(RecordingCommand::ResetClip);
+    fn pop_clip_region(&mut self) -> Result<(), Self::ErrorType> {
+        self.commands.push(RecordingCommand::PopClipRegion);
         Ok(())
     }
 
@@ -453,12 +467,25 @@ impl CanvasBackend for RecordingCanvas {
         _mask: &RecordingCanvas,
         transform: &Transform,
         mask_mode: MaskMode,
+        transfer: Option<&[u8]>,
     ) -> Result<(), Self::ErrorType> {
         self.commands.push(RecordingCommand::EndMaskLayer {
             transform: *transform,
             mask_mode,
             mask: Box::new(_mask.clone()),
+            transfer: transfer.map(<[u8]>::to_vec),
         });
         Ok(())
     }
+
+    fn begin_transparency_group(&mut self, layer: &GroupLayer) -> Result<(), Self::ErrorType> {
+        self.commands
+            .push(RecordingCommand::BeginTransparencyGroup { layer: *layer });
+        Ok(())
+    }
+
+    fn end_transparency_group(&mut self) -> Result<(), Self::ErrorType> {
+        self.commands.push(RecordingCommand::EndTransparencyGroup);
+        Ok(())
+    }
 }
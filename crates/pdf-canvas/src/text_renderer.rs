@@ -1,3 +1,5 @@
+use pdf_graphics::transform::Transform;
+
 use crate::error::PdfCanvasError;
 
 /// A generic interface for rendering text content.
@@ -11,4 +13,7 @@ pub trait TextRenderer {
     /// to the current font's encoding, calculating glyph positions, and drawing
     /// the glyphs onto the canvas.
     fn render_text(&mut self, text: &[u8]) -> Result<(), PdfCanvasError>;
+
+    /// Returns the text matrix (Tm), advanced past the glyphs rendered so far.
+    fn text_matrix(&self) -> Transform;
 }
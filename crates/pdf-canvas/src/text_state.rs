@@ -1,5 +1,6 @@
 use pdf_font::font::Font;
-use pdf_graphics::transform::Transform;
+use pdf_graphics::{TextRenderingMode, transform::Transform};
+use pdf_page::resources::Resources;
 
 /// Encapsulates text-specific state parameters.
 #[derive(Clone)]
@@ -16,10 +17,17 @@ pub(crate) struct TextState<'a> {
     pub(crate) character_spacing: f32,
     /// Word spacing (Tw), in unscaled text space units.
     pub(crate) word_spacing: f32,
+    /// Text leading (Tl), the vertical distance between baselines used by `T*`, `'` and `"`,
+    /// in unscaled text space units.
+    pub(crate) leading: f32,
     /// Text rise (Ts), a vertical offset from the baseline, in unscaled text space units.
     pub(crate) rise: f32,
+    /// Text rendering mode (Tr), selecting fill, stroke, clip or invisible glyphs.
+    pub(crate) rendering_mode: TextRenderingMode,
     /// The current font resource.
     pub(crate) font: Option<&'a Font>,
+    /// The resources of the current font, for Type 3 fonts that declare their own.
+    pub(crate) font_resources: Option<&'a Resources>,
 }
 
 impl Default for TextState<'_> {
@@ -31,8 +39,11 @@ impl Default for TextState<'_> {
             font_size: 0.0,
             character_spacing: 0.0,
             word_spacing: 0.0,
+            leading: 0.0,
             rise: 0.0,
+            rendering_mode: TextRenderingMode::Fill,
             font: None,
+            font_resources: None,
         }
     }
 }
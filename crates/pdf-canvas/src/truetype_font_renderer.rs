@@ -3,11 +3,16 @@ use num_traits::FromPrimitive;
 use pdf_content_stream::pdf_operator_backend::PdfOperatorBackend;
 use pdf_font::{
     font::{Font, FontEncoding},
+    font_descriptor::FontFileKind,
+    glyph_cache::GlyphCache,
     glyph_widths_map::GlyphWidthsMap,
     simple_font_glyph_map::SimpleFontGlyphWidthsMap,
-    type0_font::CidFontSubType,
+    true_type_font::TrueTypeFont,
+    type0_font::{CidFontSubType, Type0Font},
+    type1_font::Type1Font,
 };
-use pdf_graphics::{PathFillType, pdf_path::PdfPath, transform::Transform};
+use pdf_graphics::{pdf_path::PdfPath, transform::Transform};
+use std::cell::OnceCell;
 use thiserror::Error;
 use ttf_parser::{Face, GlyphId, OutlineBuilder};
 
@@ -26,6 +31,26 @@ pub enum TrueTypeFontRendererError {
     MissingFontFile,
     #[error("Not implemented")]
     NotImplemented,
+    #[error("Font program of kind {0:?} cannot be rendered as TrueType or OpenType")]
+    UnsupportedFontProgram(FontFileKind),
+}
+
+/// A simple font whose embedded program is drawn through its sfnt tables.
+#[derive(Clone, Copy)]
+enum SimpleFont<'a> {
+    TrueType(&'a TrueTypeFont),
+    /// A Type1 font embedding a program with TrueType outlines.
+    Type1(&'a Type1Font),
+}
+
+impl SimpleFont<'_> {
+    /// Maps a single-byte character code to a glyph in the embedded program.
+    fn glyph_id(self, face: &Face<'_>, char_code: u8) -> Option<GlyphId> {
+        match self {
+            SimpleFont::TrueType(font) => font.glyph_id(face, char_code),
+            SimpleFont::Type1(font) => font.sfnt_glyph_id(face, char_code),
+        }
+    }
 }
 
 /// A text renderer for TrueType-based fonts.
@@ -36,8 +61,14 @@ pub(crate) struct TrueTypeFontRenderer<'a, T: PdfOperatorBackend + Canvas> {
     object_stream: Option<&'a pdf_object::stream::StreamObject>,
     /// Optional character map for mapping character codes to Unicode values.
     cmap: Option<&'a pdf_font::character_map::CharacterMap>,
-    /// Optional encoding for simple fonts (Type1, TrueType).
+    /// Optional CMap encoding of a composite (Type0) font.
     encoding: Option<&'a FontEncoding>,
+    /// The simple font, used to map single-byte codes to glyphs.
+    simple_font: Option<SimpleFont<'a>>,
+    /// The composite font, used for vertical writing metrics.
+    composite_font: Option<&'a Type0Font>,
+    /// Per-font cache of glyph IDs and em-space outlines.
+    glyph_cache: &'a GlyphCache,
     /// Optional glyph widths map for CID-keyed fonts.
     widths: Option<&'a GlyphWidthsMap>,
     /// Optional width map for simple fonts (Type1, TrueType).
@@ -77,14 +108,16 @@ impl<'a, T: PdfOperatorBackend + Canvas> TrueTypeFontRenderer<'a, T> {
             Font::TrueType(tt_font) => {
                 let object_stream = tt_font.font_file.as_ref();
                 let cmap = tt_font.cmap.as_ref();
-                let encoding = tt_font.encoding.as_ref();
                 let w = Some(&tt_font.widths);
 
                 Ok(Self {
                     canvas,
                     object_stream,
                     cmap,
-                    encoding,
+                    encoding: None,
+                    simple_font: Some(SimpleFont::TrueType(tt_font)),
+                    composite_font: None,
+                    glyph_cache: &tt_font.glyph_cache,
                     widths: None,
                     w,
                     default_width: 0.0,
@@ -97,12 +130,37 @@ impl<'a, T: PdfOperatorBackend + Canvas> TrueTypeFontRenderer<'a, T> {
                     horizontal_scaling,
                 })
             }
+            Font::Type1(type1_font) if type1_font.has_truetype_outlines() => Ok(Self {
+                canvas,
+                object_stream: type1_font.font_file.as_ref(),
+                cmap: type1_font.cmap.as_ref(),
+                encoding: None,
+                simple_font: Some(SimpleFont::Type1(type1_font)),
+                composite_font: None,
+                glyph_cache: &type1_font.glyph_cache,
+                widths: None,
+                w: Some(&type1_font.widths),
+                default_width: 0.0,
+                text_matrix,
+                current_transform,
+                font_size,
+                rise,
+                word_spacing,
+                char_spacing,
+                horizontal_scaling,
+            }),
             Font::Type0(type0_font) => {
-                // Ensure the CIDFont is a TrueType-based font (CIDFontType2).
-                let cid_font = match &type0_font.subtype {
-                    CidFontSubType::Type2 => type0_font,
-                    _ => {
-                        return Err(TrueTypeFontRendererError::NotImplemented.into());
+                // CIDFontType2 fonts embed TrueType programs. CIDFontType0 fonts are
+                // only supported when wrapped in OpenType, which ttf-parser reads
+                // regardless of the outline flavour.
+                let cid_font = match (type0_font.subtype, type0_font.font_file_kind) {
+                    (CidFontSubType::Type2, _)
+                    | (CidFontSubType::Type0, Some(FontFileKind::OpenType)) => type0_font,
+                    (CidFontSubType::Type0, Some(kind)) => {
+                        return Err(TrueTypeFontRendererError::UnsupportedFontProgram(kind).into());
+                    }
+                    (CidFontSubType::Type0, None) => {
+                        return Err(TrueTypeFontRendererError::MissingFontFile.into());
                     }
                 };
 
@@ -117,6 +175,9 @@ impl<'a, T: PdfOperatorBackend + Canvas> TrueTypeFontRenderer<'a, T> {
                     object_stream,
                     cmap,
                     encoding,
+                    simple_font: None,
+                    composite_font: Some(cid_font),
+                    glyph_cache: &cid_font.glyph_cache,
                     widths,
                     w: None,
                     default_width,
@@ -135,44 +196,51 @@ impl<'a, T: PdfOperatorBackend + Canvas> TrueTypeFontRenderer<'a, T> {
 }
 
 impl<T: PdfOperatorBackend + Canvas> TextRenderer for TrueTypeFontRenderer<'_, T> {
+    fn text_matrix(&self) -> Transform {
+        self.text_matrix
+    }
+
     fn render_text(&mut self, text: &[u8]) -> Result<(), crate::error::PdfCanvasError> {
         let Some(object_stream) = self.object_stream else {
             // TODO: Use BaseName from FontDescriptor to load a system font?
             return Ok(());
         };
 
-        let face = Face::parse(object_stream.data.as_slice(), 0)
-            .map_err(TrueTypeFontRendererError::TtfParseError)?;
+        // The face is only parsed when a glyph is missing from the font's cache.
+        let font_data = object_stream.data.as_slice();
+        let face_cell = OnceCell::new();
+        let face = || -> Result<&Face<'_>, PdfCanvasError> {
+            if let Some(face) = face_cell.get() {
+                return Ok(face);
+            }
+            let face =
+                Face::parse(font_data, 0).map_err(TrueTypeFontRendererError::TtfParseError)?;
+            Ok(face_cell.get_or_init(|| face))
+        };
 
-        // Extract font and text state parameters.
-        let units_per_em = face.units_per_em();
+        // Extract text state parameters.
         let char_spacing = self.char_spacing;
         let word_spacing = self.word_spacing;
         let text_rise = self.rise;
 
-        // Compute the inverse of units per em for scaling.
-        let upe_inv = if units_per_em != 0 {
-            1.0 / f32::from_u16(units_per_em)
-                .ok_or(PdfCanvasError::NumericConversionError("units_per_em"))?
-        } else {
-            0.0
-        };
-
         // Th_factor: Horizontal scaling factor (Th / 100).
         let th_factor = self.horizontal_scaling / 100.0;
 
-        // Build the text rendering transform.
+        // Build the text rendering transform. Cached outlines are in ems.
         let m_params = Transform::from_row(
-            self.font_size * upe_inv * th_factor, // sx
-            0.0,                                  // ky (skew)
-            0.0,                                  // kx (skew)
-            self.font_size * upe_inv,             // sy
-            0.0,                                  // tx
-            text_rise,                            // ty
+            self.font_size * th_factor, // sx
+            0.0,                        // ky (skew)
+            0.0,                        // kx (skew)
+            self.font_size,             // sy
+            0.0,                        // tx
+            text_rise,                  // ty
         );
 
         // Determine if the font uses a 2-byte encoding (e.g., /Identity-H for CID-keyed fonts).
-        let is_two_byte_encoding = self.encoding.is_some();
+        // Simple fonts always use single-byte codes.
+        let is_two_byte_encoding = self.simple_font.is_none() && self.encoding.is_some();
+        // In vertical writing, glyphs are positioned by their position vector and advance downwards.
+        let vertical_font = self.composite_font.filter(|font| font.is_vertical());
         let mut iter = text.iter().copied();
 
         // Iterate over each character in the input text.
@@ -193,30 +261,89 @@ impl<T: PdfOperatorBackend + Canvas> TextRenderer for TrueTypeFontRenderer<'_, T
                     .ok_or(PdfCanvasError::NumericConversionError("first_byte"))?
             };
 
-            let mut glyph_id = GlyphId(char_code);
-
-            // Compose the final transformation matrix for this glyph:
-            // m_params -> text matrix -> current transformation matrix
-            let mut glyph_matrix_for_char = m_params;
-            glyph_matrix_for_char.concat(&self.text_matrix);
-            glyph_matrix_for_char.concat(&self.current_transform);
+            // Simple fonts map the code through their encoding and the font's cmap tables.
+            // Otherwise, map the character code to glyph ID using the `/ToUnicode` map if available.
+            let gid = self.glyph_cache.glyph_id_or_insert_with(
+                u32::from(char_code),
+                || -> Result<_, PdfCanvasError> {
+                    let face = face()?;
+                    let mut glyph_id = GlyphId(char_code);
+                    if let Some(simple_font) = self.simple_font
+                        && let Ok(code) = u8::try_from(char_code)
+                        && let Some(x) = simple_font.glyph_id(face, code)
+                    {
+                        glyph_id = x;
+                    } else if let Some(cmap) = self.cmap
+                        && let Some(a) = cmap.get_mapping(u32::from(char_code))
+                        && let Some(x) = face.glyph_index(a)
+                    {
+                        glyph_id = x;
+                    }
+                    if vertical_font.is_some() {
+                        glyph_id = Type0Font::vertical_glyph_id(face, glyph_id);
+                    }
+                    Ok(Some(glyph_id.0))
+                },
+            )?;
+
+            // Build the glyph outline in ems, scaling font units by the inverse of units per em.
+            let outline = match gid {
+                Some(gid) => self.glyph_cache.outline_or_insert_with(
+                    gid,
+                    || -> Result<_, PdfCanvasError> {
+                        let face = face()?;
+                        let units_per_em = face.units_per_em();
+                        let upe_inv = if units_per_em != 0 {
+                            1.0 / f32::from_u16(units_per_em)
+                                .ok_or(PdfCanvasError::NumericConversionError("units_per_em"))?
+                        } else {
+                            0.0
+                        };
+                        let mut builder =
+                            PdfGlyphOutline::new(Transform::from_scale(upe_inv, upe_inv));
+                        Ok(face
+                            .outline_glyph(GlyphId(gid), &mut builder)
+                            .map(|_| builder.path))
+                    },
+                )?,
+                None => None,
+            };
 
-            // Build the glyph outline using the composed transform.
-            let mut builder = PdfGlyphOutline::new(glyph_matrix_for_char);
+            let vertical_metrics = vertical_font.map(|font| font.vertical_metrics(char_code));
 
-            // Map character code to glyph ID using the font's cmap if available.
-            if let Some(cmap) = self.cmap
-                && let Some(a) = cmap.get_mapping(u32::from(char_code))
-                && let Some(x) = face.glyph_index(a)
-            {
-                glyph_id = x;
+            if let Some(outline) = outline {
+                // Compose the final transformation matrix for this glyph:
+                // position vector -> m_params -> text matrix -> current transformation matrix
+                let mut glyph_matrix_for_char = match vertical_metrics {
+                    Some(metrics) => {
+                        Transform::from_translate(-metrics.vx / 1000.0, -metrics.vy / 1000.0)
+                    }
+                    None => Transform::identity(),
+                };
+                glyph_matrix_for_char.concat(&m_params);
+                glyph_matrix_for_char.concat(&self.text_matrix);
+                glyph_matrix_for_char.concat(&self.current_transform);
+
+                // Fill it on the canvas
+                let mut path = PdfPath::clone(&outline);
+                path.transform(&glyph_matrix_for_char);
+                self.canvas.paint_glyph(&path)?;
             }
 
-            face.outline_glyph(glyph_id, &mut builder);
+            // Apply word spacing only to space characters.
+            let word_spacing_for_char = if char_code == 32 { word_spacing } else { 0.0 };
 
-            // Fill it on the canvas
-            self.canvas
-                .fill_path(&builder.path, PathFillType::Winding)?;
+            // In vertical writing, advance by the vertical displacement; horizontal scaling
+            // does not apply.
+            if let Some(metrics) = vertical_metrics {
+                let advance_y =
+                    metrics.w1y / 1000.0 * self.font_size + char_spacing + word_spacing_for_char;
+                // The advance is in text space, so it applies before the text matrix.
+                let mut advanced = Transform::from_translate(0.0, advance_y);
+                advanced.concat(&self.text_matrix);
+                self.text_matrix = advanced;
+                continue;
+            }
 
             // Determine the glyph's advance width in font units.
             // Determine width source: CID descendant map or simple font widths (in glyph space 1000 units)
@@ -234,15 +361,15 @@ impl<T: PdfOperatorBackend + Canvas> TextRenderer for TrueTypeFontRenderer<'_, T
             // Scale the glyph width by the font size.
             let glyph_width_tfs_scaled = w0_ems * self.font_size;
 
-            // Apply word spacing only to space characters.
-            let word_spacing_for_char = if char_code == 32 { word_spacing } else { 0.0 };
-
             // Compute the horizontal advance for this glyph.
             let advance_x =
                 (glyph_width_tfs_scaled + char_spacing + word_spacing_for_char) * th_factor;
 
             // Advance the text matrix for the next glyph.
-            self.text_matrix.translate(advance_x, 0.0);
+            // The advance is in text space, so it applies before the text matrix.
+            let mut advanced = Transform::from_translate(advance_x, 0.0);
+            advanced.concat(&self.text_matrix);
+            self.text_matrix = advanced;
         }
 
         Ok(())
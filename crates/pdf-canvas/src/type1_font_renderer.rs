@@ -3,9 +3,8 @@
 
 use crate::{canvas::Canvas, error::PdfCanvasError, text_renderer::TextRenderer};
 use pdf_content_stream::pdf_operator_backend::PdfOperatorBackend;
-use pdf_font::cff::reader::CffFontReader;
 use pdf_font::type1_font::Type1Font;
-use pdf_graphics::PathFillType;
+use pdf_graphics::pdf_path::PdfPath;
 use pdf_graphics::transform::Transform;
 
 pub(crate) struct Type1FontRenderer<'a, T: PdfOperatorBackend + Canvas> {
@@ -56,18 +55,16 @@ impl<'a, T: PdfOperatorBackend + Canvas> Type1FontRenderer<'a, T> {
 }
 
 impl<T: PdfOperatorBackend + Canvas> TextRenderer for Type1FontRenderer<'_, T> {
+    fn text_matrix(&self) -> Transform {
+        self.text_matrix
+    }
+
     fn render_text(&mut self, text: &[u8]) -> Result<(), PdfCanvasError> {
-        let program = CffFontReader::new(
-            &self
-                .font
-                .font_file
-                .as_ref()
-                .ok_or(PdfCanvasError::InvalidFont(
-                    "Missing font file for Type1 font",
-                ))?
-                .data,
-        )
-        .read_font_program()?;
+        let font = self.font;
+        // The font program is parsed once per font and reused across text-showing operators.
+        let program = font.program()?.ok_or(PdfCanvasError::InvalidFont(
+            "Missing font file for Type1 font",
+        ))?;
 
         // Build the text rendering transform.
         // CFF/Type 1 glyph outlines are expressed in a 1000 units-per-em coordinate system.
@@ -91,10 +88,27 @@ impl<T: PdfOperatorBackend + Canvas> TextRenderer for Type1FontRenderer<'_, T> {
             glyph_matrix_for_char.concat(&self.current_transform);
 
             let char_code = *u;
-            let path = program.render_glyph(char_code)?;
-            if let Some(mut path) = path {
+            // A glyph name selected by the font's `/Encoding` takes precedence over the
+            // program's built-in encoding.
+            let gid = font.glyph_cache.glyph_id_or_insert_with(
+                u32::from(char_code),
+                || -> Result<_, PdfCanvasError> {
+                    Ok(font
+                        .encoded_glyph_name(char_code)
+                        .and_then(|name| program.glyph_id_for_name(name))
+                        .or_else(|| program.code_to_gid(char_code)))
+                },
+            )?;
+            let outline = match gid {
+                Some(gid) => font
+                    .glyph_cache
+                    .outline_or_insert_with(gid, || program.render_glyph_id(gid))?,
+                None => None,
+            };
+            if let Some(outline) = outline {
+                let mut path = PdfPath::clone(&outline);
                 path.transform(&glyph_matrix_for_char);
-                self.canvas.fill_path(&path, PathFillType::Winding)?;
+                self.canvas.paint_glyph(&path)?;
             } else {
                 // Glyph ID not found for character code.
                 // Use the missing width from the FontDescriptor to advance the text position.
@@ -122,7 +136,10 @@ impl<T: PdfOperatorBackend + Canvas> TextRenderer for Type1FontRenderer<'_, T> {
             };
             let advance_x =
                 (glyph_width_tfs_scaled + self.char_spacing + word_spacing_for_char) * th_factor;
-            self.text_matrix.translate(advance_x, 0.0);
+            // The advance is in text space, so it applies before the text matrix.
+            let mut advanced = Transform::from_translate(advance_x, 0.0);
+            advanced.concat(&self.text_matrix);
+            self.text_matrix = advanced;
         }
 
         Ok(())
@@ -34,14 +34,23 @@ pub(crate) struct Type3FontRenderer<'a, T: PdfOperatorBackend + Canvas> {
     type3_font: &'a Type3Font,
     /// The font size.
     font_size: f32,
+    /// The horizontal scaling factor (Th / 100).
+    th_factor: f32,
+    /// The spacing to add between words, applied to space characters.
+    word_spacing: f32,
+    /// The spacing to add between individual characters.
+    char_spacing: f32,
 }
 
 impl<'a, T: PdfOperatorBackend + Canvas> Type3FontRenderer<'a, T> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         canvas: &'a mut T,
         font_size: f32,
         horizontal_scaling: f32,
         text_rise: f32,
+        word_spacing: f32,
+        char_spacing: f32,
         current_transform: Transform,
         text_matrix: Transform,
         type3_font: &'a Type3Font,
@@ -74,11 +83,18 @@ impl<'a, T: PdfOperatorBackend + Canvas> Type3FontRenderer<'a, T> {
             text_matrix,
             type3_font,
             font_size,
+            th_factor,
+            word_spacing,
+            char_spacing,
         })
     }
 }
 
 impl<T: PdfOperatorBackend + Canvas> TextRenderer for Type3FontRenderer<'_, T> {
+    fn text_matrix(&self) -> Transform {
+        self.text_matrix
+    }
+
     fn render_text(&mut self, text: &[u8]) -> Result<(), crate::error::PdfCanvasError> {
         // 1. Iterate through each character code in the input text.
         let iter = text.iter().copied();
@@ -98,53 +114,60 @@ impl<T: PdfOperatorBackend + Canvas> TextRenderer for Type3FontRenderer<'_, T> {
                 .as_ref()
                 .and_then(|enc| enc.differences.get(&char_code_byte));
 
-            let Some(glyph_name) = glyph_name else {
-                continue;
-            };
-
-            // 3. Look up the glyph's content stream from the `CharProcs` map.
-            let Some(char_procs) = self.type3_font.char_procs.get(glyph_name) else {
-                // If the character code does not map to a glyph name via the font's encoding,
-                // this character is skipped.
-                continue;
-            };
-
-            // 4. Save graphics state before drawing the glyph.
-            self.canvas.save()?;
+            // 3. Look up the glyph's content stream from the `CharProcs` map. Codes that
+            // do not map to a glyph description are not painted, but still advance.
+            let char_procs = glyph_name.and_then(|name| self.type3_font.char_procs.get(name));
 
             let mut glyph_width = None;
 
-            // 5. Set the transformation matrix for the glyph and execute its content stream.
-            // The CTM is temporarily replaced with the computed text rendering matrix.
-            self.canvas.set_matrix(text_rendering_matrix)?;
-
-            for op in char_procs {
-                // Check if this the `d1` operator. The `d1` operator is only used within the
-                // content stream of a Type 3 font's character procedure. It sets the width
-                // and bounding box of the character being defined.
-                // The backend is responsible for storing the width (`wx`, `wy`)
-                // so it can be used to advance the text matrix after the glyph is painted.
-                if let PdfOperatorVariant::SetCharWidthAndBoundingBox(op) = op {
-                    glyph_width = Some(op.wx);
-                } else {
+            if let Some(char_procs) = char_procs {
+                // 4. Save graphics state before drawing the glyph.
+                self.canvas.save()?;
+
+                // 5. Set the transformation matrix for the glyph and execute its content stream.
+                // The CTM is temporarily replaced with the computed text rendering matrix.
+                self.canvas.set_matrix(text_rendering_matrix)?;
+                self.canvas.begin_glyph_description()?;
+
+                for op in char_procs {
+                    // `d0` and `d1` carry the glyph width, used when `/Widths` has no entry
+                    // for the code. The backend applies their effect on the graphics state.
+                    match op {
+                        PdfOperatorVariant::SetCharWidth(op) => glyph_width = Some(op.wx),
+                        PdfOperatorVariant::SetCharWidthAndBoundingBox(op) => {
+                            glyph_width = Some(op.wx)
+                        }
+                        _ => {}
+                    }
                     op.call(self.canvas)
                         .map_err(|err| Type3FontRendererError::CharProcError {
                             err: format!("Error calling operator: {:?}", err),
                         })?;
                 }
-            }
-
-            // 6. Restore the original graphics state.
-            self.canvas.restore()?;
 
-            // 7. Advance the text matrix (Tm) to position the next glyph.
-            if let Some(width) = glyph_width {
-                // The glyph width is given in glyph space. Scale it up by
-                // the font size and a conventional 1000-unit glyph space grid to
-                // calculate the final horizontal displacement in text space.
-                let advance = width * self.font_size / 1000.0;
-                self.text_matrix.translate(advance, 0.0);
+                // 6. Restore the original graphics state.
+                self.canvas.restore()?;
             }
+
+            // 7. Advance the text matrix (Tm) to position the next glyph. The width is
+            // given in glyph space and mapped to text space by the font matrix.
+            let width = self
+                .type3_font
+                .widths
+                .get_width(u16::from(char_code_byte))
+                .or(glyph_width)
+                .unwrap_or(0.0);
+            let w0 = width * self.font_matrix.sx;
+            let word_spacing = if char_code_byte == 32 {
+                self.word_spacing
+            } else {
+                0.0
+            };
+            let advance = (w0 * self.font_size + self.char_spacing + word_spacing) * self.th_factor;
+            // The advance is in text space, so it applies before the text matrix.
+            let mut advanced = Transform::from_translate(advance, 0.0);
+            advanced.concat(&self.text_matrix);
+            self.text_matrix = advanced;
         }
 
         Ok(())
@@ -1,5 +1,5 @@
 use pdf_object::error::ObjectError;
-use pdf_parser::{comment::CommentError, error::ParserError};
+use pdf_parser::{comment::CommentError, error::ParserError, filter::FilterError};
 use pdf_tokenizer::error::TokenizerError;
 use thiserror::Error;
 
@@ -42,4 +42,8 @@ pub enum PdfOperatorError {
     EmptyText,
     #[error("Comment parse error: {0}")]
     Comment(#[from] CommentError),
+    #[error("Inline image data is not terminated by EI")]
+    MissingInlineImageEnd,
+    #[error("Failed to decode inline image data: {0}")]
+    InlineImageFilter(#[from] FilterError),
 }
@@ -0,0 +1,378 @@
+//! Helpers for inline images (`BI … ID … EI`, ISO 32000-1, 8.9.7).
+//!
+//! An inline image carries its dictionary and data directly in the content
+//! stream. The dictionary may use abbreviated keys and names, and the data is
+//! not length-prefixed, so the end of it has to be found by looking for `EI`.
+
+use std::{borrow::Cow, collections::BTreeMap, rc::Rc};
+
+use pdf_object::{ObjectVariant, dictionary::Dictionary};
+use pdf_parser::filter;
+
+use crate::error::PdfOperatorError;
+
+/// The entries of an inline image dictionary, keyed by their full names.
+pub(crate) type InlineImageEntries = BTreeMap<String, Box<ObjectVariant>>;
+
+/// How many bytes after a candidate `EI` must look like content stream text for
+/// it to be taken as the end of the image data.
+const PLAUSIBLE_TAIL: usize = 32;
+
+/// Expands an abbreviated inline image key to the key used by image XObjects.
+pub(crate) fn expand_key(key: &str) -> &str {
+    match key {
+        "BPC" => "BitsPerComponent",
+        "CS" => "ColorSpace",
+        "D" => "Decode",
+        "DP" => "DecodeParms",
+        "F" => "Filter",
+        "H" => "Height",
+        "IM" => "ImageMask",
+        "I" => "Interpolate",
+        "L" => "Length",
+        "W" => "Width",
+        _ => key,
+    }
+}
+
+/// Expands the abbreviated names used as values of `/ColorSpace` and `/Filter`.
+///
+/// `key` is the already expanded key of the entry.
+pub(crate) fn expand_value(key: &str, value: ObjectVariant) -> ObjectVariant {
+    let expand: fn(&str) -> &str = match key {
+        "ColorSpace" => expand_color_space,
+        "Filter" => expand_filter,
+        _ => return value,
+    };
+    match value {
+        ObjectVariant::Name(name) => ObjectVariant::Name(expand(&name).to_string()),
+        // Covers filter arrays and `[/I /RGB 1 <…>]` indexed colour spaces.
+        ObjectVariant::Array(items) => ObjectVariant::Array(
+            items
+                .into_iter()
+                .map(|item| match item {
+                    ObjectVariant::Name(name) => ObjectVariant::Name(expand(&name).to_string()),
+                    other => other,
+                })
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn expand_color_space(name: &str) -> &str {
+    match name {
+        "G" => "DeviceGray",
+        "RGB" => "DeviceRGB",
+        "CMYK" => "DeviceCMYK",
+        "I" => "Indexed",
+        _ => name,
+    }
+}
+
+fn expand_filter(name: &str) -> &str {
+    match name {
+        "AHx" => "ASCIIHexDecode",
+        "A85" => "ASCII85Decode",
+        "LZW" => "LZWDecode",
+        "Fl" => "FlateDecode",
+        "RL" => "RunLengthDecode",
+        "CCF" => "CCITTFaxDecode",
+        "DCT" => "DCTDecode",
+        _ => name,
+    }
+}
+
+/// Returns whether `data` starts with the keyword `keyword` standing on its own.
+pub(crate) fn starts_with_keyword(data: &[u8], keyword: &[u8]) -> bool {
+    data.starts_with(keyword)
+        && data
+            .get(keyword.len())
+            .is_none_or(|&b| is_whitespace(b) || is_delimiter(b))
+}
+
+const fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r' | b'\x0C' | b'\0')
+}
+
+const fn is_delimiter(b: u8) -> bool {
+    matches!(
+        b,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
+
+/// Computes the length of the image data when the dictionary determines it: from
+/// `/Length`, or from the dimensions of an unfiltered image.
+pub(crate) fn expected_length(entries: &InlineImageEntries) -> Option<usize> {
+    let number = |key: &str| entries.get(key).and_then(|v| v.as_number::<usize>().ok());
+    if let Some(length) = number("Length") {
+        return Some(length);
+    }
+    if entries.contains_key("Filter") {
+        return None;
+    }
+
+    let image_mask = matches!(
+        entries.get("ImageMask").map(|v| v.as_ref()),
+        Some(ObjectVariant::Boolean(true))
+    );
+    let components = if image_mask {
+        1
+    } else {
+        let space: Cow<'_, str> = match entries.get("ColorSpace").map(|v| v.as_ref())? {
+            ObjectVariant::Name(name) => Cow::Borrowed(name),
+            ObjectVariant::Array(items) => items.first()?.as_str()?,
+            _ => return None,
+        };
+        match space.as_ref() {
+            "DeviceGray" | "CalGray" | "Indexed" => 1,
+            "DeviceRGB" | "CalRGB" | "Lab" => 3,
+            "DeviceCMYK" => 4,
+            _ => return None,
+        }
+    };
+    let bits = if image_mask {
+        1
+    } else {
+        number("BitsPerComponent")?
+    };
+    let row_bits = number("Width")?
+        .checked_mul(components)?
+        .checked_mul(bits)?;
+    row_bits.div_ceil(8).checked_mul(number("Height")?)
+}
+
+/// Finds the end of the image data in `data`, which starts right after the
+/// whitespace following `ID`.
+///
+/// Returns the length of the image data and the offset just past `EI`. The
+/// expected length, from `/Length` or the image dimensions, is tried first.
+/// Otherwise the first `EI` surrounded by whitespace and followed by text is
+/// taken, since image data may itself contain the bytes `EI`; failing that,
+/// the first `EI` surrounded by whitespace at all.
+pub(crate) fn find_end(data: &[u8], expected: Option<usize>) -> Option<(usize, usize)> {
+    if let Some(length) = expected
+        && let Some(rest) = data.get(length..)
+    {
+        let skipped = rest.iter().take_while(|&&b| is_whitespace(b)).count();
+        let start = length.checked_add(skipped)?;
+        if starts_with_keyword(data.get(start..)?, b"EI") {
+            return Some((length, start.checked_add(2)?));
+        }
+    }
+
+    let mut first_delimited = None;
+    let mut position = 0usize;
+    while let Some(found) = data
+        .get(position..)?
+        .windows(2)
+        .position(|window| window == b"EI")
+    {
+        let start = position.checked_add(found)?;
+        let end = start.checked_add(2)?;
+        let preceded = start
+            .checked_sub(1)
+            .is_none_or(|before| data.get(before).is_some_and(|&b| is_whitespace(b)));
+        if preceded && starts_with_keyword(data.get(start..)?, b"EI") {
+            let candidate = (start.saturating_sub(1), end);
+            if is_plausible_tail(data.get(end..)?) {
+                return Some(candidate);
+            }
+            first_delimited.get_or_insert(candidate);
+        }
+        position = start.checked_add(1)?;
+    }
+    first_delimited
+}
+
+/// Returns whether the bytes after a candidate `EI` look like content stream
+/// operators rather than more binary image data.
+fn is_plausible_tail(tail: &[u8]) -> bool {
+    tail.iter()
+        .take(PLAUSIBLE_TAIL)
+        .all(|&b| b.is_ascii_graphic() || is_whitespace(b))
+}
+
+/// Decodes the leading `/Filter`s of the image that are only transport
+/// encodings, rewriting `/Filter` and `/DecodeParms` to the filters left, such
+/// as `/DCTDecode`, and their parameters.
+pub(crate) fn decode_filters(
+    entries: &mut InlineImageEntries,
+    mut data: Vec<u8>,
+) -> Result<Vec<u8>, PdfOperatorError> {
+    let filters: Vec<String> = match entries.remove("Filter").map(|f| *f) {
+        Some(ObjectVariant::Name(name)) => vec![name],
+        Some(ObjectVariant::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(Cow::into_owned))
+            .collect(),
+        _ => Vec::new(),
+    };
+    // `/DecodeParms` is a single dictionary for a single filter, or an array
+    // holding the parameters of each filter in turn.
+    let mut params: Vec<Option<Rc<Dictionary>>> = match entries.remove("DecodeParms").map(|p| *p) {
+        Some(ObjectVariant::Dictionary(params)) => vec![Some(params)],
+        Some(ObjectVariant::Array(items)) => items
+            .into_iter()
+            .map(|item| match item {
+                ObjectVariant::Dictionary(params) => Some(params),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    params.resize(filters.len(), None);
+
+    let mut filters = filters.into_iter().zip(params).peekable();
+    while let Some((name, params)) = filters.next_if(|(name, _)| filter::is_supported(name)) {
+        data = filter::decode(&name, &data, params.as_deref())?;
+    }
+
+    let (mut remaining, mut remaining_params): (Vec<_>, Vec<_>) = filters
+        .map(|(name, params)| {
+            let params = params.map_or(ObjectVariant::Null, ObjectVariant::Dictionary);
+            (ObjectVariant::Name(name), params)
+        })
+        .unzip();
+    let has_params = remaining_params
+        .iter()
+        .any(|params| !matches!(params, ObjectVariant::Null));
+    if remaining.len() > 1 {
+        entries.insert(
+            "Filter".to_string(),
+            Box::new(ObjectVariant::Array(remaining)),
+        );
+        if has_params {
+            let params = ObjectVariant::Array(remaining_params);
+            entries.insert("DecodeParms".to_string(), Box::new(params));
+        }
+    } else if let Some(filter) = remaining.pop() {
+        entries.insert("Filter".to_string(), Box::new(filter));
+        if let Some(params) = remaining_params.pop().filter(|_| has_params) {
+            entries.insert("DecodeParms".to_string(), Box::new(params));
+        }
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use pdf_parser::filter::FilterError;
+
+    use super::*;
+
+    fn name(name: &str) -> Box<ObjectVariant> {
+        Box::new(ObjectVariant::Name(name.to_string()))
+    }
+
+    #[test]
+    fn test_find_end_uses_expected_length() {
+        // The data itself contains a delimited `EI` followed by text.
+        let data = b"ab EI cd\nEI Q";
+        assert_eq!(find_end(data, Some(8)), Some((8, 11)));
+        assert_eq!(find_end(data, None), Some((2, 5)));
+    }
+
+    #[test]
+    fn test_find_end_falls_back_to_first_delimited_ei() {
+        let data = b"\x01\x02 EI \xff\xfe\x00 more binary";
+        assert_eq!(find_end(data, None), Some((2, 5)));
+        assert_eq!(find_end(b"EIEI", None), None);
+    }
+
+    #[test]
+    fn test_decode_filters_keeps_image_filter_and_its_params() {
+        let params = Dictionary::new(BTreeMap::from([(
+            "ColorTransform".to_string(),
+            Box::new(ObjectVariant::Integer(0)),
+        )]));
+        let mut entries = BTreeMap::from([
+            (
+                "Filter".to_string(),
+                Box::new(ObjectVariant::Array(vec![
+                    ObjectVariant::Name("ASCIIHexDecode".to_string()),
+                    ObjectVariant::Name("DCTDecode".to_string()),
+                ])),
+            ),
+            (
+                "DecodeParms".to_string(),
+                Box::new(ObjectVariant::Array(vec![
+                    ObjectVariant::Null,
+                    ObjectVariant::Dictionary(Rc::new(params.clone())),
+                ])),
+            ),
+        ]);
+
+        let data = decode_filters(&mut entries, b"FFD8>".to_vec()).unwrap();
+        assert_eq!(data, [0xFF, 0xD8]);
+        assert_eq!(entries.get("Filter"), Some(&name("DCTDecode")));
+        assert_eq!(
+            entries.get("DecodeParms").map(|p| p.as_ref()),
+            Some(&ObjectVariant::Dictionary(Rc::new(params)))
+        );
+    }
+
+    /// Wraps `data` in a zlib stream holding a single uncompressed block.
+    fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let length = u16::try_from(data.len()).unwrap();
+        let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+            let a = a.wrapping_add(u32::from(byte)) % 65521;
+            (a, b.wrapping_add(a) % 65521)
+        });
+        let mut stream = vec![0x78, 0x01, 0x01];
+        stream.extend_from_slice(&length.to_le_bytes());
+        stream.extend_from_slice(&(!length).to_le_bytes());
+        stream.extend_from_slice(data);
+        stream.extend_from_slice(&((b << 16) | a).to_be_bytes());
+        stream
+    }
+
+    #[test]
+    fn test_decode_filters_applies_predictor() {
+        let params = Dictionary::new(BTreeMap::from([
+            (
+                "Predictor".to_string(),
+                Box::new(ObjectVariant::Integer(12)),
+            ),
+            ("Columns".to_string(), Box::new(ObjectVariant::Integer(2))),
+        ]));
+        let mut entries = BTreeMap::from([
+            ("Filter".to_string(), name("FlateDecode")),
+            (
+                "DecodeParms".to_string(),
+                Box::new(ObjectVariant::Dictionary(Rc::new(params))),
+            ),
+        ]);
+
+        // Two PNG rows: Sub, then Up.
+        let data = zlib_stored(&[1, 5, 1, 2, 1, 1]);
+        let data = decode_filters(&mut entries, data).unwrap();
+        assert_eq!(data, [5, 6, 6, 7]);
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_decode_filters_rejects_unsupported_predictor() {
+        let params = Dictionary::new(BTreeMap::from([(
+            "Predictor".to_string(),
+            Box::new(ObjectVariant::Integer(7)),
+        )]));
+        let mut entries = BTreeMap::from([
+            ("Filter".to_string(), name("FlateDecode")),
+            (
+                "DecodeParms".to_string(),
+                Box::new(ObjectVariant::Dictionary(Rc::new(params))),
+            ),
+        ]);
+
+        let result = decode_filters(&mut entries, zlib_stored(&[1, 2, 3]));
+        assert!(matches!(
+            result,
+            Err(PdfOperatorError::InlineImageFilter(
+                FilterError::UnsupportedPredictor { predictor: 7, .. }
+            ))
+        ));
+    }
+}
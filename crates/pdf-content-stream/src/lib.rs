@@ -2,6 +2,7 @@ pub mod clipping_path_operators;
 pub mod color_operators;
 pub mod error;
 pub mod graphics_state_operators;
+mod inline_image;
 pub mod marked_content_operators;
 pub mod operation_map;
 pub mod operator_tokenizer;
@@ -37,6 +37,16 @@ impl PdfOperator for BeginMarkedContent {
     }
 }
 
+/// The property list operand of a marked-content operator.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyList {
+    /// The name of an entry in the `/Properties` subdictionary of the current
+    /// resource dictionary.
+    Named(String),
+    /// A property list given inline in the content stream.
+    Inline(Rc<Dictionary>),
+}
+
 /// Begins a marked-content sequence with an associated property list.
 /// The property list can be either a name (referring to an entry in the Properties subdictionary
 /// of the current resource dictionary) or an inline dictionary.
@@ -45,11 +55,11 @@ pub struct BeginMarkedContentWithProps {
     /// The tag indicating the role or nature of the marked-content sequence.
     tag: String,
     /// The property list, which can be a name (of an entry in the resource dictionary's Properties subdictionary) or an inline dictionary.
-    properties: Rc<Dictionary>,
+    properties: PropertyList,
 }
 
 impl BeginMarkedContentWithProps {
-    pub fn new(tag: String, properties: Rc<Dictionary>) -> Self {
+    pub fn new(tag: String, properties: PropertyList) -> Self {
         Self { tag, properties }
     }
 }
@@ -61,7 +71,7 @@ impl PdfOperator for BeginMarkedContentWithProps {
 
     fn read(operands: &mut Operands) -> Result<PdfOperatorVariant, PdfOperatorError> {
         let tag = operands.get_str()?;
-        let properties = operands.get_dictionary()?;
+        let properties = operands.get_property_list()?;
         Ok(PdfOperatorVariant::BeginMarkedContentWithProps(Self::new(
             tag, properties,
         )))
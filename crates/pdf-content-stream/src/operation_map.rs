@@ -12,7 +12,7 @@ use crate::{
     text_positioning_operators::*,
     text_showing_operators::*,
     text_state_operators::*,
-    type3_font_operators::SetCharWidthAndBoundingBox,
+    type3_font_operators::{SetCharWidth, SetCharWidthAndBoundingBox},
     xobject_and_image_operators::*,
 };
 
@@ -92,10 +92,9 @@ pub(crate) const READ_MAP: &[OpDescriptor] = &[
     OpDescriptor::from::<SetRenderingMode>(),
     OpDescriptor::from::<SetTextRise>(),
     OpDescriptor::from::<InvokeXObject>(),
-    OpDescriptor::from::<BeginInlineImage>(),
-    OpDescriptor::from::<InlineImageData>(),
-    OpDescriptor::from::<EndInlineImage>(),
+    OpDescriptor::from::<InlineImage>(),
     OpDescriptor::from::<PaintShading>(),
+    OpDescriptor::from::<SetCharWidth>(),
     OpDescriptor::from::<SetCharWidthAndBoundingBox>(),
     OpDescriptor::from::<SetStrokeColorSpace>(),
     OpDescriptor::from::<SetNonStrokingColorSpace>(),
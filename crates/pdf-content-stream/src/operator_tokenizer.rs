@@ -1,8 +1,11 @@
 use alloc::borrow::Cow;
+use std::collections::BTreeMap;
+
+use pdf_object::{ObjectVariant, dictionary::Dictionary};
 use pdf_parser::{parser::PdfParser, traits::CommentParser};
 use pdf_tokenizer::PdfToken;
 
-use crate::error::PdfOperatorError;
+use crate::{error::PdfOperatorError, inline_image};
 
 /// Defines a trait for reading PDF operators and their operands from an input source.
 ///
@@ -17,15 +20,29 @@ pub trait OperatorReader<'a> {
 
     /// Skips whitespaces and comments.
     fn skip_whitespace_and_comments(&mut self) -> Result<(), PdfOperatorError>;
+
+    /// Reads the body of an inline image, following the `BI` operator up to and
+    /// including `EI`.
+    ///
+    /// Abbreviated keys and names in the image dictionary are expanded to the ones
+    /// used by image XObjects, and the data is decoded from the filters the
+    /// renderer cannot apply itself, leaving e.g. `/DCTDecode` in `/Filter`.
+    fn read_inline_image(&mut self) -> Result<(Dictionary, Vec<u8>), PdfOperatorError>;
 }
 
 impl<'a> OperatorReader<'a> for PdfParser<'a> {
     fn read_operation_name(&mut self) -> Result<Cow<'a, str>, PdfOperatorError> {
         self.skip_whitespace();
 
+        // The quote operators are single delimiter-like characters, never part of a longer name.
+        if let Some(quote @ (b'\'' | b'"')) = self.tokenizer.data().first().copied() {
+            let _ = self.tokenizer.read_excactly(1);
+            return Ok(Cow::Borrowed(if quote == b'"' { "\"" } else { "'" }));
+        }
+
         let name_bytes = self
             .tokenizer
-            .read_while_u8(|b| b.is_ascii_alphabetic() || b == b'*' || b == b'1');
+            .read_while_u8(|b| b.is_ascii_alphabetic() || b == b'*' || b == b'0' || b == b'1');
         if name_bytes.is_empty() {
             return Ok(Cow::Borrowed(""));
         }
@@ -41,4 +58,48 @@ impl<'a> OperatorReader<'a> for PdfParser<'a> {
         }
         Ok(())
     }
+
+    fn read_inline_image(&mut self) -> Result<(Dictionary, Vec<u8>), PdfOperatorError> {
+        let mut entries = BTreeMap::new();
+        loop {
+            self.skip_whitespace_and_comments()?;
+            if inline_image::starts_with_keyword(self.tokenizer.data(), b"ID") {
+                self.tokenizer.read_excactly(2)?;
+                break;
+            }
+            let key = match self.parse_object()? {
+                ObjectVariant::Name(key) => key,
+                other => {
+                    return Err(PdfOperatorError::InvalidOperandType {
+                        expected_type: "Name",
+                        found_type: other.name(),
+                    });
+                }
+            };
+            self.skip_whitespace_and_comments()?;
+            let key = inline_image::expand_key(&key).to_string();
+            let value = inline_image::expand_value(&key, self.parse_object()?);
+            entries.insert(key, Box::new(value));
+        }
+
+        // `ID` is followed by a single whitespace byte before the data.
+        if self
+            .tokenizer
+            .data()
+            .first()
+            .is_some_and(|b| b.is_ascii_whitespace())
+        {
+            self.tokenizer.read_excactly(1)?;
+        }
+
+        let remaining = self.tokenizer.data();
+        let (length, end) =
+            inline_image::find_end(remaining, inline_image::expected_length(&entries))
+                .ok_or(PdfOperatorError::MissingInlineImageEnd)?;
+        let data = remaining.get(..length).unwrap_or_default().to_vec();
+        self.tokenizer.read_excactly(end)?;
+
+        let data = inline_image::decode_filters(&mut entries, data)?;
+        Ok((Dictionary::new(entries), data))
+    }
 }
@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use pdf_object::{ObjectVariant, dictionary::Dictionary};
 
-use crate::{TextElement, error::PdfOperatorError};
+use crate::{TextElement, error::PdfOperatorError, marked_content_operators::PropertyList};
 
 pub struct Operands<'a> {
     pub values: &'a [ObjectVariant],
@@ -88,6 +88,17 @@ impl<'a> Operands<'a> {
         })
     }
 
+    pub fn get_property_list(&mut self) -> Result<PropertyList, PdfOperatorError> {
+        self.take_and_map("Name or Dictionary", |value| match value {
+            ObjectVariant::Name(name) => Ok(PropertyList::Named(name.clone())),
+            ObjectVariant::Dictionary(dict) => Ok(PropertyList::Inline(Rc::clone(dict))),
+            _ => Err(PdfOperatorError::InvalidOperandType {
+                expected_type: "Name or Dictionary",
+                found_type: value.name(),
+            }),
+        })
+    }
+
     pub fn get_str(&mut self) -> Result<String, PdfOperatorError> {
         self.take_and_map("String", |value| {
             value.as_str().map(|s| s.to_string()).ok_or_else(|| {
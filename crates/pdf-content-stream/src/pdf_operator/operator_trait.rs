@@ -1,4 +1,4 @@
-use crate::pdf_operator_backend::PdfOperatorBackend;
+use crate::{error::PdfOperatorError, pdf_operator_backend::PdfOperatorBackend};
 
 /// Represents a PDF content stream operator.
 ///
@@ -21,7 +21,11 @@ pub trait PdfOperator {
         operands: &mut crate::pdf_operator::Operands,
     ) -> Result<crate::pdf_operator::PdfOperatorVariant, crate::error::PdfOperatorError>;
 
+    /// Dispatches the operator to the matching method of `backend`.
+    ///
+    /// Operators that cannot be executed yet report
+    /// [`PdfOperatorError::UnimplementedOperation`] instead of panicking.
     fn call<T: PdfOperatorBackend>(&self, _backend: &mut T) -> Result<(), T::ErrorType> {
-        todo!("Unimplemented operator {}", Self::NAME)
+        Err(PdfOperatorError::UnimplementedOperation(Self::NAME).into())
     }
 }
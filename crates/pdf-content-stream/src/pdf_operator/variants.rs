@@ -1,13 +1,26 @@
+use std::rc::Rc;
+
+use pdf_object::ObjectVariant;
 use pdf_parser::{parser::PdfParser, traits::CommentParser};
 use pdf_tokenizer::PdfToken;
 
 use crate::{
-    clipping_path_operators::*, color_operators::*, error::PdfOperatorError,
-    graphics_state_operators::*, marked_content_operators::*, operation_map::READ_MAP,
-    operator_tokenizer::OperatorReader, path_operators::*, path_paint_operators::*,
-    pdf_operator_backend::PdfOperatorBackend, shadings_operators::PaintShading,
-    text_object_operators::*, text_positioning_operators::*, text_showing_operators::*,
-    text_state_operators::*, type3_font_operators::SetCharWidthAndBoundingBox,
+    clipping_path_operators::*,
+    color_operators::*,
+    error::PdfOperatorError,
+    graphics_state_operators::*,
+    marked_content_operators::*,
+    operation_map::READ_MAP,
+    operator_tokenizer::OperatorReader,
+    path_operators::*,
+    path_paint_operators::*,
+    pdf_operator_backend::PdfOperatorBackend,
+    shadings_operators::PaintShading,
+    text_object_operators::*,
+    text_positioning_operators::*,
+    text_showing_operators::*,
+    text_state_operators::*,
+    type3_font_operators::{SetCharWidth, SetCharWidthAndBoundingBox},
     xobject_and_image_operators::*,
 };
 
@@ -70,10 +83,9 @@ pub enum PdfOperatorVariant {
     SetRenderingMode(SetRenderingMode),
     SetTextRise(SetTextRise),
     InvokeXObject(InvokeXObject),
-    BeginInlineImage(BeginInlineImage),
-    InlineImageData(InlineImageData),
-    EndInlineImage(EndInlineImage),
+    InlineImage(InlineImage),
     PaintShading(PaintShading),
+    SetCharWidth(SetCharWidth),
     SetCharWidthAndBoundingBox(SetCharWidthAndBoundingBox),
     SetStrokeColorSpace(SetStrokeColorSpace),
     SetNonStrokingColorSpace(SetNonStrokingColorSpace),
@@ -95,17 +107,26 @@ impl PdfOperatorVariant {
                 continue;
             }
 
+            let is_quote_operator = matches!(parser.tokenizer.data().first(), Some(b'\'' | b'"'));
             let peeked = parser.tokenizer.peek();
-            if peeked.is_none() {
+            if peeked.is_none() && !is_quote_operator {
                 break;
             }
 
-            if let Some(PdfToken::Alphabetic(_)) = &peeked {
+            if is_quote_operator || matches!(peeked, Some(PdfToken::Alphabetic(_))) {
                 let name = parser.read_operation_name()?;
                 if name.is_empty() {
                     break;
                 }
 
+                // The inline image dictionary and data follow `BI` rather than
+                // preceding it, so they are read here and passed on as operands.
+                if name == InlineImage::NAME {
+                    let (dictionary, data) = parser.read_inline_image()?;
+                    operands.push(ObjectVariant::Dictionary(Rc::new(dictionary)));
+                    operands.push(ObjectVariant::HexString(data));
+                }
+
                 let mut handled = false;
                 for operation in READ_MAP {
                     if name == operation.name {
@@ -200,10 +221,9 @@ impl PdfOperatorVariant {
             PdfOperatorVariant::SetRenderingMode(op) => op.call(backend),
             PdfOperatorVariant::SetTextRise(op) => op.call(backend),
             PdfOperatorVariant::InvokeXObject(op) => op.call(backend),
-            PdfOperatorVariant::BeginInlineImage(op) => op.call(backend),
-            PdfOperatorVariant::InlineImageData(op) => op.call(backend),
-            PdfOperatorVariant::EndInlineImage(op) => op.call(backend),
+            PdfOperatorVariant::InlineImage(op) => op.call(backend),
             PdfOperatorVariant::PaintShading(op) => op.call(backend),
+            PdfOperatorVariant::SetCharWidth(op) => op.call(backend),
             PdfOperatorVariant::SetCharWidthAndBoundingBox(op) => op.call(backend),
             PdfOperatorVariant::SetStrokeColorSpace(op) => op.call(backend),
             PdfOperatorVariant::SetNonStrokingColorSpace(op) => op.call(backend),
@@ -216,6 +236,8 @@ impl PdfOperatorVariant {
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 mod tests {
+    use pdf_object::dictionary::Dictionary;
+
     use super::*;
 
     #[test]
@@ -461,6 +483,53 @@ mod tests {
                     PdfOperatorVariant::StrokePath(StrokePath),
                 ],
             },
+            TestCase {
+                description: "25. Type 3 glyph metrics (d0, d1)",
+                input: b"750 0 d0 500 0 10 -20 490 700 d1",
+                expected_ops: vec![
+                    PdfOperatorVariant::SetCharWidth(SetCharWidth::new(750.0, 0.0)),
+                    PdfOperatorVariant::SetCharWidthAndBoundingBox(
+                        SetCharWidthAndBoundingBox::new(500.0, 0.0, 10.0, -20.0, 490.0, 700.0),
+                    ),
+                ],
+            },
+            TestCase {
+                description: "26. Leading and the quote operators (TL, TD, T*, ', \")",
+                input: b"14 TL 0 -14 TD T* (one)'2 1(two)\"(three)'",
+                expected_ops: vec![
+                    PdfOperatorVariant::SetLeading(SetLeading::new(14.0)),
+                    PdfOperatorVariant::MoveTextPositionAndSetLeading(
+                        MoveTextPositionAndSetLeading::new(0.0, -14.0),
+                    ),
+                    PdfOperatorVariant::MoveToNextLine(MoveToNextLine),
+                    PdfOperatorVariant::MoveNextLineShowText(MoveNextLineShowText::new(
+                        b"one".to_vec(),
+                    )),
+                    PdfOperatorVariant::SetSpacingMoveShowText(SetSpacingMoveShowText::new(
+                        2.0,
+                        1.0,
+                        b"two".to_vec(),
+                    )),
+                    PdfOperatorVariant::MoveNextLineShowText(MoveNextLineShowText::new(
+                        b"three".to_vec(),
+                    )),
+                ],
+            },
+            TestCase {
+                description: "27. Optional content marked with a named property list (BDC)",
+                input: b"/OC /MC0 BDC 0 0 10 10 re f EMC",
+                expected_ops: vec![
+                    PdfOperatorVariant::BeginMarkedContentWithProps(
+                        BeginMarkedContentWithProps::new(
+                            "OC".to_string(),
+                            PropertyList::Named("MC0".to_string()),
+                        ),
+                    ),
+                    PdfOperatorVariant::Rectangle(Rectangle::new(0.0, 0.0, 10.0, 10.0)),
+                    PdfOperatorVariant::FillPathNonZero(FillPathNonZero),
+                    PdfOperatorVariant::EndMarkedContent(EndMarkedContent),
+                ],
+            },
         ];
 
         for tc in test_cases {
@@ -481,4 +550,70 @@ mod tests {
             );
         }
     }
+
+    fn inline_image(entries: &[(&str, ObjectVariant)], data: &[u8]) -> PdfOperatorVariant {
+        let dictionary = Dictionary::new(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), Box::new(value.clone())))
+                .collect(),
+        );
+        PdfOperatorVariant::InlineImage(InlineImage::new(Rc::new(dictionary), data.to_vec()))
+    }
+
+    #[test]
+    fn test_inline_image() {
+        let name = |name: &str| ObjectVariant::Name(name.to_string());
+        let gray_2x1 = [
+            ("BitsPerComponent", ObjectVariant::Integer(8)),
+            ("ColorSpace", name("DeviceGray")),
+            ("Height", ObjectVariant::Integer(1)),
+            ("Width", ObjectVariant::Integer(2)),
+        ];
+
+        // The data has the expected length of an unfiltered image, even though it
+        // contains `EI` itself.
+        let ops = PdfOperatorVariant::from(b"q BI /W 2 /H 1 /BPC 8 /CS /G ID EI\nEI Q").unwrap();
+        assert_eq!(
+            ops,
+            vec![
+                PdfOperatorVariant::SaveGraphicsState(SaveGraphicsState),
+                inline_image(&gray_2x1, b"EI"),
+                PdfOperatorVariant::RestoreGraphicsState(RestoreGraphicsState),
+            ]
+        );
+
+        // Transport filters are decoded and removed from the dictionary; the end is
+        // found by looking for `EI`.
+        let ops =
+            PdfOperatorVariant::from(b"BI /W 2 /H 1 /BPC 8 /CS /G /F /AHx ID 00f>\nEI Q").unwrap();
+        assert_eq!(
+            ops,
+            vec![
+                inline_image(&gray_2x1, &[0x00, 0xf0]),
+                PdfOperatorVariant::RestoreGraphicsState(RestoreGraphicsState),
+            ]
+        );
+
+        // Image compression filters are kept for the renderer.
+        let ops = PdfOperatorVariant::from(b"BI /W 1 /H 1 /IM true /F [/A85 /DCT] ID 9jqo^~> EI")
+            .unwrap();
+        assert_eq!(
+            ops,
+            vec![inline_image(
+                &[
+                    ("Filter", name("DCTDecode")),
+                    ("Height", ObjectVariant::Integer(1)),
+                    ("ImageMask", ObjectVariant::Boolean(true)),
+                    ("Width", ObjectVariant::Integer(1)),
+                ],
+                b"Man ",
+            )]
+        );
+
+        assert_eq!(
+            PdfOperatorVariant::from(b"BI /W 2 /H 1 /F /Fl ID \x01\x02"),
+            Err(PdfOperatorError::MissingInlineImageEnd)
+        );
+    }
 }
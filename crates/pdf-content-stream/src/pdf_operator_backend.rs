@@ -3,16 +3,16 @@
 //! PDF drawing, text, and state commands, allowing for different backends
 //! (e.g., renderers, text extractors) to selectively implement functionality.
 
-use std::rc::Rc;
-
 use pdf_graphics::{LineCap, LineJoin, TextRenderingMode};
 use pdf_object::dictionary::Dictionary;
 
-use crate::TextElement;
+use crate::{TextElement, error::PdfOperatorError, marked_content_operators::PropertyList};
 
 pub trait PdfOperatorBackendError {
     /// The error type that can be returned by operator handling methods.
-    type ErrorType: std::fmt::Debug + std::fmt::Display;
+    ///
+    /// Operator errors, such as an operator the crate cannot execute yet, convert into it.
+    type ErrorType: std::fmt::Debug + std::fmt::Display + From<PdfOperatorError>;
 }
 
 /// Defines methods for handling PDF path construction operators.
@@ -741,6 +741,23 @@ pub trait XObjectOps: PdfOperatorBackendError {
     ///
     /// A `Result` indicating success or an `ErrorType` on failure.
     fn invoke_xobject(&mut self, xobject_name: &str) -> Result<(), Self::ErrorType>;
+
+    /// Paints an inline image, given in the content stream by `BI … ID … EI`.
+    ///
+    /// # Parameters
+    ///
+    /// - `dictionary`: The image dictionary, with abbreviated keys and names expanded.
+    /// - `data`: The image data, already decoded from any filter other than the
+    ///   image compression filters left in the dictionary's `/Filter`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or an `ErrorType` on failure.
+    fn draw_inline_image(
+        &mut self,
+        dictionary: &Dictionary,
+        data: &[u8],
+    ) -> Result<(), Self::ErrorType>;
 }
 
 /// Defines methods to handle PDF Shading operators.
@@ -811,7 +828,7 @@ pub trait MarkedContentOps: PdfOperatorBackendError {
     fn begin_marked_content_with_properties(
         &mut self,
         tag: &str,
-        properties_name_or_dict: &Rc<Dictionary>,
+        properties_name_or_dict: &PropertyList,
     ) -> Result<(), Self::ErrorType>;
 
     /// Ends a marked-content sequence.
@@ -822,6 +839,46 @@ pub trait MarkedContentOps: PdfOperatorBackendError {
     fn end_marked_content(&mut self) -> Result<(), Self::ErrorType>;
 }
 
+/// Defines methods for handling the glyph metrics operators of Type 3 glyph descriptions.
+///
+/// One of these operators must be the first operator of a Type 3 character procedure.
+pub trait Type3FontOps: PdfOperatorBackendError {
+    /// Sets the width of a glyph whose description also sets its colour (`d0`).
+    ///
+    /// # Parameters
+    ///
+    /// - `wx`: The horizontal displacement in glyph space.
+    /// - `wy`: The vertical displacement in glyph space. Must be 0.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or an `ErrorType` on failure.
+    fn set_char_width(&mut self, wx: f32, wy: f32) -> Result<(), Self::ErrorType>;
+
+    /// Sets the width and bounding box of an uncoloured glyph (`d1`).
+    ///
+    /// The rest of the glyph description only defines a shape, so colour operators
+    /// in it are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `wx`, `wy`: The glyph displacement in glyph space. `wy` must be 0.
+    /// - `llx`, `lly`, `urx`, `ury`: The glyph bounding box in glyph space.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or an `ErrorType` on failure.
+    fn set_char_width_and_bounding_box(
+        &mut self,
+        wx: f32,
+        wy: f32,
+        llx: f32,
+        lly: f32,
+        urx: f32,
+        ury: f32,
+    ) -> Result<(), Self::ErrorType>;
+}
+
 /// A comprehensive backend that implements all operator categories.
 /// This can be used as a blanket implementation if a backend supports everything,
 /// or as a way to group all the specialized traits.
@@ -839,5 +896,6 @@ pub trait PdfOperatorBackend:
     + XObjectOps
     + ShadingOps
     + MarkedContentOps
+    + Type3FontOps
 {
 }
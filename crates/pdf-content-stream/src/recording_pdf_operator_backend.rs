@@ -1,5 +1,3 @@
-use std::rc::Rc;
-
 use pdf_graphics::LineCap;
 use pdf_graphics::LineJoin;
 use pdf_graphics::TextRenderingMode;
@@ -7,6 +5,8 @@ use pdf_object::dictionary::Dictionary;
 use thiserror::Error;
 
 use crate::TextElement;
+use crate::error::PdfOperatorError;
+use crate::marked_content_operators::PropertyList;
 use crate::pdf_operator_backend::*;
 
 /// Represents a recorded operation with its parameters.
@@ -199,6 +199,10 @@ pub enum RecordedOperation {
     InvokeXObject {
         xobject_name: String,
     },
+    DrawInlineImage {
+        dictionary: Dictionary,
+        data: Vec<u8>,
+    },
     PaintShading {
         shading_name: String,
     },
@@ -214,9 +218,21 @@ pub enum RecordedOperation {
     },
     BeginMarkedContentWithProperties {
         tag: String,
-        properties_name_or_dict: Rc<Dictionary>,
+        properties_name_or_dict: PropertyList,
     },
     EndMarkedContent,
+    SetCharWidth {
+        wx: f32,
+        wy: f32,
+    },
+    SetCharWidthAndBoundingBox {
+        wx: f32,
+        wy: f32,
+        llx: f32,
+        lly: f32,
+        urx: f32,
+        ury: f32,
+    },
 }
 
 /// A [`PdfOperatorBackend`] implementation that records all operations called on it.
@@ -232,7 +248,10 @@ impl RecordingBackend {
 }
 
 #[derive(Debug, Error)]
-pub enum PdfRecordingCanvasError {}
+pub enum PdfRecordingCanvasError {
+    #[error("{0}")]
+    Operator(#[from] PdfOperatorError),
+}
 
 impl PdfOperatorBackendError for RecordingBackend {
     type ErrorType = PdfRecordingCanvasError;
@@ -699,6 +718,18 @@ impl XObjectOps for RecordingBackend {
         });
         Ok(())
     }
+
+    fn draw_inline_image(
+        &mut self,
+        dictionary: &Dictionary,
+        data: &[u8],
+    ) -> Result<(), Self::ErrorType> {
+        self.operations.push(RecordedOperation::DrawInlineImage {
+            dictionary: dictionary.clone(),
+            data: data.to_vec(),
+        });
+        Ok(())
+    }
 }
 
 impl ShadingOps for RecordingBackend {
@@ -741,12 +772,12 @@ impl MarkedContentOps for RecordingBackend {
     fn begin_marked_content_with_properties(
         &mut self,
         tag: &str,
-        properties_name_or_dict: &Rc<Dictionary>,
+        properties_name_or_dict: &PropertyList,
     ) -> Result<(), Self::ErrorType> {
         self.operations
             .push(RecordedOperation::BeginMarkedContentWithProperties {
                 tag: tag.to_string(),
-                properties_name_or_dict: std::rc::Rc::clone(properties_name_or_dict),
+                properties_name_or_dict: properties_name_or_dict.clone(),
             });
         Ok(())
     }
@@ -757,4 +788,33 @@ impl MarkedContentOps for RecordingBackend {
     }
 }
 
+impl Type3FontOps for RecordingBackend {
+    fn set_char_width(&mut self, wx: f32, wy: f32) -> Result<(), Self::ErrorType> {
+        self.operations
+            .push(RecordedOperation::SetCharWidth { wx, wy });
+        Ok(())
+    }
+
+    fn set_char_width_and_bounding_box(
+        &mut self,
+        wx: f32,
+        wy: f32,
+        llx: f32,
+        lly: f32,
+        urx: f32,
+        ury: f32,
+    ) -> Result<(), Self::ErrorType> {
+        self.operations
+            .push(RecordedOperation::SetCharWidthAndBoundingBox {
+                wx,
+                wy,
+                llx,
+                lly,
+                urx,
+                ury,
+            });
+        Ok(())
+    }
+}
+
 impl PdfOperatorBackend for RecordingBackend {}
@@ -4,6 +4,41 @@ use crate::{
     pdf_operator_backend::PdfOperatorBackend,
 };
 
+/// Sets the width of a Type 3 glyph whose description may specify its own colour (`d0`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SetCharWidth {
+    /// The x-component of the character width vector.
+    pub wx: f32,
+    /// The y-component of the character width vector. Must be 0.
+    wy: f32,
+}
+
+impl SetCharWidth {
+    pub fn new(wx: f32, wy: f32) -> Self {
+        Self { wx, wy }
+    }
+}
+
+impl PdfOperator for SetCharWidth {
+    const NAME: &'static str = "d0";
+
+    const OPERAND_COUNT: Option<usize> = Some(2);
+
+    fn read(operands: &mut Operands) -> Result<PdfOperatorVariant, PdfOperatorError> {
+        let wx = operands.get_f32()?;
+        let wy = operands.get_f32()?;
+        Ok(PdfOperatorVariant::SetCharWidth(Self::new(wx, wy)))
+    }
+
+    fn call<T: PdfOperatorBackend>(&self, backend: &mut T) -> Result<(), T::ErrorType> {
+        backend.set_char_width(self.wx, self.wy)
+    }
+}
+
+/// Sets the width and bounding box of an uncoloured Type 3 glyph (`d1`).
+///
+/// The glyph description following `d1` defines only a shape; it is painted with
+/// the current fill colour, and colour operators inside it are ignored.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct SetCharWidthAndBoundingBox {
     /// The x-component of the character width vector.
@@ -20,6 +55,19 @@ pub struct SetCharWidthAndBoundingBox {
     ury: f32,
 }
 
+impl SetCharWidthAndBoundingBox {
+    pub fn new(wx: f32, wy: f32, llx: f32, lly: f32, urx: f32, ury: f32) -> Self {
+        Self {
+            wx,
+            wy,
+            llx,
+            lly,
+            urx,
+            ury,
+        }
+    }
+}
+
 impl PdfOperator for SetCharWidthAndBoundingBox {
     const NAME: &'static str = "d1";
 
@@ -33,17 +81,64 @@ impl PdfOperator for SetCharWidthAndBoundingBox {
         let urx = operands.get_f32()?;
         let ury = operands.get_f32()?;
 
-        Ok(PdfOperatorVariant::SetCharWidthAndBoundingBox(Self {
-            wx,
-            wy,
-            llx,
-            lly,
-            urx,
-            ury,
-        }))
+        Ok(PdfOperatorVariant::SetCharWidthAndBoundingBox(Self::new(
+            wx, wy, llx, lly, urx, ury,
+        )))
     }
 
-    fn call<T: PdfOperatorBackend>(&self, _backend: &mut T) -> Result<(), T::ErrorType> {
-        todo!()
+    fn call<T: PdfOperatorBackend>(&self, backend: &mut T) -> Result<(), T::ErrorType> {
+        backend.set_char_width_and_bounding_box(
+            self.wx, self.wy, self.llx, self.lly, self.urx, self.ury,
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use crate::recording_pdf_operator_backend::{RecordedOperation, RecordingBackend};
+
+    use super::*;
+
+    #[test]
+    fn test_glyph_metrics_reach_backend() {
+        let mut backend = RecordingBackend::default();
+        SetCharWidth::new(600.0, 0.0).call(&mut backend).unwrap();
+        SetCharWidthAndBoundingBox::new(500.0, 0.0, 0.0, -10.0, 480.0, 700.0)
+            .call(&mut backend)
+            .unwrap();
+        assert_eq!(
+            backend.operations,
+            vec![
+                RecordedOperation::SetCharWidth { wx: 600.0, wy: 0.0 },
+                RecordedOperation::SetCharWidthAndBoundingBox {
+                    wx: 500.0,
+                    wy: 0.0,
+                    llx: 0.0,
+                    lly: -10.0,
+                    urx: 480.0,
+                    ury: 700.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unimplemented_operator_returns_error() {
+        /// An operator relying on the default `call`.
+        struct Unimplemented;
+
+        impl PdfOperator for Unimplemented {
+            const NAME: &'static str = "xx";
+
+            const OPERAND_COUNT: Option<usize> = Some(0);
+
+            fn read(_operands: &mut Operands) -> Result<PdfOperatorVariant, PdfOperatorError> {
+                Err(PdfOperatorError::UnimplementedOperation(Self::NAME))
+            }
+        }
+
+        let mut backend = RecordingBackend::default();
+        assert!(Unimplemented.call(&mut backend).is_err());
     }
 }
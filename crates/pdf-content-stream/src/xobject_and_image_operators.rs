@@ -1,3 +1,7 @@
+use std::rc::Rc;
+
+use pdf_object::dictionary::Dictionary;
+
 use crate::{
     error::PdfOperatorError,
     pdf_operator::{Operands, PdfOperator, PdfOperatorVariant},
@@ -33,65 +37,37 @@ impl PdfOperator for InvokeXObject {
     }
 }
 
-/// Begins an inline image object.
-/// This operator is followed by key-value pairs defining the image's properties,
-/// then the `ID` operator and image data, and finally `EI`.
-#[derive(Debug, Clone, PartialEq, Default)]
-pub struct BeginInlineImage;
-
-impl PdfOperator for BeginInlineImage {
-    const NAME: &'static str = "BI";
-
-    const OPERAND_COUNT: Option<usize> = Some(0);
-
-    fn read(_operands: &mut Operands) -> Result<PdfOperatorVariant, PdfOperatorError> {
-        // The BI operator itself does not consume operands from the stack.
-        // The inline image dictionary key-value pairs follow BI directly in the stream.
-        // A full parser would need to enter a special state here to parse those pairs,
-        // then the ID operator, then image data, then EI.
-        // This function merely constructs the BeginInlineImage marker.
-        Ok(PdfOperatorVariant::BeginInlineImage(Self))
-    }
-}
-
-/// Represents the image data within an inline image object.
-/// The `ID` operator itself marks the beginning of the image data stream, which is then
-/// followed by the actual image data.
+/// Paints an inline image: the `BI` operator, the image dictionary, `ID`, the
+/// image data, and `EI`.
+///
+/// The dictionary and data are read by the tokenizer when it meets `BI`, with
+/// abbreviated keys expanded and transport filters such as `/AHx` already decoded.
 #[derive(Debug, Clone, PartialEq)]
-pub struct InlineImageData {
-    /// The raw byte data of the inline image.
+pub struct InlineImage {
+    /// The image dictionary, with the keys used by image XObjects.
+    dictionary: Rc<Dictionary>,
+    /// The image data.
     data: Vec<u8>,
 }
 
-impl PdfOperator for InlineImageData {
-    const NAME: &'static str = "ID";
-
-    const OPERAND_COUNT: Option<usize> = Some(0);
-
-    fn read(_operands: &mut Operands) -> Result<PdfOperatorVariant, PdfOperatorError> {
-        // The ID (Image Data) operator itself does not have preceding operands that form the image data.
-        // The image data stream follows the ID token and is terminated by EI.
-        // The `_operands` received here would typically contain the key-value pairs of the
-        // inline image dictionary if the main parser collected them as generic operands before ID.
-        // This `read` function, within the current `Operands` model, cannot access or parse
-        // the actual image data that follows the ID token.
-        // Proper parsing of inline image data requires special handling in the main parser loop.
-        Err(PdfOperatorError::UnimplementedOperation(Self::NAME))
+impl InlineImage {
+    pub fn new(dictionary: Rc<Dictionary>, data: Vec<u8>) -> Self {
+        Self { dictionary, data }
     }
 }
 
-/// Ends an inline image object.
-#[derive(Debug, Clone, PartialEq, Default)]
-pub struct EndInlineImage;
+impl PdfOperator for InlineImage {
+    const NAME: &'static str = "BI";
 
-impl PdfOperator for EndInlineImage {
-    const NAME: &'static str = "EI";
+    const OPERAND_COUNT: Option<usize> = Some(2);
 
-    const OPERAND_COUNT: Option<usize> = Some(0);
+    fn read(operands: &mut Operands) -> Result<PdfOperatorVariant, PdfOperatorError> {
+        let dictionary = operands.get_dictionary()?;
+        let data = operands.get_bytes()?.to_vec();
+        Ok(PdfOperatorVariant::InlineImage(Self::new(dictionary, data)))
+    }
 
-    fn read(_operands: &mut Operands) -> Result<PdfOperatorVariant, PdfOperatorError> {
-        // The EI operator does not take any operands from the stack.
-        // It simply marks the end of the inline image data.
-        Ok(PdfOperatorVariant::EndInlineImage(Self))
+    fn call<T: PdfOperatorBackend>(&self, backend: &mut T) -> Result<(), T::ErrorType> {
+        backend.draw_inline_image(&self.dictionary, &self.data)
     }
 }
@@ -1,5 +1,5 @@
 use pdf_object::error::ObjectError;
-use pdf_page::pages::PdfPagesError;
+use pdf_page::{output_intent::OutputIntentError, pages::PdfPagesError};
 use pdf_parser::{error::ParserError, header::HeaderError};
 use thiserror::Error;
 
@@ -26,6 +26,8 @@ pub enum PdfError {
     PdfPagesError(#[from] PdfPagesError),
     #[error("{0}")]
     ParserError(#[from] ParserError),
+    #[error("{0}")]
+    OutputIntentError(#[from] OutputIntentError),
     #[error("Error parsing PDF header: {0}")]
     HeaderError(#[from] HeaderError),
 }
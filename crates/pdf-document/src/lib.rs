@@ -1,8 +1,14 @@
 use error::PdfError;
+use pdf_graphics::color_management::IccProfile;
 use pdf_object::{
     ObjectVariant, object_collection::ObjectCollection, traits::FromDictionary, version::Version,
 };
-use pdf_page::{page::PdfPage, pages::PdfPages};
+use pdf_page::{
+    optional_content::{OptionalContentGroup, OptionalContentProperties},
+    output_intent::OutputIntent,
+    page::PdfPage,
+    pages::PdfPages,
+};
 use pdf_parser::{parser::PdfParser, traits::HeaderParser};
 
 pub mod error;
@@ -15,6 +21,10 @@ pub struct PdfDocument {
     pub objects: ObjectCollection,
     /// The pages in the PDF document.
     pub pages: Vec<PdfPage>,
+    /// The output intents from the document catalog's `/OutputIntents` array.
+    pub output_intents: Vec<OutputIntent>,
+    /// The optional content (layers) from the document catalog's `/OCProperties`.
+    pub optional_content: Option<OptionalContentProperties>,
 }
 
 impl PdfDocument {
@@ -26,6 +36,33 @@ impl PdfDocument {
         self.pages.get(index)
     }
 
+    /// Returns the destination profile of the first output intent that has one.
+    ///
+    /// This profile characterises the device colour spaces of the document.
+    pub fn output_profile(&self) -> Option<&IccProfile> {
+        self.output_intents
+            .iter()
+            .find_map(|intent| intent.dest_output_profile.as_ref())
+    }
+
+    /// Returns the document's optional content groups, or layers, with their
+    /// current visibility. The list is empty for documents without layers.
+    pub fn layers(&self) -> &[OptionalContentGroup] {
+        self.optional_content
+            .as_ref()
+            .map_or(&[], |properties| properties.groups.as_slice())
+    }
+
+    /// Shows or hides the layer whose group has object number `id`, for pages
+    /// rendered afterwards.
+    ///
+    /// Returns `false` if the document has no such layer.
+    pub fn set_layer_visible(&mut self, id: i32, visible: bool) -> bool {
+        self.optional_content
+            .as_mut()
+            .is_some_and(|properties| properties.set_group_visible(id, visible))
+    }
+
     pub fn from(input: &[u8]) -> Result<Self, PdfError> {
         let mut parser = PdfParser::from(input);
         let version = parser.parse_header()?;
@@ -61,10 +98,71 @@ impl PdfDocument {
 
         let pages = PdfPages::from_dictionary(pages_dict, &objects)?;
 
+        // Read the optional `/OutputIntents` array. An intent that cannot be read is
+        // skipped, as the document renders without it.
+        let mut output_intents = Vec::new();
+        if let Some(Ok(intents)) = catalog
+            .get("OutputIntents")
+            .map(|intents| objects.resolve_object(intents))
+            && let Ok(intents) = intents.try_array()
+        {
+            for intent in intents {
+                if let Ok(dictionary) = objects.resolve_dictionary(intent)
+                    && let Ok(intent) = OutputIntent::from_dictionary(dictionary, &objects)
+                {
+                    output_intents.push(intent);
+                }
+            }
+        }
+
+        // Read the optional `/OCProperties` dictionary. Properties that cannot be
+        // read are ignored, and the document is drawn with every layer shown.
+        let optional_content = catalog.get("OCProperties").and_then(|properties| {
+            let properties = objects.resolve_dictionary(properties).ok()?;
+            OptionalContentProperties::from_dictionary(properties, &objects).ok()
+        });
+
         Ok(PdfDocument {
             version,
             objects,
             pages: pages.pages,
+            output_intents,
+            optional_content,
         })
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    /// A one-page document whose catalog has the given `/OCProperties` entry.
+    fn document_with_oc_properties(oc_properties: &str) -> Vec<u8> {
+        format!(
+            "%PDF-1.7\n\
+             1 0 obj\n<< /Type /Catalog /Pages 2 0 R /OCProperties {oc_properties} >>\nendobj\n\
+             2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n\
+             3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 10 10] >>\nendobj\n\
+             4 0 obj\n<< /Type /OCG /Name (Layer) >>\nendobj\n\
+             trailer\n<< /Size 5 /Root 1 0 R >>\nstartxref\n0\n%%EOF\n"
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn optional_content_is_read_from_the_catalog() {
+        let input = document_with_oc_properties("<< /OCGs [4 0 R] /D << /OFF [4 0 R] >> >>");
+        let document = PdfDocument::from(&input).unwrap();
+        assert_eq!(document.layers().len(), 1);
+        assert!(!document.layers()[0].visible);
+    }
+
+    #[test]
+    fn unreadable_optional_content_is_ignored() {
+        let input = document_with_oc_properties("<< /OCGs [4 0 R] /D << /ON 4 >> >>");
+        let document = PdfDocument::from(&input).unwrap();
+        assert_eq!(document.page_count(), 1);
+        assert!(document.optional_content.is_none());
+    }
+}
@@ -21,6 +21,9 @@ pub struct Charset {
 }
 
 impl Charset {
+    /// The highest SID covered by the predefined ISOAdobe charset.
+    const ISO_ADOBE_LAST_SID: u16 = 228;
+
     /// Reads a CFF charset from the supplied cursor.
     ///
     /// # Parameters
@@ -58,11 +61,48 @@ impl Charset {
                 }
                 Ok(Charset { map: sids })
             }
+            1 | 2 => {
+                let mut sids = HashMap::new();
+                sids.insert(0, 0);
+                // Each range covers `first..=first + n_left` consecutive SIDs, assigned to
+                // consecutive GIDs. Format 1 stores `n_left` as a byte, format 2 as a u16.
+                let mut gid = 1u16;
+                while gid < number_of_glyphs {
+                    let first = cur.read_u16()?;
+                    let n_left = if format == 1 {
+                        u16::from(cur.read_u8()?)
+                    } else {
+                        cur.read_u16()?
+                    };
+                    for offset in 0..=n_left {
+                        if gid >= number_of_glyphs {
+                            break;
+                        }
+                        sids.insert(first.saturating_add(offset), gid);
+                        gid = gid.saturating_add(1);
+                    }
+                }
+                Ok(Charset { map: sids })
+            }
 
             _ => Err(CharsetError::UnsupportedCharsetFormat(format)),
         }
     }
 
+    /// Builds the predefined ISOAdobe charset, in which SID and GID coincide.
+    ///
+    /// # Parameters
+    ///
+    /// - `number_of_glyphs`: The total number of glyphs in the font
+    pub fn iso_adobe(number_of_glyphs: usize) -> Charset {
+        let last = number_of_glyphs.min(usize::from(Self::ISO_ADOBE_LAST_SID) + 1);
+        let map = (0..last)
+            .filter_map(|gid| u16::try_from(gid).ok())
+            .map(|gid| (gid, gid))
+            .collect();
+        Charset { map }
+    }
+
     /// Looks up the GID (glyph index) for a given SID.
     ///
     /// # Parameters
@@ -1,9 +1,18 @@
 use thiserror::Error;
 
-use crate::cff::{char_string_interpreter::CharStringEvalError, cursor::CursorReadError};
+use crate::{
+    cff::{char_string_interpreter::CharStringEvalError, cursor::CursorReadError},
+    font_descriptor::FontFileKind,
+};
 
 #[derive(Debug, Error)]
 pub enum CompactFontFormatError {
+    #[error("Font program of kind {0:?} is not in CFF format")]
+    UnsupportedFontProgram(FontFileKind),
+    #[error("OpenType font program has no CFF table")]
+    MissingCffTable,
+    #[error("Malformed OpenType font program: {0}")]
+    OpenType(ttf_parser::FaceParsingError),
     #[error("Unexpected end of file: {0}")]
     UnexpectedEof(&'static str),
     #[error("Invalid data: {0}")]
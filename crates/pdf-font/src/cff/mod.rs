@@ -8,4 +8,5 @@ pub mod error;
 mod parser;
 pub mod program;
 pub mod reader;
+mod standard_strings;
 mod top_dictionary_entry;
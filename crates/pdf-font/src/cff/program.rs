@@ -2,7 +2,8 @@ use pdf_graphics::pdf_path::PdfPath;
 
 use crate::cff::{
     char_string_interpreter::CharStringOperator, char_string_interpreter_stack::CharStringStack,
-    encoding::Encoding, error::CompactFontFormatError,
+    charset::Charset, encoding::Encoding, error::CompactFontFormatError,
+    standard_strings::STANDARD_STRINGS,
 };
 
 /// Represents a parsed CFF font program.
@@ -14,6 +15,11 @@ pub struct CffFontProgram {
     /// The encoding mapping from 8-bit character codes (u8) appearing in a PDF
     /// content stream to glyph IDs (GIDs).
     pub encoding: Encoding,
+    /// The charset mapping string identifiers (SIDs) of glyph names to glyph IDs.
+    pub(crate) charset: Charset,
+    /// Strings from the font's String INDEX. The string with index `i` has the
+    /// SID `STANDARD_STRINGS.len() + i`.
+    pub(crate) strings: Vec<String>,
 }
 
 impl CffFontProgram {
@@ -27,7 +33,7 @@ impl CffFontProgram {
     ///
     /// Returns `Some(u16)` containing the glyph ID if a mapping exists for the given
     /// `code_point`, or `None` if no mapping is found.
-    fn code_to_gid(&self, code_point: u8) -> Option<u16> {
+    pub fn code_to_gid(&self, code_point: u8) -> Option<u16> {
         match &self.encoding {
             Encoding::Standard(mapping) => {
                 let code_point = u16::from(code_point);
@@ -36,6 +42,33 @@ impl CffFontProgram {
         }
     }
 
+    /// Looks up the glyph ID (GID) of a glyph by its PostScript name.
+    ///
+    /// The name is resolved to a string identifier (SID) through the predefined
+    /// CFF strings and the font's String INDEX, then mapped to a GID through the
+    /// font's charset.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The glyph name, e.g. `Aacute`.
+    ///
+    /// # Returns
+    ///
+    /// The glyph ID, or `None` if the font has no glyph with that name.
+    pub fn glyph_id_for_name(&self, name: &str) -> Option<u16> {
+        let sid = STANDARD_STRINGS
+            .iter()
+            .position(|s| *s == name)
+            .or_else(|| {
+                self.strings
+                    .iter()
+                    .position(|s| s == name)
+                    .and_then(|index| index.checked_add(STANDARD_STRINGS.len()))
+            })
+            .and_then(|sid| u16::try_from(sid).ok())?;
+        self.charset.get_gid(sid)
+    }
+
     /// Renders a glyph for the given character code by interpreting its CFF charstring.
     ///
     /// This method first converts the character code to a Glyph ID (GID) using the
@@ -56,7 +89,22 @@ impl CffFontProgram {
         let Some(gid) = self.code_to_gid(char_code) else {
             return Ok(None);
         };
+        self.render_glyph_id(gid)
+    }
 
+    /// Renders the glyph with the given glyph ID (GID) by interpreting its CFF charstring.
+    ///
+    /// # Parameters
+    ///
+    /// - `gid`: The glyph ID, e.g. obtained from [`Self::glyph_id_for_name`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(PdfPath))` if the glyph is successfully rendered.
+    /// - `Ok(None)` if the font has no glyph with that ID.
+    /// - `Err(CompactFontFormatError)` if an error occurs during the evaluation of the
+    ///   charstring program.
+    pub fn render_glyph_id(&self, gid: u16) -> Result<Option<PdfPath>, CompactFontFormatError> {
         let mut path = PdfPath::default();
         let mut eval_stack = CharStringStack::default();
 
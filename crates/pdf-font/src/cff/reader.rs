@@ -3,6 +3,7 @@ use crate::cff::{
     error::CompactFontFormatError, parser::parse_index, program::CffFontProgram,
     top_dictionary_entry::TopDictEntry,
 };
+use ttf_parser::{RawFace, Tag};
 
 pub struct CffFontReader<'a> {
     cursor: Cursor<'a>,
@@ -15,6 +16,15 @@ impl<'a> CffFontReader<'a> {
         }
     }
 
+    /// Creates a reader for the `CFF ` table of an OpenType font program.
+    pub fn from_open_type(data: &'a [u8]) -> Result<Self, CompactFontFormatError> {
+        let face = RawFace::parse(data, 0).map_err(CompactFontFormatError::OpenType)?;
+        let table = face
+            .table(Tag::from_bytes(b"CFF "))
+            .ok_or(CompactFontFormatError::MissingCffTable)?;
+        Ok(Self::new(table))
+    }
+
     pub fn read_font_program(&mut self) -> Result<CffFontProgram, CompactFontFormatError> {
         // Read header
         let _major = self.cursor.read_u8()?;
@@ -28,6 +38,11 @@ impl<'a> CffFontReader<'a> {
         let _ = parse_index(&mut self.cursor)?;
         // Read Top DICT index
         let top_dict_index = parse_index(&mut self.cursor)?;
+        // Read String index, holding the names of glyphs outside the standard strings.
+        let strings = parse_index(&mut self.cursor)?
+            .into_iter()
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+            .collect();
 
         // Parse operators from the Top DICT table (use the first entry as the main Top DICT)
         let Some(&top_dict_bytes) = top_dict_index.first() else {
@@ -57,15 +72,28 @@ impl<'a> CffFontReader<'a> {
             ));
         };
 
-        // If the offset is greater than 2, we have to parse the charset table.
-        self.cursor.set_pos(usize::from(charset_offset));
-        let charset = Charset::read(&mut self.cursor, char_string_operators.len())?;
+        // Offsets 0, 1 and 2 select the predefined charsets. Otherwise, we have to parse
+        // the charset table.
+        let charset = match charset_offset {
+            0 => Charset::iso_adobe(char_string_operators.len()),
+            1 | 2 => {
+                return Err(CompactFontFormatError::InvalidData(
+                    "predefined Expert charsets are not supported",
+                ));
+            }
+            _ => {
+                self.cursor.set_pos(usize::from(charset_offset));
+                Charset::read(&mut self.cursor, char_string_operators.len())?
+            }
+        };
 
         let encoding = Encoding::from_charset(&charset, dict.encoding.unwrap_or(0))?;
 
         Ok(CffFontProgram {
             char_string_operators,
             encoding,
+            charset,
+            strings,
         })
     }
 }
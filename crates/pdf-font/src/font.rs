@@ -8,6 +8,7 @@ use thiserror::Error;
 
 use crate::{
     character_map::CMapError,
+    glyph_list::glyph_name_to_unicode,
     true_type_font::{TrueTypeFont, TrueTypeFontError},
     type0_font::{Type0Font, Type0FontError},
     type1_font::{Type1Font, Type1FontError},
@@ -68,6 +69,33 @@ pub enum Font {
     TrueType(TrueTypeFont),
 }
 
+impl Font {
+    /// Returns `true` if text shown with this font is laid out in vertical writing mode.
+    ///
+    /// Only composite fonts can use vertical writing.
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, Font::Type0(font) if font.is_vertical())
+    }
+
+    /// Maps a character code shown with this font to Unicode, for text extraction.
+    ///
+    /// Composite fonts rely on their `/ToUnicode` CMap. Simple fonts additionally fall
+    /// back to the glyph name selected by their encoding, resolved through the Adobe
+    /// Glyph List.
+    pub fn to_unicode(&self, char_code: u32) -> Option<char> {
+        match self {
+            Font::Type0(font) => font.cmap.as_ref()?.get_mapping(char_code),
+            Font::Type1(font) => font.to_unicode(u8::try_from(char_code).ok()?),
+            Font::TrueType(font) => font.to_unicode(u8::try_from(char_code).ok()?),
+            Font::Type3(font) => {
+                let code = u8::try_from(char_code).ok()?;
+                let name = font.encoding.as_ref()?.differences.get(&code)?;
+                glyph_name_to_unicode(name)
+            }
+        }
+    }
+}
+
 impl FromDictionary for Font {
     const KEY: &'static str = "Font";
     type ResultType = Self;
@@ -13,6 +13,45 @@ pub enum FontDescriptorError {
     ObjectError(#[from] ObjectError),
 }
 
+/// The format of an embedded font program, determined by the font descriptor key
+/// holding it and, for `/FontFile3`, the stream's `/Subtype`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontFileKind {
+    /// `/FontFile`: a Type 1 font program in PostScript format.
+    Type1,
+    /// `/FontFile2`: a TrueType font program.
+    TrueType,
+    /// `/FontFile3` with `/Type1C`: a bare CFF font program.
+    Type1C,
+    /// `/FontFile3` with `/CIDFontType0C`: a bare CID-keyed CFF font program.
+    CidFontType0C,
+    /// `/FontFile3` with `/OpenType`: an OpenType font program with either
+    /// TrueType (`glyf`) or CFF (`CFF `) outlines.
+    OpenType,
+}
+
+impl FontFileKind {
+    /// Resolves the `/Subtype` of a `/FontFile3` stream.
+    ///
+    /// The entry is required; when it is missing the kind is inferred from the
+    /// program's leading bytes, as OpenType programs start with an sfnt version tag.
+    /// Returns `None` for an unknown subtype, whose program cannot be used.
+    fn from_font_file3(stream: &StreamObject) -> Result<Option<Self>, FontDescriptorError> {
+        match stream.dictionary.get("Subtype") {
+            Some(subtype) => Ok(match subtype.try_str()?.as_ref() {
+                "Type1C" => Some(Self::Type1C),
+                "CIDFontType0C" => Some(Self::CidFontType0C),
+                "OpenType" => Some(Self::OpenType),
+                _ => None,
+            }),
+            None => match stream.data.get(..4) {
+                Some(b"OTTO" | b"true" | [0x00, 0x01, 0x00, 0x00]) => Ok(Some(Self::OpenType)),
+                _ => Ok(Some(Self::Type1C)),
+            },
+        }
+    }
+}
+
 /// Represents a font descriptor, a dictionary that provides detailed information
 /// about a font, such as its metrics, style, and font file data.
 #[derive(Debug)]
@@ -20,6 +59,20 @@ pub struct FontDescriptor {
     /// A stream containing the font program.
     /// This can be FontFile, FontFile2, or FontFile3 depending on the font type.
     pub font_file: Option<StreamObject>,
+    /// The format of `font_file`, present whenever `font_file` is.
+    pub font_file_kind: Option<FontFileKind>,
+    /// The `/Flags` entry, a collection of flags defining various characteristics of the font.
+    pub flags: u32,
+}
+
+impl FontDescriptor {
+    /// Flag bit set when the font contains glyphs outside the Adobe standard Latin character set.
+    const FLAG_SYMBOLIC: u32 = 1 << 2;
+
+    /// Returns `true` if the `/Flags` entry marks the font as symbolic.
+    pub fn is_symbolic(&self) -> bool {
+        self.flags & Self::FLAG_SYMBOLIC != 0
+    }
 }
 
 impl FromDictionary for FontDescriptor {
@@ -36,16 +89,71 @@ impl FromDictionary for FontDescriptor {
             objects.resolve_stream(dictionary.get(key)?).ok()
         };
 
-        let font_file = resolve_font_file_stream("FontFile2")
-            .or_else(|| resolve_font_file_stream("FontFile3"))
-            .or_else(|| resolve_font_file_stream("FontFile"));
-
-        let Some(font_file) = font_file.cloned() else {
-            return Ok(Self { font_file: None });
+        let font_file = if let Some(stream) = resolve_font_file_stream("FontFile2") {
+            Some((stream, FontFileKind::TrueType))
+        } else if let Some(stream) = resolve_font_file_stream("FontFile3") {
+            // A program in an unknown format is treated as not embedded, so that the
+            // text falls back to a substitute font.
+            FontFileKind::from_font_file3(stream)?.map(|kind| (stream, kind))
+        } else {
+            resolve_font_file_stream("FontFile").map(|stream| (stream, FontFileKind::Type1))
         };
+        let (font_file, font_file_kind) = font_file.unzip();
+
+        // `/Flags` is required, but tolerate its absence by treating the font as nonsymbolic.
+        let flags = dictionary
+            .get("Flags")
+            .map(|flags| flags.as_number::<u32>())
+            .transpose()?
+            .unwrap_or(0);
 
         Ok(Self {
-            font_file: Some(font_file),
+            font_file: font_file.cloned(),
+            font_file_kind,
+            flags,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, rc::Rc};
+
+    use pdf_object::object_variant::ObjectVariant;
+
+    use super::*;
+
+    fn font_file3(subtype: Option<&str>, data: &[u8]) -> StreamObject {
+        let mut entries = BTreeMap::new();
+        if let Some(subtype) = subtype {
+            entries.insert(
+                "Subtype".to_string(),
+                Box::new(ObjectVariant::Name(subtype.to_string())),
+            );
+        }
+        StreamObject::new(1, 0, Rc::new(Dictionary::new(entries)), data.to_vec())
+    }
+
+    #[test]
+    fn font_file3_subtypes() {
+        let kind = |subtype| FontFileKind::from_font_file3(&font_file3(Some(subtype), b""));
+        assert_eq!(kind("Type1C"), Ok(Some(FontFileKind::Type1C)));
+        assert_eq!(kind("CIDFontType0C"), Ok(Some(FontFileKind::CidFontType0C)));
+        assert_eq!(kind("OpenType"), Ok(Some(FontFileKind::OpenType)));
+        assert_eq!(kind("Type3C"), Ok(None));
+    }
+
+    #[test]
+    fn font_file3_without_subtype_is_sniffed() {
+        let kind = |data| FontFileKind::from_font_file3(&font_file3(None, data));
+        assert_eq!(kind(b"OTTO\x00\x0a"), Ok(Some(FontFileKind::OpenType)));
+        assert_eq!(
+            kind(&[0x00, 0x01, 0x00, 0x00]),
+            Ok(Some(FontFileKind::OpenType))
+        );
+        assert_eq!(
+            kind(&[0x01, 0x00, 0x04, 0x02]),
+            Ok(Some(FontFileKind::Type1C))
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    mem::size_of,
+    rc::Rc,
+};
+
+use pdf_graphics::pdf_path::{PathVerb, PdfPath};
+
+/// Caches per-font results of font program interpretation, so that repeated
+/// glyphs in text-showing operators reuse previously built outlines.
+///
+/// The cache holds two maps: character code to glyph ID, and glyph ID to the
+/// glyph outline in the font program's own coordinate space. Outlines are
+/// shared through `Rc`, and callers transform a clone into device space.
+///
+/// Memory use is bounded by an approximate byte budget over all cached
+/// outlines. When inserting an outline would exceed the budget, the outline
+/// map is emptied before the new entry is stored.
+pub struct GlyphCache {
+    /// Resolved glyph IDs, keyed by character code. `None` records a code that
+    /// maps to no glyph.
+    glyph_ids: RefCell<HashMap<u32, Option<u16>>>,
+    /// Glyph outlines, keyed by glyph ID. `None` records a glyph without an outline.
+    outlines: RefCell<HashMap<u16, Option<Rc<PdfPath>>>>,
+    /// Approximate number of bytes used by the cached outlines.
+    size_bytes: Cell<usize>,
+    /// Maximum number of bytes the cached outlines may occupy.
+    capacity_bytes: usize,
+}
+
+impl GlyphCache {
+    /// Default outline budget per font, in bytes.
+    pub const DEFAULT_CAPACITY_BYTES: usize = 4 * 1024 * 1024;
+
+    /// Creates an empty cache with the given outline budget in bytes.
+    pub fn with_capacity(capacity_bytes: usize) -> Self {
+        Self {
+            glyph_ids: RefCell::new(HashMap::new()),
+            outlines: RefCell::new(HashMap::new()),
+            size_bytes: Cell::new(0),
+            capacity_bytes,
+        }
+    }
+
+    /// Returns the glyph ID for `char_code`, resolving and storing it with `resolve` on a miss.
+    pub fn glyph_id_or_insert_with<E>(
+        &self,
+        char_code: u32,
+        resolve: impl FnOnce() -> Result<Option<u16>, E>,
+    ) -> Result<Option<u16>, E> {
+        if let Some(gid) = self.glyph_ids.borrow().get(&char_code) {
+            return Ok(*gid);
+        }
+        let gid = resolve()?;
+        self.glyph_ids.borrow_mut().insert(char_code, gid);
+        Ok(gid)
+    }
+
+    /// Returns the outline of glyph `gid`, building and storing it with `build` on a miss.
+    ///
+    /// Errors returned by `build` are propagated and not cached.
+    pub fn outline_or_insert_with<E>(
+        &self,
+        gid: u16,
+        build: impl FnOnce() -> Result<Option<PdfPath>, E>,
+    ) -> Result<Option<Rc<PdfPath>>, E> {
+        if let Some(outline) = self.outlines.borrow().get(&gid) {
+            return Ok(outline.clone());
+        }
+
+        let outline = build()?.map(Rc::new);
+        let entry_size = outline
+            .as_deref()
+            .map_or(0, |path| {
+                path.verbs.capacity().saturating_mul(size_of::<PathVerb>())
+            })
+            .saturating_add(size_of::<(u16, Option<Rc<PdfPath>>)>());
+
+        let mut outlines = self.outlines.borrow_mut();
+        let mut size_bytes = self.size_bytes.get().saturating_add(entry_size);
+        if size_bytes > self.capacity_bytes {
+            outlines.clear();
+            size_bytes = entry_size;
+        }
+        // Outlines larger than the whole budget are returned but never stored.
+        if entry_size <= self.capacity_bytes {
+            outlines.insert(gid, outline.clone());
+            self.size_bytes.set(size_bytes);
+        } else {
+            self.size_bytes.set(0);
+        }
+        Ok(outline)
+    }
+
+    /// Returns the approximate number of bytes used by the cached outlines.
+    pub fn size_bytes(&self) -> usize {
+        self.size_bytes.get()
+    }
+
+    /// Removes all cached glyph IDs and outlines.
+    pub fn clear(&self) {
+        self.glyph_ids.borrow_mut().clear();
+        self.outlines.borrow_mut().clear();
+        self.size_bytes.set(0);
+    }
+}
+
+impl Default for GlyphCache {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY_BYTES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> PdfPath {
+        let mut path = PdfPath::default();
+        path.move_to(0.0, 0.0);
+        path.line_to(1.0, 0.0);
+        path.line_to(1.0, 1.0);
+        path.close();
+        path
+    }
+
+    #[test]
+    fn outlines_are_built_once() {
+        let cache = GlyphCache::default();
+        let mut builds = 0;
+        for _ in 0..3 {
+            let outline = cache
+                .outline_or_insert_with(7, || -> Result<_, ()> {
+                    builds += 1;
+                    Ok(Some(square()))
+                })
+                .unwrap_or_default();
+            assert!(outline.is_some());
+        }
+        assert_eq!(builds, 1);
+        assert!(cache.size_bytes() > 0);
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let cache = GlyphCache::default();
+        assert!(cache.outline_or_insert_with(1, || Err("bad")).is_err());
+        assert!(matches!(
+            cache.outline_or_insert_with(1, || Ok::<_, &str>(Some(square()))),
+            Ok(Some(_))
+        ));
+    }
+
+    #[test]
+    fn budget_evicts_previous_outlines() {
+        let cache = GlyphCache::with_capacity(200);
+        let _ = cache.outline_or_insert_with(1, || Ok::<_, ()>(Some(square())));
+        let first_size = cache.size_bytes();
+        let _ = cache.outline_or_insert_with(2, || Ok::<_, ()>(Some(square())));
+        assert!(cache.size_bytes() <= 200);
+        assert_eq!(cache.size_bytes(), first_size);
+    }
+
+    #[test]
+    fn glyph_ids_are_memoized() {
+        let cache = GlyphCache::default();
+        assert_eq!(
+            cache.glyph_id_or_insert_with(65, || Ok::<_, ()>(Some(3))),
+            Ok(Some(3))
+        );
+        assert_eq!(cache.glyph_id_or_insert_with(65, || Err(())), Ok(Some(3)));
+    }
+}
@@ -0,0 +1,956 @@
+//! Glyph name to Unicode mapping.
+//!
+//! Implements the lookup part of the Adobe Glyph List Specification: glyph
+//! names are resolved against a table of known names first, falling back to
+//! the `uniXXXX` and `uXXXX[XX]` naming conventions. The table covers the
+//! Adobe Glyph List For New Fonts together with every glyph name used by the
+//! predefined encodings in [`crate::standard_encodings`].
+
+/// Known glyph names and the Unicode scalar value each one maps to, sorted
+/// by name for binary search.
+#[rustfmt::skip]
+const GLYPH_LIST: &[(&str, char)] = &[
+    ("A", '\u{0041}'),
+    ("AE", '\u{00C6}'),
+    ("AEsmall", '\u{F7E6}'),
+    ("Aacute", '\u{00C1}'),
+    ("Aacutesmall", '\u{F7E1}'),
+    ("Abreve", '\u{0102}'),
+    ("Acircumflex", '\u{00C2}'),
+    ("Acircumflexsmall", '\u{F7E2}'),
+    ("Acutesmall", '\u{F7B4}'),
+    ("Adieresis", '\u{00C4}'),
+    ("Adieresissmall", '\u{F7E4}'),
+    ("Agrave", '\u{00C0}'),
+    ("Agravesmall", '\u{F7E0}'),
+    ("Alpha", '\u{0391}'),
+    ("Amacron", '\u{0100}'),
+    ("Aogonek", '\u{0104}'),
+    ("Aring", '\u{00C5}'),
+    ("Aringsmall", '\u{F7E5}'),
+    ("Asmall", '\u{F761}'),
+    ("Atilde", '\u{00C3}'),
+    ("Atildesmall", '\u{F7E3}'),
+    ("B", '\u{0042}'),
+    ("Beta", '\u{0392}'),
+    ("Brevesmall", '\u{F6F4}'),
+    ("Bsmall", '\u{F762}'),
+    ("C", '\u{0043}'),
+    ("Cacute", '\u{0106}'),
+    ("Caronsmall", '\u{F6F5}'),
+    ("Ccaron", '\u{010C}'),
+    ("Ccedilla", '\u{00C7}'),
+    ("Ccedillasmall", '\u{F7E7}'),
+    ("Ccircumflex", '\u{0108}'),
+    ("Cdotaccent", '\u{010A}'),
+    ("Cedillasmall", '\u{F7B8}'),
+    ("Chi", '\u{03A7}'),
+    ("Circumflexsmall", '\u{F6F6}'),
+    ("Csmall", '\u{F763}'),
+    ("D", '\u{0044}'),
+    ("Dcaron", '\u{010E}'),
+    ("Dcroat", '\u{0110}'),
+    ("Delta", '\u{2206}'),
+    ("Dieresissmall", '\u{F7A8}'),
+    ("Dotaccentsmall", '\u{F6F7}'),
+    ("Dsmall", '\u{F764}'),
+    ("E", '\u{0045}'),
+    ("Eacute", '\u{00C9}'),
+    ("Eacutesmall", '\u{F7E9}'),
+    ("Ebreve", '\u{0114}'),
+    ("Ecaron", '\u{011A}'),
+    ("Ecircumflex", '\u{00CA}'),
+    ("Ecircumflexsmall", '\u{F7EA}'),
+    ("Edieresis", '\u{00CB}'),
+    ("Edieresissmall", '\u{F7EB}'),
+    ("Edotaccent", '\u{0116}'),
+    ("Egrave", '\u{00C8}'),
+    ("Egravesmall", '\u{F7E8}'),
+    ("Emacron", '\u{0112}'),
+    ("Eng", '\u{014A}'),
+    ("Eogonek", '\u{0118}'),
+    ("Epsilon", '\u{0395}'),
+    ("Esmall", '\u{F765}'),
+    ("Eta", '\u{0397}'),
+    ("Eth", '\u{00D0}'),
+    ("Ethsmall", '\u{F7F0}'),
+    ("Euro", '\u{20AC}'),
+    ("F", '\u{0046}'),
+    ("Fsmall", '\u{F766}'),
+    ("G", '\u{0047}'),
+    ("Gamma", '\u{0393}'),
+    ("Gbreve", '\u{011E}'),
+    ("Gcircumflex", '\u{011C}'),
+    ("Gcommaaccent", '\u{0122}'),
+    ("Gdotaccent", '\u{0120}'),
+    ("Gravesmall", '\u{F760}'),
+    ("Gsmall", '\u{F767}'),
+    ("H", '\u{0048}'),
+    ("Hbar", '\u{0126}'),
+    ("Hcircumflex", '\u{0124}'),
+    ("Hsmall", '\u{F768}'),
+    ("Hungarumlautsmall", '\u{F6F8}'),
+    ("I", '\u{0049}'),
+    ("IJ", '\u{0132}'),
+    ("Iacute", '\u{00CD}'),
+    ("Iacutesmall", '\u{F7ED}'),
+    ("Ibreve", '\u{012C}'),
+    ("Icircumflex", '\u{00CE}'),
+    ("Icircumflexsmall", '\u{F7EE}'),
+    ("Idieresis", '\u{00CF}'),
+    ("Idieresissmall", '\u{F7EF}'),
+    ("Idotaccent", '\u{0130}'),
+    ("Ifraktur", '\u{2111}'),
+    ("Igrave", '\u{00CC}'),
+    ("Igravesmall", '\u{F7EC}'),
+    ("Imacron", '\u{012A}'),
+    ("Iogonek", '\u{012E}'),
+    ("Iota", '\u{0399}'),
+    ("Ismall", '\u{F769}'),
+    ("Itilde", '\u{0128}'),
+    ("J", '\u{004A}'),
+    ("Jcircumflex", '\u{0134}'),
+    ("Jsmall", '\u{F76A}'),
+    ("K", '\u{004B}'),
+    ("Kappa", '\u{039A}'),
+    ("Kcommaaccent", '\u{0136}'),
+    ("Ksmall", '\u{F76B}'),
+    ("L", '\u{004C}'),
+    ("Lacute", '\u{0139}'),
+    ("Lambda", '\u{039B}'),
+    ("Lcaron", '\u{013D}'),
+    ("Lcommaaccent", '\u{013B}'),
+    ("Ldot", '\u{013F}'),
+    ("Lslash", '\u{0141}'),
+    ("Lslashsmall", '\u{F6F9}'),
+    ("Lsmall", '\u{F76C}'),
+    ("M", '\u{004D}'),
+    ("Macronsmall", '\u{F7AF}'),
+    ("Msmall", '\u{F76D}'),
+    ("Mu", '\u{039C}'),
+    ("N", '\u{004E}'),
+    ("Nacute", '\u{0143}'),
+    ("Ncaron", '\u{0147}'),
+    ("Ncommaaccent", '\u{0145}'),
+    ("Nsmall", '\u{F76E}'),
+    ("Ntilde", '\u{00D1}'),
+    ("Ntildesmall", '\u{F7F1}'),
+    ("Nu", '\u{039D}'),
+    ("O", '\u{004F}'),
+    ("OE", '\u{0152}'),
+    ("OEsmall", '\u{F6FA}'),
+    ("Oacute", '\u{00D3}'),
+    ("Oacutesmall", '\u{F7F3}'),
+    ("Obreve", '\u{014E}'),
+    ("Ocircumflex", '\u{00D4}'),
+    ("Ocircumflexsmall", '\u{F7F4}'),
+    ("Odieresis", '\u{00D6}'),
+    ("Odieresissmall", '\u{F7F6}'),
+    ("Ogoneksmall", '\u{F6FB}'),
+    ("Ograve", '\u{00D2}'),
+    ("Ogravesmall", '\u{F7F2}'),
+    ("Ohm", '\u{2126}'),
+    ("Ohungarumlaut", '\u{0150}'),
+    ("Omacron", '\u{014C}'),
+    ("Omega", '\u{03A9}'),
+    ("Omicron", '\u{039F}'),
+    ("Oslash", '\u{00D8}'),
+    ("Oslashsmall", '\u{F7F8}'),
+    ("Osmall", '\u{F76F}'),
+    ("Otilde", '\u{00D5}'),
+    ("Otildesmall", '\u{F7F5}'),
+    ("P", '\u{0050}'),
+    ("Phi", '\u{03A6}'),
+    ("Pi", '\u{03A0}'),
+    ("Psi", '\u{03A8}'),
+    ("Psmall", '\u{F770}'),
+    ("Q", '\u{0051}'),
+    ("Qsmall", '\u{F771}'),
+    ("R", '\u{0052}'),
+    ("Racute", '\u{0154}'),
+    ("Rcaron", '\u{0158}'),
+    ("Rcommaaccent", '\u{0156}'),
+    ("Rfraktur", '\u{211C}'),
+    ("Rho", '\u{03A1}'),
+    ("Ringsmall", '\u{F6FC}'),
+    ("Rsmall", '\u{F772}'),
+    ("S", '\u{0053}'),
+    ("Sacute", '\u{015A}'),
+    ("Scaron", '\u{0160}'),
+    ("Scaronsmall", '\u{F6FD}'),
+    ("Scedilla", '\u{015E}'),
+    ("Scircumflex", '\u{015C}'),
+    ("Scommaaccent", '\u{0218}'),
+    ("Sigma", '\u{03A3}'),
+    ("Ssmall", '\u{F773}'),
+    ("T", '\u{0054}'),
+    ("Tau", '\u{03A4}'),
+    ("Tbar", '\u{0166}'),
+    ("Tcaron", '\u{0164}'),
+    ("Tcommaaccent", '\u{0162}'),
+    ("Theta", '\u{0398}'),
+    ("Thorn", '\u{00DE}'),
+    ("Thornsmall", '\u{F7FE}'),
+    ("Tildesmall", '\u{F6FE}'),
+    ("Tsmall", '\u{F774}'),
+    ("U", '\u{0055}'),
+    ("Uacute", '\u{00DA}'),
+    ("Uacutesmall", '\u{F7FA}'),
+    ("Ubreve", '\u{016C}'),
+    ("Ucircumflex", '\u{00DB}'),
+    ("Ucircumflexsmall", '\u{F7FB}'),
+    ("Udieresis", '\u{00DC}'),
+    ("Udieresissmall", '\u{F7FC}'),
+    ("Ugrave", '\u{00D9}'),
+    ("Ugravesmall", '\u{F7F9}'),
+    ("Uhungarumlaut", '\u{0170}'),
+    ("Umacron", '\u{016A}'),
+    ("Uogonek", '\u{0172}'),
+    ("Upsilon", '\u{03A5}'),
+    ("Upsilon1", '\u{03D2}'),
+    ("Uring", '\u{016E}'),
+    ("Usmall", '\u{F775}'),
+    ("Utilde", '\u{0168}'),
+    ("V", '\u{0056}'),
+    ("Vsmall", '\u{F776}'),
+    ("W", '\u{0057}'),
+    ("Wcircumflex", '\u{0174}'),
+    ("Wsmall", '\u{F777}'),
+    ("X", '\u{0058}'),
+    ("Xi", '\u{039E}'),
+    ("Xsmall", '\u{F778}'),
+    ("Y", '\u{0059}'),
+    ("Yacute", '\u{00DD}'),
+    ("Yacutesmall", '\u{F7FD}'),
+    ("Ycircumflex", '\u{0176}'),
+    ("Ydieresis", '\u{0178}'),
+    ("Ydieresissmall", '\u{F7FF}'),
+    ("Ysmall", '\u{F779}'),
+    ("Z", '\u{005A}'),
+    ("Zacute", '\u{0179}'),
+    ("Zcaron", '\u{017D}'),
+    ("Zcaronsmall", '\u{F6FF}'),
+    ("Zdotaccent", '\u{017B}'),
+    ("Zeta", '\u{0396}'),
+    ("Zsmall", '\u{F77A}'),
+    ("a", '\u{0061}'),
+    ("a1", '\u{2701}'),
+    ("a10", '\u{2721}'),
+    ("a100", '\u{275E}'),
+    ("a101", '\u{2761}'),
+    ("a102", '\u{2762}'),
+    ("a103", '\u{2763}'),
+    ("a104", '\u{2764}'),
+    ("a105", '\u{2710}'),
+    ("a106", '\u{2765}'),
+    ("a107", '\u{2766}'),
+    ("a108", '\u{2767}'),
+    ("a109", '\u{2660}'),
+    ("a11", '\u{261B}'),
+    ("a110", '\u{2665}'),
+    ("a111", '\u{2666}'),
+    ("a112", '\u{2663}'),
+    ("a117", '\u{2709}'),
+    ("a118", '\u{2708}'),
+    ("a119", '\u{2707}'),
+    ("a12", '\u{261E}'),
+    ("a120", '\u{2460}'),
+    ("a121", '\u{2461}'),
+    ("a122", '\u{2462}'),
+    ("a123", '\u{2463}'),
+    ("a124", '\u{2464}'),
+    ("a125", '\u{2465}'),
+    ("a126", '\u{2466}'),
+    ("a127", '\u{2467}'),
+    ("a128", '\u{2468}'),
+    ("a129", '\u{2469}'),
+    ("a13", '\u{270C}'),
+    ("a130", '\u{2776}'),
+    ("a131", '\u{2777}'),
+    ("a132", '\u{2778}'),
+    ("a133", '\u{2779}'),
+    ("a134", '\u{277A}'),
+    ("a135", '\u{277B}'),
+    ("a136", '\u{277C}'),
+    ("a137", '\u{277D}'),
+    ("a138", '\u{277E}'),
+    ("a139", '\u{277F}'),
+    ("a14", '\u{270D}'),
+    ("a140", '\u{2780}'),
+    ("a141", '\u{2781}'),
+    ("a142", '\u{2782}'),
+    ("a143", '\u{2783}'),
+    ("a144", '\u{2784}'),
+    ("a145", '\u{2785}'),
+    ("a146", '\u{2786}'),
+    ("a147", '\u{2787}'),
+    ("a148", '\u{2788}'),
+    ("a149", '\u{2789}'),
+    ("a15", '\u{270E}'),
+    ("a150", '\u{278A}'),
+    ("a151", '\u{278B}'),
+    ("a152", '\u{278C}'),
+    ("a153", '\u{278D}'),
+    ("a154", '\u{278E}'),
+    ("a155", '\u{278F}'),
+    ("a156", '\u{2790}'),
+    ("a157", '\u{2791}'),
+    ("a158", '\u{2792}'),
+    ("a159", '\u{2793}'),
+    ("a16", '\u{270F}'),
+    ("a160", '\u{2794}'),
+    ("a161", '\u{2192}'),
+    ("a162", '\u{27A3}'),
+    ("a163", '\u{2194}'),
+    ("a164", '\u{2195}'),
+    ("a165", '\u{2799}'),
+    ("a166", '\u{279B}'),
+    ("a167", '\u{279C}'),
+    ("a168", '\u{279D}'),
+    ("a169", '\u{279E}'),
+    ("a17", '\u{2711}'),
+    ("a170", '\u{279F}'),
+    ("a171", '\u{27A0}'),
+    ("a172", '\u{27A1}'),
+    ("a173", '\u{27A2}'),
+    ("a174", '\u{27A4}'),
+    ("a175", '\u{27A5}'),
+    ("a176", '\u{27A6}'),
+    ("a177", '\u{27A7}'),
+    ("a178", '\u{27A8}'),
+    ("a179", '\u{27A9}'),
+    ("a18", '\u{2712}'),
+    ("a180", '\u{27AB}'),
+    ("a181", '\u{27AD}'),
+    ("a182", '\u{27AF}'),
+    ("a183", '\u{27B2}'),
+    ("a184", '\u{27B3}'),
+    ("a185", '\u{27B5}'),
+    ("a186", '\u{27B8}'),
+    ("a187", '\u{27BA}'),
+    ("a188", '\u{27BB}'),
+    ("a189", '\u{27BC}'),
+    ("a19", '\u{2713}'),
+    ("a190", '\u{27BD}'),
+    ("a191", '\u{27BE}'),
+    ("a192", '\u{279A}'),
+    ("a193", '\u{27AA}'),
+    ("a194", '\u{27B6}'),
+    ("a195", '\u{27B9}'),
+    ("a196", '\u{2798}'),
+    ("a197", '\u{27B4}'),
+    ("a198", '\u{27B7}'),
+    ("a199", '\u{27AC}'),
+    ("a2", '\u{2702}'),
+    ("a20", '\u{2714}'),
+    ("a200", '\u{27AE}'),
+    ("a201", '\u{27B1}'),
+    ("a202", '\u{2703}'),
+    ("a203", '\u{2750}'),
+    ("a204", '\u{2752}'),
+    ("a205", '\u{276E}'),
+    ("a206", '\u{2770}'),
+    ("a21", '\u{2715}'),
+    ("a22", '\u{2716}'),
+    ("a23", '\u{2717}'),
+    ("a24", '\u{2718}'),
+    ("a25", '\u{2719}'),
+    ("a26", '\u{271A}'),
+    ("a27", '\u{271B}'),
+    ("a28", '\u{271C}'),
+    ("a29", '\u{2722}'),
+    ("a3", '\u{2704}'),
+    ("a30", '\u{2723}'),
+    ("a31", '\u{2724}'),
+    ("a32", '\u{2725}'),
+    ("a33", '\u{2726}'),
+    ("a34", '\u{2727}'),
+    ("a35", '\u{2605}'),
+    ("a36", '\u{2729}'),
+    ("a37", '\u{272A}'),
+    ("a38", '\u{272B}'),
+    ("a39", '\u{272C}'),
+    ("a4", '\u{260E}'),
+    ("a40", '\u{272D}'),
+    ("a41", '\u{272E}'),
+    ("a42", '\u{272F}'),
+    ("a43", '\u{2730}'),
+    ("a44", '\u{2731}'),
+    ("a45", '\u{2732}'),
+    ("a46", '\u{2733}'),
+    ("a47", '\u{2734}'),
+    ("a48", '\u{2735}'),
+    ("a49", '\u{2736}'),
+    ("a5", '\u{2706}'),
+    ("a50", '\u{2737}'),
+    ("a51", '\u{2738}'),
+    ("a52", '\u{2739}'),
+    ("a53", '\u{273A}'),
+    ("a54", '\u{273B}'),
+    ("a55", '\u{273C}'),
+    ("a56", '\u{273D}'),
+    ("a57", '\u{273E}'),
+    ("a58", '\u{273F}'),
+    ("a59", '\u{2740}'),
+    ("a6", '\u{271D}'),
+    ("a60", '\u{2741}'),
+    ("a61", '\u{2742}'),
+    ("a62", '\u{2743}'),
+    ("a63", '\u{2744}'),
+    ("a64", '\u{2745}'),
+    ("a65", '\u{2746}'),
+    ("a66", '\u{2747}'),
+    ("a67", '\u{2748}'),
+    ("a68", '\u{2749}'),
+    ("a69", '\u{274A}'),
+    ("a7", '\u{271E}'),
+    ("a70", '\u{274B}'),
+    ("a71", '\u{25CF}'),
+    ("a72", '\u{274D}'),
+    ("a73", '\u{25A0}'),
+    ("a74", '\u{274F}'),
+    ("a75", '\u{2751}'),
+    ("a76", '\u{25B2}'),
+    ("a77", '\u{25BC}'),
+    ("a78", '\u{25C6}'),
+    ("a79", '\u{2756}'),
+    ("a8", '\u{271F}'),
+    ("a81", '\u{25D7}'),
+    ("a82", '\u{2758}'),
+    ("a83", '\u{2759}'),
+    ("a84", '\u{275A}'),
+    ("a85", '\u{276F}'),
+    ("a86", '\u{2771}'),
+    ("a87", '\u{2772}'),
+    ("a88", '\u{2773}'),
+    ("a89", '\u{2768}'),
+    ("a9", '\u{2720}'),
+    ("a90", '\u{2769}'),
+    ("a91", '\u{276C}'),
+    ("a92", '\u{276D}'),
+    ("a93", '\u{276A}'),
+    ("a94", '\u{276B}'),
+    ("a95", '\u{2774}'),
+    ("a96", '\u{2775}'),
+    ("a97", '\u{275B}'),
+    ("a98", '\u{275C}'),
+    ("a99", '\u{275D}'),
+    ("aacute", '\u{00E1}'),
+    ("abreve", '\u{0103}'),
+    ("acircumflex", '\u{00E2}'),
+    ("acute", '\u{00B4}'),
+    ("adieresis", '\u{00E4}'),
+    ("ae", '\u{00E6}'),
+    ("agrave", '\u{00E0}'),
+    ("aleph", '\u{2135}'),
+    ("alpha", '\u{03B1}'),
+    ("amacron", '\u{0101}'),
+    ("ampersand", '\u{0026}'),
+    ("ampersandsmall", '\u{F726}'),
+    ("angle", '\u{2220}'),
+    ("angleleft", '\u{2329}'),
+    ("angleright", '\u{232A}'),
+    ("aogonek", '\u{0105}'),
+    ("approxequal", '\u{2248}'),
+    ("aring", '\u{00E5}'),
+    ("arrowboth", '\u{2194}'),
+    ("arrowdblboth", '\u{21D4}'),
+    ("arrowdbldown", '\u{21D3}'),
+    ("arrowdblleft", '\u{21D0}'),
+    ("arrowdblright", '\u{21D2}'),
+    ("arrowdblup", '\u{21D1}'),
+    ("arrowdown", '\u{2193}'),
+    ("arrowhorizex", '\u{F8E7}'),
+    ("arrowleft", '\u{2190}'),
+    ("arrowright", '\u{2192}'),
+    ("arrowup", '\u{2191}'),
+    ("arrowvertex", '\u{F8E6}'),
+    ("asciicircum", '\u{005E}'),
+    ("asciitilde", '\u{007E}'),
+    ("asterisk", '\u{002A}'),
+    ("asteriskmath", '\u{2217}'),
+    ("asuperior", '\u{F6E9}'),
+    ("at", '\u{0040}'),
+    ("atilde", '\u{00E3}'),
+    ("b", '\u{0062}'),
+    ("backslash", '\u{005C}'),
+    ("bar", '\u{007C}'),
+    ("beta", '\u{03B2}'),
+    ("braceex", '\u{F8F4}'),
+    ("braceleft", '\u{007B}'),
+    ("braceleftbt", '\u{F8F3}'),
+    ("braceleftmid", '\u{F8F2}'),
+    ("bracelefttp", '\u{F8F1}'),
+    ("braceright", '\u{007D}'),
+    ("bracerightbt", '\u{F8FE}'),
+    ("bracerightmid", '\u{F8FD}'),
+    ("bracerighttp", '\u{F8FC}'),
+    ("bracketleft", '\u{005B}'),
+    ("bracketleftbt", '\u{F8F0}'),
+    ("bracketleftex", '\u{F8EF}'),
+    ("bracketlefttp", '\u{F8EE}'),
+    ("bracketright", '\u{005D}'),
+    ("bracketrightbt", '\u{F8FB}'),
+    ("bracketrightex", '\u{F8FA}'),
+    ("bracketrighttp", '\u{F8F9}'),
+    ("breve", '\u{02D8}'),
+    ("brokenbar", '\u{00A6}'),
+    ("bsuperior", '\u{F6EA}'),
+    ("bullet", '\u{2022}'),
+    ("c", '\u{0063}'),
+    ("cacute", '\u{0107}'),
+    ("caron", '\u{02C7}'),
+    ("carriagereturn", '\u{21B5}'),
+    ("ccaron", '\u{010D}'),
+    ("ccedilla", '\u{00E7}'),
+    ("ccircumflex", '\u{0109}'),
+    ("cdotaccent", '\u{010B}'),
+    ("cedilla", '\u{00B8}'),
+    ("cent", '\u{00A2}'),
+    ("centinferior", '\u{F6DF}'),
+    ("centoldstyle", '\u{F7A2}'),
+    ("centsuperior", '\u{F6E0}'),
+    ("chi", '\u{03C7}'),
+    ("circlemultiply", '\u{2297}'),
+    ("circleplus", '\u{2295}'),
+    ("circumflex", '\u{02C6}'),
+    ("club", '\u{2663}'),
+    ("colon", '\u{003A}'),
+    ("colonmonetary", '\u{20A1}'),
+    ("comma", '\u{002C}'),
+    ("commaaccent", '\u{F6C3}'),
+    ("commainferior", '\u{F6E1}'),
+    ("commasuperior", '\u{F6E2}'),
+    ("congruent", '\u{2245}'),
+    ("copyright", '\u{00A9}'),
+    ("copyrightsans", '\u{F8E9}'),
+    ("copyrightserif", '\u{F6D9}'),
+    ("currency", '\u{00A4}'),
+    ("d", '\u{0064}'),
+    ("dagger", '\u{2020}'),
+    ("daggerdbl", '\u{2021}'),
+    ("dcaron", '\u{010F}'),
+    ("dcroat", '\u{0111}'),
+    ("degree", '\u{00B0}'),
+    ("delta", '\u{03B4}'),
+    ("diamond", '\u{2666}'),
+    ("dieresis", '\u{00A8}'),
+    ("divide", '\u{00F7}'),
+    ("dollar", '\u{0024}'),
+    ("dollarinferior", '\u{F6E3}'),
+    ("dollaroldstyle", '\u{F724}'),
+    ("dollarsuperior", '\u{F6E4}'),
+    ("dotaccent", '\u{02D9}'),
+    ("dotlessi", '\u{0131}'),
+    ("dotlessj", '\u{0237}'),
+    ("dotmath", '\u{22C5}'),
+    ("dsuperior", '\u{F6EB}'),
+    ("e", '\u{0065}'),
+    ("eacute", '\u{00E9}'),
+    ("ebreve", '\u{0115}'),
+    ("ecaron", '\u{011B}'),
+    ("ecircumflex", '\u{00EA}'),
+    ("edieresis", '\u{00EB}'),
+    ("edotaccent", '\u{0117}'),
+    ("egrave", '\u{00E8}'),
+    ("eight", '\u{0038}'),
+    ("eightinferior", '\u{2088}'),
+    ("eightoldstyle", '\u{F738}'),
+    ("eightsuperior", '\u{2078}'),
+    ("element", '\u{2208}'),
+    ("ellipsis", '\u{2026}'),
+    ("emacron", '\u{0113}'),
+    ("emdash", '\u{2014}'),
+    ("emptyset", '\u{2205}'),
+    ("endash", '\u{2013}'),
+    ("eng", '\u{014B}'),
+    ("eogonek", '\u{0119}'),
+    ("epsilon", '\u{03B5}'),
+    ("equal", '\u{003D}'),
+    ("equivalence", '\u{2261}'),
+    ("estimated", '\u{212E}'),
+    ("esuperior", '\u{F6EC}'),
+    ("eta", '\u{03B7}'),
+    ("eth", '\u{00F0}'),
+    ("exclam", '\u{0021}'),
+    ("exclamdbl", '\u{203C}'),
+    ("exclamdown", '\u{00A1}'),
+    ("exclamdownsmall", '\u{F7A1}'),
+    ("exclamsmall", '\u{F721}'),
+    ("existential", '\u{2203}'),
+    ("f", '\u{0066}'),
+    ("ff", '\u{FB00}'),
+    ("ffi", '\u{FB03}'),
+    ("ffl", '\u{FB04}'),
+    ("fi", '\u{FB01}'),
+    ("figuredash", '\u{2012}'),
+    ("five", '\u{0035}'),
+    ("fiveeighths", '\u{215D}'),
+    ("fiveinferior", '\u{2085}'),
+    ("fiveoldstyle", '\u{F735}'),
+    ("fivesuperior", '\u{2075}'),
+    ("fl", '\u{FB02}'),
+    ("florin", '\u{0192}'),
+    ("four", '\u{0034}'),
+    ("fourinferior", '\u{2084}'),
+    ("fouroldstyle", '\u{F734}'),
+    ("foursuperior", '\u{2074}'),
+    ("fraction", '\u{2044}'),
+    ("franc", '\u{20A3}'),
+    ("g", '\u{0067}'),
+    ("gamma", '\u{03B3}'),
+    ("gbreve", '\u{011F}'),
+    ("gcircumflex", '\u{011D}'),
+    ("gcommaaccent", '\u{0123}'),
+    ("gdotaccent", '\u{0121}'),
+    ("germandbls", '\u{00DF}'),
+    ("gradient", '\u{2207}'),
+    ("grave", '\u{0060}'),
+    ("greater", '\u{003E}'),
+    ("greaterequal", '\u{2265}'),
+    ("guillemotleft", '\u{00AB}'),
+    ("guillemotright", '\u{00BB}'),
+    ("guilsinglleft", '\u{2039}'),
+    ("guilsinglright", '\u{203A}'),
+    ("h", '\u{0068}'),
+    ("hbar", '\u{0127}'),
+    ("hcircumflex", '\u{0125}'),
+    ("heart", '\u{2665}'),
+    ("hungarumlaut", '\u{02DD}'),
+    ("hyphen", '\u{002D}'),
+    ("hypheninferior", '\u{F6E5}'),
+    ("hyphensuperior", '\u{F6E6}'),
+    ("i", '\u{0069}'),
+    ("iacute", '\u{00ED}'),
+    ("ibreve", '\u{012D}'),
+    ("icircumflex", '\u{00EE}'),
+    ("idieresis", '\u{00EF}'),
+    ("igrave", '\u{00EC}'),
+    ("ij", '\u{0133}'),
+    ("imacron", '\u{012B}'),
+    ("infinity", '\u{221E}'),
+    ("integral", '\u{222B}'),
+    ("integralbt", '\u{2321}'),
+    ("integralex", '\u{F8F5}'),
+    ("integraltp", '\u{2320}'),
+    ("intersection", '\u{2229}'),
+    ("iogonek", '\u{012F}'),
+    ("iota", '\u{03B9}'),
+    ("isuperior", '\u{F6ED}'),
+    ("itilde", '\u{0129}'),
+    ("j", '\u{006A}'),
+    ("jcircumflex", '\u{0135}'),
+    ("k", '\u{006B}'),
+    ("kappa", '\u{03BA}'),
+    ("kcommaaccent", '\u{0137}'),
+    ("kgreenlandic", '\u{0138}'),
+    ("l", '\u{006C}'),
+    ("lacute", '\u{013A}'),
+    ("lambda", '\u{03BB}'),
+    ("lcaron", '\u{013E}'),
+    ("lcommaaccent", '\u{013C}'),
+    ("ldot", '\u{0140}'),
+    ("less", '\u{003C}'),
+    ("lessequal", '\u{2264}'),
+    ("lira", '\u{20A4}'),
+    ("logicaland", '\u{2227}'),
+    ("logicalnot", '\u{00AC}'),
+    ("logicalor", '\u{2228}'),
+    ("longs", '\u{017F}'),
+    ("lozenge", '\u{25CA}'),
+    ("lslash", '\u{0142}'),
+    ("lsuperior", '\u{F6EE}'),
+    ("m", '\u{006D}'),
+    ("macron", '\u{00AF}'),
+    ("middot", '\u{00B7}'),
+    ("minus", '\u{2212}'),
+    ("minute", '\u{2032}'),
+    ("msuperior", '\u{F6EF}'),
+    ("mu", '\u{00B5}'),
+    ("multiply", '\u{00D7}'),
+    ("n", '\u{006E}'),
+    ("nacute", '\u{0144}'),
+    ("napostrophe", '\u{0149}'),
+    ("nbspace", '\u{00A0}'),
+    ("ncaron", '\u{0148}'),
+    ("ncommaaccent", '\u{0146}'),
+    ("nine", '\u{0039}'),
+    ("nineinferior", '\u{2089}'),
+    ("nineoldstyle", '\u{F739}'),
+    ("ninesuperior", '\u{2079}'),
+    ("notelement", '\u{2209}'),
+    ("notequal", '\u{2260}'),
+    ("notsubset", '\u{2284}'),
+    ("nsuperior", '\u{207F}'),
+    ("ntilde", '\u{00F1}'),
+    ("nu", '\u{03BD}'),
+    ("numbersign", '\u{0023}'),
+    ("o", '\u{006F}'),
+    ("oacute", '\u{00F3}'),
+    ("obreve", '\u{014F}'),
+    ("ocircumflex", '\u{00F4}'),
+    ("odieresis", '\u{00F6}'),
+    ("oe", '\u{0153}'),
+    ("ogonek", '\u{02DB}'),
+    ("ograve", '\u{00F2}'),
+    ("ohungarumlaut", '\u{0151}'),
+    ("omacron", '\u{014D}'),
+    ("omega", '\u{03C9}'),
+    ("omega1", '\u{03D6}'),
+    ("omicron", '\u{03BF}'),
+    ("one", '\u{0031}'),
+    ("onedotenleader", '\u{2024}'),
+    ("oneeighth", '\u{215B}'),
+    ("onefitted", '\u{F6DC}'),
+    ("onehalf", '\u{00BD}'),
+    ("oneinferior", '\u{2081}'),
+    ("oneoldstyle", '\u{F731}'),
+    ("onequarter", '\u{00BC}'),
+    ("onesuperior", '\u{00B9}'),
+    ("onethird", '\u{2153}'),
+    ("ordfeminine", '\u{00AA}'),
+    ("ordmasculine", '\u{00BA}'),
+    ("oslash", '\u{00F8}'),
+    ("osuperior", '\u{F6F0}'),
+    ("otilde", '\u{00F5}'),
+    ("overscore", '\u{00AF}'),
+    ("p", '\u{0070}'),
+    ("paragraph", '\u{00B6}'),
+    ("parenleft", '\u{0028}'),
+    ("parenleftbt", '\u{F8ED}'),
+    ("parenleftex", '\u{F8EC}'),
+    ("parenleftinferior", '\u{208D}'),
+    ("parenleftsuperior", '\u{207D}'),
+    ("parenlefttp", '\u{F8EB}'),
+    ("parenright", '\u{0029}'),
+    ("parenrightbt", '\u{F8F8}'),
+    ("parenrightex", '\u{F8F7}'),
+    ("parenrightinferior", '\u{208E}'),
+    ("parenrightsuperior", '\u{207E}'),
+    ("parenrighttp", '\u{F8F6}'),
+    ("partialdiff", '\u{2202}'),
+    ("percent", '\u{0025}'),
+    ("period", '\u{002E}'),
+    ("periodcentered", '\u{00B7}'),
+    ("periodinferior", '\u{F6E7}'),
+    ("periodsuperior", '\u{F6E8}'),
+    ("perpendicular", '\u{22A5}'),
+    ("perthousand", '\u{2030}'),
+    ("peseta", '\u{20A7}'),
+    ("phi", '\u{03C6}'),
+    ("phi1", '\u{03D5}'),
+    ("pi", '\u{03C0}'),
+    ("plus", '\u{002B}'),
+    ("plusminus", '\u{00B1}'),
+    ("product", '\u{220F}'),
+    ("propersubset", '\u{2282}'),
+    ("propersuperset", '\u{2283}'),
+    ("proportional", '\u{221D}'),
+    ("psi", '\u{03C8}'),
+    ("q", '\u{0071}'),
+    ("question", '\u{003F}'),
+    ("questiondown", '\u{00BF}'),
+    ("questiondownsmall", '\u{F7BF}'),
+    ("questionsmall", '\u{F73F}'),
+    ("quotedbl", '\u{0022}'),
+    ("quotedblbase", '\u{201E}'),
+    ("quotedblleft", '\u{201C}'),
+    ("quotedblright", '\u{201D}'),
+    ("quoteleft", '\u{2018}'),
+    ("quotereversed", '\u{201B}'),
+    ("quoteright", '\u{2019}'),
+    ("quotesinglbase", '\u{201A}'),
+    ("quotesingle", '\u{0027}'),
+    ("r", '\u{0072}'),
+    ("racute", '\u{0155}'),
+    ("radical", '\u{221A}'),
+    ("radicalex", '\u{F8E5}'),
+    ("rcaron", '\u{0159}'),
+    ("rcommaaccent", '\u{0157}'),
+    ("reflexsubset", '\u{2286}'),
+    ("reflexsuperset", '\u{2287}'),
+    ("registered", '\u{00AE}'),
+    ("registersans", '\u{F8E8}'),
+    ("registerserif", '\u{F6DA}'),
+    ("rho", '\u{03C1}'),
+    ("ring", '\u{02DA}'),
+    ("rsuperior", '\u{F6F1}'),
+    ("rupiah", '\u{F6DD}'),
+    ("s", '\u{0073}'),
+    ("sacute", '\u{015B}'),
+    ("scaron", '\u{0161}'),
+    ("scedilla", '\u{015F}'),
+    ("scircumflex", '\u{015D}'),
+    ("scommaaccent", '\u{0219}'),
+    ("second", '\u{2033}'),
+    ("section", '\u{00A7}'),
+    ("semicolon", '\u{003B}'),
+    ("seven", '\u{0037}'),
+    ("seveneighths", '\u{215E}'),
+    ("seveninferior", '\u{2087}'),
+    ("sevenoldstyle", '\u{F737}'),
+    ("sevensuperior", '\u{2077}'),
+    ("sfthyphen", '\u{00AD}'),
+    ("sigma", '\u{03C3}'),
+    ("sigma1", '\u{03C2}'),
+    ("similar", '\u{223C}'),
+    ("six", '\u{0036}'),
+    ("sixinferior", '\u{2086}'),
+    ("sixoldstyle", '\u{F736}'),
+    ("sixsuperior", '\u{2076}'),
+    ("slash", '\u{002F}'),
+    ("space", '\u{0020}'),
+    ("spade", '\u{2660}'),
+    ("ssuperior", '\u{F6F2}'),
+    ("sterling", '\u{00A3}'),
+    ("suchthat", '\u{220B}'),
+    ("summation", '\u{2211}'),
+    ("t", '\u{0074}'),
+    ("tau", '\u{03C4}'),
+    ("tbar", '\u{0167}'),
+    ("tcaron", '\u{0165}'),
+    ("tcommaaccent", '\u{0163}'),
+    ("therefore", '\u{2234}'),
+    ("theta", '\u{03B8}'),
+    ("theta1", '\u{03D1}'),
+    ("thorn", '\u{00FE}'),
+    ("three", '\u{0033}'),
+    ("threeeighths", '\u{215C}'),
+    ("threeinferior", '\u{2083}'),
+    ("threeoldstyle", '\u{F733}'),
+    ("threequarters", '\u{00BE}'),
+    ("threequartersemdash", '\u{F6DE}'),
+    ("threesuperior", '\u{00B3}'),
+    ("tilde", '\u{02DC}'),
+    ("trademark", '\u{2122}'),
+    ("trademarksans", '\u{F8EA}'),
+    ("trademarkserif", '\u{F6DB}'),
+    ("tsuperior", '\u{F6F3}'),
+    ("two", '\u{0032}'),
+    ("twodotenleader", '\u{2025}'),
+    ("twoinferior", '\u{2082}'),
+    ("twooldstyle", '\u{F732}'),
+    ("twosuperior", '\u{00B2}'),
+    ("twothirds", '\u{2154}'),
+    ("u", '\u{0075}'),
+    ("uacute", '\u{00FA}'),
+    ("ubreve", '\u{016D}'),
+    ("ucircumflex", '\u{00FB}'),
+    ("udieresis", '\u{00FC}'),
+    ("ugrave", '\u{00F9}'),
+    ("uhungarumlaut", '\u{0171}'),
+    ("umacron", '\u{016B}'),
+    ("underscore", '\u{005F}'),
+    ("union", '\u{222A}'),
+    ("universal", '\u{2200}'),
+    ("uogonek", '\u{0173}'),
+    ("upsilon", '\u{03C5}'),
+    ("uring", '\u{016F}'),
+    ("utilde", '\u{0169}'),
+    ("v", '\u{0076}'),
+    ("w", '\u{0077}'),
+    ("wcircumflex", '\u{0175}'),
+    ("weierstrass", '\u{2118}'),
+    ("x", '\u{0078}'),
+    ("xi", '\u{03BE}'),
+    ("y", '\u{0079}'),
+    ("yacute", '\u{00FD}'),
+    ("ycircumflex", '\u{0177}'),
+    ("ydieresis", '\u{00FF}'),
+    ("yen", '\u{00A5}'),
+    ("z", '\u{007A}'),
+    ("zacute", '\u{017A}'),
+    ("zcaron", '\u{017E}'),
+    ("zdotaccent", '\u{017C}'),
+    ("zero", '\u{0030}'),
+    ("zeroinferior", '\u{2080}'),
+    ("zerooldstyle", '\u{F730}'),
+    ("zerosuperior", '\u{2070}'),
+    ("zeta", '\u{03B6}'),
+];
+
+/// Maps a glyph name to the Unicode character it represents.
+///
+/// Any suffix following the first period (e.g. `a.sc`, `one.oldstyle`) is
+/// ignored, as required by the glyph list specification.
+///
+/// # Parameters
+///
+/// - `name`: The glyph name, without the leading slash.
+///
+/// # Returns
+///
+/// The Unicode character for the glyph, or `None` if the name is unknown or
+/// names a sequence of characters (e.g. ligatures written as `f_i`).
+pub fn glyph_name_to_unicode(name: &str) -> Option<char> {
+    let name = name.split('.').next().unwrap_or(name);
+    if name.is_empty() || name.contains('_') {
+        return None;
+    }
+
+    if let Ok(index) = GLYPH_LIST.binary_search_by(|(known, _)| (*known).cmp(name)) {
+        return GLYPH_LIST.get(index).map(|(_, ch)| *ch);
+    }
+
+    // `uniXXXX`: exactly one 4-digit uppercase hexadecimal BMP code point.
+    if let Some(hex) = name.strip_prefix("uni")
+        && hex.len() == 4
+    {
+        return parse_code_point(hex);
+    }
+
+    // `uXXXX` to `uXXXXXX`: a single code point with 4 to 6 hexadecimal digits.
+    if let Some(hex) = name.strip_prefix('u')
+        && (4..=6).contains(&hex.len())
+    {
+        return parse_code_point(hex);
+    }
+
+    None
+}
+
+/// Parses an uppercase hexadecimal string into a Unicode scalar value.
+fn parse_code_point(hex: &str) -> Option<char> {
+    if !hex
+        .chars()
+        .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+    {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyph_list_is_sorted() {
+        assert!(GLYPH_LIST.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn resolves_known_names() {
+        assert_eq!(glyph_name_to_unicode("A"), Some('A'));
+        assert_eq!(glyph_name_to_unicode("eacute"), Some('é'));
+        assert_eq!(glyph_name_to_unicode("quoteright"), Some('\u{2019}'));
+        assert_eq!(glyph_name_to_unicode("Euro"), Some('€'));
+        assert_eq!(glyph_name_to_unicode("alpha"), Some('α'));
+        assert_eq!(glyph_name_to_unicode("a20"), Some('\u{2714}'));
+    }
+
+    #[test]
+    fn resolves_algorithmic_names() {
+        assert_eq!(glyph_name_to_unicode("uni20AC"), Some('€'));
+        assert_eq!(glyph_name_to_unicode("u1F600"), Some('\u{1F600}'));
+        assert_eq!(glyph_name_to_unicode("uni20ac"), None);
+        assert_eq!(glyph_name_to_unicode("uniD800"), None);
+    }
+
+    #[test]
+    fn ignores_suffixes() {
+        assert_eq!(glyph_name_to_unicode("a.sc"), Some('a'));
+        assert_eq!(glyph_name_to_unicode("f_i"), None);
+        assert_eq!(glyph_name_to_unicode(".notdef"), None);
+    }
+}
@@ -30,6 +30,9 @@ pub enum GlyphWidthsMapError {
     ObjectError(#[from] ObjectError),
     #[error("Missing CID")]
     MissingCID,
+    /// A `/W2` entry does not consist of `w1y v1x v1y` triples.
+    #[error("Vertical metrics for CID {cid} are not a multiple of three numbers")]
+    InvalidVerticalMetricsLength { cid: u16 },
 }
 
 /// Stores glyph widths for CIDs, parsed from the /W array.
@@ -39,20 +42,39 @@ pub enum GlyphWidthsMapError {
 /// widths; for an explicit array, the vector contains the widths as specified.
 /// Represents either an explicit sequence of widths or a uniform run.
 #[derive(Debug, Clone, PartialEq)]
-enum WidthRun {
+enum WidthRun<T> {
     /// Explicit widths: `[c_first [w1 ... wn]]` form.
-    Explicit(Vec<f32>),
+    Explicit(Vec<T>),
     /// Uniform width for a continuous range: `[c_first c_last w]` (inclusive end CID).
-    Uniform { width: f32, end: u16 },
+    Uniform { width: T, end: u16 },
 }
 
-/// Represents a glyph widths map parsed from a PDF `/W` array. This
-/// applies to CID-keyed fonts `/CIDFontType0` and `/CIDFontType2`
-/// (descendants of /Type0).
-#[derive(Default)]
-pub struct GlyphWidthsMap {
+/// Vertical metrics of a CID, parsed from a `/W2` array.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerticalMetrics {
+    /// The vertical displacement (w1y), in glyph space units; negative values move down.
+    pub w1y: f32,
+    /// The horizontal component of the position vector (v), from the glyph origin
+    /// used in horizontal writing to the origin used in vertical writing.
+    pub vx: f32,
+    /// The vertical component of the position vector (v).
+    pub vy: f32,
+}
+
+/// Represents a glyph widths map parsed from a PDF `/W` array, or with
+/// [`VerticalMetrics`] entries from a `/W2` array. This applies to CID-keyed
+/// fonts `/CIDFontType0` and `/CIDFontType2` (descendants of /Type0).
+pub struct GlyphWidthsMap<T = f32> {
     /// Ordered mapping from starting CID -> width run segment.
-    runs: BTreeMap<u16, WidthRun>,
+    runs: BTreeMap<u16, WidthRun<T>>,
+}
+
+impl<T> Default for GlyphWidthsMap<T> {
+    fn default() -> Self {
+        Self {
+            runs: BTreeMap::new(),
+        }
+    }
 }
 
 impl GlyphWidthsMap {
@@ -117,16 +139,82 @@ impl GlyphWidthsMap {
         cid: u16,
         widths_arr: &[ObjectVariant],
     ) -> Result<(), GlyphWidthsMapError> {
-        if widths_arr.is_empty() {
-            return Err(GlyphWidthsMapError::EmptyWidthsArray { cid });
-        }
-        if self.runs.contains_key(&cid) {
-            return Err(GlyphWidthsMapError::DuplicateCIDStart { cid });
-        }
         let widths = widths_arr
             .iter()
             .map(ObjectVariant::as_number::<f32>)
             .collect::<Result<Vec<_>, _>>()?;
+        self.insert_explicit_values(cid, widths)
+    }
+}
+
+impl GlyphWidthsMap<VerticalMetrics> {
+    /// Parses a PDF `/W2` array into a map of vertical metrics.
+    ///
+    /// The `/W2` array can contain entries of the form:
+    /// - `[c_first [w1y v1x v1y ...]]` (one triple per consecutive CID starting at `c_first`)
+    /// - `[c_first c_last w1y v1x v1y]` (the same metrics for all CIDs in the range)
+    ///
+    /// # Errors
+    ///
+    /// Returns `GlyphWidthsMapError` if the array is malformed or contains invalid values.
+    pub fn from_w2_array(array: &[ObjectVariant]) -> Result<Self, GlyphWidthsMapError> {
+        let mut map = GlyphWidthsMap::default();
+        let mut i = 0usize;
+        while i < array.len() {
+            let cid = array
+                .get(i)
+                .ok_or(GlyphWidthsMapError::MissingCID)?
+                .as_number::<u16>()?;
+            i = i.saturating_add(1);
+
+            match array.get(i) {
+                Some(ObjectVariant::Array(metrics_arr)) => {
+                    let numbers = metrics_arr
+                        .iter()
+                        .map(ObjectVariant::as_number::<f32>)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let (triples, remainder) = numbers.as_chunks::<3>();
+                    if !remainder.is_empty() {
+                        return Err(GlyphWidthsMapError::InvalidVerticalMetricsLength { cid });
+                    }
+                    let metrics = triples
+                        .iter()
+                        .map(|&[w1y, vx, vy]| VerticalMetrics { w1y, vx, vy })
+                        .collect();
+                    map.insert_explicit_values(cid, metrics)?;
+                    i = i.saturating_add(1);
+                }
+                Some(other) => {
+                    let c_last = other.as_number::<u16>()?;
+                    let mut values = [0.0f32; 3];
+                    for (offset, value) in values.iter_mut().enumerate() {
+                        *value = array
+                            .get(i.saturating_add(1).saturating_add(offset))
+                            .ok_or(GlyphWidthsMapError::MissingWidthForCIDRange { c_first: cid })?
+                            .as_number::<f32>()?;
+                    }
+                    let [w1y, vx, vy] = values;
+                    map.insert_uniform(cid, c_last, VerticalMetrics { w1y, vx, vy })?;
+                    // Advance past c_last and the three metrics.
+                    i = i.saturating_add(4);
+                }
+                None => return Err(GlyphWidthsMapError::IncompleteCIDEntry { cid }),
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl<T: Copy> GlyphWidthsMap<T> {
+    /// Insert an explicit run of already parsed values starting at `cid`.
+    fn insert_explicit_values(
+        &mut self,
+        cid: u16,
+        widths: Vec<T>,
+    ) -> Result<(), GlyphWidthsMapError> {
+        if self.runs.contains_key(&cid) {
+            return Err(GlyphWidthsMapError::DuplicateCIDStart { cid });
+        }
 
         let length = widths.len();
         // Ensure explicit run does not exceed CID space.
@@ -160,7 +248,7 @@ impl GlyphWidthsMap {
         &mut self,
         cid: u16,
         c_last: u16,
-        width: f32,
+        width: T,
     ) -> Result<(), GlyphWidthsMapError> {
         if c_last < cid {
             return Err(GlyphWidthsMapError::InvalidCIDRange {
@@ -223,7 +311,7 @@ impl GlyphWidthsMap {
     /// # Returns
     ///
     /// `Some(width)` if the width is found, or `None` if not present.
-    pub fn get_width(&self, character_id: u16) -> Option<f32> {
+    pub fn get_width(&self, character_id: u16) -> Option<T> {
         let (start, run) = self.runs.range(..=character_id).next_back()?;
         let offset = character_id.checked_sub(*start)?;
         match run {
@@ -315,7 +403,7 @@ mod tests {
 
     #[test]
     fn test_get_width_empty_map() {
-        let glyph_widths_map = GlyphWidthsMap {
+        let glyph_widths_map: GlyphWidthsMap = GlyphWidthsMap {
             runs: BTreeMap::new(),
         };
         assert_eq!(glyph_widths_map.get_width(0), None);
@@ -528,4 +616,46 @@ mod tests {
             Err(GlyphWidthsMapError::MissingWidthForCIDRange { c_first: 10 })
         ));
     }
+
+    #[test]
+    fn test_from_w2_array_both_forms() {
+        // [ 120 [-1000 500 880 -900 450 880] 200 201 -1000 500 900 ]
+        let input_array = vec![
+            num_i64(120),
+            arr(vec![
+                num_f32(-1000.0),
+                num_f32(500.0),
+                num_f32(880.0),
+                num_f32(-900.0),
+                num_f32(450.0),
+                num_f32(880.0),
+            ]),
+            num_i64(200),
+            num_i64(201),
+            num_f32(-1000.0),
+            num_f32(500.0),
+            num_f32(900.0),
+        ];
+        let map = GlyphWidthsMap::from_w2_array(&input_array).unwrap();
+        assert_eq!(
+            map.get_width(121),
+            Some(VerticalMetrics {
+                w1y: -900.0,
+                vx: 450.0,
+                vy: 880.0
+            })
+        );
+        assert_eq!(map.get_width(201).map(|m| m.vy), Some(900.0));
+        assert_eq!(map.get_width(122), None);
+    }
+
+    #[test]
+    fn test_from_w2_array_error_incomplete_triple() {
+        // [ 1 [-1000 500] ]
+        let input_array = vec![num_i64(1), arr(vec![num_f32(-1000.0), num_f32(500.0)])];
+        assert!(matches!(
+            GlyphWidthsMap::from_w2_array(&input_array),
+            Err(GlyphWidthsMapError::InvalidVerticalMetricsLength { cid: 1 })
+        ));
+    }
 }
@@ -2,8 +2,12 @@ pub mod cff;
 pub mod character_map;
 pub mod font;
 pub mod font_descriptor;
+pub mod glyph_cache;
+pub mod glyph_list;
 pub mod glyph_widths_map;
+pub mod simple_font_encoding;
 pub mod simple_font_glyph_map;
+mod standard_encodings;
 pub mod true_type_font;
 pub mod type0_font;
 pub mod type1_font;
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use pdf_object::{
+    ObjectVariant, dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
+    traits::FromDictionary,
+};
+use thiserror::Error;
+
+use crate::{
+    glyph_list::glyph_name_to_unicode,
+    standard_encodings::{
+        MAC_EXPERT_ENCODING, MAC_ROMAN_ENCODING, STANDARD_ENCODING, SYMBOL_ENCODING,
+        WIN_ANSI_ENCODING, ZAPF_DINGBATS_ENCODING,
+    },
+};
+
+/// Defines errors that can occur while parsing a font encoding dictionary.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum EncodingError {
+    #[error("Invalid entry in /Differences array: expected Integer or Name, found {found_type}")]
+    InvalidDifferencesEntryType { found_type: &'static str },
+    #[error("Invalid character code in /Differences array: expected 0-255, found {code}")]
+    InvalidDifferenceCharCode { code: i64 },
+    #[error(
+        "Character code overflow in /Differences array while incrementing after code {last_code}"
+    )]
+    DifferencesCodeOverflow { last_code: u8 },
+    #[error("Invalid /Encoding entry: expected Name or Dictionary, found {found_type}")]
+    InvalidEncodingEntryType { found_type: &'static str },
+    #[error("{0}")]
+    ObjectError(#[from] ObjectError),
+}
+
+/// One of the predefined character encodings for simple fonts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseEncoding {
+    /// Adobe standard Latin-text encoding, the built-in encoding of most Type 1 fonts.
+    Standard,
+    /// Windows code page 1252.
+    WinAnsi,
+    /// Mac OS standard encoding for Latin text.
+    MacRoman,
+    /// Encoding for expert fonts (small caps, old-style figures, ligatures).
+    MacExpert,
+    /// Built-in encoding of the standard `Symbol` font.
+    Symbol,
+    /// Built-in encoding of the standard `ZapfDingbats` font.
+    ZapfDingbats,
+}
+
+impl BaseEncoding {
+    /// Resolves an encoding name as it appears in an `/Encoding` or `/BaseEncoding` entry.
+    ///
+    /// Only `StandardEncoding`, `WinAnsiEncoding`, `MacRomanEncoding` and
+    /// `MacExpertEncoding` may be named in a PDF file; the `Symbol` and
+    /// `ZapfDingbats` encodings are only reachable as font built-in encodings.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "StandardEncoding" => Some(Self::Standard),
+            "WinAnsiEncoding" => Some(Self::WinAnsi),
+            "MacRomanEncoding" => Some(Self::MacRoman),
+            "MacExpertEncoding" => Some(Self::MacExpert),
+            _ => None,
+        }
+    }
+
+    /// Returns the built-in encoding for one of the standard 14 fonts, based on its base font name.
+    ///
+    /// Subset prefixes such as `ABCDEF+` are ignored.
+    pub fn builtin_for_base_font(base_font: &str) -> Self {
+        let name = base_font
+            .split_once('+')
+            .map_or(base_font, |(_, name)| name);
+        match name {
+            "Symbol" => Self::Symbol,
+            "ZapfDingbats" => Self::ZapfDingbats,
+            _ => Self::Standard,
+        }
+    }
+
+    /// Returns the code-to-glyph-name table for this encoding.
+    fn table(self) -> &'static [Option<&'static str>; 256] {
+        match self {
+            Self::Standard => &STANDARD_ENCODING,
+            Self::WinAnsi => &WIN_ANSI_ENCODING,
+            Self::MacRoman => &MAC_ROMAN_ENCODING,
+            Self::MacExpert => &MAC_EXPERT_ENCODING,
+            Self::Symbol => &SYMBOL_ENCODING,
+            Self::ZapfDingbats => &ZAPF_DINGBATS_ENCODING,
+        }
+    }
+
+    /// Returns the glyph name assigned to `code`, or `None` if the code is unassigned.
+    pub fn glyph_name(self, code: u8) -> Option<&'static str> {
+        self.table().get(usize::from(code)).copied().flatten()
+    }
+
+    /// Returns the character code to which this encoding assigns `glyph_name`.
+    pub fn code_for_glyph_name(self, glyph_name: &str) -> Option<u8> {
+        self.table()
+            .iter()
+            .position(|name| *name == Some(glyph_name))
+            .and_then(|code| u8::try_from(code).ok())
+    }
+}
+
+/// Represents a font encoding dictionary, used to map character codes to glyph names.
+#[derive(Debug)]
+pub struct FontEncodingDictionary {
+    /// The base encoding, which can be a predefined name like `/StandardEncoding`
+    /// or `/MacRomanEncoding`.
+    pub base_encoding: Option<String>,
+    /// A dictionary of differences from the base encoding.
+    /// Maps character codes (0-255) to glyph names.
+    pub differences: HashMap<u8, String>,
+}
+
+impl FromDictionary for FontEncodingDictionary {
+    const KEY: &'static str = "Encoding";
+    type ResultType = Self;
+    type ErrorType = EncodingError;
+
+    fn from_dictionary(
+        dictionary: &Dictionary,
+        _objects: &ObjectCollection, // No need for objects here based on spec
+    ) -> Result<Self::ResultType, Self::ErrorType> {
+        let base_encoding = dictionary
+            .get("BaseEncoding")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let mut differences = HashMap::new();
+
+        if let Some(diff_array) = dictionary.get("Differences") {
+            let mut current_code: u8 = 0;
+            let mut exhausted = false;
+            for entry in diff_array.try_array()?.iter() {
+                match entry {
+                    ObjectVariant::Integer(code) => {
+                        let code_i64 = *code;
+                        current_code = u8::try_from(code_i64).map_err(|_| {
+                            EncodingError::InvalidDifferenceCharCode { code: code_i64 }
+                        })?;
+                        exhausted = false;
+                    }
+                    ObjectVariant::Name(name) => {
+                        if exhausted {
+                            return Err(EncodingError::DifferencesCodeOverflow {
+                                last_code: current_code,
+                            });
+                        }
+                        differences.insert(current_code, name.clone());
+                        // A run may legitimately end at code 255; only a further name overflows.
+                        match current_code.checked_add(1) {
+                            Some(next) => current_code = next,
+                            None => exhausted = true,
+                        }
+                    }
+                    _ => {
+                        return Err(EncodingError::InvalidDifferencesEntryType {
+                            found_type: entry.name(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(FontEncodingDictionary {
+            base_encoding,
+            differences,
+        })
+    }
+}
+
+/// The encoding of a simple font (Type 1, TrueType or Type 3), mapping single-byte
+/// character codes to glyph names.
+///
+/// Codes listed in `/Differences` take precedence over the base encoding. When
+/// the font dictionary names no base encoding, the font's built-in encoding
+/// applies, which callers supply through the `builtin` parameters below.
+#[derive(Debug, Default)]
+pub struct SimpleFontEncoding {
+    /// The predefined encoding named by `/Encoding` or `/BaseEncoding`, if any.
+    pub base_encoding: Option<BaseEncoding>,
+    /// Glyph names overriding the base encoding for individual character codes.
+    pub differences: HashMap<u8, String>,
+}
+
+impl SimpleFontEncoding {
+    /// Parses the value of a font dictionary's `/Encoding` entry.
+    ///
+    /// The entry is either the name of a predefined encoding or an encoding
+    /// dictionary with optional `/BaseEncoding` and `/Differences`. Unknown
+    /// encoding names are treated as absent so that the built-in encoding applies.
+    pub fn from_object(
+        object: &ObjectVariant,
+        objects: &ObjectCollection,
+    ) -> Result<Self, EncodingError> {
+        match objects.resolve_object(object)? {
+            ObjectVariant::Name(name) => Ok(Self {
+                base_encoding: BaseEncoding::from_name(name),
+                differences: HashMap::new(),
+            }),
+            ObjectVariant::Dictionary(dictionary) => {
+                let FontEncodingDictionary {
+                    base_encoding,
+                    differences,
+                } = FontEncodingDictionary::from_dictionary(dictionary, objects)?;
+                Ok(Self {
+                    base_encoding: base_encoding.as_deref().and_then(BaseEncoding::from_name),
+                    differences,
+                })
+            }
+            other => Err(EncodingError::InvalidEncodingEntryType {
+                found_type: other.name(),
+            }),
+        }
+    }
+
+    /// Returns `true` if the encoding names a base encoding or lists any differences.
+    pub fn is_specified(&self) -> bool {
+        self.base_encoding.is_some() || !self.differences.is_empty()
+    }
+
+    /// Returns the glyph name for `code`, consulting `/Differences` first, then the
+    /// base encoding, and finally `builtin` when no base encoding was named.
+    ///
+    /// # Parameters
+    ///
+    /// - `code`: The single-byte character code from a content stream.
+    /// - `builtin`: The font's built-in encoding, if it is known to be one of the
+    ///   predefined encodings.
+    pub fn glyph_name(&self, code: u8, builtin: Option<BaseEncoding>) -> Option<&str> {
+        if let Some(name) = self.differences.get(&code) {
+            return Some(name.as_str());
+        }
+        self.base_encoding
+            .or(builtin)
+            .and_then(|encoding| encoding.glyph_name(code))
+    }
+
+    /// Maps `code` to Unicode through its glyph name and the Adobe Glyph List.
+    pub fn to_unicode(&self, code: u8, builtin: Option<BaseEncoding>) -> Option<char> {
+        self.glyph_name(code, builtin)
+            .and_then(glyph_name_to_unicode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predefined_tables_resolve_names() {
+        assert_eq!(BaseEncoding::Standard.glyph_name(0x27), Some("quoteright"));
+        assert_eq!(BaseEncoding::WinAnsi.glyph_name(0x27), Some("quotesingle"));
+        assert_eq!(BaseEncoding::WinAnsi.glyph_name(0x80), Some("Euro"));
+        assert_eq!(BaseEncoding::MacRoman.glyph_name(0x8E), Some("eacute"));
+        assert_eq!(BaseEncoding::Symbol.glyph_name(0x61), Some("alpha"));
+        assert_eq!(BaseEncoding::ZapfDingbats.glyph_name(0x21), Some("a1"));
+        assert_eq!(BaseEncoding::MacExpert.glyph_name(0x56), Some("ff"));
+        assert_eq!(BaseEncoding::WinAnsi.glyph_name(0x81), None);
+    }
+
+    #[test]
+    fn code_for_glyph_name_inverts_table() {
+        assert_eq!(
+            BaseEncoding::MacRoman.code_for_glyph_name("eacute"),
+            Some(0x8E)
+        );
+        assert_eq!(BaseEncoding::MacRoman.code_for_glyph_name("Euro"), None);
+    }
+
+    #[test]
+    fn differences_override_base_encoding() {
+        let mut differences = HashMap::new();
+        differences.insert(0x41, "Eacute".to_string());
+        let encoding = SimpleFontEncoding {
+            base_encoding: Some(BaseEncoding::WinAnsi),
+            differences,
+        };
+        assert_eq!(encoding.glyph_name(0x41, None), Some("Eacute"));
+        assert_eq!(encoding.to_unicode(0x41, None), Some('É'));
+        assert_eq!(encoding.to_unicode(0x42, None), Some('B'));
+    }
+
+    #[test]
+    fn builtin_encoding_applies_without_base() {
+        let encoding = SimpleFontEncoding::default();
+        assert_eq!(encoding.to_unicode(0x61, None), None);
+        assert_eq!(
+            encoding.to_unicode(0x61, Some(BaseEncoding::Symbol)),
+            Some('α')
+        );
+        assert_eq!(
+            BaseEncoding::builtin_for_base_font("ABCDEF+ZapfDingbats"),
+            BaseEncoding::ZapfDingbats
+        );
+    }
+}
@@ -17,6 +17,7 @@ pub enum SimpleFontGlyphWidthsMapError {
 
 /// Represents a simple font's glyph widths map parsed from a
 /// `/Type1`, `/TrueType`, or `/Type3` font.
+#[derive(Debug)]
 pub struct SimpleFontGlyphWidthsMap {
     /// Widths for character codes 0..=255 if provided via /Widths.
     /// Index is the character code, value is the width.
@@ -0,0 +1,226 @@
+//! Predefined simple-font encodings.
+//!
+//! Each table maps an 8-bit character code to a glyph name, following
+//! Annex D of ISO 32000-1. Unassigned codes are `None`.
+
+/// Adobe `StandardEncoding`, the built-in encoding of most Latin-text Type 1 fonts.
+#[rustfmt::skip]
+pub(crate) const STANDARD_ENCODING: [Option<&str>; 256] = [
+    /* 0o000 */ None, None, None, None, None, None, None, None,
+    /* 0o010 */ None, None, None, None, None, None, None, None,
+    /* 0o020 */ None, None, None, None, None, None, None, None,
+    /* 0o030 */ None, None, None, None, None, None, None, None,
+    /* 0o040 */ Some("space"), Some("exclam"), Some("quotedbl"), Some("numbersign"), Some("dollar"), Some("percent"), Some("ampersand"), Some("quoteright"),
+    /* 0o050 */ Some("parenleft"), Some("parenright"), Some("asterisk"), Some("plus"), Some("comma"), Some("hyphen"), Some("period"), Some("slash"),
+    /* 0o060 */ Some("zero"), Some("one"), Some("two"), Some("three"), Some("four"), Some("five"), Some("six"), Some("seven"),
+    /* 0o070 */ Some("eight"), Some("nine"), Some("colon"), Some("semicolon"), Some("less"), Some("equal"), Some("greater"), Some("question"),
+    /* 0o100 */ Some("at"), Some("A"), Some("B"), Some("C"), Some("D"), Some("E"), Some("F"), Some("G"),
+    /* 0o110 */ Some("H"), Some("I"), Some("J"), Some("K"), Some("L"), Some("M"), Some("N"), Some("O"),
+    /* 0o120 */ Some("P"), Some("Q"), Some("R"), Some("S"), Some("T"), Some("U"), Some("V"), Some("W"),
+    /* 0o130 */ Some("X"), Some("Y"), Some("Z"), Some("bracketleft"), Some("backslash"), Some("bracketright"), Some("asciicircum"), Some("underscore"),
+    /* 0o140 */ Some("quoteleft"), Some("a"), Some("b"), Some("c"), Some("d"), Some("e"), Some("f"), Some("g"),
+    /* 0o150 */ Some("h"), Some("i"), Some("j"), Some("k"), Some("l"), Some("m"), Some("n"), Some("o"),
+    /* 0o160 */ Some("p"), Some("q"), Some("r"), Some("s"), Some("t"), Some("u"), Some("v"), Some("w"),
+    /* 0o170 */ Some("x"), Some("y"), Some("z"), Some("braceleft"), Some("bar"), Some("braceright"), Some("asciitilde"), None,
+    /* 0o200 */ None, None, None, None, None, None, None, None,
+    /* 0o210 */ None, None, None, None, None, None, None, None,
+    /* 0o220 */ None, None, None, None, None, None, None, None,
+    /* 0o230 */ None, None, None, None, None, None, None, None,
+    /* 0o240 */ None, Some("exclamdown"), Some("cent"), Some("sterling"), Some("fraction"), Some("yen"), Some("florin"), Some("section"),
+    /* 0o250 */ Some("currency"), Some("quotesingle"), Some("quotedblleft"), Some("guillemotleft"), Some("guilsinglleft"), Some("guilsinglright"), Some("fi"), Some("fl"),
+    /* 0o260 */ None, Some("endash"), Some("dagger"), Some("daggerdbl"), Some("periodcentered"), None, Some("paragraph"), Some("bullet"),
+    /* 0o270 */ Some("quotesinglbase"), Some("quotedblbase"), Some("quotedblright"), Some("guillemotright"), Some("ellipsis"), Some("perthousand"), None, Some("questiondown"),
+    /* 0o300 */ None, Some("grave"), Some("acute"), Some("circumflex"), Some("tilde"), Some("macron"), Some("breve"), Some("dotaccent"),
+    /* 0o310 */ Some("dieresis"), None, Some("ring"), Some("cedilla"), None, Some("hungarumlaut"), Some("ogonek"), Some("caron"),
+    /* 0o320 */ Some("emdash"), None, None, None, None, None, None, None,
+    /* 0o330 */ None, None, None, None, None, None, None, None,
+    /* 0o340 */ None, Some("AE"), None, Some("ordfeminine"), None, None, None, None,
+    /* 0o350 */ Some("Lslash"), Some("Oslash"), Some("OE"), Some("ordmasculine"), None, None, None, None,
+    /* 0o360 */ None, Some("ae"), None, None, None, Some("dotlessi"), None, None,
+    /* 0o370 */ Some("lslash"), Some("oslash"), Some("oe"), Some("germandbls"), None, None, None, None,
+];
+
+/// `WinAnsiEncoding`, Windows code page 1252.
+#[rustfmt::skip]
+pub(crate) const WIN_ANSI_ENCODING: [Option<&str>; 256] = [
+    /* 0o000 */ None, None, None, None, None, None, None, None,
+    /* 0o010 */ None, None, None, None, None, None, None, None,
+    /* 0o020 */ None, None, None, None, None, None, None, None,
+    /* 0o030 */ None, None, None, None, None, None, None, None,
+    /* 0o040 */ Some("space"), Some("exclam"), Some("quotedbl"), Some("numbersign"), Some("dollar"), Some("percent"), Some("ampersand"), Some("quotesingle"),
+    /* 0o050 */ Some("parenleft"), Some("parenright"), Some("asterisk"), Some("plus"), Some("comma"), Some("hyphen"), Some("period"), Some("slash"),
+    /* 0o060 */ Some("zero"), Some("one"), Some("two"), Some("three"), Some("four"), Some("five"), Some("six"), Some("seven"),
+    /* 0o070 */ Some("eight"), Some("nine"), Some("colon"), Some("semicolon"), Some("less"), Some("equal"), Some("greater"), Some("question"),
+    /* 0o100 */ Some("at"), Some("A"), Some("B"), Some("C"), Some("D"), Some("E"), Some("F"), Some("G"),
+    /* 0o110 */ Some("H"), Some("I"), Some("J"), Some("K"), Some("L"), Some("M"), Some("N"), Some("O"),
+    /* 0o120 */ Some("P"), Some("Q"), Some("R"), Some("S"), Some("T"), Some("U"), Some("V"), Some("W"),
+    /* 0o130 */ Some("X"), Some("Y"), Some("Z"), Some("bracketleft"), Some("backslash"), Some("bracketright"), Some("asciicircum"), Some("underscore"),
+    /* 0o140 */ Some("grave"), Some("a"), Some("b"), Some("c"), Some("d"), Some("e"), Some("f"), Some("g"),
+    /* 0o150 */ Some("h"), Some("i"), Some("j"), Some("k"), Some("l"), Some("m"), Some("n"), Some("o"),
+    /* 0o160 */ Some("p"), Some("q"), Some("r"), Some("s"), Some("t"), Some("u"), Some("v"), Some("w"),
+    /* 0o170 */ Some("x"), Some("y"), Some("z"), Some("braceleft"), Some("bar"), Some("braceright"), Some("asciitilde"), None,
+    /* 0o200 */ Some("Euro"), None, Some("quotesinglbase"), Some("florin"), Some("quotedblbase"), Some("ellipsis"), Some("dagger"), Some("daggerdbl"),
+    /* 0o210 */ Some("circumflex"), Some("perthousand"), Some("Scaron"), Some("guilsinglleft"), Some("OE"), None, Some("Zcaron"), None,
+    /* 0o220 */ None, Some("quoteleft"), Some("quoteright"), Some("quotedblleft"), Some("quotedblright"), Some("bullet"), Some("endash"), Some("emdash"),
+    /* 0o230 */ Some("tilde"), Some("trademark"), Some("scaron"), Some("guilsinglright"), Some("oe"), None, Some("zcaron"), Some("Ydieresis"),
+    /* 0o240 */ Some("space"), Some("exclamdown"), Some("cent"), Some("sterling"), Some("currency"), Some("yen"), Some("brokenbar"), Some("section"),
+    /* 0o250 */ Some("dieresis"), Some("copyright"), Some("ordfeminine"), Some("guillemotleft"), Some("logicalnot"), Some("hyphen"), Some("registered"), Some("macron"),
+    /* 0o260 */ Some("degree"), Some("plusminus"), Some("twosuperior"), Some("threesuperior"), Some("acute"), Some("mu"), Some("paragraph"), Some("periodcentered"),
+    /* 0o270 */ Some("cedilla"), Some("onesuperior"), Some("ordmasculine"), Some("guillemotright"), Some("onequarter"), Some("onehalf"), Some("threequarters"), Some("questiondown"),
+    /* 0o300 */ Some("Agrave"), Some("Aacute"), Some("Acircumflex"), Some("Atilde"), Some("Adieresis"), Some("Aring"), Some("AE"), Some("Ccedilla"),
+    /* 0o310 */ Some("Egrave"), Some("Eacute"), Some("Ecircumflex"), Some("Edieresis"), Some("Igrave"), Some("Iacute"), Some("Icircumflex"), Some("Idieresis"),
+    /* 0o320 */ Some("Eth"), Some("Ntilde"), Some("Ograve"), Some("Oacute"), Some("Ocircumflex"), Some("Otilde"), Some("Odieresis"), Some("multiply"),
+    /* 0o330 */ Some("Oslash"), Some("Ugrave"), Some("Uacute"), Some("Ucircumflex"), Some("Udieresis"), Some("Yacute"), Some("Thorn"), Some("germandbls"),
+    /* 0o340 */ Some("agrave"), Some("aacute"), Some("acircumflex"), Some("atilde"), Some("adieresis"), Some("aring"), Some("ae"), Some("ccedilla"),
+    /* 0o350 */ Some("egrave"), Some("eacute"), Some("ecircumflex"), Some("edieresis"), Some("igrave"), Some("iacute"), Some("icircumflex"), Some("idieresis"),
+    /* 0o360 */ Some("eth"), Some("ntilde"), Some("ograve"), Some("oacute"), Some("ocircumflex"), Some("otilde"), Some("odieresis"), Some("divide"),
+    /* 0o370 */ Some("oslash"), Some("ugrave"), Some("uacute"), Some("ucircumflex"), Some("udieresis"), Some("yacute"), Some("thorn"), Some("ydieresis"),
+];
+
+/// `MacRomanEncoding`, the Mac OS standard encoding for Latin text.
+#[rustfmt::skip]
+pub(crate) const MAC_ROMAN_ENCODING: [Option<&str>; 256] = [
+    /* 0o000 */ None, None, None, None, None, None, None, None,
+    /* 0o010 */ None, None, None, None, None, None, None, None,
+    /* 0o020 */ None, None, None, None, None, None, None, None,
+    /* 0o030 */ None, None, None, None, None, None, None, None,
+    /* 0o040 */ Some("space"), Some("exclam"), Some("quotedbl"), Some("numbersign"), Some("dollar"), Some("percent"), Some("ampersand"), Some("quotesingle"),
+    /* 0o050 */ Some("parenleft"), Some("parenright"), Some("asterisk"), Some("plus"), Some("comma"), Some("hyphen"), Some("period"), Some("slash"),
+    /* 0o060 */ Some("zero"), Some("one"), Some("two"), Some("three"), Some("four"), Some("five"), Some("six"), Some("seven"),
+    /* 0o070 */ Some("eight"), Some("nine"), Some("colon"), Some("semicolon"), Some("less"), Some("equal"), Some("greater"), Some("question"),
+    /* 0o100 */ Some("at"), Some("A"), Some("B"), Some("C"), Some("D"), Some("E"), Some("F"), Some("G"),
+    /* 0o110 */ Some("H"), Some("I"), Some("J"), Some("K"), Some("L"), Some("M"), Some("N"), Some("O"),
+    /* 0o120 */ Some("P"), Some("Q"), Some("R"), Some("S"), Some("T"), Some("U"), Some("V"), Some("W"),
+    /* 0o130 */ Some("X"), Some("Y"), Some("Z"), Some("bracketleft"), Some("backslash"), Some("bracketright"), Some("asciicircum"), Some("underscore"),
+    /* 0o140 */ Some("grave"), Some("a"), Some("b"), Some("c"), Some("d"), Some("e"), Some("f"), Some("g"),
+    /* 0o150 */ Some("h"), Some("i"), Some("j"), Some("k"), Some("l"), Some("m"), Some("n"), Some("o"),
+    /* 0o160 */ Some("p"), Some("q"), Some("r"), Some("s"), Some("t"), Some("u"), Some("v"), Some("w"),
+    /* 0o170 */ Some("x"), Some("y"), Some("z"), Some("braceleft"), Some("bar"), Some("braceright"), Some("asciitilde"), None,
+    /* 0o200 */ Some("Adieresis"), Some("Aring"), Some("Ccedilla"), Some("Eacute"), Some("Ntilde"), Some("Odieresis"), Some("Udieresis"), Some("aacute"),
+    /* 0o210 */ Some("agrave"), Some("acircumflex"), Some("adieresis"), Some("atilde"), Some("aring"), Some("ccedilla"), Some("eacute"), Some("egrave"),
+    /* 0o220 */ Some("ecircumflex"), Some("edieresis"), Some("iacute"), Some("igrave"), Some("icircumflex"), Some("idieresis"), Some("ntilde"), Some("oacute"),
+    /* 0o230 */ Some("ograve"), Some("ocircumflex"), Some("odieresis"), Some("otilde"), Some("uacute"), Some("ugrave"), Some("ucircumflex"), Some("udieresis"),
+    /* 0o240 */ Some("dagger"), Some("degree"), Some("cent"), Some("sterling"), Some("section"), Some("bullet"), Some("paragraph"), Some("germandbls"),
+    /* 0o250 */ Some("registered"), Some("copyright"), Some("trademark"), Some("acute"), Some("dieresis"), None, Some("AE"), Some("Oslash"),
+    /* 0o260 */ None, Some("plusminus"), None, None, Some("yen"), Some("mu"), None, None,
+    /* 0o270 */ None, None, None, Some("ordfeminine"), Some("ordmasculine"), None, Some("ae"), Some("oslash"),
+    /* 0o300 */ Some("questiondown"), Some("exclamdown"), Some("logicalnot"), None, Some("florin"), None, None, Some("guillemotleft"),
+    /* 0o310 */ Some("guillemotright"), Some("ellipsis"), Some("space"), Some("Agrave"), Some("Atilde"), Some("Otilde"), Some("OE"), Some("oe"),
+    /* 0o320 */ Some("endash"), Some("emdash"), Some("quotedblleft"), Some("quotedblright"), Some("quoteleft"), Some("quoteright"), Some("divide"), None,
+    /* 0o330 */ Some("ydieresis"), Some("Ydieresis"), Some("fraction"), Some("currency"), Some("guilsinglleft"), Some("guilsinglright"), Some("fi"), Some("fl"),
+    /* 0o340 */ Some("daggerdbl"), Some("periodcentered"), Some("quotesinglbase"), Some("quotedblbase"), Some("perthousand"), Some("Acircumflex"), Some("Ecircumflex"), Some("Aacute"),
+    /* 0o350 */ Some("Edieresis"), Some("Egrave"), Some("Iacute"), Some("Icircumflex"), Some("Idieresis"), Some("Igrave"), Some("Oacute"), Some("Ocircumflex"),
+    /* 0o360 */ None, Some("Ograve"), Some("Uacute"), Some("Ucircumflex"), Some("Ugrave"), Some("dotlessi"), Some("circumflex"), Some("tilde"),
+    /* 0o370 */ Some("macron"), Some("breve"), Some("dotaccent"), Some("ring"), Some("cedilla"), Some("hungarumlaut"), Some("ogonek"), Some("caron"),
+];
+
+/// `MacExpertEncoding`, used by expert fonts with small caps, old-style figures and ligatures.
+#[rustfmt::skip]
+pub(crate) const MAC_EXPERT_ENCODING: [Option<&str>; 256] = [
+    /* 0o000 */ None, None, None, None, None, None, None, None,
+    /* 0o010 */ None, None, None, None, None, None, None, None,
+    /* 0o020 */ None, None, None, None, None, None, None, None,
+    /* 0o030 */ None, None, None, None, None, None, None, None,
+    /* 0o040 */ Some("space"), Some("exclamsmall"), Some("Hungarumlautsmall"), Some("centoldstyle"), Some("dollaroldstyle"), Some("dollarsuperior"), Some("ampersandsmall"), Some("Acutesmall"),
+    /* 0o050 */ Some("parenleftsuperior"), Some("parenrightsuperior"), Some("twodotenleader"), Some("onedotenleader"), Some("comma"), Some("hyphen"), Some("period"), Some("fraction"),
+    /* 0o060 */ Some("zerooldstyle"), Some("oneoldstyle"), Some("twooldstyle"), Some("threeoldstyle"), Some("fouroldstyle"), Some("fiveoldstyle"), Some("sixoldstyle"), Some("sevenoldstyle"),
+    /* 0o070 */ Some("eightoldstyle"), Some("nineoldstyle"), Some("colon"), Some("semicolon"), None, Some("threequartersemdash"), None, Some("questionsmall"),
+    /* 0o100 */ None, None, None, None, Some("Ethsmall"), None, None, Some("onequarter"),
+    /* 0o110 */ Some("onehalf"), Some("threequarters"), Some("oneeighth"), Some("threeeighths"), Some("fiveeighths"), Some("seveneighths"), Some("onethird"), Some("twothirds"),
+    /* 0o120 */ None, None, None, None, None, None, Some("ff"), Some("fi"),
+    /* 0o130 */ Some("fl"), Some("ffi"), Some("ffl"), Some("parenleftinferior"), None, Some("parenrightinferior"), Some("Circumflexsmall"), Some("hypheninferior"),
+    /* 0o140 */ Some("Gravesmall"), Some("Asmall"), Some("Bsmall"), Some("Csmall"), Some("Dsmall"), Some("Esmall"), Some("Fsmall"), Some("Gsmall"),
+    /* 0o150 */ Some("Hsmall"), Some("Ismall"), Some("Jsmall"), Some("Ksmall"), Some("Lsmall"), Some("Msmall"), Some("Nsmall"), Some("Osmall"),
+    /* 0o160 */ Some("Psmall"), Some("Qsmall"), Some("Rsmall"), Some("Ssmall"), Some("Tsmall"), Some("Usmall"), Some("Vsmall"), Some("Wsmall"),
+    /* 0o170 */ Some("Xsmall"), Some("Ysmall"), Some("Zsmall"), Some("colonmonetary"), Some("onefitted"), Some("rupiah"), Some("Tildesmall"), None,
+    /* 0o200 */ None, Some("asuperior"), Some("centsuperior"), None, None, None, None, Some("Aacutesmall"),
+    /* 0o210 */ Some("Agravesmall"), Some("Acircumflexsmall"), Some("Adieresissmall"), Some("Atildesmall"), Some("Aringsmall"), Some("Ccedillasmall"), Some("Eacutesmall"), Some("Egravesmall"),
+    /* 0o220 */ Some("Ecircumflexsmall"), Some("Edieresissmall"), Some("Iacutesmall"), Some("Igravesmall"), Some("Icircumflexsmall"), Some("Idieresissmall"), Some("Ntildesmall"), Some("Oacutesmall"),
+    /* 0o230 */ Some("Ogravesmall"), Some("Ocircumflexsmall"), Some("Odieresissmall"), Some("Otildesmall"), Some("Uacutesmall"), Some("Ugravesmall"), Some("Ucircumflexsmall"), Some("Udieresissmall"),
+    /* 0o240 */ None, Some("eightsuperior"), Some("fourinferior"), Some("threeinferior"), Some("sixinferior"), Some("eightinferior"), Some("seveninferior"), Some("Scaronsmall"),
+    /* 0o250 */ None, Some("centinferior"), Some("twoinferior"), None, Some("Dieresissmall"), None, Some("Caronsmall"), Some("osuperior"),
+    /* 0o260 */ Some("fiveinferior"), None, Some("commainferior"), Some("periodinferior"), Some("Yacutesmall"), None, Some("dollarinferior"), None,
+    /* 0o270 */ None, Some("Thornsmall"), None, Some("nineinferior"), Some("zeroinferior"), Some("Zcaronsmall"), Some("AEsmall"), Some("Oslashsmall"),
+    /* 0o300 */ Some("questiondownsmall"), Some("oneinferior"), Some("Lslashsmall"), None, None, None, None, None,
+    /* 0o310 */ None, Some("Cedillasmall"), None, None, None, None, None, Some("OEsmall"),
+    /* 0o320 */ Some("figuredash"), Some("hyphensuperior"), None, None, None, None, Some("exclamdownsmall"), None,
+    /* 0o330 */ Some("Ydieresissmall"), None, Some("onesuperior"), Some("twosuperior"), Some("threesuperior"), Some("foursuperior"), Some("fivesuperior"), Some("sixsuperior"),
+    /* 0o340 */ Some("sevensuperior"), Some("ninesuperior"), Some("zerosuperior"), None, Some("esuperior"), Some("rsuperior"), Some("tsuperior"), None,
+    /* 0o350 */ None, Some("isuperior"), Some("ssuperior"), Some("dsuperior"), None, None, None, None,
+    /* 0o360 */ None, Some("lsuperior"), Some("Ogoneksmall"), Some("Brevesmall"), Some("Macronsmall"), Some("bsuperior"), Some("nsuperior"), Some("msuperior"),
+    /* 0o370 */ Some("commasuperior"), Some("periodsuperior"), Some("Dotaccentsmall"), Some("Ringsmall"), None, None, None, None,
+];
+
+/// The built-in encoding of the standard `Symbol` font.
+#[rustfmt::skip]
+pub(crate) const SYMBOL_ENCODING: [Option<&str>; 256] = [
+    /* 0o000 */ None, None, None, None, None, None, None, None,
+    /* 0o010 */ None, None, None, None, None, None, None, None,
+    /* 0o020 */ None, None, None, None, None, None, None, None,
+    /* 0o030 */ None, None, None, None, None, None, None, None,
+    /* 0o040 */ Some("space"), Some("exclam"), Some("universal"), Some("numbersign"), Some("existential"), Some("percent"), Some("ampersand"), Some("suchthat"),
+    /* 0o050 */ Some("parenleft"), Some("parenright"), Some("asteriskmath"), Some("plus"), Some("comma"), Some("minus"), Some("period"), Some("slash"),
+    /* 0o060 */ Some("zero"), Some("one"), Some("two"), Some("three"), Some("four"), Some("five"), Some("six"), Some("seven"),
+    /* 0o070 */ Some("eight"), Some("nine"), Some("colon"), Some("semicolon"), Some("less"), Some("equal"), Some("greater"), Some("question"),
+    /* 0o100 */ Some("congruent"), Some("Alpha"), Some("Beta"), Some("Chi"), Some("Delta"), Some("Epsilon"), Some("Phi"), Some("Gamma"),
+    /* 0o110 */ Some("Eta"), Some("Iota"), Some("theta1"), Some("Kappa"), Some("Lambda"), Some("Mu"), Some("Nu"), Some("Omicron"),
+    /* 0o120 */ Some("Pi"), Some("Theta"), Some("Rho"), Some("Sigma"), Some("Tau"), Some("Upsilon"), Some("sigma1"), Some("Omega"),
+    /* 0o130 */ Some("Xi"), Some("Psi"), Some("Zeta"), Some("bracketleft"), Some("therefore"), Some("bracketright"), Some("perpendicular"), Some("underscore"),
+    /* 0o140 */ Some("radicalex"), Some("alpha"), Some("beta"), Some("chi"), Some("delta"), Some("epsilon"), Some("phi"), Some("gamma"),
+    /* 0o150 */ Some("eta"), Some("iota"), Some("phi1"), Some("kappa"), Some("lambda"), Some("mu"), Some("nu"), Some("omicron"),
+    /* 0o160 */ Some("pi"), Some("theta"), Some("rho"), Some("sigma"), Some("tau"), Some("upsilon"), Some("omega1"), Some("omega"),
+    /* 0o170 */ Some("xi"), Some("psi"), Some("zeta"), Some("braceleft"), Some("bar"), Some("braceright"), Some("similar"), None,
+    /* 0o200 */ None, None, None, None, None, None, None, None,
+    /* 0o210 */ None, None, None, None, None, None, None, None,
+    /* 0o220 */ None, None, None, None, None, None, None, None,
+    /* 0o230 */ None, None, None, None, None, None, None, None,
+    /* 0o240 */ Some("Euro"), Some("Upsilon1"), Some("minute"), Some("lessequal"), Some("fraction"), Some("infinity"), Some("florin"), Some("club"),
+    /* 0o250 */ Some("diamond"), Some("heart"), Some("spade"), Some("arrowboth"), Some("arrowleft"), Some("arrowup"), Some("arrowright"), Some("arrowdown"),
+    /* 0o260 */ Some("degree"), Some("plusminus"), Some("second"), Some("greaterequal"), Some("multiply"), Some("proportional"), Some("partialdiff"), Some("bullet"),
+    /* 0o270 */ Some("divide"), Some("notequal"), Some("equivalence"), Some("approxequal"), Some("ellipsis"), Some("arrowvertex"), Some("arrowhorizex"), Some("carriagereturn"),
+    /* 0o300 */ Some("aleph"), Some("Ifraktur"), Some("Rfraktur"), Some("weierstrass"), Some("circlemultiply"), Some("circleplus"), Some("emptyset"), Some("intersection"),
+    /* 0o310 */ Some("union"), Some("propersuperset"), Some("reflexsuperset"), Some("notsubset"), Some("propersubset"), Some("reflexsubset"), Some("element"), Some("notelement"),
+    /* 0o320 */ Some("angle"), Some("gradient"), Some("registerserif"), Some("copyrightserif"), Some("trademarkserif"), Some("product"), Some("radical"), Some("dotmath"),
+    /* 0o330 */ Some("logicalnot"), Some("logicaland"), Some("logicalor"), Some("arrowdblboth"), Some("arrowdblleft"), Some("arrowdblup"), Some("arrowdblright"), Some("arrowdbldown"),
+    /* 0o340 */ Some("lozenge"), Some("angleleft"), Some("registersans"), Some("copyrightsans"), Some("trademarksans"), Some("summation"), Some("parenlefttp"), Some("parenleftex"),
+    /* 0o350 */ Some("parenleftbt"), Some("bracketlefttp"), Some("bracketleftex"), Some("bracketleftbt"), Some("bracelefttp"), Some("braceleftmid"), Some("braceleftbt"), Some("braceex"),
+    /* 0o360 */ None, Some("angleright"), Some("integral"), Some("integraltp"), Some("integralex"), Some("integralbt"), Some("parenrighttp"), Some("parenrightex"),
+    /* 0o370 */ Some("parenrightbt"), Some("bracketrighttp"), Some("bracketrightex"), Some("bracketrightbt"), Some("bracerighttp"), Some("bracerightmid"), Some("bracerightbt"), None,
+];
+
+/// The built-in encoding of the standard `ZapfDingbats` font.
+#[rustfmt::skip]
+pub(crate) const ZAPF_DINGBATS_ENCODING: [Option<&str>; 256] = [
+    /* 0o000 */ None, None, None, None, None, None, None, None,
+    /* 0o010 */ None, None, None, None, None, None, None, None,
+    /* 0o020 */ None, None, None, None, None, None, None, None,
+    /* 0o030 */ None, None, None, None, None, None, None, None,
+    /* 0o040 */ Some("space"), Some("a1"), Some("a2"), Some("a202"), Some("a3"), Some("a4"), Some("a5"), Some("a119"),
+    /* 0o050 */ Some("a118"), Some("a117"), Some("a11"), Some("a12"), Some("a13"), Some("a14"), Some("a15"), Some("a16"),
+    /* 0o060 */ Some("a105"), Some("a17"), Some("a18"), Some("a19"), Some("a20"), Some("a21"), Some("a22"), Some("a23"),
+    /* 0o070 */ Some("a24"), Some("a25"), Some("a26"), Some("a27"), Some("a28"), Some("a6"), Some("a7"), Some("a8"),
+    /* 0o100 */ Some("a9"), Some("a10"), Some("a29"), Some("a30"), Some("a31"), Some("a32"), Some("a33"), Some("a34"),
+    /* 0o110 */ Some("a35"), Some("a36"), Some("a37"), Some("a38"), Some("a39"), Some("a40"), Some("a41"), Some("a42"),
+    /* 0o120 */ Some("a43"), Some("a44"), Some("a45"), Some("a46"), Some("a47"), Some("a48"), Some("a49"), Some("a50"),
+    /* 0o130 */ Some("a51"), Some("a52"), Some("a53"), Some("a54"), Some("a55"), Some("a56"), Some("a57"), Some("a58"),
+    /* 0o140 */ Some("a59"), Some("a60"), Some("a61"), Some("a62"), Some("a63"), Some("a64"), Some("a65"), Some("a66"),
+    /* 0o150 */ Some("a67"), Some("a68"), Some("a69"), Some("a70"), Some("a71"), Some("a72"), Some("a73"), Some("a74"),
+    /* 0o160 */ Some("a203"), Some("a75"), Some("a204"), Some("a76"), Some("a77"), Some("a78"), Some("a79"), Some("a81"),
+    /* 0o170 */ Some("a82"), Some("a83"), Some("a84"), Some("a97"), Some("a98"), Some("a99"), Some("a100"), None,
+    /* 0o200 */ Some("a89"), Some("a90"), Some("a93"), Some("a94"), Some("a91"), Some("a92"), Some("a205"), Some("a85"),
+    /* 0o210 */ Some("a206"), Some("a86"), Some("a87"), Some("a88"), Some("a95"), Some("a96"), None, None,
+    /* 0o220 */ None, None, None, None, None, None, None, None,
+    /* 0o230 */ None, None, None, None, None, None, None, None,
+    /* 0o240 */ None, Some("a101"), Some("a102"), Some("a103"), Some("a104"), Some("a106"), Some("a107"), Some("a108"),
+    /* 0o250 */ Some("a112"), Some("a111"), Some("a110"), Some("a109"), Some("a120"), Some("a121"), Some("a122"), Some("a123"),
+    /* 0o260 */ Some("a124"), Some("a125"), Some("a126"), Some("a127"), Some("a128"), Some("a129"), Some("a130"), Some("a131"),
+    /* 0o270 */ Some("a132"), Some("a133"), Some("a134"), Some("a135"), Some("a136"), Some("a137"), Some("a138"), Some("a139"),
+    /* 0o300 */ Some("a140"), Some("a141"), Some("a142"), Some("a143"), Some("a144"), Some("a145"), Some("a146"), Some("a147"),
+    /* 0o310 */ Some("a148"), Some("a149"), Some("a150"), Some("a151"), Some("a152"), Some("a153"), Some("a154"), Some("a155"),
+    /* 0o320 */ Some("a156"), Some("a157"), Some("a158"), Some("a159"), Some("a160"), Some("a161"), Some("a163"), Some("a164"),
+    /* 0o330 */ Some("a196"), Some("a165"), Some("a192"), Some("a166"), Some("a167"), Some("a168"), Some("a169"), Some("a170"),
+    /* 0o340 */ Some("a171"), Some("a172"), Some("a173"), Some("a162"), Some("a174"), Some("a175"), Some("a176"), Some("a177"),
+    /* 0o350 */ Some("a178"), Some("a179"), Some("a193"), Some("a180"), Some("a199"), Some("a181"), Some("a200"), Some("a182"),
+    /* 0o360 */ None, Some("a201"), Some("a183"), Some("a184"), Some("a197"), Some("a185"), Some("a194"), Some("a198"),
+    /* 0o370 */ Some("a186"), Some("a195"), Some("a187"), Some("a188"), Some("a189"), Some("a190"), Some("a191"), None,
+];
@@ -6,30 +6,40 @@ use pdf_object::{
     traits::{FromDictionary, FromStreamObject},
 };
 use thiserror::Error;
+use ttf_parser::{Face, GlyphId, PlatformId, cmap::Subtable};
 
 use crate::{
     character_map::{CMapError, CharacterMap},
-    font::FontEncoding,
-    font_descriptor::{FontDescriptor, FontDescriptorError},
+    font_descriptor::{FontDescriptor, FontDescriptorError, FontFileKind},
+    glyph_cache::GlyphCache,
+    glyph_list::glyph_name_to_unicode,
+    simple_font_encoding::{BaseEncoding, EncodingError, SimpleFontEncoding},
     simple_font_glyph_map::{SimpleFontGlyphWidthsMap, SimpleFontGlyphWidthsMapError},
 };
 
 /// Minimal, initial representation of a PDF TrueType (simple) font.
 ///
 /// Similar to Type1 parsing logic we capture dictionary level metadata
-/// required for basic width metrics and embedded program access. Complex
-/// encoding differences, glyph substitution, etc. are deferred.
+/// required for basic width metrics and embedded program access. Glyph
+/// substitution etc. are deferred.
 pub struct TrueTypeFont {
     /// PostScript base font name (e.g., /ArialMT)
     pub base_font: String,
     /// Optional font file containing embedded TrueType program.
     pub font_file: Option<StreamObject>,
+    /// The format of `font_file`: TrueType, or OpenType with either outline flavour.
+    pub font_file_kind: Option<FontFileKind>,
     /// Widths for character codes 0..=255 if provided via /Widths.
     pub widths: SimpleFontGlyphWidthsMap,
     /// A stream defining a CMap that maps character codes to Unicode values.
     pub cmap: Option<CharacterMap>,
-    /// Optional encoding information for simple fonts (Type1, TrueType).
-    pub encoding: Option<FontEncoding>,
+    /// Optional `/Encoding`, either a predefined encoding name or an encoding
+    /// dictionary with `/Differences`.
+    pub encoding: Option<SimpleFontEncoding>,
+    /// Whether the font descriptor flags the font as symbolic.
+    pub symbolic: bool,
+    /// Cache of glyph IDs and outlines built from the embedded program.
+    pub glyph_cache: GlyphCache,
 }
 
 #[derive(Debug, Error, PartialEq)]
@@ -42,6 +52,121 @@ pub enum TrueTypeFontError {
     CMapParse(#[from] CMapError),
     #[error("SimpleFontGlyphWidthsMap parsing error: {0}")]
     SimpleFontGlyphWidthsMapError(#[from] SimpleFontGlyphWidthsMapError),
+    #[error("Encoding parsing error: {0}")]
+    EncodingError(#[from] EncodingError),
+}
+
+impl TrueTypeFont {
+    /// Maps a single-byte character code to a glyph in the embedded TrueType program.
+    ///
+    /// Implements the lookup described in ISO 32000-1, 9.6.6.4. For nonsymbolic
+    /// fonts, and symbolic fonts with an `/Encoding`, the code is mapped to a glyph
+    /// name which is then looked up through the Unicode (3,1) cmap via the Adobe
+    /// Glyph List, the Macintosh (1,0) cmap via `MacRomanEncoding`, and finally the
+    /// `post` table. Otherwise the code is looked up directly in the symbol (3,0)
+    /// cmap, also trying the `0xF000`, `0xF100` and `0xF200` ranges, or the (1,0) cmap.
+    ///
+    /// # Parameters
+    ///
+    /// - `face`: The parsed embedded font program.
+    /// - `char_code`: The character code from a content stream.
+    ///
+    /// # Returns
+    ///
+    /// The glyph ID, or `None` if no lookup succeeds.
+    pub fn glyph_id(&self, face: &Face<'_>, char_code: u8) -> Option<GlyphId> {
+        sfnt_glyph_id(
+            face,
+            self.encoding.as_ref(),
+            BaseEncoding::Standard,
+            self.symbolic,
+            char_code,
+        )
+    }
+
+    /// Maps a character code to Unicode, preferring the `/ToUnicode` CMap over the
+    /// glyph name derived from the font's encoding.
+    pub fn to_unicode(&self, char_code: u8) -> Option<char> {
+        if let Some(ch) = self
+            .cmap
+            .as_ref()
+            .and_then(|cmap| cmap.get_mapping(u32::from(char_code)))
+        {
+            return Some(ch);
+        }
+
+        let builtin = (!self.symbolic).then_some(BaseEncoding::Standard);
+        match &self.encoding {
+            Some(encoding) => encoding.to_unicode(char_code, builtin),
+            None => SimpleFontEncoding::default().to_unicode(char_code, builtin),
+        }
+    }
+}
+
+/// Maps a single-byte character code of a simple font to a glyph in an embedded
+/// TrueType or OpenType program, as described for [`TrueTypeFont::glyph_id`].
+///
+/// `builtin` is the encoding assumed for nonsymbolic fonts when `/Encoding` names
+/// no base encoding.
+pub(crate) fn sfnt_glyph_id(
+    face: &Face<'_>,
+    encoding: Option<&SimpleFontEncoding>,
+    builtin: BaseEncoding,
+    symbolic: bool,
+    char_code: u8,
+) -> Option<GlyphId> {
+    let subtable = |platform_id: PlatformId, encoding_id: u16| -> Option<Subtable<'_>> {
+        face.tables().cmap.and_then(|cmap| {
+            cmap.subtables
+                .into_iter()
+                .find(|s| s.platform_id == platform_id && s.encoding_id == encoding_id)
+        })
+    };
+    let windows_unicode = subtable(PlatformId::Windows, 1);
+    let windows_symbol = subtable(PlatformId::Windows, 0);
+    let mac_roman = subtable(PlatformId::Macintosh, 0);
+
+    let encoding = encoding.filter(|e| e.is_specified());
+    if !symbolic || encoding.is_some() {
+        // Nonsymbolic fonts without an explicit encoding use their built-in encoding.
+        let glyph_name = match encoding {
+            Some(encoding) => encoding.glyph_name(char_code, Some(builtin)),
+            None => builtin.glyph_name(char_code),
+        };
+
+        if let Some(glyph_name) = glyph_name {
+            if let Some(table) = windows_unicode
+                && let Some(ch) = glyph_name_to_unicode(glyph_name)
+                && let Some(gid) = table.glyph_index(u32::from(ch))
+            {
+                return Some(gid);
+            }
+
+            if let Some(table) = mac_roman
+                && let Some(code) = BaseEncoding::MacRoman.code_for_glyph_name(glyph_name)
+                && let Some(gid) = table.glyph_index(u32::from(code))
+            {
+                return Some(gid);
+            }
+
+            if let Some(gid) = face.glyph_index_by_name(glyph_name) {
+                return Some(gid);
+            }
+        }
+    }
+
+    // Symbolic fonts, and nonsymbolic fonts whose glyph names could not be resolved,
+    // look the code up directly.
+    if let Some(table) = windows_symbol {
+        let found = [0x0000, 0xF000, 0xF100, 0xF200]
+            .into_iter()
+            .find_map(|range: u32| table.glyph_index(range | u32::from(char_code)));
+        if found.is_some() {
+            return found;
+        }
+    }
+
+    mac_roman.and_then(|table| table.glyph_index(u32::from(char_code)))
 }
 
 impl FromDictionary for TrueTypeFont {
@@ -59,13 +184,15 @@ impl FromDictionary for TrueTypeFont {
             .unwrap_or_default();
 
         // Descriptor is optional for the 14 standard fonts; attempt to resolve if present.
-        let font_file = if let Some(fd_obj) = dictionary.get("FontDescriptor") {
-            let fd_dict = objects.resolve_dictionary(fd_obj)?;
-            let FontDescriptor { font_file } = FontDescriptor::from_dictionary(fd_dict, objects)?;
-            font_file
-        } else {
-            None
-        };
+        let (font_file, font_file_kind, symbolic) =
+            if let Some(fd_obj) = dictionary.get("FontDescriptor") {
+                let fd_dict = objects.resolve_dictionary(fd_obj)?;
+                let descriptor = FontDescriptor::from_dictionary(fd_dict, objects)?;
+                let symbolic = descriptor.is_symbolic();
+                (descriptor.font_file, descriptor.font_file_kind, symbolic)
+            } else {
+                (None, None, false)
+            };
 
         // Attempt to resolve the optional `/ToUnicode` CMap stream, which maps character codes to Unicode.
         // If present, parse it into a `CharacterMap`. If not present, set cmap to None.
@@ -76,11 +203,11 @@ impl FromDictionary for TrueTypeFont {
             .map(CharacterMap::from_stream_object)
             .transpose()?;
 
-        // Attempt to extract the `/Encoding` entry, if present, and convert it to a `FontEncoding`.
+        // Encoding may be a name or a dictionary with `/BaseEncoding` and `/Differences`.
         let encoding = dictionary
             .get("Encoding")
-            .and_then(|v| v.as_str())
-            .map(FontEncoding::from);
+            .map(|obj| SimpleFontEncoding::from_object(obj, objects))
+            .transpose()?;
 
         // Read the `/Widths` entry.
         let widths = SimpleFontGlyphWidthsMap::from_dictionary(dictionary, objects)?;
@@ -88,9 +215,12 @@ impl FromDictionary for TrueTypeFont {
         Ok(Self {
             base_font,
             font_file,
+            font_file_kind,
             widths,
             cmap,
             encoding,
+            symbolic,
+            glyph_cache: GlyphCache::default(),
         })
     }
 }
@@ -9,10 +9,15 @@ use pdf_object::{
 use crate::{
     character_map::{CMapError, CharacterMap},
     font::FontEncoding,
-    font_descriptor::{FontDescriptor, FontDescriptorError},
-    glyph_widths_map::{GlyphWidthsMap, GlyphWidthsMapError},
+    font_descriptor::{FontDescriptor, FontDescriptorError, FontFileKind},
+    glyph_cache::GlyphCache,
+    glyph_widths_map::{GlyphWidthsMap, GlyphWidthsMapError, VerticalMetrics},
 };
 use thiserror::Error;
+use ttf_parser::{
+    Face, GlyphId, Tag,
+    gsub::{SingleSubstitution, SubstitutionSubtable},
+};
 
 /// Represents a PDF Type0 (composite) font, which references a CIDFont
 /// for glyph definitions.
@@ -22,20 +27,99 @@ pub struct Type0Font {
     pub default_width: f32,
     /// The CIDFont subtype (CIDFontType0 or CIDFontType2).
     pub subtype: CidFontSubType,
-    /// Optional font file containing embedded TrueType program.
+    /// Optional font file containing the embedded font program.
     pub font_file: Option<StreamObject>,
+    /// The format of `font_file`.
+    pub font_file_kind: Option<FontFileKind>,
     /// A map of individual glyph widths, overriding the default width for specific CIDs.
     /// This corresponds to the `/W` entry in the CIDFont dictionary.
     pub widths: Option<GlyphWidthsMap>,
+    /// The default vertical metrics `[vy w1y]` from the `/DW2` entry, used in vertical writing.
+    pub default_vertical_metrics: [f32; 2],
+    /// Vertical metrics for individual CIDs, from the `/W2` entry.
+    pub vertical_metrics: Option<GlyphWidthsMap<VerticalMetrics>>,
     /// A stream defining a CMap that maps character codes to Unicode values.
     pub cmap: Option<CharacterMap>,
     /// Optional encoding information for simple fonts (Type1, TrueType).
     pub encoding: Option<FontEncoding>,
+    /// Cache of glyph outlines built from the embedded program.
+    pub glyph_cache: GlyphCache,
 }
 
 impl Type0Font {
     /// Default value for the `/DW` entry, if not present in the font dictionary.
     const DEFAULT_WIDTH: f32 = 1000.0;
+    /// Default value for the `/DW2` entry, if not present in the font dictionary.
+    const DEFAULT_VERTICAL_METRICS: [f32; 2] = [880.0, -1000.0];
+
+    /// Returns `true` if the font's CMap selects vertical writing mode.
+    pub fn is_vertical(&self) -> bool {
+        matches!(self.encoding, Some(FontEncoding::IdentityVertical))
+    }
+
+    /// Returns the horizontal width (w0) of a CID, in glyph space units.
+    pub fn width(&self, cid: u16) -> f32 {
+        self.widths
+            .as_ref()
+            .and_then(|widths| widths.get_width(cid))
+            .unwrap_or(self.default_width)
+    }
+
+    /// Returns the vertical metrics of a CID, in glyph space units.
+    ///
+    /// CIDs without a `/W2` entry use the `/DW2` displacement, with a position
+    /// vector horizontally centred on the glyph's horizontal width.
+    pub fn vertical_metrics(&self, cid: u16) -> VerticalMetrics {
+        self.vertical_metrics
+            .as_ref()
+            .and_then(|metrics| metrics.get_width(cid))
+            .unwrap_or_else(|| {
+                let [vy, w1y] = self.default_vertical_metrics;
+                VerticalMetrics {
+                    w1y,
+                    vx: self.width(cid) / 2.0,
+                    vy,
+                }
+            })
+    }
+
+    /// Substitutes the vertical form of a glyph, using the font's `vrt2` or `vert`
+    /// OpenType features.
+    ///
+    /// Only single substitutions are applied. Glyphs without a vertical form are
+    /// returned unchanged.
+    pub fn vertical_glyph_id(face: &Face<'_>, glyph_id: GlyphId) -> GlyphId {
+        let Some(gsub) = face.tables().gsub else {
+            return glyph_id;
+        };
+        for tag in [b"vrt2", b"vert"] {
+            let Some(feature) = gsub.features.find(Tag::from_bytes(tag)) else {
+                continue;
+            };
+            for lookup_index in feature.lookup_indices {
+                let Some(lookup) = gsub.lookups.get(lookup_index) else {
+                    continue;
+                };
+                for subtable in lookup.subtables.into_iter::<SubstitutionSubtable>() {
+                    let SubstitutionSubtable::Single(single) = subtable else {
+                        continue;
+                    };
+                    let Some(index) = single.coverage().get(glyph_id) else {
+                        continue;
+                    };
+                    return match single {
+                        SingleSubstitution::Format1 { delta, .. } => {
+                            GlyphId(glyph_id.0.wrapping_add_signed(delta))
+                        }
+                        SingleSubstitution::Format2 { substitutes, .. } => {
+                            substitutes.get(index).unwrap_or(glyph_id)
+                        }
+                    };
+                }
+            }
+        }
+        glyph_id
+    }
 }
 
 /// CIDFont subtypes supported by the parser.
@@ -105,9 +189,9 @@ impl FromDictionary for Type0Font {
         // Resolve the CIDFont dictionary from the reference..
         let dictionary = objects.resolve_dictionary(cid_font_ref_val)?;
 
-        // Determine the CIDFont subtype from the dictionary. Currently, only CIDFontType2
-        // (TrueType Outlines) is supported.
+        // Determine the CIDFont subtype from the dictionary.
         let subtype = match dictionary.get_or_err("Subtype")?.try_str()?.as_ref() {
+            "CIDFontType0" => CidFontSubType::Type0,
             "CIDFontType2" => CidFontSubType::Type2,
             other => {
                 return Err(Type0FontError::UnsupportedCidFontSubtype {
@@ -130,22 +214,44 @@ impl FromDictionary for Type0Font {
             })
             .transpose()?;
 
+        let default_vertical_metrics = dictionary
+            .get("DW2")
+            .map(|dw2| objects.resolve_object(dw2)?.as_array_of::<f32, 2>())
+            .transpose()?
+            .unwrap_or(Self::DEFAULT_VERTICAL_METRICS);
+
+        let vertical_metrics = dictionary
+            .get("W2")
+            .map(|obj| -> Result<_, Type0FontError> {
+                let resolved_obj = objects.resolve_object(obj)?.try_array()?;
+                GlyphWidthsMap::from_w2_array(resolved_obj).map_err(Type0FontError::from)
+            })
+            .transpose()?;
+
         // FontDescriptor must be an indirect reference according to the PDF spec.
-        let font_file = if let Some(fd_obj) = dictionary.get("FontDescriptor") {
+        let (font_file, font_file_kind) = if let Some(fd_obj) = dictionary.get("FontDescriptor") {
             let fd_dict = objects.resolve_dictionary(fd_obj)?;
-            let FontDescriptor { font_file } = FontDescriptor::from_dictionary(fd_dict, objects)?;
-            font_file
+            let FontDescriptor {
+                font_file,
+                font_file_kind,
+                ..
+            } = FontDescriptor::from_dictionary(fd_dict, objects)?;
+            (font_file, font_file_kind)
         } else {
-            None
+            (None, None)
         };
 
         Ok(Self {
             default_width,
             subtype,
             font_file,
+            font_file_kind,
             widths: widths_map,
+            default_vertical_metrics,
+            vertical_metrics,
             cmap,
             encoding,
+            glyph_cache: GlyphCache::default(),
         })
     }
 }
@@ -1,29 +1,48 @@
+use std::cell::OnceCell;
+
 use pdf_object::{
-    dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
-    stream::StreamObject, traits::FromDictionary,
+    dictionary::Dictionary,
+    error::ObjectError,
+    object_collection::ObjectCollection,
+    stream::StreamObject,
+    traits::{FromDictionary, FromStreamObject},
 };
 use thiserror::Error;
+use ttf_parser::{Face, GlyphId, RawFace, Tag};
 
 use crate::{
-    font_descriptor::{FontDescriptor, FontDescriptorError},
+    cff::{error::CompactFontFormatError, program::CffFontProgram, reader::CffFontReader},
+    character_map::{CMapError, CharacterMap},
+    font_descriptor::{FontDescriptor, FontDescriptorError, FontFileKind},
+    glyph_cache::GlyphCache,
+    simple_font_encoding::{BaseEncoding, EncodingError, SimpleFontEncoding},
     simple_font_glyph_map::{SimpleFontGlyphWidthsMap, SimpleFontGlyphWidthsMapError},
+    true_type_font::sfnt_glyph_id,
 };
 
 /// Minimal, initial representation of a PDF Type1 font.
 ///
-/// This focuses on dictionary-level metadata needed by higher layers
-/// and defers actual glyph rendering or embedded program parsing.
+/// This focuses on dictionary-level metadata needed by higher layers. The
+/// embedded font program is parsed on first use and kept for the lifetime of
+/// the font.
 pub struct Type1Font {
     /// PostScript base font name (e.g., /Helvetica)
     pub base_font: String,
     /// A stream containing the font program.
     pub font_file: Option<StreamObject>,
-    /// Optional encoding name (e.g., /WinAnsiEncoding) or custom encoding via Differences
-    /// For now we capture only the base encoding name for quick wiring; differences can be
-    /// expanded later similarly to Type3.
-    pub base_encoding: Option<String>,
+    /// The format of `font_file`.
+    pub font_file_kind: Option<FontFileKind>,
+    /// Optional `/Encoding`, either a predefined encoding name or an encoding
+    /// dictionary with `/Differences`. When absent, the font's built-in encoding applies.
+    pub encoding: Option<SimpleFontEncoding>,
     /// Widths map for character codes.
     pub widths: SimpleFontGlyphWidthsMap,
+    /// A stream defining a CMap that maps character codes to Unicode values.
+    pub cmap: Option<CharacterMap>,
+    /// The parsed font program, populated on first use by [`Type1Font::program`].
+    program: OnceCell<CffFontProgram>,
+    /// Cache of glyph IDs and outlines built from the font program.
+    pub glyph_cache: GlyphCache,
 }
 
 /// Errors that can occur while parsing a Type1 font dictionary.
@@ -35,6 +54,108 @@ pub enum Type1FontError {
     FontDescriptor(#[from] FontDescriptorError),
     #[error("SimpleFontGlyphWidthsMap parsing error: {0}")]
     SimpleFontGlyphWidthsMapError(#[from] SimpleFontGlyphWidthsMapError),
+    #[error("Encoding parsing error: {0}")]
+    EncodingError(#[from] EncodingError),
+    #[error("CMap parsing error: {0}")]
+    CMapParse(#[from] CMapError),
+}
+
+impl Type1Font {
+    /// Returns the parsed embedded font program, parsing it on the first call.
+    ///
+    /// Bare CFF programs are parsed directly, and OpenType programs through their
+    /// `CFF ` table.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(None)` if the font has no embedded program, or an error if the program
+    /// is not CFF-based or could not be parsed. Parse failures are not cached.
+    pub fn program(&self) -> Result<Option<&CffFontProgram>, CompactFontFormatError> {
+        let (Some(font_file), Some(kind)) = (self.font_file.as_ref(), self.font_file_kind) else {
+            return Ok(None);
+        };
+        if let Some(program) = self.program.get() {
+            return Ok(Some(program));
+        }
+        let mut reader = match kind {
+            FontFileKind::Type1C | FontFileKind::CidFontType0C => {
+                CffFontReader::new(&font_file.data)
+            }
+            FontFileKind::OpenType => CffFontReader::from_open_type(&font_file.data)?,
+            FontFileKind::Type1 | FontFileKind::TrueType => {
+                return Err(CompactFontFormatError::UnsupportedFontProgram(kind));
+            }
+        };
+        let program = reader.read_font_program()?;
+        Ok(Some(self.program.get_or_init(|| program)))
+    }
+
+    /// Returns whether the embedded program has TrueType (`glyf`) outlines: a TrueType
+    /// program, or an OpenType one without a `CFF ` table. Such programs are drawn
+    /// through their sfnt tables rather than [`Type1Font::program`].
+    pub fn has_truetype_outlines(&self) -> bool {
+        let Some(font_file) = self.font_file.as_ref() else {
+            return false;
+        };
+        match self.font_file_kind {
+            Some(FontFileKind::TrueType) => true,
+            Some(FontFileKind::OpenType) => RawFace::parse(&font_file.data, 0)
+                .is_ok_and(|face| face.table(Tag::from_bytes(b"CFF ")).is_none()),
+            _ => false,
+        }
+    }
+
+    /// Maps a character code to a glyph in an embedded program with TrueType outlines.
+    ///
+    /// The code is resolved to a glyph name through `/Encoding` and the built-in
+    /// encoding, then looked up in the program's cmap and `post` tables as for
+    /// nonsymbolic TrueType fonts.
+    pub fn sfnt_glyph_id(&self, face: &Face<'_>, char_code: u8) -> Option<GlyphId> {
+        sfnt_glyph_id(
+            face,
+            self.encoding.as_ref(),
+            self.builtin_encoding(),
+            false,
+            char_code,
+        )
+    }
+
+    /// Returns the built-in encoding assumed for the font when `/Encoding` names no base encoding.
+    ///
+    /// This is exact for the standard 14 fonts. For embedded programs the font's own
+    /// encoding is authoritative; `StandardEncoding` is the conventional approximation.
+    pub fn builtin_encoding(&self) -> BaseEncoding {
+        BaseEncoding::builtin_for_base_font(&self.base_font)
+    }
+
+    /// Returns the glyph name selected by `/Encoding` for a character code.
+    ///
+    /// Returns `None` when the font dictionary has no `/Encoding` entry, or when neither its
+    /// `/Differences` nor its base encoding assign the code; the font program's built-in
+    /// encoding then decides which glyph is shown.
+    pub fn encoded_glyph_name(&self, char_code: u8) -> Option<&str> {
+        self.encoding
+            .as_ref()
+            .and_then(|encoding| encoding.glyph_name(char_code, None))
+    }
+
+    /// Maps a character code to Unicode, preferring the `/ToUnicode` CMap over the
+    /// glyph name derived from the font's encoding.
+    pub fn to_unicode(&self, char_code: u8) -> Option<char> {
+        if let Some(ch) = self
+            .cmap
+            .as_ref()
+            .and_then(|cmap| cmap.get_mapping(u32::from(char_code)))
+        {
+            return Some(ch);
+        }
+
+        let builtin = Some(self.builtin_encoding());
+        match &self.encoding {
+            Some(encoding) => encoding.to_unicode(char_code, builtin),
+            None => SimpleFontEncoding::default().to_unicode(char_code, builtin),
+        }
+    }
 }
 
 impl FromDictionary for Type1Font {
@@ -52,16 +173,31 @@ impl FromDictionary for Type1Font {
             .and_then(|v| v.as_str().map(|s| s.into_owned()))
             .unwrap_or_default();
 
-        // Read '/FontDescriptor’.
-        let fd = dictionary.get_or_err("FontDescriptor")?;
-        let FontDescriptor { font_file } =
-            FontDescriptor::from_dictionary(objects.resolve_dictionary(fd)?, objects)?;
+        // Read '/FontDescriptor’. It is optional for the 14 standard fonts.
+        let (font_file, font_file_kind) = if let Some(fd) = dictionary.get("FontDescriptor") {
+            let FontDescriptor {
+                font_file,
+                font_file_kind,
+                ..
+            } = FontDescriptor::from_dictionary(objects.resolve_dictionary(fd)?, objects)?;
+            (font_file, font_file_kind)
+        } else {
+            (None, None)
+        };
 
-        // Encoding may be a name or a dictionary. For initial support, record only base name.
-        let base_encoding = dictionary
+        // Encoding may be a name or a dictionary with `/BaseEncoding` and `/Differences`.
+        let encoding = dictionary
             .get("Encoding")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
+            .map(|obj| SimpleFontEncoding::from_object(obj, objects))
+            .transpose()?;
+
+        // Attempt to resolve the optional `/ToUnicode` CMap stream.
+        let cmap = dictionary
+            .get("ToUnicode")
+            .map(|obj| objects.resolve_stream(obj))
+            .transpose()?
+            .map(CharacterMap::from_stream_object)
+            .transpose()?;
 
         // Read the `/Widths` entry.
         let widths = SimpleFontGlyphWidthsMap::from_dictionary(dictionary, objects)?;
@@ -69,8 +205,12 @@ impl FromDictionary for Type1Font {
         Ok(Self {
             base_font,
             font_file,
-            base_encoding,
+            font_file_kind,
+            encoding,
             widths,
+            cmap,
+            program: OnceCell::new(),
+            glyph_cache: GlyphCache::default(),
         })
     }
 }
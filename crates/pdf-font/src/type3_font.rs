@@ -7,7 +7,11 @@ use pdf_object::{
 };
 use thiserror::Error;
 
-use crate::font_descriptor::FontDescriptorError;
+use crate::{
+    font_descriptor::FontDescriptorError,
+    simple_font_encoding::{EncodingError, FontEncodingDictionary},
+    simple_font_glyph_map::{SimpleFontGlyphWidthsMap, SimpleFontGlyphWidthsMapError},
+};
 
 /// Represents a Type 3 font in a PDF document.
 ///
@@ -25,6 +29,8 @@ pub struct Type3Font {
     pub char_procs: HashMap<String, Vec<PdfOperatorVariant>>,
     /// The font's encoding, specifying the mapping from character codes to glyph names.
     pub encoding: Option<FontEncodingDictionary>,
+    /// Glyph widths in glyph space, from `/FirstChar`, `/LastChar` and `/Widths`.
+    pub widths: SimpleFontGlyphWidthsMap,
 }
 
 /// Defines errors that can occur while parsing a Type 3 font object.
@@ -46,6 +52,8 @@ pub enum Type3FontError {
     ObjectError(#[from] ObjectError),
     #[error("Error parsing content stream operators: {0}")]
     ContentStreamError(#[from] PdfOperatorError),
+    #[error("SimpleFontGlyphWidthsMap parsing error: {0}")]
+    SimpleFontGlyphWidthsMapError(#[from] SimpleFontGlyphWidthsMapError),
     #[error("Duplicate character name '{name}' found in /CharProcs dictionary")]
     DuplicateCharProcName { name: String },
 }
@@ -115,86 +123,14 @@ impl FromDictionary for Type3Font {
             }
         }
 
+        // Read the `/Widths` entry, expressed in glyph space.
+        let widths = SimpleFontGlyphWidthsMap::from_dictionary(dictionary, objects)?;
+
         Ok(Type3Font {
             font_matrix,
             char_procs,
             encoding,
-        })
-    }
-}
-
-/// Defines errors that can occur while parsing a font encoding dictionary.
-#[derive(Debug, Error, Clone, PartialEq)]
-pub enum EncodingError {
-    #[error("Invalid entry in /Differences array: expected Integer or Name, found {found_type}")]
-    InvalidDifferencesEntryType { found_type: &'static str },
-    #[error("Invalid character code in /Differences array: expected 0-255, found {code}")]
-    InvalidDifferenceCharCode { code: i64 },
-    #[error(
-        "Character code overflow in /Differences array while incrementing after code {last_code}"
-    )]
-    DifferencesCodeOverflow { last_code: u8 },
-    #[error("{0}")]
-    ObjectError(#[from] ObjectError),
-}
-
-/// Represents a font encoding dictionary, used to map character codes to glyph names.
-#[derive(Debug)]
-pub struct FontEncodingDictionary {
-    /// The base encoding, which can be a predefined name like `/StandardEncoding`
-    /// or `/MacRomanEncoding`.
-    pub base_encoding: Option<String>,
-    /// A dictionary of differences from the base encoding.
-    /// Maps character codes (0-255) to glyph names.
-    pub differences: HashMap<u8, String>,
-}
-
-impl FromDictionary for FontEncodingDictionary {
-    const KEY: &'static str = "Encoding";
-    type ResultType = Self;
-    type ErrorType = EncodingError;
-
-    fn from_dictionary(
-        dictionary: &Dictionary,
-        _objects: &ObjectCollection, // No need for objects here based on spec
-    ) -> Result<Self::ResultType, Self::ErrorType> {
-        let base_encoding = dictionary
-            .get("BaseEncoding")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-
-        let mut differences = HashMap::new();
-
-        if let Some(diff_array) = dictionary.get("Differences") {
-            let mut current_code: u8 = 0;
-            for entry in diff_array.try_array()?.iter() {
-                match entry {
-                    ObjectVariant::Integer(code) => {
-                        let code_i64 = *code;
-                        current_code = u8::try_from(code_i64).map_err(|_| {
-                            EncodingError::InvalidDifferenceCharCode { code: code_i64 }
-                        })?;
-                    }
-                    ObjectVariant::Name(name) => {
-                        differences.insert(current_code, name.clone());
-                        current_code = current_code.checked_add(1).ok_or(
-                            EncodingError::DifferencesCodeOverflow {
-                                last_code: current_code,
-                            },
-                        )?;
-                    }
-                    _ => {
-                        return Err(EncodingError::InvalidDifferencesEntryType {
-                            found_type: entry.name(),
-                        });
-                    }
-                }
-            }
-        }
-
-        Ok(FontEncodingDictionary {
-            base_encoding,
-            differences,
+            widths,
         })
     }
 }
@@ -1,10 +1,10 @@
-use femtovg::{Canvas, Color, FillRule, Paint, Path};
+use femtovg::{Canvas, Color, FillRule, LineCap, LineJoin, Paint, Path};
 use pdf_canvas::{
-    canvas_backend::{CanvasBackend, Image, Shader},
+    canvas_backend::{CanvasBackend, GroupLayer, Image, Shader},
     recording_canvas::RecordingCanvas,
 };
 use pdf_graphics::{
-    BlendMode, MaskMode, PathFillType,
+    BlendMode, MaskMode, PathFillType, StrokeStyle,
     pdf_path::{PathVerb, PdfPath},
 };
 
@@ -73,15 +73,31 @@ impl CanvasBackend for CanvasImpl<'_> {
         &mut self,
         path: &PdfPath,
         color: pdf_graphics::color::Color,
-        line_width: f32,
+        style: &StrokeStyle,
         _shader: &Option<Shader>,
         _blend_mode: Option<pdf_graphics::BlendMode>,
     ) -> Result<(), Self::ErrorType> {
-        let path = to_femtovg_path(path);
+        // femtovg has no dash support, so dashes are cut out of the path up front.
+        let path = if style.is_dashed() {
+            to_femtovg_path(&path.dashed(&style.dash_array, style.dash_phase))
+        } else {
+            to_femtovg_path(path)
+        };
 
         let mut stroke_paint = Paint::color(Color::rgbf(color.r, color.g, color.b));
         stroke_paint.set_anti_alias(true);
-        stroke_paint.set_line_width(line_width);
+        stroke_paint.set_line_width(style.line_width);
+        stroke_paint.set_line_cap(match style.line_cap {
+            pdf_graphics::LineCap::Butt => LineCap::Butt,
+            pdf_graphics::LineCap::Round => LineCap::Round,
+            pdf_graphics::LineCap::Square => LineCap::Square,
+        });
+        stroke_paint.set_line_join(match style.line_join {
+            pdf_graphics::LineJoin::Miter => LineJoin::Miter,
+            pdf_graphics::LineJoin::Round => LineJoin::Round,
+            pdf_graphics::LineJoin::Bevel => LineJoin::Bevel,
+        });
+        stroke_paint.set_miter_limit(style.miter_limit);
         self.canvas.stroke_path(&path, &stroke_paint);
         Ok(())
     }
@@ -94,20 +110,21 @@ impl CanvasBackend for CanvasImpl<'_> {
         self.canvas.height() as f32
     }
 
-    fn set_clip_region(
+    fn push_clip_region(
         &mut self,
-        _path: &PdfPath,
-        mode: PathFillType,
+        path: &PdfPath,
+        _mode: PathFillType,
     ) -> Result<(), Self::ErrorType> {
-        // let mut path = to_femtovg_path(path);
-        match mode {
-            PathFillType::Winding => {}
-            PathFillType::EvenOdd => {}
-        }
+        // FemtoVG can only scissor to rectangles, so the clip is approximated by the
+        // path's bounds. Its state stack restores the previous scissor on pop.
+        self.canvas.save();
+        let [x0, y0, x1, y1] = path.bounds().unwrap_or_default();
+        self.canvas.intersect_scissor(x0, y0, x1 - x0, y1 - y0);
         Ok(())
     }
 
-    fn reset_clip(&mut self) -> Result<(), Self::ErrorType> {
+    fn pop_clip_region(&mut self) -> Result<(), Self::ErrorType> {
+        self.canvas.restore();
         Ok(())
     }
 
@@ -135,8 +152,18 @@ impl CanvasBackend for CanvasImpl<'_> {
         _mask: &RecordingCanvas,
         _transform: &pdf_graphics::transform::Transform,
         _mask_mode: MaskMode,
+        _transfer: Option<&[u8]>,
     ) -> Result<(), Self::ErrorType> {
         // Not yet implemented in femtovg backend
         Ok(())
     }
+
+    fn begin_transparency_group(&mut self, _layer: &GroupLayer) -> Result<(), Self::ErrorType> {
+        // FemtoVG has no offscreen layers, so group content is drawn directly.
+        Ok(())
+    }
+
+    fn end_transparency_group(&mut self) -> Result<(), Self::ErrorType> {
+        Ok(())
+    }
 }
@@ -1,11 +1,11 @@
 use std::borrow::Cow;
 
 use pdf_canvas::{
-    canvas_backend::{CanvasBackend, Image, Shader},
+    canvas_backend::{CanvasBackend, GroupLayer, Image, Shader},
     recording_canvas::RecordingCanvas,
 };
 use pdf_graphics::{
-    BlendMode, ImageEncoding, MaskMode, PathFillType,
+    BlendMode, ImageEncoding, LineCap, LineJoin, MaskMode, PathFillType, StrokeStyle,
     color::Color,
     pdf_path::{PathVerb, PdfPath},
     transform::Transform,
@@ -76,9 +76,9 @@ fn to_skia_a8_mask_image(
 /// Convert a PDF `Image` into a Skia `Image`.
 ///
 /// Supports JPEG-encoded data via Skia's decoder and raw pixel buffers with
-/// 1 (A8), 3 (RGB), or 4 (RGBA) components. When a soft mask is present, the
-/// alpha channel is combined with the mask to produce premultiplied output as
-/// needed. The resulting Skia image uses `AlphaType::Unpremul`.
+/// 1 (A8), 3 (RGB), or 4 (RGBA) bytes per pixel. When a soft mask is present, the
+/// alpha channel is combined with the mask. The resulting Skia image uses
+/// `AlphaType::Unpremul`.
 ///
 /// # Parameters
 ///
@@ -90,21 +90,62 @@ fn to_skia_a8_mask_image(
 /// - A Skia `Image` ready to be drawn with `draw_image`/`draw_image_rect`.
 fn to_skia_image(image: &Image<'_>) -> Result<skia_safe::Image, SkiaCanvasBackendError> {
     if image.encoding == ImageEncoding::Jpeg {
-        return skia_safe::Image::from_encoded(skia_safe::Data::new_copy(&image.data))
-            .ok_or(SkiaCanvasBackendError::ImageDecodeFailed { encoding: "jpeg" });
+        let decoded = skia_safe::Image::from_encoded(skia_safe::Data::new_copy(&image.data))
+            .ok_or(SkiaCanvasBackendError::ImageDecodeFailed { encoding: "jpeg" })?;
+        if image.mask.is_none() {
+            return Ok(decoded);
+        }
+        // Read the decoded pixels back so the mask can be combined with them.
+        let info = skia_safe::ImageInfo::new(
+            (decoded.width(), decoded.height()),
+            skia_safe::ColorType::RGBA8888,
+            skia_safe::AlphaType::Unpremul,
+            None,
+        );
+        let row_bytes = decoded.width() as usize * info.bytes_per_pixel();
+        let mut rgba = vec![0u8; row_bytes * decoded.height() as usize];
+        if !decoded.read_pixels(
+            &info,
+            rgba.as_mut_slice(),
+            row_bytes,
+            (0, 0),
+            skia_safe::image::CachingHint::Allow,
+        ) {
+            return Err(SkiaCanvasBackendError::ImageDecodeFailed { encoding: "jpeg" });
+        }
+        let (_, pixel_data) = get_skia_image_data(
+            &rgba,
+            4,
+            decoded.width() as usize,
+            decoded.height() as usize,
+            &image.mask,
+        )?;
+        return skia_safe::images::raster_from_data(&info, pixel_data, row_bytes).ok_or(
+            SkiaCanvasBackendError::RasterImageCreationFailed {
+                width: image.width,
+                height: image.height,
+            },
+        );
     }
 
     let (color_type, pixel_data) = get_skia_image_data(
         &image.data,
+        image.bytes_per_pixel.unwrap_or(4) as usize,
         image.width as usize,
         image.height as usize,
         &image.mask,
     )?;
 
+    // Alpha-only images have no colour to premultiply.
+    let alpha_type = if color_type == skia_safe::ColorType::Alpha8 {
+        skia_safe::AlphaType::Premul
+    } else {
+        skia_safe::AlphaType::Unpremul
+    };
     let image_info = skia_safe::ImageInfo::new(
         (image.width as i32, image.height as i32),
         color_type,
-        skia_safe::AlphaType::Unpremul,
+        alpha_type,
         None,
     );
 
@@ -182,6 +223,7 @@ fn to_skia_blend_mode(mode: BlendMode) -> skia_safe::BlendMode {
         BlendMode::Color => skia_safe::BlendMode::Color,
         BlendMode::Luminosity => skia_safe::BlendMode::Luminosity,
         BlendMode::DestinationIn => skia_safe::BlendMode::DstIn,
+        BlendMode::Source => skia_safe::BlendMode::Src,
     }
 }
 
@@ -192,8 +234,8 @@ fn to_skia_shader(shader: &Shader) -> Result<skia_safe::Shader, SkiaCanvasBacken
             y0,
             x1,
             y1,
-            positions,
-            colors,
+            ref positions,
+            ref colors,
         } => {
             let colors: Vec<skia_safe::Color> = colors
                 .iter()
@@ -203,7 +245,7 @@ fn to_skia_shader(shader: &Shader) -> Result<skia_safe::Shader, SkiaCanvasBacken
             skia_safe::Shader::linear_gradient(
                 (skia_safe::Point::new(x0, y0), skia_safe::Point::new(x1, y1)),
                 skia_safe::gradient_shader::GradientShaderColors::Colors(&colors),
-                Some(positions),
+                Some(&positions[..]),
                 skia_safe::TileMode::Clamp,
                 None,
                 None,
@@ -219,8 +261,8 @@ fn to_skia_shader(shader: &Shader) -> Result<skia_safe::Shader, SkiaCanvasBacken
             end_x,
             end_y,
             end_r,
-            positions,
-            colors,
+            ref positions,
+            ref colors,
             transform,
         } => {
             let colors: Vec<skia_safe::Color> = colors
@@ -240,7 +282,7 @@ fn to_skia_shader(shader: &Shader) -> Result<skia_safe::Shader, SkiaCanvasBacken
                 skia_safe::Point::new(end_x, end_y),
                 end_r,
                 skia_safe::gradient_shader::GradientShaderColors::Colors(&colors),
-                Some(positions),
+                Some(&positions[..]),
                 skia_safe::TileMode::Clamp,
                 None,
                 Some(&mat),
@@ -294,12 +336,12 @@ fn make_paint(
 /// Converts image data to Skia's expected format.
 fn get_skia_image_data(
     image: &[u8],
+    num_components: usize,
     width: usize,
     height: usize,
     smask: &Option<Cow<'_, [u8]>>,
 ) -> Result<(skia_safe::ColorType, skia_safe::Data), SkiaCanvasBackendError> {
     let num_pixels = width * height;
-    let num_components = image.len() / num_pixels;
     match num_components {
         // RGBA input. If no soft mask, we can pass-through without copying.
         4 if smask.is_none() => Ok((
@@ -372,7 +414,7 @@ impl CanvasBackend for SkiaCanvasBackend<'_> {
         &mut self,
         path: &PdfPath,
         color: Color,
-        line_width: f32,
+        style: &StrokeStyle,
         shader: &Option<Shader>,
         blend_mode: Option<BlendMode>,
     ) -> Result<(), Self::ErrorType> {
@@ -380,9 +422,28 @@ impl CanvasBackend for SkiaCanvasBackend<'_> {
         let mut paint = make_paint(
             color,
             skia_safe::paint::Style::Stroke,
-            Some(line_width),
+            Some(style.line_width),
             blend_mode,
         );
+        paint.set_stroke_cap(match style.line_cap {
+            LineCap::Butt => skia_safe::paint::Cap::Butt,
+            LineCap::Round => skia_safe::paint::Cap::Round,
+            LineCap::Square => skia_safe::paint::Cap::Square,
+        });
+        paint.set_stroke_join(match style.line_join {
+            LineJoin::Miter => skia_safe::paint::Join::Miter,
+            LineJoin::Round => skia_safe::paint::Join::Round,
+            LineJoin::Bevel => skia_safe::paint::Join::Bevel,
+        });
+        paint.set_stroke_miter(style.miter_limit);
+        if style.is_dashed() {
+            // Skia expects an even number of intervals; odd PDF arrays repeat once more.
+            let mut intervals = style.dash_array.clone();
+            if intervals.len() % 2 == 1 {
+                intervals.extend_from_within(..);
+            }
+            paint.set_path_effect(skia_safe::PathEffect::dash(&intervals, style.dash_phase));
+        }
         if let Some(shader_spec) = shader {
             let shader = to_skia_shader(shader_spec)?;
             paint.set_shader(shader);
@@ -400,7 +461,7 @@ impl CanvasBackend for SkiaCanvasBackend<'_> {
         self.height
     }
 
-    fn set_clip_region(
+    fn push_clip_region(
         &mut self,
         path: &PdfPath,
         mode: PathFillType,
@@ -414,7 +475,7 @@ impl CanvasBackend for SkiaCanvasBackend<'_> {
         Ok(())
     }
 
-    fn reset_clip(&mut self) -> Result<(), Self::ErrorType> {
+    fn pop_clip_region(&mut self) -> Result<(), Self::ErrorType> {
         self.surface.canvas().restore();
         Ok(())
     }
@@ -448,6 +509,27 @@ impl CanvasBackend for SkiaCanvasBackend<'_> {
         Ok(())
     }
 
+    fn begin_transparency_group(&mut self, layer: &GroupLayer) -> Result<(), Self::ErrorType> {
+        let mut paint = skia_safe::Paint::default();
+        paint.set_alpha_f(layer.alpha);
+        let blend_mode = layer.blend_mode.unwrap_or(BlendMode::Normal);
+        paint.set_blend_mode(to_skia_blend_mode(blend_mode));
+        let mut rec = skia_safe::canvas::SaveLayerRec::default().paint(&paint);
+        // A non-isolated group starts from a copy of the backdrop. The layer is then
+        // composited over that same backdrop, which only comes out right for the
+        // normal blend mode, so other groups are always treated as isolated.
+        if !layer.isolated && blend_mode == BlendMode::Normal {
+            rec = rec.flags(skia_safe::canvas::SaveLayerFlags::INIT_WITH_PREVIOUS);
+        }
+        self.surface.canvas().save_layer(&rec);
+        Ok(())
+    }
+
+    fn end_transparency_group(&mut self) -> Result<(), Self::ErrorType> {
+        self.surface.canvas().restore();
+        Ok(())
+    }
+
     fn begin_mask_layer(
         &mut self,
         mask: &RecordingCanvas,
@@ -462,7 +544,6 @@ impl CanvasBackend for SkiaCanvasBackend<'_> {
         self.surface
             .canvas()
             .clip_rect(rect, skia_safe::ClipOp::Intersect, None);
-        self.surface.canvas().clear(skia_safe::Color::WHITE);
         self.surface.canvas().save_layer(&Default::default());
         Ok(())
     }
@@ -472,6 +553,7 @@ impl CanvasBackend for SkiaCanvasBackend<'_> {
         mask: &RecordingCanvas,
         transform: &Transform,
         mask_mode: MaskMode,
+        transfer: Option<&[u8]>,
     ) -> Result<(), Self::ErrorType> {
         // Render mask into a temporary surface depending on the requested mask mode.
         // - Alpha: render directly into an A8 mask surface.
@@ -521,49 +603,56 @@ impl CanvasBackend for SkiaCanvasBackend<'_> {
         let mat = to_skia_matrix(transform);
         self.surface.canvas().concat(&mat);
 
-        if mask_mode == MaskMode::Luminosity {
-            // Convert RGBA to A8 using standard luminance coefficients.
-            let w = mask_image.width();
-            let h = mask_image.height();
-            let rgba_info = skia_safe::ImageInfo::new(
-                (w, h),
-                skia_safe::ColorType::RGBA8888,
-                skia_safe::AlphaType::Unpremul,
-                None,
-            );
-            let row_bytes_rgba = (w as usize) * rgba_info.bytes_per_pixel();
-            let mut rgba = vec![0u8; row_bytes_rgba * (h as usize)];
-            let ok = mask_image.read_pixels(
-                &rgba_info,
-                rgba.as_mut_slice(),
-                row_bytes_rgba,
-                (0, 0),
-                skia_safe::image::CachingHint::Allow,
-            );
-            if !ok {
-                return Err(SkiaCanvasBackendError::ImageDecodeFailed {
-                    encoding: "read_pixels",
-                });
-            }
-            // Number of bytes per pixel in RGBA format
-            const BYTES_PER_RGBA: usize = 4;
-
-            // Luminance coefficients per ITU-R BT.601 used to convert RGB to luma.
-            const LUMA_COEFF_R_BT601: f32 = 0.299;
-            const LUMA_COEFF_G_BT601: f32 = 0.587;
-            const LUMA_COEFF_B_BT601: f32 = 0.114;
-
-            // Compute luminance per pixel
-            let mut a8 = vec![0u8; (w as usize) * (h as usize)];
-            for (i, px) in rgba.chunks_exact(BYTES_PER_RGBA).enumerate() {
-                let r = px[0] as f32;
-                let g = px[1] as f32;
-                let b = px[2] as f32;
-                let y = LUMA_COEFF_R_BT601 * r + LUMA_COEFF_G_BT601 * g + LUMA_COEFF_B_BT601 * b;
-                a8[i] = y.clamp(0.0, 255.0) as u8;
+        // Mask values as one byte per pixel, when they have to be computed on the CPU:
+        // always for a luminosity mask, and for an alpha mask with a transfer function.
+        let (w, h) = (mask_image.width(), mask_image.height());
+        let values = match mask_mode {
+            MaskMode::Luminosity => {
+                // Convert RGBA to A8 using standard luminance coefficients.
+                let rgba_info = skia_safe::ImageInfo::new(
+                    (w, h),
+                    skia_safe::ColorType::RGBA8888,
+                    skia_safe::AlphaType::Unpremul,
+                    None,
+                );
+                let rgba = read_mask_pixels(&mask_image, &rgba_info)?;
+                // Number of bytes per pixel in RGBA format
+                const BYTES_PER_RGBA: usize = 4;
+
+                // Luminance coefficients per ITU-R BT.601 used to convert RGB to luma.
+                const LUMA_COEFF_R_BT601: f32 = 0.299;
+                const LUMA_COEFF_G_BT601: f32 = 0.587;
+                const LUMA_COEFF_B_BT601: f32 = 0.114;
+
+                // Compute luminance per pixel
+                let luma = rgba
+                    .chunks_exact(BYTES_PER_RGBA)
+                    .map(|px| {
+                        let r = px[0] as f32;
+                        let g = px[1] as f32;
+                        let b = px[2] as f32;
+                        let y = LUMA_COEFF_R_BT601 * r
+                            + LUMA_COEFF_G_BT601 * g
+                            + LUMA_COEFF_B_BT601 * b;
+                        y.clamp(0.0, 255.0) as u8
+                    })
+                    .collect();
+                Some(luma)
             }
+            MaskMode::Alpha if transfer.is_some() => Some(read_mask_pixels(
+                &mask_image,
+                &skia_safe::ImageInfo::new_a8((w, h)),
+            )?),
+            MaskMode::Alpha => None,
+        };
 
-            // Create an A8 image from the luminance buffer
+        if let Some(mut a8) = values {
+            if let Some(transfer) = transfer {
+                for value in a8.iter_mut() {
+                    *value = transfer.get(*value as usize).copied().unwrap_or(*value);
+                }
+            }
+            // Create an A8 image from the mask values
             let a8_info = skia_safe::ImageInfo::new_a8((w, h));
             let row_bytes_a8 = w as usize;
             if let Some(img) = skia_safe::images::raster_from_data(
@@ -591,3 +680,24 @@ impl CanvasBackend for SkiaCanvasBackend<'_> {
         Ok(())
     }
 }
+
+/// Reads the pixels of a rendered mask in the given format.
+fn read_mask_pixels(
+    image: &skia_safe::Image,
+    info: &skia_safe::ImageInfo,
+) -> Result<Vec<u8>, SkiaCanvasBackendError> {
+    let row_bytes = (image.width() as usize) * info.bytes_per_pixel();
+    let mut pixels = vec![0u8; row_bytes * (image.height() as usize)];
+    if !image.read_pixels(
+        info,
+        pixels.as_mut_slice(),
+        row_bytes,
+        (0, 0),
+        skia_safe::image::CachingHint::Allow,
+    ) {
+        return Err(SkiaCanvasBackendError::ImageDecodeFailed {
+            encoding: "read_pixels",
+        });
+    }
+    Ok(pixels)
+}
@@ -1,3 +1,41 @@
+/// CIE XYZ tristimulus values of the D65 white point, the reference white of sRGB.
+const D65_WHITE_POINT: [f32; 3] = [0.950_47, 1.0, 1.088_83];
+
+/// Bradford cone response matrix, used for chromatic adaptation.
+const BRADFORD: [[f32; 3]; 3] = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+/// Inverse of [`BRADFORD`].
+const BRADFORD_INVERSE: [[f32; 3]; 3] = [
+    [0.986_993, -0.147_054, 0.159_963],
+    [0.432_305, 0.518_360, 0.049_291],
+    [-0.008_529, 0.040_043, 0.968_487],
+];
+
+/// Maps D65-relative XYZ to linear sRGB.
+const XYZ_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [3.240_454, -1.537_139, -0.498_531],
+    [-0.969_266, 1.876_011, 0.041_556],
+    [0.055_643, -0.204_026, 1.057_225],
+];
+
+fn mul(matrix: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+/// Applies the sRGB transfer function to a linear channel value and clamps it to [0.0, 1.0].
+fn srgb_encode(linear: f32) -> f32 {
+    let encoded = if linear <= 0.003_130_8 {
+        12.92 * linear
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    };
+    encoded.clamp(0.0, 1.0)
+}
+
 /// Unpremultiplied color with RGBA channel
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Color {
@@ -53,6 +91,58 @@ impl Color {
         let b = (1.0 - y) * (1.0 - k);
         Self { r, g, b, a: 1.0 }
     }
+
+    /// Returns the sRGB color of CIE XYZ tristimulus values.
+    ///
+    /// The values are adapted from `white_point` to D65 with the Bradford
+    /// transform, so that the source white maps to sRGB white. Out-of-gamut
+    /// colors are clamped. Alpha defaults to 1.0 (opaque).
+    ///
+    /// # Arguments
+    ///
+    /// - `xyz`: The X, Y and Z values, with Y = 1.0 for the white point.
+    /// - `white_point`: The XYZ values of the diffuse white of the source.
+    pub fn from_xyz(xyz: [f32; 3], white_point: [f32; 3]) -> Self {
+        let source = mul(&BRADFORD, white_point);
+        let target = mul(&BRADFORD, D65_WHITE_POINT);
+        let cone = mul(&BRADFORD, xyz);
+        let adapted = [0, 1, 2].map(|i| {
+            if source[i].abs() > f32::EPSILON {
+                cone[i] * target[i] / source[i]
+            } else {
+                cone[i]
+            }
+        });
+        let [r, g, b] = mul(&XYZ_TO_LINEAR_SRGB, mul(&BRADFORD_INVERSE, adapted)).map(srgb_encode);
+        Self { r, g, b, a: 1.0 }
+    }
+
+    /// Returns the sRGB color of a CIE L*a*b* value.
+    ///
+    /// # Arguments
+    ///
+    /// - `l`: Lightness, between 0.0 and 100.0.
+    /// - `a`, `b`: The chromaticity components.
+    /// - `white_point`: The XYZ values of the diffuse white the value is relative to.
+    pub fn from_lab(l: f32, a: f32, b: f32, white_point: [f32; 3]) -> Self {
+        const DELTA: f32 = 6.0 / 29.0;
+        let inverse = |t: f32| {
+            if t >= DELTA {
+                t * t * t
+            } else {
+                3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+            }
+        };
+        let fy = (l + 16.0) / 116.0;
+        let fx = fy + a / 500.0;
+        let fz = fy - b / 200.0;
+        let xyz = [
+            white_point[0] * inverse(fx),
+            white_point[1] * inverse(fy),
+            white_point[2] * inverse(fz),
+        ];
+        Self::from_xyz(xyz, white_point)
+    }
 }
 
 #[cfg(test)]
@@ -83,6 +173,35 @@ mod tests {
         assert!(approx_eq(yellow.r, 1.0) && approx_eq(yellow.g, 1.0) && approx_eq(yellow.b, 0.0));
     }
 
+    #[test]
+    fn xyz_white_points_map_to_white() {
+        let d50 = [0.9642, 1.0, 0.8249];
+        for white_point in [d50, super::D65_WHITE_POINT] {
+            let white = Color::from_xyz(white_point, white_point);
+            assert!((white.r - 1.0).abs() < 1e-3, "{white:?}");
+            assert!((white.g - 1.0).abs() < 1e-3, "{white:?}");
+            assert!((white.b - 1.0).abs() < 1e-3, "{white:?}");
+        }
+
+        let black = Color::from_xyz([0.0, 0.0, 0.0], d50);
+        assert!(approx_eq(black.r, 0.0) && approx_eq(black.g, 0.0) && approx_eq(black.b, 0.0));
+    }
+
+    #[test]
+    fn lab_lightness_and_chroma() {
+        let d50 = [0.9642, 1.0, 0.8249];
+        let white = Color::from_lab(100.0, 0.0, 0.0, d50);
+        assert!((white.r - 1.0).abs() < 1e-3 && (white.b - 1.0).abs() < 1e-3);
+
+        // L* = 50 is a middle gray, about 0.47 in sRGB.
+        let gray = Color::from_lab(50.0, 0.0, 0.0, d50);
+        assert!((gray.r - 0.466).abs() < 5e-3, "{gray:?}");
+        assert!((gray.r - gray.g).abs() < 1e-3 && (gray.g - gray.b).abs() < 1e-3);
+
+        let red = Color::from_lab(50.0, 80.0, 60.0, d50);
+        assert!(red.r > 0.7 && red.g < 0.2 && red.b < 0.2, "{red:?}");
+    }
+
     #[test]
     fn gray_levels() {
         let black = Color::from_gray(0.0);
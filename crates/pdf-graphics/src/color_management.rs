@@ -0,0 +1,311 @@
+use std::fmt;
+#[cfg(feature = "color-management")]
+use std::sync::{Arc, OnceLock};
+
+#[cfg(feature = "color-management")]
+use moxcms::{ColorProfile, DataColorSpace, Layout, TransformF32Executor, TransformOptions};
+#[cfg(feature = "color-management")]
+use num_traits::ToPrimitive;
+use thiserror::Error;
+
+use crate::color::Color;
+
+/// Specifies how colours are mapped into the gamut of the output device.
+///
+/// Set by the `ri` operator and the `/RI` graphics state entry. It selects which
+/// transform of an ICC profile is used.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash)]
+pub enum RenderingIntent {
+    /// Reproduces colours exactly, including the tint of the source white.
+    AbsoluteColorimetric,
+    /// Reproduces colours exactly relative to the white points, clipping the rest.
+    #[default]
+    RelativeColorimetric,
+    /// Preserves saturation at the expense of accuracy.
+    Saturation,
+    /// Compresses the whole source gamut to preserve the relationships between colours.
+    Perceptual,
+}
+
+impl RenderingIntent {
+    /// Returns the intent with the given PDF name.
+    ///
+    /// Unrecognised names select `RelativeColorimetric`, as ISO 32000-1, 8.6.5.8 requires.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "AbsoluteColorimetric" => RenderingIntent::AbsoluteColorimetric,
+            "Saturation" => RenderingIntent::Saturation,
+            "Perceptual" => RenderingIntent::Perceptual,
+            _ => RenderingIntent::RelativeColorimetric,
+        }
+    }
+
+    #[cfg(feature = "color-management")]
+    fn index(self) -> usize {
+        match self {
+            RenderingIntent::AbsoluteColorimetric => 0,
+            RenderingIntent::RelativeColorimetric => 1,
+            RenderingIntent::Saturation => 2,
+            RenderingIntent::Perceptual => 3,
+        }
+    }
+}
+
+#[cfg(feature = "color-management")]
+impl From<RenderingIntent> for moxcms::RenderingIntent {
+    fn from(intent: RenderingIntent) -> Self {
+        match intent {
+            RenderingIntent::AbsoluteColorimetric => moxcms::RenderingIntent::AbsoluteColorimetric,
+            RenderingIntent::RelativeColorimetric => moxcms::RenderingIntent::RelativeColorimetric,
+            RenderingIntent::Saturation => moxcms::RenderingIntent::Saturation,
+            RenderingIntent::Perceptual => moxcms::RenderingIntent::Perceptual,
+        }
+    }
+}
+
+/// Errors that can occur while reading an ICC profile.
+#[derive(Debug, Error)]
+pub enum IccProfileError {
+    #[error("Colour management support is not compiled in")]
+    Unavailable,
+    #[error("Invalid ICC profile: {0}")]
+    InvalidProfile(String),
+    #[error("Unsupported ICC profile colour space '{0}'")]
+    UnsupportedColorSpace(String),
+}
+
+/// A parsed ICC profile of a gray, RGB or CMYK colour space.
+///
+/// Colours are converted to sRGB through a transform that is built on first use
+/// for each rendering intent and kept for the lifetime of the profile.
+pub struct IccProfile {
+    /// The number of colour components the profile describes.
+    components: usize,
+    #[cfg(feature = "color-management")]
+    profile: Box<ColorProfile>,
+    /// Transforms to sRGB, indexed by [`RenderingIntent::index`]. `None` records
+    /// that the profile does not support the intent.
+    #[cfg(feature = "color-management")]
+    transforms: [OnceLock<Option<Arc<TransformF32Executor>>>; 4],
+}
+
+impl fmt::Debug for IccProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IccProfile")
+            .field("components", &self.components)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IccProfile {
+    /// Parses an ICC profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a valid profile, if the profile's colour
+    /// space is not gray, RGB or CMYK, or if colour management is compiled out.
+    #[cfg(feature = "color-management")]
+    pub fn new(data: &[u8]) -> Result<Self, IccProfileError> {
+        let profile = ColorProfile::new_from_slice(data)
+            .map_err(|err| IccProfileError::InvalidProfile(err.to_string()))?;
+        let components = match profile.color_space {
+            DataColorSpace::Gray => 1,
+            DataColorSpace::Rgb => 3,
+            DataColorSpace::Cmyk => 4,
+            other => return Err(IccProfileError::UnsupportedColorSpace(format!("{other:?}"))),
+        };
+        Ok(Self {
+            components,
+            profile: Box::new(profile),
+            transforms: Default::default(),
+        })
+    }
+
+    /// Parses an ICC profile.
+    ///
+    /// # Errors
+    ///
+    /// Always fails, since colour management is compiled out.
+    #[cfg(not(feature = "color-management"))]
+    pub fn new(_data: &[u8]) -> Result<Self, IccProfileError> {
+        Err(IccProfileError::Unavailable)
+    }
+
+    /// Returns the number of colour components the profile describes.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// Returns the transform to sRGB for `intent`, building it on first use.
+    #[cfg(feature = "color-management")]
+    fn transform(&self, intent: RenderingIntent) -> Option<&TransformF32Executor> {
+        let layout = match self.components {
+            1 => Layout::Gray,
+            3 => Layout::Rgb,
+            _ => Layout::Rgba,
+        };
+        self.transforms
+            .get(intent.index())?
+            .get_or_init(|| {
+                let options = TransformOptions {
+                    rendering_intent: intent.into(),
+                    ..Default::default()
+                };
+                self.profile
+                    .create_transform_f32(layout, &ColorProfile::new_srgb(), Layout::Rgb, options)
+                    .ok()
+            })
+            .as_deref()
+    }
+
+    /// Converts colour components in the profile's space to an opaque sRGB [`Color`].
+    ///
+    /// # Parameters
+    ///
+    /// - `components`: The colour components, each between 0.0 and 1.0.
+    /// - `intent`: The rendering intent selecting the profile's transform.
+    ///
+    /// # Returns
+    ///
+    /// `None` if too few components are given or the profile cannot be used for
+    /// `intent`; callers then fall back to an alternate conversion.
+    pub fn to_color(&self, components: &[f32], intent: RenderingIntent) -> Option<Color> {
+        #[cfg(feature = "color-management")]
+        {
+            let source = components.get(..self.components)?;
+            let mut rgb = [0.0; 3];
+            self.transform(intent)?.transform(source, &mut rgb).ok()?;
+            let [r, g, b] = rgb.map(|channel| channel.clamp(0.0, 1.0));
+            Some(Color::from_rgb(r, g, b))
+        }
+        #[cfg(not(feature = "color-management"))]
+        {
+            let _ = (components, intent);
+            None
+        }
+    }
+
+    /// Converts 8-bit image samples in the profile's space to 8-bit sRGB.
+    ///
+    /// # Parameters
+    ///
+    /// - `samples`: Interleaved samples, [`IccProfile::components`] bytes per pixel.
+    /// - `intent`: The rendering intent selecting the profile's transform.
+    ///
+    /// # Returns
+    ///
+    /// Three bytes per pixel, or `None` if the sample count is not a whole number
+    /// of pixels or the profile cannot be used for `intent`.
+    pub fn transform_samples(&self, samples: &[u8], intent: RenderingIntent) -> Option<Vec<u8>> {
+        #[cfg(feature = "color-management")]
+        {
+            if samples.len().checked_rem(self.components)? != 0 {
+                return None;
+            }
+            let pixels = samples.len().checked_div(self.components)?;
+            let source: Vec<f32> = samples.iter().map(|&s| f32::from(s) / 255.0).collect();
+            let mut rgb = vec![0.0_f32; pixels.checked_mul(3)?];
+            self.transform(intent)?.transform(&source, &mut rgb).ok()?;
+            rgb.iter()
+                .map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round().to_u8())
+                .collect()
+        }
+        #[cfg(not(feature = "color-management"))]
+        {
+            let _ = (samples, intent);
+            None
+        }
+    }
+}
+
+#[cfg(all(test, feature = "color-management"))]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use moxcms::ColorProfile;
+
+    use super::{IccProfile, IccProfileError, RenderingIntent};
+
+    fn srgb_profile() -> IccProfile {
+        let data = ColorProfile::new_srgb()
+            .encode()
+            .expect("encodable profile");
+        IccProfile::new(&data).expect("valid profile")
+    }
+
+    #[test]
+    fn rendering_intent_names() {
+        assert_eq!(
+            RenderingIntent::from_name("Perceptual"),
+            RenderingIntent::Perceptual
+        );
+        assert_eq!(
+            RenderingIntent::from_name("AbsoluteColorimetric"),
+            RenderingIntent::AbsoluteColorimetric
+        );
+        assert_eq!(
+            RenderingIntent::from_name("Bogus"),
+            RenderingIntent::RelativeColorimetric
+        );
+    }
+
+    #[test]
+    fn srgb_profile_is_identity() {
+        let profile = srgb_profile();
+        assert_eq!(profile.components(), 3);
+        for intent in [
+            RenderingIntent::RelativeColorimetric,
+            RenderingIntent::Perceptual,
+        ] {
+            let color = profile
+                .to_color(&[1.0, 0.5, 0.0], intent)
+                .expect("supported intent");
+            assert!((color.r - 1.0).abs() < 0.01, "{color:?}");
+            assert!((color.g - 0.5).abs() < 0.01, "{color:?}");
+            assert!(color.b.abs() < 0.01, "{color:?}");
+        }
+        assert!(
+            profile
+                .to_color(&[1.0], RenderingIntent::default())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn gray_profile_to_srgb() {
+        let data = ColorProfile::new_gray_with_gamma(2.2)
+            .encode()
+            .expect("encodable profile");
+        let profile = IccProfile::new(&data).expect("valid profile");
+        assert_eq!(profile.components(), 1);
+        let white = profile
+            .to_color(&[1.0], RenderingIntent::default())
+            .expect("supported intent");
+        assert!(
+            white.r > 0.99 && white.g > 0.99 && white.b > 0.99,
+            "{white:?}"
+        );
+    }
+
+    #[test]
+    fn samples_to_srgb() {
+        let profile = srgb_profile();
+        let rgb = profile
+            .transform_samples(&[255, 0, 0, 0, 0, 255], RenderingIntent::default())
+            .expect("whole pixels");
+        assert_eq!(rgb.len(), 6);
+        assert!(rgb[0] > 250 && rgb[1] < 5 && rgb[5] > 250, "{rgb:?}");
+        assert!(
+            profile
+                .transform_samples(&[255, 0], RenderingIntent::default())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn invalid_profile() {
+        assert!(matches!(
+            IccProfile::new(b"not a profile"),
+            Err(IccProfileError::InvalidProfile(_))
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod color;
+pub mod color_management;
 pub mod pdf_path;
 pub mod point;
 pub mod transform;
@@ -26,6 +27,50 @@ pub enum LineJoin {
     Bevel = 2,
 }
 
+/// The parameters that control how a path is stroked.
+///
+/// Lengths are in the same space as the path being stroked, so callers
+/// working in device space scale them by the current transformation.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StrokeStyle {
+    /// The stroke width. Zero requests the thinnest line the device can render.
+    pub line_width: f32,
+    /// The shape at the ends of open subpaths and dashes.
+    pub line_cap: LineCap,
+    /// The shape at the corners of the path.
+    pub line_join: LineJoin,
+    /// The maximum ratio of miter length to line width before a miter join is beveled.
+    pub miter_limit: f32,
+    /// Alternating dash and gap lengths. An empty array strokes a solid line.
+    pub dash_array: Vec<f32>,
+    /// The distance into the dash pattern at which the pattern starts.
+    pub dash_phase: f32,
+}
+
+impl StrokeStyle {
+    /// Returns `true` if the style describes a usable dash pattern.
+    ///
+    /// Patterns with negative lengths or a zero total length stroke a solid line.
+    pub fn is_dashed(&self) -> bool {
+        !self.dash_array.is_empty()
+            && self.dash_array.iter().all(|len| *len >= 0.0)
+            && self.dash_array.iter().sum::<f32>() > 0.0
+    }
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            line_width: 1.0,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            miter_limit: 10.0,
+            dash_array: Vec::new(),
+            dash_phase: 0.0,
+        }
+    }
+}
+
 /// Represents the standard blend modes allowed in PDF for compositing graphics.
 ///
 /// Blend modes determine how colors from different layers are combined:
@@ -50,6 +95,9 @@ pub enum BlendMode {
     Color,
     Luminosity,
     DestinationIn,
+    /// Replaces the backdrop with the source where the source covers it. This is
+    /// not a PDF blend mode; it paints the objects of knockout groups.
+    Source,
 }
 
 /// Specifies how a path should be painted in PDF graphics operations.
@@ -74,6 +122,8 @@ pub enum PathFillType {
     EvenOdd,
 }
 
+/// Determines whether glyph outlines are filled, stroked, added to the clipping
+/// path, or a combination of these (the `Tr` operator).
 #[derive(Clone, Copy, Default, PartialEq, FromPrimitive, Debug)]
 pub enum TextRenderingMode {
     #[default]
@@ -87,6 +137,27 @@ pub enum TextRenderingMode {
     Clip,
 }
 
+impl TextRenderingMode {
+    /// Returns how glyph outlines are painted, or `None` if they are not painted.
+    pub fn paint_mode(self) -> Option<PaintMode> {
+        match self {
+            Self::Fill | Self::FillClip => Some(PaintMode::Fill),
+            Self::Stroke | Self::StrokeClip => Some(PaintMode::Stroke),
+            Self::FillAndStroke | Self::FillStrokeClip => Some(PaintMode::FillAndStroke),
+            Self::Invisible | Self::Clip => None,
+        }
+    }
+
+    /// Returns `true` if glyph outlines are added to the clipping path at the end
+    /// of the text object.
+    pub fn clips(self) -> bool {
+        matches!(
+            self,
+            Self::FillClip | Self::StrokeClip | Self::FillStrokeClip | Self::Clip
+        )
+    }
+}
+
 /// Specifies the mode for applying a soft mask in PDF graphics.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum MaskMode {
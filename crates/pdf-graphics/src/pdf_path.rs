@@ -1,4 +1,8 @@
 use crate::transform::Transform;
+use num_traits::FromPrimitive;
+
+/// The most dashes [`PdfPath::dashed`] breaks a path into before stroking it solid.
+const MAX_DASH_SEGMENTS: f32 = 100_000.0;
 
 /// Represents a single operation in a graphics path.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -116,11 +120,196 @@ impl PdfPath {
         self.verbs.push(PathVerb::QuadTo { x1, y1, x2, y2 });
     }
 
+    /// Appends all subpaths of `other` to this path.
+    pub fn append(&mut self, other: &PdfPath) {
+        self.verbs.extend_from_slice(&other.verbs);
+        if !other.verbs.is_empty() {
+            self.current_x = other.current_x;
+            self.current_y = other.current_y;
+        }
+    }
+
     /// Appends a `Close` verb to the path.
     pub fn close(&mut self) {
         self.verbs.push(PathVerb::Close);
     }
 
+    /// Returns the bounds `[min_x, min_y, max_x, max_y]` of the path's points,
+    /// including the control points of curves, or `None` if the path is empty.
+    pub fn bounds(&self) -> Option<[f32; 4]> {
+        let points = self.verbs.iter().flat_map(|verb| match *verb {
+            PathVerb::MoveTo { x, y } | PathVerb::LineTo { x, y } => vec![(x, y)],
+            PathVerb::CubicTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x3,
+                y3,
+            } => vec![(x1, y1), (x2, y2), (x3, y3)],
+            PathVerb::QuadTo { x1, y1, x2, y2 } => vec![(x1, y1), (x2, y2)],
+            PathVerb::Close => Vec::new(),
+        });
+        points.fold(None, |bounds, (x, y)| {
+            Some(match bounds {
+                None => [x, y, x, y],
+                Some([x0, y0, x1, y1]) => [x0.min(x), y0.min(y), x1.max(x), y1.max(y)],
+            })
+        })
+    }
+
+    /// Returns the path broken into the "on" segments of a dash pattern.
+    ///
+    /// Curves are flattened into line segments first. The pattern restarts at the
+    /// beginning of every subpath, as described in ISO 32000-1, 8.4.3.6, and zero
+    /// length dashes are kept as degenerate segments so that round or square caps
+    /// still render them as dots.
+    ///
+    /// # Arguments
+    ///
+    /// - `dash_array`: Alternating dash and gap lengths; its total must be positive.
+    /// - `dash_phase`: The distance into the pattern at which each subpath starts.
+    ///
+    /// # Returns
+    ///
+    /// The dashes, or the path unchanged, and so stroked solid, when the pattern is
+    /// invalid or would break the path into more than `MAX_DASH_SEGMENTS` dashes.
+    pub fn dashed(&self, dash_array: &[f32], dash_phase: f32) -> PdfPath {
+        let mut dashed = PdfPath::default();
+        let period: f32 = dash_array.iter().sum();
+        if dash_array.iter().any(|len| *len < 0.0) || !period.is_finite() || period <= 0.0 {
+            return self.clone();
+        }
+
+        let polylines = self.flatten();
+        let length: f32 = polylines
+            .iter()
+            .flat_map(|points| points.windows(2))
+            .map(|segment| (segment[1].0 - segment[0].0).hypot(segment[1].1 - segment[0].1))
+            .sum();
+        let elements = f32::from_usize(dash_array.len()).unwrap_or(f32::INFINITY);
+        let segment_count = (length / period + 1.0) * elements;
+        if segment_count.is_nan() || segment_count > MAX_DASH_SEGMENTS {
+            return self.clone();
+        }
+
+        for points in polylines {
+            let mut dash = DashCursor::new(dash_array, dash_phase.rem_euclid(period));
+            let mut pen_down = false;
+            for segment in points.windows(2) {
+                let [(x0, y0), (x1, y1)] = [segment[0], segment[1]];
+                let length = (x1 - x0).hypot(y1 - y0);
+                let point_at = |t: f32| (x0 + (x1 - x0) * t / length, y0 + (y1 - y0) * t / length);
+                let mut t = 0.0;
+                while t < length {
+                    while dash.remaining <= 0.0 {
+                        if dash.on && !pen_down {
+                            let (x, y) = point_at(t);
+                            dashed.move_to(x, y);
+                            dashed.line_to(x, y);
+                        }
+                        pen_down = false;
+                        dash.next();
+                    }
+                    let step = (length - t).min(dash.remaining);
+                    if dash.on {
+                        if !pen_down {
+                            let (x, y) = point_at(t);
+                            dashed.move_to(x, y);
+                            pen_down = true;
+                        }
+                        let (x, y) = point_at(t + step);
+                        dashed.line_to(x, y);
+                    }
+                    // Steps below the precision of `t` still move it on by one unit in
+                    // the last place, so that every iteration makes progress.
+                    t = (t + step).max(t.next_up());
+                    dash.remaining -= step;
+                }
+            }
+        }
+        dashed
+    }
+
+    /// Splits the path into polylines, one per subpath, with curves approximated
+    /// by line segments. Closed subpaths end with their starting point.
+    fn flatten(&self) -> Vec<Vec<(f32, f32)>> {
+        /// Number of line segments used per Bezier curve.
+        const CURVE_SEGMENTS: u16 = 16;
+
+        let mut polylines: Vec<Vec<(f32, f32)>> = Vec::new();
+        let mut current: Vec<(f32, f32)> = Vec::new();
+        let mut start = (0.0, 0.0);
+        let mut last = (0.0, 0.0);
+        for verb in &self.verbs {
+            match *verb {
+                PathVerb::MoveTo { x, y } => {
+                    if current.len() > 1 {
+                        polylines.push(std::mem::take(&mut current));
+                    }
+                    current = vec![(x, y)];
+                    start = (x, y);
+                    last = (x, y);
+                }
+                PathVerb::LineTo { x, y } => {
+                    if current.is_empty() {
+                        current.push(last);
+                    }
+                    current.push((x, y));
+                    last = (x, y);
+                }
+                PathVerb::CubicTo {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x3,
+                    y3,
+                } => {
+                    if current.is_empty() {
+                        current.push(last);
+                    }
+                    let (x0, y0) = last;
+                    for i in 1..=CURVE_SEGMENTS {
+                        let t = f32::from(i) / f32::from(CURVE_SEGMENTS);
+                        let mt = 1.0 - t;
+                        let (a, b, c, d) =
+                            (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+                        current.push((
+                            a * x0 + b * x1 + c * x2 + d * x3,
+                            a * y0 + b * y1 + c * y2 + d * y3,
+                        ));
+                    }
+                    last = (x3, y3);
+                }
+                PathVerb::QuadTo { x1, y1, x2, y2 } => {
+                    if current.is_empty() {
+                        current.push(last);
+                    }
+                    let (x0, y0) = last;
+                    for i in 1..=CURVE_SEGMENTS {
+                        let t = f32::from(i) / f32::from(CURVE_SEGMENTS);
+                        let mt = 1.0 - t;
+                        let (a, b, c) = (mt * mt, 2.0 * mt * t, t * t);
+                        current.push((a * x0 + b * x1 + c * x2, a * y0 + b * y1 + c * y2));
+                    }
+                    last = (x2, y2);
+                }
+                PathVerb::Close => {
+                    if !current.is_empty() {
+                        current.push(start);
+                        polylines.push(std::mem::take(&mut current));
+                    }
+                    last = start;
+                }
+            }
+        }
+        if current.len() > 1 {
+            polylines.push(current);
+        }
+        polylines
+    }
+
     pub fn transform(&mut self, transform: &Transform) {
         for verb in &mut self.verbs {
             match verb {
@@ -166,5 +355,143 @@ impl PdfPath {
     }
 }
 
+/// Tracks the position within a dash pattern while walking along a path.
+struct DashCursor<'a> {
+    dash_array: &'a [f32],
+    index: usize,
+    /// Whether the current element is a dash rather than a gap.
+    on: bool,
+    /// Length left in the current element.
+    remaining: f32,
+}
+
+impl<'a> DashCursor<'a> {
+    /// Creates a cursor positioned `phase` units into the pattern.
+    ///
+    /// Dash and gap states alternate independently of the element index, so
+    /// arrays of odd length repeat with the roles of their elements swapped.
+    fn new(dash_array: &'a [f32], mut phase: f32) -> Self {
+        let mut cursor = Self {
+            dash_array,
+            index: 0,
+            on: true,
+            remaining: dash_array[0],
+        };
+        while phase > 0.0 {
+            if phase < cursor.remaining {
+                cursor.remaining -= phase;
+                break;
+            }
+            phase -= cursor.remaining;
+            cursor.next();
+        }
+        cursor
+    }
+
+    /// Moves to the next element of the pattern.
+    fn next(&mut self) {
+        self.index = self
+            .index
+            .checked_add(1)
+            .filter(|index| *index < self.dash_array.len())
+            .unwrap_or(0);
+        self.on = !self.on;
+        self.remaining = self.dash_array[self.index];
+    }
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    fn line(length: f32) -> PdfPath {
+        let mut path = PdfPath::default();
+        path.move_to(0.0, 0.0);
+        path.line_to(length, 0.0);
+        path
+    }
+
+    fn dash_spans(path: &PdfPath) -> Vec<(f32, f32)> {
+        let mut spans = Vec::new();
+        let mut start = 0.0;
+        for verb in &path.verbs {
+            match *verb {
+                PathVerb::MoveTo { x, .. } => start = x,
+                PathVerb::LineTo { x, .. } => spans.push((start, x)),
+                _ => {}
+            }
+        }
+        spans
+    }
+
+    #[test]
+    fn dashes_alternate_along_a_line() {
+        let dashed = line(10.0).dashed(&[3.0, 2.0], 0.0);
+        assert_eq!(dash_spans(&dashed), vec![(0.0, 3.0), (5.0, 8.0)]);
+    }
+
+    #[test]
+    fn phase_offsets_the_pattern() {
+        let dashed = line(10.0).dashed(&[3.0, 2.0], 4.0);
+        assert_eq!(dash_spans(&dashed), vec![(1.0, 4.0), (6.0, 9.0)]);
+    }
+
+    #[test]
+    fn odd_arrays_swap_dash_and_gap() {
+        let dashed = line(10.0).dashed(&[2.0], 0.0);
+        assert_eq!(
+            dash_spans(&dashed),
+            vec![(0.0, 2.0), (4.0, 6.0), (8.0, 10.0)]
+        );
+    }
+
+    #[test]
+    fn zero_length_dashes_become_dots() {
+        let dashed = line(4.0).dashed(&[0.0, 2.0], 0.0);
+        assert_eq!(dash_spans(&dashed), vec![(0.0, 0.0), (2.0, 2.0)]);
+    }
+
+    #[test]
+    fn patterns_with_too_many_dashes_stroke_solid() {
+        assert_eq!(line(10.0).dashed(&[1e-9], 0.0).verbs, line(10.0).verbs);
+        assert_eq!(
+            line(f32::MAX).dashed(&[1.0], 0.0).verbs,
+            line(f32::MAX).verbs
+        );
+    }
+
+    #[test]
+    fn gaps_below_the_precision_of_the_position_end() {
+        let dashed = line(2000.0).dashed(&[1000.0, 1e-9], 500.0);
+        assert_eq!(dash_spans(&dashed).first(), Some(&(0.0, 500.0)));
+    }
+
+    #[test]
+    fn closed_subpaths_dash_the_closing_segment() {
+        let mut square = PdfPath::default();
+        square.move_to(0.0, 0.0);
+        square.line_to(4.0, 0.0);
+        square.line_to(4.0, 4.0);
+        square.line_to(0.0, 4.0);
+        square.close();
+        let dashed = square.dashed(&[16.0], 0.0);
+        let line_tos = dashed
+            .verbs
+            .iter()
+            .filter(|verb| matches!(verb, PathVerb::LineTo { .. }))
+            .count();
+        assert_eq!(line_tos, 4);
+        assert_eq!(dashed.current_point(), Some((0.0, 0.0)));
+    }
+
+    #[test]
+    fn bounds_cover_all_points_including_control_points() {
+        assert_eq!(PdfPath::default().bounds(), None);
+        let mut path = PdfPath::default();
+        path.move_to(1.0, 2.0);
+        path.line_to(-3.0, 4.0);
+        path.curve_to(0.0, 10.0, 5.0, -1.0, 2.0, 2.0);
+        path.close();
+        assert_eq!(path.bounds(), Some([-3.0, -1.0, 5.0, 10.0]));
+    }
+}
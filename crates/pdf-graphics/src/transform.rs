@@ -1,5 +1,5 @@
 /// An affine transformation matrix.
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Transform {
     pub sx: f32,
     pub kx: f32,
@@ -188,4 +188,33 @@ impl Transform {
         let new_y = self.ky * x + self.sy * y + self.ty;
         (new_x, new_y)
     }
+
+    /// Returns the factor by which the transform scales lengths on average.
+    ///
+    /// This is the square root of the absolute determinant, which maps user-space
+    /// distances such as line widths and dash lengths to device space. It is exact
+    /// for uniform scales and rotations.
+    pub fn mean_scale(&self) -> f32 {
+        (self.sx * self.sy - self.kx * self.ky).abs().sqrt()
+    }
+
+    /// Returns the inverse transform, or `None` if the transform is singular.
+    pub fn invert(&self) -> Option<Transform> {
+        let det = self.sx * self.sy - self.kx * self.ky;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let sx = self.sy / det;
+        let kx = -self.kx / det;
+        let ky = -self.ky / det;
+        let sy = self.sx / det;
+        Some(Transform {
+            sx,
+            kx,
+            ky,
+            sy,
+            tx: -(sx * self.tx + kx * self.ty),
+            ty: -(ky * self.tx + sy * self.ty),
+        })
+    }
 }
@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+
+use pdf_graphics::transform::Transform;
+use pdf_object::{
+    ObjectVariant, dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
+    traits::FromDictionary,
+};
+use thiserror::Error;
+
+use crate::{
+    form::{FormXObject, FormXObjectError},
+    optional_content::{OptionalContent, OptionalContentError},
+    xobject::XObjectReader,
+};
+
+/// Errors that can occur while reading an annotation dictionary.
+#[derive(Debug, Error)]
+pub enum AnnotationError {
+    #[error("Error parsing appearance stream: {0}")]
+    AppearanceError(#[from] FormXObjectError),
+    #[error("Error parsing annotation /OC: {0}")]
+    OptionalContentError(#[from] OptionalContentError),
+    #[error("{0}")]
+    ObjectError(#[from] ObjectError),
+}
+
+/// The annotation flags of the `/F` entry (ISO 32000-1, 12.5.3).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AnnotationFlags(pub u32);
+
+impl AnnotationFlags {
+    /// An annotation of an unknown type without an appearance is not shown.
+    pub const INVISIBLE: u32 = 1;
+    /// The annotation is neither shown nor printed.
+    pub const HIDDEN: u32 = 2;
+    /// The annotation is printed.
+    pub const PRINT: u32 = 4;
+    /// The appearance is not scaled with the page.
+    pub const NO_ZOOM: u32 = 8;
+    /// The appearance is not rotated with the page.
+    pub const NO_ROTATE: u32 = 16;
+    /// The annotation is not shown on screen, though it may be printed.
+    pub const NO_VIEW: u32 = 32;
+    /// The annotation cannot be interacted with.
+    pub const READ_ONLY: u32 = 64;
+
+    /// Returns whether all of the bits of `flag` are set.
+    pub fn contains(self, flag: u32) -> bool {
+        self.0 & flag == flag
+    }
+}
+
+/// Which of an annotation's appearances to use, depending on the pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AppearanceKind {
+    /// The appearance when the annotation is not interacted with (`/N`).
+    #[default]
+    Normal,
+    /// The appearance when the pointer is over the annotation (`/R`).
+    Rollover,
+    /// The appearance when the mouse button is pressed on the annotation (`/D`).
+    Down,
+}
+
+/// An entry of an appearance dictionary.
+pub enum AppearanceStream {
+    /// A single appearance.
+    Single(Box<FormXObject>),
+    /// One appearance per state, such as `/On` and `/Off` for a check box,
+    /// selected by the annotation's `/AS`.
+    States(HashMap<String, FormXObject>),
+}
+
+/// The border of an annotation, from `/BS` or the older `/Border` array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorderStyle {
+    /// The border width in default user space units. A width of 0 draws no border.
+    pub width: f32,
+    /// The dash array of a dashed border, or `None` for a solid one.
+    pub dash: Option<Vec<f32>>,
+}
+
+impl Default for BorderStyle {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            dash: None,
+        }
+    }
+}
+
+/// An annotation dictionary from a page's `/Annots` array (ISO 32000-1, 12.5).
+///
+/// An annotation without a normal appearance of its own is given one generated
+/// from its geometry and colours, if its subtype is one that can be generated
+/// (see [`Annotation::generate_appearance`]).
+pub struct Annotation {
+    /// The `/Subtype`, such as `Link`, `Highlight` or `Widget`.
+    pub subtype: String,
+    /// The `/Rect` the annotation occupies on the page, in default user space,
+    /// normalised so that the first corner is the lower left one.
+    pub rect: [f32; 4],
+    /// The `/F` flags.
+    pub flags: AnnotationFlags,
+    /// The normal appearance, from `/AP /N`.
+    pub normal_appearance: Option<AppearanceStream>,
+    /// The rollover appearance, from `/AP /R`.
+    pub rollover_appearance: Option<AppearanceStream>,
+    /// The down appearance, from `/AP /D`.
+    pub down_appearance: Option<AppearanceStream>,
+    /// The `/AS` appearance state, selecting among per-state appearances.
+    pub appearance_state: Option<String>,
+    /// The optional content the annotation belongs to, from `/OC`.
+    pub optional_content: Option<OptionalContent>,
+    /// The `/C` colour components. An empty array means the annotation has no
+    /// colour, while `None` means the entry is absent.
+    pub color: Option<Vec<f32>>,
+    /// The `/IC` interior colour components of shapes and lines.
+    pub interior_color: Option<Vec<f32>>,
+    /// The border, from `/BS` or `/Border`.
+    pub border: BorderStyle,
+    /// The `/CA` constant opacity.
+    pub opacity: f32,
+    /// The `/DA` default appearance string of free text annotations.
+    pub default_appearance: Option<String>,
+    /// The `/QuadPoints` of text markup annotations, eight numbers per quadrilateral.
+    pub quad_points: Vec<f32>,
+    /// The `/InkList` paths of ink annotations, as alternating x and y coordinates.
+    pub ink_list: Vec<Vec<f32>>,
+    /// The `/L` end points of a line annotation.
+    pub line: Option<[f32; 4]>,
+    /// The `/Vertices` of polygon and polyline annotations, as alternating x and
+    /// y coordinates.
+    pub vertices: Vec<f32>,
+    /// The `/Name` of the icon of a text annotation, such as `Note` or `Comment`.
+    pub icon: Option<String>,
+}
+
+impl FromDictionary for Annotation {
+    const KEY: &'static str = "Annot";
+    type ResultType = Self;
+    type ErrorType = AnnotationError;
+
+    fn from_dictionary(
+        dictionary: &Dictionary,
+        objects: &ObjectCollection,
+    ) -> Result<Self::ResultType, Self::ErrorType> {
+        let subtype = dictionary.get_or_err("Subtype")?.try_str()?.into_owned();
+        let [x0, y0, x1, y1] = objects
+            .resolve_object(dictionary.get_or_err("Rect")?)?
+            .as_array_of::<f32, 4>()?;
+        let rect = [x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)];
+        let flags = AnnotationFlags(
+            dictionary
+                .get("F")
+                .map_or(Ok(0), |flags| flags.as_number::<u32>())?,
+        );
+        let appearance_state = dictionary
+            .get("AS")
+            .and_then(|state| state.as_str().map(|state| state.into_owned()));
+
+        let appearances = dictionary
+            .get("AP")
+            .map(|ap| objects.resolve_dictionary(ap))
+            .transpose()?;
+        let appearance = |key: &str| -> Result<Option<AppearanceStream>, AnnotationError> {
+            match appearances.and_then(|ap| ap.get(key)) {
+                Some(stream) => read_appearance(stream, objects),
+                None => Ok(None),
+            }
+        };
+
+        let numbers = |key: &str| -> Result<Option<Vec<f32>>, AnnotationError> {
+            dictionary
+                .get(key)
+                .map(|value| Ok(objects.resolve_object(value)?.as_vec_of::<f32>()?))
+                .transpose()
+        };
+        let ink_list = match dictionary.get("InkList") {
+            Some(paths) => objects
+                .resolve_object(paths)?
+                .try_array()?
+                .iter()
+                .map(|path| Ok(objects.resolve_object(path)?.as_vec_of::<f32>()?))
+                .collect::<Result<Vec<_>, AnnotationError>>()?,
+            None => Vec::new(),
+        };
+        let line = dictionary
+            .get("L")
+            .map(|line| objects.resolve_object(line)?.as_array_of::<f32, 4>())
+            .transpose()?;
+
+        let mut annotation = Self {
+            subtype,
+            rect,
+            flags,
+            normal_appearance: appearance("N")?,
+            // Only the normal appearance is drawn, so the others are dropped when
+            // they cannot be read rather than losing the annotation.
+            rollover_appearance: appearance("R").ok().flatten(),
+            down_appearance: appearance("D").ok().flatten(),
+            appearance_state,
+            optional_content: OptionalContent::from_dictionary(dictionary, objects)?,
+            color: numbers("C")?,
+            interior_color: numbers("IC")?,
+            border: read_border(dictionary, objects)?,
+            opacity: dictionary
+                .get("CA")
+                .map_or(Ok(1.0), |opacity| opacity.as_number::<f32>())?,
+            default_appearance: dictionary
+                .get("DA")
+                .and_then(|da| da.as_str().map(|da| da.into_owned())),
+            quad_points: numbers("QuadPoints")?.unwrap_or_default(),
+            ink_list,
+            line,
+            vertices: numbers("Vertices")?.unwrap_or_default(),
+            icon: dictionary
+                .get("Name")
+                .and_then(|name| name.as_str().map(|name| name.into_owned())),
+        };
+        if annotation.normal_appearance.is_none() {
+            annotation.normal_appearance = annotation
+                .generate_appearance()
+                .map(|form| AppearanceStream::Single(Box::new(form)));
+        }
+        Ok(annotation)
+    }
+}
+
+/// Reads the border width and dash pattern from `/BS`, falling back to the
+/// `[h v width dash]` array of `/Border`.
+fn read_border(
+    dictionary: &Dictionary,
+    objects: &ObjectCollection,
+) -> Result<BorderStyle, AnnotationError> {
+    if let Some(style) = dictionary.get("BS") {
+        let style = objects.resolve_dictionary(style)?;
+        let width = style
+            .get("W")
+            .map_or(Ok(1.0), |width| width.as_number::<f32>())?;
+        let dashed = style
+            .get("S")
+            .and_then(|s| s.as_str())
+            .is_some_and(|s| s == "D");
+        let dash = match style.get("D") {
+            _ if !dashed => None,
+            Some(dash) => Some(objects.resolve_object(dash)?.as_vec_of::<f32>()?),
+            None => Some(vec![3.0]),
+        };
+        return Ok(BorderStyle { width, dash });
+    }
+
+    let Some(border) = dictionary.get("Border") else {
+        return Ok(BorderStyle::default());
+    };
+    let border = objects.resolve_object(border)?;
+    let items = border.try_array()?;
+    let width = items
+        .get(2)
+        .map_or(Ok(1.0), |width| width.as_number::<f32>())?;
+    let dash = items
+        .get(3)
+        .map(|dash| dash.as_vec_of::<f32>())
+        .transpose()?;
+    Ok(BorderStyle { width, dash })
+}
+
+/// Reads an entry of an appearance dictionary: a form XObject, or a dictionary of
+/// them keyed by appearance state.
+fn read_appearance(
+    object: &ObjectVariant,
+    objects: &ObjectCollection,
+) -> Result<Option<AppearanceStream>, AnnotationError> {
+    let read_form = |object: &ObjectVariant| -> Result<FormXObject, AnnotationError> {
+        let stream = objects.resolve_stream(object)?;
+        Ok(FormXObject::read_xobject(
+            &stream.dictionary,
+            &stream.data,
+            objects,
+        )?)
+    };
+
+    match objects.resolve_object(object)? {
+        ObjectVariant::Dictionary(states) => {
+            let mut forms = HashMap::new();
+            for (state, stream) in &states.dictionary {
+                forms.insert(state.to_owned(), read_form(stream)?);
+            }
+            Ok(Some(AppearanceStream::States(forms)))
+        }
+        ObjectVariant::Null => Ok(None),
+        _ => Ok(Some(AppearanceStream::Single(Box::new(read_form(object)?)))),
+    }
+}
+
+impl Annotation {
+    /// Returns whether the annotation is left out when the page is displayed.
+    pub fn is_hidden(&self) -> bool {
+        self.flags.contains(AnnotationFlags::HIDDEN)
+            || self.flags.contains(AnnotationFlags::NO_VIEW)
+    }
+
+    /// Returns the appearance of the given kind, in the annotation's current
+    /// appearance state.
+    ///
+    /// Rollover and down appearances fall back to the normal one.
+    pub fn appearance(&self, kind: AppearanceKind) -> Option<&FormXObject> {
+        let stream = match kind {
+            AppearanceKind::Normal => None,
+            AppearanceKind::Rollover => self.rollover_appearance.as_ref(),
+            AppearanceKind::Down => self.down_appearance.as_ref(),
+        }
+        .or(self.normal_appearance.as_ref())?;
+
+        match stream {
+            AppearanceStream::Single(form) => Some(form),
+            AppearanceStream::States(states) => states.get(self.appearance_state.as_deref()?),
+        }
+    }
+
+    /// Computes the matrix that maps the appearance's form space onto the
+    /// annotation's rectangle, after the form's own `/Matrix` (ISO 32000-1, 12.5.5).
+    ///
+    /// Returns `None` if the appearance or the rectangle is empty.
+    pub fn appearance_transform(&self, form: &FormXObject) -> Option<Transform> {
+        let [bx0, by0, bx1, by1] = form.bbox;
+        let matrix = form.matrix.unwrap_or_default();
+        let corners = [(bx0, by0), (bx1, by0), (bx1, by1), (bx0, by1)]
+            .map(|(x, y)| matrix.transform_point(x, y));
+        let (min_x, max_x) = corners
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &(x, _)| {
+                (min.min(x), max.max(x))
+            });
+        let (min_y, max_y) = corners
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &(_, y)| {
+                (min.min(y), max.max(y))
+            });
+
+        let [x0, y0, x1, y1] = self.rect;
+        let (width, height) = (max_x - min_x, max_y - min_y);
+        if width <= 0.0 || height <= 0.0 || x1 <= x0 || y1 <= y0 {
+            return None;
+        }
+        let sx = (x1 - x0) / width;
+        let sy = (y1 - y0) / height;
+        Some(Transform::from_row(
+            sx,
+            0.0,
+            0.0,
+            sy,
+            x0 - min_x * sx,
+            y0 - min_y * sy,
+        ))
+    }
+}
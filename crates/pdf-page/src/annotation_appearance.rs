@@ -0,0 +1,692 @@
+//! Appearance streams generated for annotations that do not have one.
+//!
+//! Many generators leave out `/AP` for markup annotations and rely on the viewer
+//! to draw them from their geometry and colours (ISO 32000-1, 12.5.6). The
+//! appearances here are built as ordinary content stream operators in default
+//! user space, so that they are drawn like any other form XObject.
+
+use std::collections::HashMap;
+
+use pdf_content_stream::{
+    color_operators::{
+        SetCMYKFill, SetCMYKStroke, SetGrayFill, SetGrayStroke, SetRGBFill, SetRGBStroke,
+    },
+    graphics_state_operators::{
+        ConcatMatrix, SetDashPattern, SetGraphicsStateFromDict, SetLineCapStyle, SetLineJoinStyle,
+        SetLineWidth,
+    },
+    path_operators::{ClosePath, CurveTo, LineTo, MoveTo, Rectangle},
+    path_paint_operators::{FillAndStrokePathNonZero, FillPathNonZero, StrokePath},
+    pdf_operator::PdfOperatorVariant,
+};
+use pdf_graphics::BlendMode;
+
+use crate::{
+    annotation::Annotation,
+    content_stream::ContentStream,
+    external_graphics_state::{ExternalGraphicsState, ExternalGraphicsStateKey},
+    form::FormXObject,
+    resources::Resources,
+};
+
+/// The name of the graphics state that carries the opacity and blend mode.
+const GRAPHICS_STATE: &str = "GS0";
+
+/// The default colour of highlights and text annotation icons.
+const YELLOW: [f32; 3] = [1.0, 1.0, 0.0];
+
+/// The control point distance of a cubic Bézier quarter ellipse, relative to the radius.
+const KAPPA: f32 = 0.552_284_8;
+
+/// The side of the square that text annotation icons are designed in.
+const ICON_SIZE: f32 = 20.0;
+
+/// The most segments a squiggly underline is drawn with, per quadrilateral.
+const MAX_SQUIGGLES: f32 = 1000.0;
+
+impl Annotation {
+    /// Generates a normal appearance from the annotation's geometry and colours.
+    ///
+    /// Supported are the text markup annotations (`Highlight`, `Underline`,
+    /// `StrikeOut` and `Squiggly`) from their `/QuadPoints`, `Ink` from its
+    /// `/InkList`, `Square`, `Circle`, `Line`, `Polygon` and `PolyLine`, the box of
+    /// `FreeText` with its `/DA` colour for the border, and the `Note` and
+    /// `Comment` icons of `Text` annotations. The text of free text annotations
+    /// is not drawn, since that would need a font program.
+    ///
+    /// When `/C` is absent, highlights and icons are yellow and lines are black;
+    /// an empty `/C` draws nothing. `/CA` is applied through a graphics state,
+    /// with highlights also multiplied onto the page.
+    ///
+    /// Returns `None` for other subtypes, or when there is nothing to draw.
+    pub fn generate_appearance(&self) -> Option<FormXObject> {
+        let mut operations = Vec::new();
+        let multiply = self.subtype == "Highlight";
+        let needs_state = multiply || self.opacity < 1.0;
+        if needs_state {
+            operations.push(PdfOperatorVariant::SetGraphicsStateFromDict(
+                SetGraphicsStateFromDict::new(GRAPHICS_STATE.to_string()),
+            ));
+        }
+        let start = operations.len();
+
+        match self.subtype.as_str() {
+            "Highlight" => self.highlight(&mut operations),
+            "Underline" | "StrikeOut" | "Squiggly" => self.text_decoration(&mut operations),
+            "Ink" => self.ink(&mut operations),
+            "Square" | "Circle" => self.shape(&mut operations),
+            "Line" => self.line(&mut operations),
+            "Polygon" | "PolyLine" => self.polygon(&mut operations),
+            "FreeText" => self.free_text(&mut operations),
+            "Text" => self.text_icon(&mut operations),
+            _ => {}
+        }
+        if operations.len() == start {
+            return None;
+        }
+
+        let resources = needs_state.then(|| {
+            let mut params = vec![
+                ExternalGraphicsStateKey::StrokingAlpha(self.opacity),
+                ExternalGraphicsStateKey::NonStrokingAlpha(self.opacity),
+            ];
+            if multiply {
+                params.push(ExternalGraphicsStateKey::BlendMode(vec![
+                    BlendMode::Multiply,
+                ]));
+            }
+            Resources {
+                external_graphics_states: HashMap::from([(
+                    GRAPHICS_STATE.to_string(),
+                    ExternalGraphicsState { params },
+                )]),
+                ..Resources::default()
+            }
+        });
+
+        Some(FormXObject {
+            bbox: self.rect,
+            matrix: None,
+            resources,
+            group: None,
+            optional_content: None,
+            content_stream: ContentStream { operations },
+        })
+    }
+
+    /// Returns the `/C` colour, or `default` if the entry is absent.
+    fn color_or<'c>(&'c self, default: &'c [f32]) -> &'c [f32] {
+        self.color.as_deref().unwrap_or(default)
+    }
+
+    /// Returns the quadrilaterals of `/QuadPoints`, ordered upper left, upper
+    /// right, lower left and lower right, as written by common generators.
+    fn quads(&self) -> impl Iterator<Item = [(f32, f32); 4]> + '_ {
+        self.quad_points
+            .chunks_exact(8)
+            .filter_map(|quad| match quad {
+                &[x1, y1, x2, y2, x3, y3, x4, y4] => Some([(x1, y1), (x2, y2), (x3, y3), (x4, y4)]),
+                _ => None,
+            })
+    }
+
+    fn highlight(&self, operations: &mut Vec<PdfOperatorVariant>) {
+        let Some(fill) = color_operator(self.color_or(&YELLOW), false) else {
+            return;
+        };
+        let start = operations.len();
+        for [upper_left, upper_right, lower_left, lower_right] in self.quads() {
+            polyline(
+                operations,
+                [upper_left, upper_right, lower_right, lower_left].into_iter(),
+            );
+            operations.push(PdfOperatorVariant::ClosePath(ClosePath));
+        }
+        if operations.len() > start {
+            operations.insert(start, fill);
+            operations.push(PdfOperatorVariant::FillPathNonZero(FillPathNonZero));
+        }
+    }
+
+    fn text_decoration(&self, operations: &mut Vec<PdfOperatorVariant>) {
+        let Some(stroke) = color_operator(self.color_or(&[0.0]), true) else {
+            return;
+        };
+        let start = operations.len();
+        for [upper_left, upper_right, lower_left, lower_right] in self.quads() {
+            let height = distance(upper_left, lower_left);
+            if height <= 0.0 {
+                continue;
+            }
+            // The unit vector from the bottom edge towards the top one.
+            let up = (
+                (upper_left.0 - lower_left.0) / height,
+                (upper_left.1 - lower_left.1) / height,
+            );
+            let thickness = height / 14.0;
+            operations.push(PdfOperatorVariant::SetLineWidth(SetLineWidth::new(
+                thickness,
+            )));
+
+            match self.subtype.as_str() {
+                "StrikeOut" => {
+                    let start = midpoint(upper_left, lower_left);
+                    let end = midpoint(upper_right, lower_right);
+                    polyline(operations, [start, end].into_iter());
+                }
+                "Squiggly" => {
+                    squiggle(operations, lower_left, lower_right, up, height / 6.0);
+                }
+                _ => {
+                    let offset = thickness / 2.0;
+                    let start = (lower_left.0 + up.0 * offset, lower_left.1 + up.1 * offset);
+                    let end = (lower_right.0 + up.0 * offset, lower_right.1 + up.1 * offset);
+                    polyline(operations, [start, end].into_iter());
+                }
+            }
+            operations.push(PdfOperatorVariant::StrokePath(StrokePath));
+        }
+        if operations.len() > start {
+            operations.insert(start, stroke);
+        }
+    }
+
+    fn ink(&self, operations: &mut Vec<PdfOperatorVariant>) {
+        if self.ink_list.iter().all(|path| path.len() < 4) {
+            return;
+        }
+        if !self.stroke_setup(operations) {
+            return;
+        }
+        round_joins(operations);
+        for path in &self.ink_list {
+            polyline(operations, points(path));
+        }
+        operations.push(PdfOperatorVariant::StrokePath(StrokePath));
+    }
+
+    fn shape(&self, operations: &mut Vec<PdfOperatorVariant>) {
+        let fill = self
+            .interior_color
+            .as_deref()
+            .and_then(|color| color_operator(color, false));
+        let stroke = self.stroke_setup(operations);
+        let filled = fill.is_some();
+        if !filled && !stroke {
+            return;
+        }
+        operations.extend(fill);
+
+        // The border is drawn inside the rectangle.
+        let inset = if stroke { self.border.width / 2.0 } else { 0.0 };
+        let [x0, y0, x1, y1] = self.rect;
+        let (x0, y0, x1, y1) = (x0 + inset, y0 + inset, x1 - inset, y1 - inset);
+        if self.subtype == "Circle" {
+            ellipse(operations, x0, y0, x1, y1);
+        } else {
+            operations.push(PdfOperatorVariant::Rectangle(Rectangle::new(
+                x0,
+                y0,
+                x1 - x0,
+                y1 - y0,
+            )));
+        }
+        paint(operations, filled, stroke);
+    }
+
+    fn line(&self, operations: &mut Vec<PdfOperatorVariant>) {
+        let Some([x1, y1, x2, y2]) = self.line else {
+            return;
+        };
+        if !self.stroke_setup(operations) {
+            return;
+        }
+        polyline(operations, [(x1, y1), (x2, y2)].into_iter());
+        operations.push(PdfOperatorVariant::StrokePath(StrokePath));
+    }
+
+    fn polygon(&self, operations: &mut Vec<PdfOperatorVariant>) {
+        if self.vertices.len() < 4 {
+            return;
+        }
+        let closed = self.subtype == "Polygon";
+        let fill = self
+            .interior_color
+            .as_deref()
+            .filter(|_| closed)
+            .and_then(|color| color_operator(color, false));
+        let stroke = self.stroke_setup(operations);
+        let filled = fill.is_some();
+        if !filled && !stroke {
+            return;
+        }
+        operations.extend(fill);
+        polyline(operations, points(&self.vertices));
+        if closed {
+            operations.push(PdfOperatorVariant::ClosePath(ClosePath));
+        }
+        paint(operations, filled, stroke);
+    }
+
+    fn free_text(&self, operations: &mut Vec<PdfOperatorVariant>) {
+        // `/C` is the background of a free text annotation, with the border in
+        // the text colour of `/DA`.
+        let fill = self
+            .color
+            .as_deref()
+            .and_then(|color| color_operator(color, false));
+        let text_color = self
+            .default_appearance
+            .as_deref()
+            .and_then(appearance_color)
+            .unwrap_or_else(|| vec![0.0]);
+        let stroke = self.border.width > 0.0 && self.stroke_style(operations, &text_color);
+        let filled = fill.is_some();
+        if !filled && !stroke {
+            return;
+        }
+        operations.extend(fill);
+
+        let inset = if stroke { self.border.width / 2.0 } else { 0.0 };
+        let [x0, y0, x1, y1] = self.rect;
+        operations.push(PdfOperatorVariant::Rectangle(Rectangle::new(
+            x0 + inset,
+            y0 + inset,
+            x1 - x0 - 2.0 * inset,
+            y1 - y0 - 2.0 * inset,
+        )));
+        paint(operations, filled, stroke);
+    }
+
+    fn text_icon(&self, operations: &mut Vec<PdfOperatorVariant>) {
+        let Some(fill) = color_operator(self.color_or(&YELLOW), false) else {
+            return;
+        };
+        let [x0, y0, x1, y1] = self.rect;
+        if x1 <= x0 || y1 <= y0 {
+            return;
+        }
+
+        // The icon is designed in a square and scaled onto the rectangle.
+        operations.push(PdfOperatorVariant::ConcatMatrix(ConcatMatrix::new([
+            (x1 - x0) / ICON_SIZE,
+            0.0,
+            0.0,
+            (y1 - y0) / ICON_SIZE,
+            x0,
+            y0,
+        ])));
+        operations.push(fill);
+        operations.push(PdfOperatorVariant::SetGrayStroke(SetGrayStroke::new(0.2)));
+        operations.push(PdfOperatorVariant::SetLineWidth(SetLineWidth::new(1.0)));
+        round_joins(operations);
+
+        let (outline, lines): (&[(f32, f32)], &[f32]) = match self.icon.as_deref() {
+            // A speech bubble.
+            Some("Comment") => (
+                &[
+                    (2.0, 18.0),
+                    (18.0, 18.0),
+                    (18.0, 7.0),
+                    (9.0, 7.0),
+                    (5.0, 2.5),
+                    (6.0, 7.0),
+                    (2.0, 7.0),
+                ],
+                &[14.5, 10.5],
+            ),
+            // A sheet of paper with a folded corner.
+            _ => (
+                &[
+                    (3.0, 1.0),
+                    (3.0, 19.0),
+                    (13.0, 19.0),
+                    (17.0, 15.0),
+                    (17.0, 1.0),
+                ],
+                &[12.0, 9.0, 6.0],
+            ),
+        };
+        polyline(operations, outline.iter().copied());
+        operations.push(PdfOperatorVariant::ClosePath(ClosePath));
+        operations.push(PdfOperatorVariant::FillAndStrokePathNonZero(
+            FillAndStrokePathNonZero,
+        ));
+        if self.icon.as_deref() != Some("Comment") {
+            polyline(
+                operations,
+                [(13.0, 19.0), (13.0, 15.0), (17.0, 15.0)].into_iter(),
+            );
+        }
+        for &y in lines {
+            polyline(operations, [(6.0, y), (14.0, y)].into_iter());
+        }
+        operations.push(PdfOperatorVariant::StrokePath(StrokePath));
+    }
+
+    /// Sets the stroke colour from `/C`, defaulting to black, and the border
+    /// width and dash pattern.
+    ///
+    /// Returns `false`, pushing nothing, if there is no border to stroke.
+    fn stroke_setup(&self, operations: &mut Vec<PdfOperatorVariant>) -> bool {
+        self.border.width > 0.0 && self.stroke_style(operations, self.color_or(&[0.0]))
+    }
+
+    fn stroke_style(&self, operations: &mut Vec<PdfOperatorVariant>, color: &[f32]) -> bool {
+        let Some(stroke) = color_operator(color, true) else {
+            return false;
+        };
+        operations.push(stroke);
+        operations.push(PdfOperatorVariant::SetLineWidth(SetLineWidth::new(
+            self.border.width,
+        )));
+        if let Some(dash) = &self.border.dash {
+            operations.push(PdfOperatorVariant::SetDashPattern(SetDashPattern::new(
+                dash.clone(),
+                0.0,
+            )));
+        }
+        true
+    }
+}
+
+/// Returns the operator that sets a gray, RGB or CMYK colour, depending on the
+/// number of components, or `None` for an empty or malformed colour.
+fn color_operator(components: &[f32], stroke: bool) -> Option<PdfOperatorVariant> {
+    Some(match (components, stroke) {
+        (&[gray], false) => PdfOperatorVariant::SetGrayFill(SetGrayFill::new(gray)),
+        (&[gray], true) => PdfOperatorVariant::SetGrayStroke(SetGrayStroke::new(gray)),
+        (&[r, g, b], false) => PdfOperatorVariant::SetRGBFill(SetRGBFill::new(r, g, b)),
+        (&[r, g, b], true) => PdfOperatorVariant::SetRGBStroke(SetRGBStroke::new(r, g, b)),
+        (&[c, m, y, k], false) => PdfOperatorVariant::SetCMYKFill(SetCMYKFill::new(c, m, y, k)),
+        (&[c, m, y, k], true) => PdfOperatorVariant::SetCMYKStroke(SetCMYKStroke::new(c, m, y, k)),
+        _ => return None,
+    })
+}
+
+/// Finds the components of the last `g`, `rg` or `k` colour in a `/DA` string.
+fn appearance_color(default_appearance: &str) -> Option<Vec<f32>> {
+    let mut operands = Vec::new();
+    let mut color = None;
+    for token in default_appearance.split_ascii_whitespace() {
+        if let Ok(number) = token.parse::<f32>() {
+            operands.push(number);
+            continue;
+        }
+        let count = match token {
+            "g" => 1,
+            "rg" => 3,
+            "k" => 4,
+            _ => 0,
+        };
+        if count > 0 {
+            let first = operands.len().checked_sub(count);
+            color = first
+                .and_then(|first| operands.get(first..))
+                .map(<[f32]>::to_vec);
+        }
+        operands.clear();
+    }
+    color
+}
+
+/// Pairs up alternating x and y coordinates.
+fn points(coordinates: &[f32]) -> impl Iterator<Item = (f32, f32)> + '_ {
+    coordinates.chunks_exact(2).filter_map(|point| match point {
+        &[x, y] => Some((x, y)),
+        _ => None,
+    })
+}
+
+/// Adds an open subpath through the points.
+fn polyline(operations: &mut Vec<PdfOperatorVariant>, points: impl Iterator<Item = (f32, f32)>) {
+    for (i, (x, y)) in points.enumerate() {
+        operations.push(if i == 0 {
+            PdfOperatorVariant::MoveTo(MoveTo::new(x, y))
+        } else {
+            PdfOperatorVariant::LineTo(LineTo::new(x, y))
+        });
+    }
+}
+
+/// Adds the ellipse inscribed in a rectangle, as four Bézier curves.
+fn ellipse(operations: &mut Vec<PdfOperatorVariant>, x0: f32, y0: f32, x1: f32, y1: f32) {
+    let (cx, cy) = ((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+    let (ox, oy) = ((x1 - x0) / 2.0 * KAPPA, (y1 - y0) / 2.0 * KAPPA);
+    operations.extend([
+        PdfOperatorVariant::MoveTo(MoveTo::new(x1, cy)),
+        PdfOperatorVariant::CurveTo(CurveTo::new(x1, cy + oy, cx + ox, y1, cx, y1)),
+        PdfOperatorVariant::CurveTo(CurveTo::new(cx - ox, y1, x0, cy + oy, x0, cy)),
+        PdfOperatorVariant::CurveTo(CurveTo::new(x0, cy - oy, cx - ox, y0, cx, y0)),
+        PdfOperatorVariant::CurveTo(CurveTo::new(cx + ox, y0, x1, cy - oy, x1, cy)),
+        PdfOperatorVariant::ClosePath(ClosePath),
+    ]);
+}
+
+/// Adds a zigzag along the bottom edge of a quadrilateral, rising by `amplitude`
+/// towards `up` every other step.
+fn squiggle(
+    operations: &mut Vec<PdfOperatorVariant>,
+    start: (f32, f32),
+    end: (f32, f32),
+    up: (f32, f32),
+    amplitude: f32,
+) {
+    let length = distance(start, end);
+    if length <= 0.0 || amplitude <= 0.0 {
+        return;
+    }
+    let direction = ((end.0 - start.0) / length, (end.1 - start.1) / length);
+    let step = amplitude.max(length / MAX_SQUIGGLES);
+
+    operations.push(PdfOperatorVariant::MoveTo(MoveTo::new(start.0, start.1)));
+    let mut along = step;
+    let mut raised = true;
+    while along < length {
+        let lift = if raised { amplitude } else { 0.0 };
+        operations.push(PdfOperatorVariant::LineTo(LineTo::new(
+            start.0 + direction.0 * along + up.0 * lift,
+            start.1 + direction.1 * along + up.1 * lift,
+        )));
+        along += step;
+        raised = !raised;
+    }
+
+    // The last segment ends on `end`, part of the way to the next peak or trough.
+    let fraction = (length - (along - step)) / step;
+    let lift = if raised {
+        amplitude * fraction
+    } else {
+        amplitude * (1.0 - fraction)
+    };
+    operations.push(PdfOperatorVariant::LineTo(LineTo::new(
+        end.0 + up.0 * lift,
+        end.1 + up.1 * lift,
+    )));
+}
+
+/// Paints the current path by filling, stroking or both.
+fn paint(operations: &mut Vec<PdfOperatorVariant>, fill: bool, stroke: bool) {
+    operations.push(match (fill, stroke) {
+        (true, true) => PdfOperatorVariant::FillAndStrokePathNonZero(FillAndStrokePathNonZero),
+        (true, false) => PdfOperatorVariant::FillPathNonZero(FillPathNonZero),
+        _ => PdfOperatorVariant::StrokePath(StrokePath),
+    });
+}
+
+/// Sets round line caps and joins, as used for freehand strokes and icons.
+fn round_joins(operations: &mut Vec<PdfOperatorVariant>) {
+    if let Ok(cap) = SetLineCapStyle::new(1) {
+        operations.push(PdfOperatorVariant::SetLineCapStyle(cap));
+    }
+    if let Ok(join) = SetLineJoinStyle::new(1) {
+        operations.push(PdfOperatorVariant::SetLineJoinStyle(join));
+    }
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+fn midpoint(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use pdf_object::{
+        ObjectVariant, dictionary::Dictionary, object_collection::ObjectCollection,
+        traits::FromDictionary,
+    };
+
+    use super::*;
+
+    fn numbers(values: &[f32]) -> ObjectVariant {
+        ObjectVariant::Array(
+            values
+                .iter()
+                .map(|&v| ObjectVariant::Real(f64::from(v)))
+                .collect(),
+        )
+    }
+
+    fn annotation(subtype: &str, entries: &[(&str, ObjectVariant)]) -> Annotation {
+        let mut dictionary: BTreeMap<_, _> = entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), Box::new(value.clone())))
+            .collect();
+        dictionary.insert(
+            "Subtype".to_string(),
+            Box::new(ObjectVariant::Name(subtype.to_string())),
+        );
+        dictionary
+            .entry("Rect".to_string())
+            .or_insert_with(|| Box::new(numbers(&[0.0, 0.0, 100.0, 20.0])));
+        Annotation::from_dictionary(&Dictionary::new(dictionary), &ObjectCollection::default())
+            .unwrap()
+    }
+
+    fn move_to(x: f32, y: f32) -> PdfOperatorVariant {
+        PdfOperatorVariant::MoveTo(MoveTo::new(x, y))
+    }
+
+    fn line_to(x: f32, y: f32) -> PdfOperatorVariant {
+        PdfOperatorVariant::LineTo(LineTo::new(x, y))
+    }
+
+    #[test]
+    fn appearance_color_takes_the_last_complete_color() {
+        assert_eq!(
+            appearance_color("/Helv 12 Tf 0 0 1 rg"),
+            Some(vec![0.0, 0.0, 1.0])
+        );
+        assert_eq!(
+            appearance_color("0.5 g /Helv 12 Tf 1 0 0 0 k"),
+            Some(vec![1.0, 0.0, 0.0, 0.0])
+        );
+        assert_eq!(appearance_color("0.5 g"), Some(vec![0.5]));
+        // Operands before another operator do not carry over.
+        assert_eq!(appearance_color("1 0 /Helv 12 Tf 0 rg"), None);
+        assert_eq!(appearance_color("/Helv 12 Tf"), None);
+        assert_eq!(appearance_color(""), None);
+    }
+
+    #[test]
+    fn color_operator_follows_the_number_of_components() {
+        assert_eq!(
+            color_operator(&[0.5], false),
+            Some(PdfOperatorVariant::SetGrayFill(SetGrayFill::new(0.5)))
+        );
+        assert_eq!(
+            color_operator(&[1.0, 0.0, 0.0], true),
+            Some(PdfOperatorVariant::SetRGBStroke(SetRGBStroke::new(
+                1.0, 0.0, 0.0
+            )))
+        );
+        assert_eq!(
+            color_operator(&[0.0, 1.0, 0.0, 0.0], false),
+            Some(PdfOperatorVariant::SetCMYKFill(SetCMYKFill::new(
+                0.0, 1.0, 0.0, 0.0
+            )))
+        );
+        for components in [&[][..], &[0.0, 1.0], &[0.0; 5]] {
+            assert_eq!(color_operator(components, false), None);
+            assert_eq!(color_operator(components, true), None);
+        }
+    }
+
+    #[test]
+    fn highlight_traces_quads_in_reading_order() {
+        // Upper left, upper right, lower left, lower right.
+        let quads = numbers(&[10.0, 20.0, 90.0, 20.0, 10.0, 5.0, 90.0, 5.0]);
+        let form = annotation("Highlight", &[("QuadPoints", quads)])
+            .generate_appearance()
+            .unwrap();
+        let operations = &form.content_stream.operations;
+        let path = operations
+            .iter()
+            .skip_while(|op| !matches!(op, PdfOperatorVariant::MoveTo(_)))
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            path,
+            [
+                move_to(10.0, 20.0),
+                line_to(90.0, 20.0),
+                line_to(90.0, 5.0),
+                line_to(10.0, 5.0),
+                PdfOperatorVariant::ClosePath(ClosePath),
+                PdfOperatorVariant::FillPathNonZero(FillPathNonZero),
+            ]
+        );
+        assert!(
+            operations.contains(&PdfOperatorVariant::SetRGBFill(SetRGBFill::new(
+                1.0, 1.0, 0.0
+            )))
+        );
+    }
+
+    #[test]
+    fn empty_color_draws_nothing() {
+        let quads = numbers(&[10.0, 20.0, 90.0, 20.0, 10.0, 5.0, 90.0, 5.0]);
+        for subtype in ["Highlight", "Underline", "Squiggly"] {
+            let annotation = annotation(
+                subtype,
+                &[("QuadPoints", quads.clone()), ("C", numbers(&[]))],
+            );
+            assert!(annotation.generate_appearance().is_none(), "{subtype}");
+        }
+        for subtype in ["Square", "Circle", "Text"] {
+            let annotation = annotation(subtype, &[("C", numbers(&[]))]);
+            assert!(annotation.generate_appearance().is_none(), "{subtype}");
+        }
+        // An interior colour is still filled without a border colour.
+        let filled = annotation("Square", &[("C", numbers(&[])), ("IC", numbers(&[1.0]))]);
+        assert!(filled.generate_appearance().is_some());
+    }
+
+    #[test]
+    fn squiggle_reaches_the_end_point() {
+        let mut operations = Vec::new();
+        squiggle(&mut operations, (0.0, 0.0), (9.0, 0.0), (0.0, 1.0), 2.0);
+        assert_eq!(
+            operations,
+            [
+                move_to(0.0, 0.0),
+                line_to(2.0, 2.0),
+                line_to(4.0, 0.0),
+                line_to(6.0, 2.0),
+                line_to(8.0, 0.0),
+                // Half way up to the next peak.
+                line_to(9.0, 1.0),
+            ]
+        );
+
+        let mut operations = Vec::new();
+        squiggle(&mut operations, (0.0, 0.0), (1.0, 0.0), (0.0, 1.0), 2.0);
+        assert_eq!(operations, [move_to(0.0, 0.0), line_to(1.0, 1.0)]);
+    }
+}
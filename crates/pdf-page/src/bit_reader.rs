@@ -0,0 +1,48 @@
+use num_traits::ToPrimitive;
+
+/// Reads packed big-endian values of up to 32 bits, as found in the data of mesh
+/// shadings and sampled functions.
+pub(crate) struct BitReader<'a> {
+    data: &'a [u8],
+    /// The position of the next bit to read.
+    bit: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, bit: 0 }
+    }
+
+    /// Reads an unsigned value of `bits` bits, or `None` at the end of the data.
+    pub(crate) fn read(&mut self, bits: u8) -> Option<u32> {
+        let mut value: u32 = 0;
+        for _ in 0..bits {
+            let byte = *self.data.get(self.bit.checked_div(8)?)?;
+            let shift = 7_usize.checked_sub(self.bit.checked_rem(8)?)?;
+            let bit = u32::from(byte.checked_shr(shift.to_u32()?)? & 1);
+            value = value.checked_shl(1).unwrap_or(0) | bit;
+            self.bit = self.bit.checked_add(1)?;
+        }
+        Some(value)
+    }
+
+    /// Reads a value of `bits` bits and maps it linearly onto `[min, max]`.
+    pub(crate) fn read_decoded(&mut self, bits: u8, [min, max]: [f32; 2]) -> Option<f32> {
+        let raw = f64::from(self.read(bits)?);
+        let largest = 2.0_f64.powi(i32::from(bits)) - 1.0;
+        let value = f64::from(min) + raw * f64::from(max - min) / largest;
+        value.to_f32()
+    }
+
+    /// Skips to the next byte boundary.
+    pub(crate) fn align(&mut self) {
+        if let Some(aligned) = self
+            .bit
+            .checked_add(7)
+            .and_then(|bit| bit.checked_div(8))
+            .and_then(|byte| byte.checked_mul(8))
+        {
+            self.bit = aligned;
+        }
+    }
+}
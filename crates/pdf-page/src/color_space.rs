@@ -1,32 +1,543 @@
-use pdf_object::ObjectVariant;
+use num_traits::ToPrimitive;
+use pdf_graphics::{
+    color::Color,
+    color_management::{IccProfile, RenderingIntent},
+};
+use pdf_object::{
+    ObjectVariant, dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
+};
 use thiserror::Error;
 
+use crate::function::{Function, FunctionInterpolationError, FunctionReadError};
+
+/// How deeply colour spaces may nest through base, alternate and underlying spaces.
+/// Deeper nesting can only come from reference cycles.
+const MAX_NESTING: u8 = 16;
+
+/// Errors that can occur while reading a colour space.
 #[derive(Debug, Error)]
 pub enum ColorSpaceReadError {
     #[error("Unsupported ColorSpace '{name}'")]
     UnsupportedColorSpace { name: String },
+    #[error("Invalid type for entry '{entry_name}': expected {expected_type}, found {found_type}")]
+    InvalidEntryType {
+        entry_name: &'static str,
+        expected_type: &'static str,
+        found_type: &'static str,
+    },
+    #[error("Colour space array '{family}' is missing its parameters")]
+    MissingParameters { family: String },
+    #[error("ICCBased colour space has {0} components, expected 1, 3 or 4")]
+    InvalidIccComponentCount(usize),
+    #[error("Indexed colour space /HiVal {0} is outside 0..=255")]
+    InvalidHiVal(i64),
+    #[error("Indexed colour space lookup table has {found} bytes, expected at least {expected}")]
+    LookupTooShort { expected: usize, found: usize },
+    #[error("Colour space is nested too deeply")]
+    TooDeeplyNested,
+    #[error("Error parsing tint transform: {0}")]
+    FunctionReadError(#[from] FunctionReadError),
+    #[error("{0}")]
+    ObjectError(#[from] ObjectError),
+}
+
+/// Errors that can occur while converting colour components to a [`Color`].
+#[derive(Debug, Error)]
+pub enum ColorSpaceError {
+    #[error("Colour space expects {expected} components, found {found}")]
+    MissingComponents { expected: usize, found: usize },
+    #[error("Pattern colour space has no underlying colour space for the given components")]
+    UncolouredPatternWithoutBase,
+    #[error("Tint transform error: {0}")]
+    TintTransform(#[from] FunctionInterpolationError),
+}
+
+/// Options that control how colour components are converted to sRGB.
+#[derive(Clone, Copy, Debug)]
+pub struct ColorConversion<'a> {
+    /// Whether ICC profiles are used. When off, ICC-based spaces use their alternate
+    /// spaces and the device spaces use the plain conversion formulas.
+    pub color_management: bool,
+    /// The rendering intent passed to ICC profile transforms.
+    pub rendering_intent: RenderingIntent,
+    /// The profile of the document's output intent, which characterises the device
+    /// colour space with the same number of components.
+    pub output_intent: Option<&'a IccProfile>,
 }
 
+impl Default for ColorConversion<'_> {
+    fn default() -> Self {
+        Self {
+            color_management: true,
+            rendering_intent: RenderingIntent::default(),
+            output_intent: None,
+        }
+    }
+}
+
+/// A PDF colour space, which determines how colour components are interpreted.
 #[derive(Debug)]
 pub enum ColorSpace {
     DeviceGray,
     DeviceRGB,
     DeviceCMYK,
-    Indexed,
-    Unsupported(String),
+    /// A single-component CIE-based space, interpreted as `A^gamma` scaled to the white point.
+    CalGray {
+        white_point: [f32; 3],
+        gamma: f32,
+    },
+    /// A three-component CIE-based space with per-component gammas and a linear
+    /// transformation to XYZ, stored column by column (`XA YA ZA XB YB ZB XC YC ZC`).
+    CalRGB {
+        white_point: [f32; 3],
+        gamma: [f32; 3],
+        matrix: [f32; 9],
+    },
+    /// CIE L*a*b*, with `range` bounding the a* and b* components.
+    Lab {
+        white_point: [f32; 3],
+        range: [f32; 4],
+    },
+    /// A space defined by an ICC profile. Colours are converted through `alternate`
+    /// when the profile is unusable or colour management is off.
+    IccBased {
+        components: usize,
+        alternate: Box<ColorSpace>,
+        range: Vec<f32>,
+        profile: Option<IccProfile>,
+    },
+    /// A colour map: a single index component selects one of `hival + 1` colours,
+    /// stored in `lookup` as consecutive base space components scaled to 0..=255.
+    Indexed {
+        base: Box<ColorSpace>,
+        hival: u8,
+        lookup: Vec<u8>,
+    },
+    /// A single colorant, converted through the tint transform to `alternate`.
+    Separation {
+        colorant: String,
+        alternate: Box<ColorSpace>,
+        tint_transform: Function,
+    },
+    /// Several colorants, converted through the tint transform to `alternate`.
+    DeviceN {
+        colorants: Vec<String>,
+        alternate: Box<ColorSpace>,
+        tint_transform: Function,
+    },
+    /// Pattern colour space. Uncoloured tiling patterns take their colour in `underlying`.
+    Pattern {
+        underlying: Option<Box<ColorSpace>>,
+    },
+}
+
+static DEVICE_GRAY: ColorSpace = ColorSpace::DeviceGray;
+static DEVICE_RGB: ColorSpace = ColorSpace::DeviceRGB;
+static DEVICE_CMYK: ColorSpace = ColorSpace::DeviceCMYK;
+static PATTERN: ColorSpace = ColorSpace::Pattern { underlying: None };
+
+/// Reads a required three-number `/WhitePoint` from a CIE-based colour space dictionary.
+fn read_white_point(dictionary: &Dictionary) -> Result<[f32; 3], ColorSpaceReadError> {
+    Ok(dictionary
+        .get_or_err("WhitePoint")?
+        .as_array_of::<f32, 3>()?)
+}
+
+/// Returns the `[min, max]` pair at component `index` of a flat range array.
+fn range_pair(range: &[f32], index: usize) -> Option<[f32; 2]> {
+    let start = index.checked_mul(2)?;
+    let pair = range.get(start..start.checked_add(2)?)?;
+    Some([pair[0], pair[1]])
 }
 
 impl ColorSpace {
-    pub fn from(obj: &ObjectVariant) -> Self {
-        if let Some(name) = obj.as_str() {
-            match name.as_ref() {
-                "DeviceGray" => ColorSpace::DeviceGray,
-                "DeviceRGB" => ColorSpace::DeviceRGB,
-                "DeviceCMYK" => ColorSpace::DeviceCMYK,
-                _ => ColorSpace::Unsupported(name.to_string()),
+    /// Returns `DeviceGray`, the initial colour space of the graphics state.
+    pub fn device_gray() -> &'static ColorSpace {
+        &DEVICE_GRAY
+    }
+
+    /// Returns the colour space for a family name that takes no parameters:
+    /// `DeviceGray`, `DeviceRGB`, `DeviceCMYK` or `Pattern`, along with the
+    /// abbreviations used by inline images.
+    pub fn from_family_name(name: &str) -> Option<&'static ColorSpace> {
+        match name {
+            "DeviceGray" | "G" => Some(&DEVICE_GRAY),
+            "DeviceRGB" | "RGB" => Some(&DEVICE_RGB),
+            "DeviceCMYK" | "CMYK" => Some(&DEVICE_CMYK),
+            "Pattern" => Some(&PATTERN),
+            _ => None,
+        }
+    }
+
+    /// Reads a colour space from a family name or a colour space array.
+    ///
+    /// # Parameters
+    ///
+    /// - `object`: The colour space object, or a reference to it.
+    /// - `objects`: The document's objects, used to resolve references.
+    pub fn from_object(
+        object: &ObjectVariant,
+        objects: &ObjectCollection,
+    ) -> Result<ColorSpace, ColorSpaceReadError> {
+        Self::read(object, objects, 0)
+    }
+
+    /// Reads a colour space nested `depth` levels inside other colour spaces.
+    fn read(
+        object: &ObjectVariant,
+        objects: &ObjectCollection,
+        depth: u8,
+    ) -> Result<ColorSpace, ColorSpaceReadError> {
+        if depth > MAX_NESTING {
+            return Err(ColorSpaceReadError::TooDeeplyNested);
+        }
+        let object = objects.resolve_object(object)?;
+        if let ObjectVariant::Name(name) = object {
+            return match name.as_str() {
+                "DeviceGray" | "G" => Ok(ColorSpace::DeviceGray),
+                "DeviceRGB" | "RGB" => Ok(ColorSpace::DeviceRGB),
+                "DeviceCMYK" | "CMYK" => Ok(ColorSpace::DeviceCMYK),
+                "Pattern" => Ok(ColorSpace::Pattern { underlying: None }),
+                _ => Err(ColorSpaceReadError::UnsupportedColorSpace { name: name.clone() }),
+            };
+        }
+
+        let array = object
+            .as_array()
+            .ok_or_else(|| ColorSpaceReadError::InvalidEntryType {
+                entry_name: "ColorSpace",
+                expected_type: "Name or Array",
+                found_type: object.name(),
+            })?;
+        let (family, params) = array.split_first().ok_or(ObjectError::InvalidArrayLength {
+            expected: 1,
+            found: 0,
+        })?;
+        let family = family.try_str()?;
+        let param = |index: usize| {
+            params
+                .get(index)
+                .ok_or_else(|| ColorSpaceReadError::MissingParameters {
+                    family: family.to_string(),
+                })
+        };
+
+        let nested = depth.saturating_add(1);
+        match family.as_ref() {
+            "DeviceGray" | "DeviceRGB" | "DeviceCMYK" | "G" | "RGB" | "CMYK" => {
+                Self::read(&array[0], objects, nested)
+            }
+            // CalCMYK was never specified beyond its name; PDF 1.4 readers treat it as DeviceCMYK.
+            "CalCMYK" => Ok(ColorSpace::DeviceCMYK),
+            "CalGray" => {
+                let dictionary = objects.resolve_dictionary(param(0)?)?;
+                let gamma = dictionary
+                    .get("Gamma")
+                    .map(|obj| obj.as_number::<f32>())
+                    .transpose()?
+                    .unwrap_or(1.0);
+                Ok(ColorSpace::CalGray {
+                    white_point: read_white_point(dictionary)?,
+                    gamma,
+                })
+            }
+            "CalRGB" => {
+                let dictionary = objects.resolve_dictionary(param(0)?)?;
+                let gamma = dictionary
+                    .get("Gamma")
+                    .map(|obj| obj.as_array_of::<f32, 3>())
+                    .transpose()?
+                    .unwrap_or([1.0; 3]);
+                let matrix = dictionary
+                    .get("Matrix")
+                    .map(|obj| obj.as_array_of::<f32, 9>())
+                    .transpose()?
+                    .unwrap_or([1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+                Ok(ColorSpace::CalRGB {
+                    white_point: read_white_point(dictionary)?,
+                    gamma,
+                    matrix,
+                })
+            }
+            "Lab" => {
+                let dictionary = objects.resolve_dictionary(param(0)?)?;
+                let range = dictionary
+                    .get("Range")
+                    .map(|obj| obj.as_array_of::<f32, 4>())
+                    .transpose()?
+                    .unwrap_or([-100.0, 100.0, -100.0, 100.0]);
+                Ok(ColorSpace::Lab {
+                    white_point: read_white_point(dictionary)?,
+                    range,
+                })
             }
-        } else {
-            ColorSpace::Unsupported("<non-name color space>".to_string())
+            "ICCBased" => {
+                let stream = objects.resolve_stream(param(0)?)?;
+                let components = stream.dictionary.get_or_err("N")?.as_number::<usize>()?;
+                // Without `/Alternate`, the device space with the same number of components is used.
+                let device = match components {
+                    1 => ColorSpace::DeviceGray,
+                    3 => ColorSpace::DeviceRGB,
+                    4 => ColorSpace::DeviceCMYK,
+                    n => return Err(ColorSpaceReadError::InvalidIccComponentCount(n)),
+                };
+                let alternate = match stream.dictionary.get("Alternate") {
+                    Some(alternate) => Self::read(alternate, objects, nested)?,
+                    None => device,
+                };
+                let range = match stream.dictionary.get("Range") {
+                    Some(range) => range.as_vec_of::<f32>()?,
+                    None => [0.0, 1.0].repeat(components),
+                };
+                Ok(ColorSpace::IccBased {
+                    components,
+                    alternate: Box::new(alternate),
+                    range,
+                    // A profile that cannot be parsed, or that disagrees with `/N`, is ignored.
+                    profile: IccProfile::new(&stream.data)
+                        .ok()
+                        .filter(|profile| profile.components() == components),
+                })
+            }
+            "Indexed" | "I" => {
+                let base = Self::read(param(0)?, objects, nested)?;
+                let hival = param(1)?.as_number::<i64>()?;
+                let hival =
+                    u8::try_from(hival).map_err(|_| ColorSpaceReadError::InvalidHiVal(hival))?;
+                let lookup = match objects.resolve_object(param(2)?)? {
+                    ObjectVariant::Stream(stream) => stream.data.clone(),
+                    obj => obj.try_bytes()?.to_vec(),
+                };
+                let expected = usize::from(hival)
+                    .checked_add(1)
+                    .and_then(|colors| colors.checked_mul(base.component_count()))
+                    .unwrap_or(usize::MAX);
+                if lookup.len() < expected {
+                    return Err(ColorSpaceReadError::LookupTooShort {
+                        expected,
+                        found: lookup.len(),
+                    });
+                }
+                Ok(ColorSpace::Indexed {
+                    base: Box::new(base),
+                    hival,
+                    lookup,
+                })
+            }
+            "Separation" => Ok(ColorSpace::Separation {
+                colorant: param(0)?.try_str()?.into_owned(),
+                alternate: Box::new(Self::read(param(1)?, objects, nested)?),
+                tint_transform: Function::from_object(param(2)?, objects)?,
+            }),
+            "DeviceN" => {
+                let colorants = objects
+                    .resolve_object(param(0)?)?
+                    .try_array()?
+                    .iter()
+                    .map(|name| name.try_str().map(|name| name.into_owned()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(ColorSpace::DeviceN {
+                    colorants,
+                    alternate: Box::new(Self::read(param(1)?, objects, nested)?),
+                    tint_transform: Function::from_object(param(2)?, objects)?,
+                })
+            }
+            "Pattern" => {
+                let underlying = params
+                    .first()
+                    .map(|underlying| Self::read(underlying, objects, nested))
+                    .transpose()?
+                    .map(Box::new);
+                Ok(ColorSpace::Pattern { underlying })
+            }
+            name => Err(ColorSpaceReadError::UnsupportedColorSpace {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Returns the number of colour components a colour in this space has.
+    ///
+    /// For a pattern space this is the number of components of its underlying space,
+    /// which an uncoloured pattern takes along with the pattern name.
+    pub fn component_count(&self) -> usize {
+        match self {
+            ColorSpace::DeviceGray
+            | ColorSpace::CalGray { .. }
+            | ColorSpace::Indexed { .. }
+            | ColorSpace::Separation { .. } => 1,
+            ColorSpace::DeviceRGB | ColorSpace::CalRGB { .. } | ColorSpace::Lab { .. } => 3,
+            ColorSpace::DeviceCMYK => 4,
+            ColorSpace::IccBased { components, .. } => *components,
+            ColorSpace::DeviceN { colorants, .. } => colorants.len(),
+            ColorSpace::Pattern { underlying } => underlying
+                .as_ref()
+                .map_or(0, |underlying| underlying.component_count()),
+        }
+    }
+
+    /// Returns the valid `[min, max]` range of the component at `index`.
+    pub fn component_range(&self, index: usize) -> [f32; 2] {
+        match self {
+            ColorSpace::Lab { range, .. } => match index {
+                0 => [0.0, 100.0],
+                _ => range_pair(range, index.saturating_sub(1)).unwrap_or([-100.0, 100.0]),
+            },
+            ColorSpace::IccBased { range, .. } => range_pair(range, index).unwrap_or([0.0, 1.0]),
+            ColorSpace::Indexed { hival, .. } => [0.0, f32::from(*hival)],
+            _ => [0.0, 1.0],
+        }
+    }
+
+    /// Returns the colour components that `CS` and `cs` select along with this space.
+    ///
+    /// This is black for the device and CIE-based spaces (clamped into range for
+    /// Lab and ICC-based spaces), index 0 for indexed spaces and full tint for
+    /// separations. Pattern spaces have no initial components.
+    pub fn initial_components(&self) -> Vec<f32> {
+        match self {
+            ColorSpace::DeviceCMYK => vec![0.0, 0.0, 0.0, 1.0],
+            ColorSpace::Separation { .. } | ColorSpace::DeviceN { .. } => {
+                vec![1.0; self.component_count()]
+            }
+            ColorSpace::Pattern { .. } => Vec::new(),
+            _ => (0..self.component_count())
+                .map(|index| {
+                    let [min, max] = self.component_range(index);
+                    0.0_f32.clamp(min, max)
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the ICC profile that colours in this space are converted with, if any.
+    ///
+    /// This is the embedded profile of an ICC-based space, or the output intent
+    /// for a device space with the same number of components. Image samples in the
+    /// space can be converted with [`IccProfile::transform_samples`].
+    pub fn managed_profile<'b>(
+        &'b self,
+        conversion: &ColorConversion<'b>,
+    ) -> Option<&'b IccProfile> {
+        if !conversion.color_management {
+            return None;
+        }
+        match self {
+            ColorSpace::IccBased { profile, .. } => profile.as_ref(),
+            ColorSpace::DeviceGray | ColorSpace::DeviceRGB | ColorSpace::DeviceCMYK => conversion
+                .output_intent
+                .filter(|profile| profile.components() == self.component_count()),
+            _ => None,
+        }
+    }
+
+    /// Converts colour components in this space to an opaque [`Color`].
+    ///
+    /// Components are clamped to their valid ranges; extra components are ignored.
+    /// CIE-based spaces are converted colorimetrically; the rendering intent only
+    /// applies to ICC profiles.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if too few components are given, or if a tint transform fails.
+    pub fn to_color(
+        &self,
+        components: &[f32],
+        conversion: &ColorConversion,
+    ) -> Result<Color, ColorSpaceError> {
+        let expected = self.component_count();
+        if components.len() < expected {
+            return Err(ColorSpaceError::MissingComponents {
+                expected,
+                found: components.len(),
+            });
         }
+        let c = |index: usize| {
+            let [min, max] = self.component_range(index);
+            components[index].clamp(min, max)
+        };
+
+        if let Some(profile) = self.managed_profile(conversion) {
+            let clamped: Vec<f32> = (0..expected).map(c).collect();
+            if let Some(color) = profile.to_color(&clamped, conversion.rendering_intent) {
+                return Ok(color);
+            }
+        }
+
+        let color = match self {
+            ColorSpace::DeviceGray => Color::from_gray(c(0)),
+            ColorSpace::DeviceRGB => Color::from_rgb(c(0), c(1), c(2)),
+            ColorSpace::DeviceCMYK => Color::from_cmyk(c(0), c(1), c(2), c(3)),
+            ColorSpace::CalGray { white_point, gamma } => {
+                let luminance = c(0).powf(*gamma);
+                Color::from_xyz(white_point.map(|w| w * luminance), *white_point)
+            }
+            ColorSpace::CalRGB {
+                white_point,
+                gamma,
+                matrix,
+            } => {
+                let [a, b, c] = [0, 1, 2].map(|i| c(i).powf(gamma[i]));
+                let [xa, ya, za, xb, yb, zb, xc, yc, zc] = *matrix;
+                let xyz = [
+                    xa * a + xb * b + xc * c,
+                    ya * a + yb * b + yc * c,
+                    za * a + zb * b + zc * c,
+                ];
+                Color::from_xyz(xyz, *white_point)
+            }
+            ColorSpace::Lab { white_point, .. } => Color::from_lab(c(0), c(1), c(2), *white_point),
+            ColorSpace::IccBased {
+                alternate,
+                components: count,
+                ..
+            } => {
+                let clamped: Vec<f32> = (0..*count).map(c).collect();
+                alternate.to_color(&clamped, conversion)?
+            }
+            ColorSpace::Indexed {
+                base,
+                hival,
+                lookup,
+            } => {
+                let index = c(0)
+                    .round()
+                    .to_usize()
+                    .unwrap_or(0)
+                    .min(usize::from(*hival));
+                let count = base.component_count();
+                let start = index.checked_mul(count).unwrap_or(0);
+                // The table length is checked when the space is read.
+                let entry = lookup.get(start..).unwrap_or_default();
+                let base_components: Vec<f32> = entry
+                    .iter()
+                    .take(count)
+                    .enumerate()
+                    .map(|(i, &byte)| {
+                        let [min, max] = base.component_range(i);
+                        min + f32::from(byte) / 255.0 * (max - min)
+                    })
+                    .collect();
+                base.to_color(&base_components, conversion)?
+            }
+            ColorSpace::Separation {
+                alternate,
+                tint_transform,
+                ..
+            } => alternate.to_color(&tint_transform.interpolate(&[c(0)])?, conversion)?,
+            ColorSpace::DeviceN {
+                alternate,
+                tint_transform,
+                ..
+            } => {
+                let tints: Vec<f32> = (0..expected).map(c).collect();
+                alternate.to_color(&tint_transform.interpolate(&tints)?, conversion)?
+            }
+            ColorSpace::Pattern { underlying } => underlying
+                .as_ref()
+                .ok_or(ColorSpaceError::UncolouredPatternWithoutBase)?
+                .to_color(components, conversion)?,
+        };
+        Ok(color)
     }
 }
@@ -12,10 +12,6 @@ pub enum PageError {
     #[error("Missing /Contents entry in page dictionary")]
     MissingContent,
 
-    /// The `/MediaBox` entry in the page dictionary is invalid.
-    #[error("Invalid /MediaBox entry: {0}")]
-    InvalidMediaBox(&'static str),
-
     /// Error originating from PDF object handling.
     #[error(transparent)]
     ObjectError(#[from] ObjectError),
@@ -7,6 +7,7 @@ use pdf_object::{
 
 use thiserror::Error;
 
+use crate::function::{Function, FunctionReadError};
 use crate::xobject::{XObject, XObjectError, XObjectReader};
 use num_traits::FromPrimitive;
 use pdf_graphics::{BlendMode, LineCap, LineJoin, MaskMode};
@@ -37,6 +38,8 @@ pub enum ExternalGraphicsStateError {
     },
     #[error("Error reading Soft Mask XObject: {0}")]
     SMaskReadError(#[from] XObjectError),
+    #[error("Error reading Soft Mask transfer function: {0}")]
+    SMaskTransferError(#[from] FunctionReadError),
     #[error("{0}")]
     ObjectError(#[from] ObjectError),
 }
@@ -49,6 +52,12 @@ pub struct SoftMask {
     /// The transparency group XObject (`G`) whose rendered result provides the
     /// input used to compute the soft mask.
     pub shape: XObject,
+    /// The backdrop colour (`BC`) the group is composited over before a luminosity
+    /// mask is computed, in the group's colour space. Black when absent.
+    pub backdrop: Option<Vec<f32>>,
+    /// The transfer function (`TR`) mapping the computed mask values to the ones
+    /// used, or `None` for `/Identity`.
+    pub transfer: Option<Function>,
 }
 
 /// Represents a key-value pair from a PDF External Graphics State dictionary (`ExtGState`).
@@ -89,7 +98,7 @@ pub enum ExternalGraphicsStateKey {
     /// when compositing objects.
     BlendMode(Vec<BlendMode>),
     /// Soft mask (`SMask`). A dictionary specifying the soft mask to be used, or the name `None`.
-    SoftMask(Option<SoftMask>),
+    SoftMask(Option<Box<SoftMask>>),
     /// Stroking alpha constant (`CA`). A number in the range 0.0 to 1.0 specifying the constant
     /// opacity value to be used for stroking operations.
     StrokingAlpha(f32),
@@ -236,7 +245,7 @@ fn parse_blend_mode(
     Ok(ExternalGraphicsStateKey::BlendMode(blend_modes_vec))
 }
 
-/// Parse the soft mask `SMask` -> SoftMask(Option<SoftMask>)
+/// Parse the soft mask `SMask` -> SoftMask(Option<Box<SoftMask>>)
 fn parse_soft_mask(
     key_name: &str,
     value: &ObjectVariant,
@@ -253,7 +262,30 @@ fn parse_soft_mask(
 
             let shape = XObject::read_xobject(dictionary, data.as_slice(), objects)?;
 
-            Some(SoftMask { mask_type, shape })
+            let backdrop = dict
+                .get("BC")
+                .map(|bc| {
+                    objects
+                        .resolve_object(bc)?
+                        .try_array()?
+                        .iter()
+                        .map(|c| c.as_number::<f32>())
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?;
+
+            let transfer = match dict.get("TR") {
+                Some(tr) if tr.as_str().is_some_and(|name| name.as_ref() == "Identity") => None,
+                Some(tr) => Some(Function::from_object(tr, objects)?),
+                None => None,
+            };
+
+            Some(Box::new(SoftMask {
+                mask_type,
+                shape,
+                backdrop,
+                transfer,
+            }))
         }
         other => match other.as_str() {
             Some(name) if name.as_ref() == "None" => None,
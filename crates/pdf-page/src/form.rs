@@ -8,7 +8,9 @@ use thiserror::Error;
 
 use crate::content_stream::ContentStream;
 use crate::matrix::{Matrix, MatrixReadError};
+use crate::optional_content::{OptionalContent, OptionalContentError};
 use crate::resources::{Resources, ResourcesError};
+use crate::transparency_group::{TransparencyGroup, TransparencyGroupError};
 use crate::xobject::XObjectReader;
 
 /// Errors that can occur during parsing of a Form XObject.
@@ -26,6 +28,10 @@ pub enum FormXObjectError {
     ContentStreamError(#[from] PdfOperatorError),
     #[error("Error parsing /Matrix: {0}")]
     MatrixReadError(#[from] MatrixReadError),
+    #[error("Error parsing /Group: {0}")]
+    GroupReadError(#[from] TransparencyGroupError),
+    #[error("Error parsing /OC: {0}")]
+    OptionalContentError(#[from] OptionalContentError),
     #[error("{0}")]
     ObjectError(#[from] ObjectError),
 }
@@ -38,6 +44,11 @@ pub struct FormXObject {
     pub matrix: Option<Transform>,
     /// Resources used by the form.
     pub resources: Option<Resources>,
+    /// The transparency group the form's content forms, if it has a `/Group`.
+    pub group: Option<TransparencyGroup>,
+    /// The optional content the form belongs to, from `/OC`. The form is not
+    /// drawn while that content is hidden.
+    pub optional_content: Option<OptionalContent>,
     /// The content stream that defines the graphics of the pattern cell.
     pub content_stream: ContentStream,
 }
@@ -64,6 +75,12 @@ impl XObjectReader for FormXObject {
             }
         })?;
 
+        // Retrieve the `/Group` entry if present.
+        let group = TransparencyGroup::from_dictionary(dictionary, objects)?;
+
+        // Retrieve the `/OC` entry if present.
+        let optional_content = OptionalContent::from_dictionary(dictionary, objects)?;
+
         // Parse the content stream data.
         let content_stream = ContentStream {
             operations: pdf_content_stream::pdf_operator::PdfOperatorVariant::from(stream_data)?,
@@ -73,6 +90,8 @@ impl XObjectReader for FormXObject {
             bbox,
             matrix,
             resources,
+            group,
+            optional_content,
             content_stream,
         })
     }
@@ -1,9 +1,20 @@
-use pdf_object::{dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection};
+use pdf_object::{
+    ObjectVariant, dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
+};
 
 use num_traits::ToPrimitive;
 use pdf_postscript::{calculator::CalcError, operator::Operator};
 use thiserror::Error;
 
+use crate::bit_reader::BitReader;
+
+/// The most levels of stitching functions nested inside one another.
+const MAX_STITCHING_DEPTH: u8 = 16;
+
+/// The most inputs a sampled function may take. Interpolation visits up to 2^m
+/// samples per output, and every input also multiplies the size of the table.
+const MAX_SAMPLED_INPUTS: usize = 16;
+
 #[derive(Debug, Error)]
 pub enum FunctionReadError {
     #[error("Invalid /FunctionType value")]
@@ -23,6 +34,16 @@ pub enum FunctionReadError {
     DomainParsingError(#[from] ObjectError),
     #[error("PostScript calculator error: {0}")]
     PostScriptCalculatorError(#[from] CalcError),
+    #[error("Expected a function dictionary or stream, found {0}")]
+    InvalidFunctionObject(&'static str),
+    #[error("Function of type {0:?} must be a stream")]
+    MissingStream(FunctionType),
+    #[error("Invalid /BitsPerSample value {0}")]
+    InvalidBitsPerSample(i32),
+    #[error("Invalid sampled function table: {0}")]
+    InvalidSampleTable(&'static str),
+    #[error("Stitching functions are nested too deeply")]
+    NestingTooDeep,
 }
 
 #[derive(Debug, Error)]
@@ -78,6 +99,19 @@ impl FunctionType {
 
 #[derive(Debug)]
 enum FunctionData {
+    Sampled {
+        domain: Vec<f32>,
+        range: Vec<f32>,
+        /// The number of samples along each input dimension.
+        size: Vec<usize>,
+        /// Whether samples are interpolated with cubic splines (`/Order 3`).
+        cubic: bool,
+        encode: Vec<f32>,
+        decode: Vec<f32>,
+        /// The samples, scaled to [0, 1], with the outputs of each sample adjacent
+        /// and the first input varying fastest.
+        samples: Vec<f32>,
+    },
     Exponential {
         c0: Vec<f32>,
         c1: Vec<f32>,
@@ -97,6 +131,45 @@ enum FunctionData {
     },
 }
 
+/// The parts of a sampled function that its evaluation needs.
+struct SampleTable<'a> {
+    domain: &'a [f32],
+    range: &'a [f32],
+    size: &'a [usize],
+    cubic: bool,
+    encode: &'a [f32],
+    decode: &'a [f32],
+    samples: &'a [f32],
+}
+
+impl SampleTable<'_> {
+    /// Returns an output of the sample at the given index along each input, with
+    /// indices past the end of the table clamped to its last sample.
+    fn sample(&self, indices: &[usize], output: usize) -> Option<f32> {
+        let output_count = self.range.len().checked_div(2)?;
+        let mut offset = 0_usize;
+        for (&index, &samples_along) in indices.iter().zip(self.size).rev() {
+            offset = offset
+                .checked_mul(samples_along)?
+                .checked_add(index.min(samples_along.checked_sub(1)?))?;
+        }
+        self.samples
+            .get(offset.checked_mul(output_count)?.checked_add(output)?)
+            .copied()
+    }
+}
+
+/// Interpolates between `p[1]` and `p[2]` with a Catmull-Rom spline through the
+/// four neighbouring samples.
+fn catmull_rom(p: [f32; 4], t: f32) -> f32 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p[1]
+        + (p[2] - p[0]) * t
+        + (2.0 * p[0] - 5.0 * p[1] + 4.0 * p[2] - p[3]) * t2
+        + (3.0 * p[1] - p[0] - 3.0 * p[2] + p[3]) * t3)
+}
+
 #[derive(Debug)]
 pub struct Function {
     pub function_type: FunctionType,
@@ -106,6 +179,10 @@ pub struct Function {
 impl Function {
     pub fn domain(&self) -> Option<[f32; 2]> {
         match &self.data {
+            FunctionData::Sampled { domain, .. } => match domain.as_slice() {
+                [start, end, ..] => Some([*start, *end]),
+                _ => None,
+            },
             FunctionData::Exponential { domain, .. } => Some(*domain),
             FunctionData::Stitching { domain, .. } => Some(*domain),
             FunctionData::PostScriptCalculator { domain, .. } => {
@@ -118,16 +195,52 @@ impl Function {
         }
     }
 
-    /// Interpolates an input value `x` according to the function's definition.
+    /// Evaluates the function at a point given by one value per input.
+    ///
+    /// Exponential and stitching functions take a single input. Sampled and
+    /// PostScript calculator functions take any number, as the 2-in functions of
+    /// function-based shadings and the n-in tint transforms of `DeviceN` spaces do.
     ///
     /// # Returns
     ///
     /// A `Result` containing a `Vec<f32>` of the output values on success, or a
     /// `FunctionInterpolationError` on failure.
-    pub fn interpolate(&self, x: f32) -> Result<Vec<f32>, FunctionInterpolationError> {
-        if x.is_nan() {
+    pub fn interpolate(&self, inputs: &[f32]) -> Result<Vec<f32>, FunctionInterpolationError> {
+        if inputs.iter().any(|x| x.is_nan()) {
             return Err(FunctionInterpolationError::InputIsNaN);
         }
+        match &self.data {
+            FunctionData::Sampled {
+                domain,
+                range,
+                size,
+                cubic,
+                encode,
+                decode,
+                samples,
+            } => Self::interpolate_sampled(
+                SampleTable {
+                    domain,
+                    range,
+                    size,
+                    cubic: *cubic,
+                    encode,
+                    decode,
+                    samples,
+                },
+                inputs,
+            ),
+            FunctionData::PostScriptCalculator {
+                operators,
+                domain,
+                range,
+            } => Self::evaluate_postscript(operators, domain, range, inputs),
+            _ => self.interpolate_single(inputs.first().copied().unwrap_or_default()),
+        }
+    }
+
+    /// Interpolates a single input value `x` through an exponential or stitching function.
+    fn interpolate_single(&self, x: f32) -> Result<Vec<f32>, FunctionInterpolationError> {
         if let FunctionData::Exponential {
             c0,
             c1,
@@ -256,90 +369,196 @@ impl Function {
             let x_mapped = e0 + t * (e1 - e0);
 
             // 6. Call the selected subfunction with the mapped input value.
-            functions[index].interpolate(x_mapped)
-        } else if let FunctionData::PostScriptCalculator {
-            operators,
-            domain,
-            range,
-        } = &self.data
+            functions[index].interpolate(&[x_mapped])
+        } else {
+            Err(FunctionInterpolationError::UnsupportedFunctionType(
+                self.function_type,
+            ))
+        }
+    }
+
+    /// Evaluates a sampled function.
+    ///
+    /// Each input is clipped to its domain and encoded as a position in the sample
+    /// table. Outputs are interpolated between the surrounding samples, decoded and
+    /// clipped to the range. Cubic interpolation is used for 1-in functions with
+    /// `/Order 3`; all other functions are interpolated multilinearly.
+    fn interpolate_sampled(
+        table: SampleTable,
+        inputs: &[f32],
+    ) -> Result<Vec<f32>, FunctionInterpolationError> {
+        let output_count = table.range.len().checked_div(2).unwrap_or(0);
+
+        // The sample index below each input's position, and the fraction beyond it.
+        let mut cells = Vec::with_capacity(table.size.len());
+        for (i, (&samples_along, (bounds, encoding))) in table
+            .size
+            .iter()
+            .zip(
+                table
+                    .domain
+                    .chunks_exact(2)
+                    .zip(table.encode.chunks_exact(2)),
+            )
+            .enumerate()
         {
-            // 1. Clip input to domain
-            let mut stack = Vec::new();
-            let input_count = domain.len() / 2;
-            if input_count == 1 {
-                let x_clipped = x.max(domain[0]).min(domain[1]);
-                stack.push(f64::from(x_clipped));
+            let (start, end) = (bounds[0], bounds[1]);
+            let x = inputs.get(i).map_or(start, |x| x.max(start).min(end));
+            let last = samples_along.saturating_sub(1).to_f32().unwrap_or(0.0);
+            let position = if end > start {
+                encoding[0] + (x - start) * (encoding[1] - encoding[0]) / (end - start)
             } else {
-                // If you want to support multi-input, change interpolate signature to accept &[f32]
-                // For now, treat x as the first input and fill others with domain min
-                for i in 0..input_count {
-                    let val = if i == 0 {
-                        x.max(domain[0]).min(domain[1])
-                    } else {
-                        let pair_index = i
-                            .checked_mul(2)
-                            .ok_or(FunctionInterpolationError::EncodeIndexError)?;
-                        let start = *domain
-                            .get(pair_index)
-                            .ok_or(FunctionInterpolationError::EncodeIndexError)?;
-                        let end = *domain
-                            .get(
-                                pair_index
-                                    .checked_add(1)
-                                    .ok_or(FunctionInterpolationError::EncodeIndexError)?,
-                            )
-                            .ok_or(FunctionInterpolationError::EncodeIndexError)?;
-                        start.max(start).min(end)
-                    };
-                    stack.push(f64::from(val));
-                }
+                encoding[0]
             }
+            .clamp(0.0, last);
+            let index = position.floor().to_usize().unwrap_or(0);
+            cells.push((index, position - position.floor()));
+        }
 
-            // 2. Evaluate PostScript operators
-            let result_stack = pdf_postscript::calculator::execute(&stack, operators)?;
-
-            // 3. Clip outputs to range
-            let mut outputs = Vec::new();
-            let output_count = range.len() / 2;
-            for i in 0..output_count {
-                let val = result_stack
-                    .get(i)
-                    .ok_or(FunctionInterpolationError::InsufficientResultStack)?;
-                let base = i
-                    .checked_mul(2)
-                    .ok_or(FunctionInterpolationError::EncodeIndexError)?;
-                let min = *range
-                    .get(base)
-                    .ok_or(FunctionInterpolationError::EncodeIndexError)?;
-                let max = *range
-                    .get(
-                        base.checked_add(1)
-                            .ok_or(FunctionInterpolationError::EncodeIndexError)?,
-                    )
-                    .ok_or(FunctionInterpolationError::EncodeIndexError)?;
-                let v_f64 = *val;
-
-                match v_f64.to_f32() {
-                    Some(v_f32) => {
-                        outputs.push(v_f32.max(min).min(max));
+        let mut outputs = Vec::with_capacity(output_count);
+        let mut indices = vec![0; cells.len()];
+        for output in 0..output_count {
+            let value = match (table.cubic, cells.as_slice()) {
+                (true, &[(index, fraction)]) => {
+                    let at = |index: Option<usize>| {
+                        index.and_then(|index| table.sample(&[index], output))
+                    };
+                    let p1 = at(Some(index)).ok_or(FunctionInterpolationError::EncodeIndexError)?;
+                    let p2 = at(index.checked_add(1)).unwrap_or(p1);
+                    let p0 = at(index.checked_sub(1)).unwrap_or(p1);
+                    let p3 = at(index.checked_add(2)).unwrap_or(p2);
+                    catmull_rom([p0, p1, p2, p3], fraction)
+                }
+                _ => {
+                    // Multilinear interpolation over the corners of the cell. Inputs that
+                    // fall on a sample contribute a single corner, so only the dimensions
+                    // with a fractional position double the corner count.
+                    let fractional = cells
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, (_, fraction))| *fraction > 0.0)
+                        .map(|(dimension, _)| dimension)
+                        .collect::<Vec<_>>();
+                    let corner_count = u32::try_from(fractional.len())
+                        .ok()
+                        .and_then(|m| 1_usize.checked_shl(m))
+                        .ok_or(FunctionInterpolationError::EncodeIndexError)?;
+                    for (slot, &(index, _)) in indices.iter_mut().zip(&cells) {
+                        *slot = index;
                     }
-                    None => {
-                        return Err(FunctionInterpolationError::InputIsNaN);
+                    let mut value = 0.0;
+                    for corner in 0..corner_count {
+                        let mut weight = 1.0;
+                        let mut bits = corner;
+                        for &dimension in &fractional {
+                            let (index, fraction) = cells[dimension];
+                            if bits & 1 == 1 {
+                                indices[dimension] = index.saturating_add(1);
+                                weight *= fraction;
+                            } else {
+                                indices[dimension] = index;
+                                weight *= 1.0 - fraction;
+                            }
+                            bits = bits.checked_shr(1).unwrap_or(0);
+                        }
+                        if weight > 0.0 {
+                            value += weight
+                                * table
+                                    .sample(&indices, output)
+                                    .ok_or(FunctionInterpolationError::EncodeIndexError)?;
+                        }
                     }
+                    value
+                }
+            };
+
+            let base = output.saturating_mul(2);
+            let next = base.saturating_add(1);
+            let (Some(&d0), Some(&d1), Some(&min), Some(&max)) = (
+                table.decode.get(base),
+                table.decode.get(next),
+                table.range.get(base),
+                table.range.get(next),
+            ) else {
+                return Err(FunctionInterpolationError::EncodeIndexError);
+            };
+            outputs.push((d0 + value * (d1 - d0)).max(min).min(max));
+        }
+        Ok(outputs)
+    }
+
+    /// Runs a PostScript calculator function.
+    ///
+    /// Inputs are clipped to the domain, and missing inputs take the lower bound of
+    /// their domain interval. Outputs are clipped to the range.
+    fn evaluate_postscript(
+        operators: &[Operator],
+        domain: &[f32],
+        range: &[f32],
+        inputs: &[f32],
+    ) -> Result<Vec<f32>, FunctionInterpolationError> {
+        // 1. Clip inputs to domain
+        let mut stack = Vec::new();
+        for (i, bounds) in domain.chunks_exact(2).enumerate() {
+            let (start, end) = (bounds[0], bounds[1]);
+            let val = inputs.get(i).map_or(start, |x| x.max(start).min(end));
+            stack.push(f64::from(val));
+        }
+
+        // 2. Evaluate PostScript operators
+        let result_stack = pdf_postscript::calculator::execute(&stack, operators)?;
+
+        // 3. Clip outputs to range
+        let mut outputs = Vec::new();
+        for (i, bounds) in range.chunks_exact(2).enumerate() {
+            let val = result_stack
+                .get(i)
+                .ok_or(FunctionInterpolationError::InsufficientResultStack)?;
+            match val.to_f32() {
+                Some(v_f32) => {
+                    outputs.push(v_f32.max(bounds[0]).min(bounds[1]));
+                }
+                None => {
+                    return Err(FunctionInterpolationError::InputIsNaN);
                 }
             }
-            Ok(outputs)
-        } else {
-            unreachable!();
         }
+        Ok(outputs)
     }
 }
 
 impl Function {
-    pub(crate) fn from_dictionary(
+    /// Reads a function from an object that is, or refers to, a function dictionary
+    /// or a function stream.
+    pub(crate) fn from_object(
+        object: &ObjectVariant,
+        objects: &ObjectCollection,
+    ) -> Result<Function, FunctionReadError> {
+        Function::read(object, objects, 0)
+    }
+
+    /// Reads a function whose stitching functions are nested `depth` levels deep.
+    fn read(
+        object: &ObjectVariant,
+        objects: &ObjectCollection,
+        depth: u8,
+    ) -> Result<Function, FunctionReadError> {
+        match objects.resolve_object(object)? {
+            ObjectVariant::Dictionary(dictionary) => {
+                Function::from_dictionary(dictionary, objects, None, depth)
+            }
+            ObjectVariant::Stream(stream) => {
+                Function::from_dictionary(&stream.dictionary, objects, Some(&stream.data), depth)
+            }
+            obj => Err(FunctionReadError::InvalidFunctionObject(obj.name())),
+        }
+    }
+
+    fn from_dictionary(
         dictionary: &Dictionary,
-        _objects: &ObjectCollection,
+        objects: &ObjectCollection,
         stream: Option<&[u8]>,
+        depth: u8,
     ) -> Result<Function, FunctionReadError> {
         let function_type_int = dictionary.get_or_err("FunctionType")?.as_number::<i32>()?;
 
@@ -347,6 +566,123 @@ impl Function {
             .ok_or(FunctionReadError::InvalidFunctionType)?;
 
         match function_type {
+            FunctionType::Sampled => {
+                let domain = dictionary
+                    .get_or_err("Domain")?
+                    .as_vec_of::<f32>()
+                    .map_err(FunctionReadError::DomainParsingError)?;
+
+                let range = dictionary
+                    .get_or_err("Range")?
+                    .as_vec_of::<f32>()
+                    .map_err(FunctionReadError::DomainParsingError)?;
+
+                let size = dictionary
+                    .get_or_err("Size")?
+                    .as_vec_of::<i32>()
+                    .map_err(|e| FunctionReadError::EntryReadError {
+                        entry_description: "Size",
+                        source: e,
+                    })?
+                    .into_iter()
+                    .map(|n| n.to_usize().filter(|&n| n > 0))
+                    .collect::<Option<Vec<usize>>>()
+                    .ok_or(FunctionReadError::InvalidSampleTable(
+                        "/Size entries must be positive",
+                    ))?;
+                if size.len().checked_mul(2) != Some(domain.len()) {
+                    return Err(FunctionReadError::InvalidSampleTable(
+                        "/Size must have one entry per input",
+                    ));
+                }
+                if size.len() > MAX_SAMPLED_INPUTS {
+                    return Err(FunctionReadError::InvalidSampleTable(
+                        "sampled functions take at most 16 inputs",
+                    ));
+                }
+
+                let bits_per_sample = dictionary.get_or_err("BitsPerSample")?.as_number::<i32>()?;
+                let bits = [1, 2, 4, 8, 12, 16, 24, 32]
+                    .contains(&bits_per_sample)
+                    .then(|| bits_per_sample.to_u8())
+                    .flatten()
+                    .ok_or(FunctionReadError::InvalidBitsPerSample(bits_per_sample))?;
+
+                // /Order 3 selects cubic spline interpolation; any other value is linear.
+                let cubic = dictionary
+                    .get("Order")
+                    .map(|obj| obj.as_number::<i32>())
+                    .transpose()?
+                    == Some(3);
+
+                // /Encode defaults to [0 (Size_0 - 1) 0 (Size_1 - 1) ...].
+                let encode = match dictionary.get("Encode") {
+                    Some(obj) => {
+                        obj.as_vec_of::<f32>()
+                            .map_err(|e| FunctionReadError::EntryReadError {
+                                entry_description: "Encode",
+                                source: e,
+                            })?
+                    }
+                    None => size
+                        .iter()
+                        .flat_map(|&n| [0.0, n.saturating_sub(1).to_f32().unwrap_or(0.0)])
+                        .collect(),
+                };
+                if encode.len() != domain.len() {
+                    return Err(FunctionReadError::InvalidEncodeLength);
+                }
+
+                // /Decode defaults to /Range.
+                let decode = match dictionary.get("Decode") {
+                    Some(obj) => {
+                        obj.as_vec_of::<f32>()
+                            .map_err(|e| FunctionReadError::EntryReadError {
+                                entry_description: "Decode",
+                                source: e,
+                            })?
+                    }
+                    None => range.clone(),
+                };
+                if decode.len() != range.len() {
+                    return Err(FunctionReadError::InvalidSampleTable(
+                        "/Decode must have one pair per output",
+                    ));
+                }
+
+                let stream = stream.ok_or(FunctionReadError::MissingStream(function_type))?;
+                let sample_count = size
+                    .iter()
+                    .try_fold(range.len().checked_div(2).unwrap_or(0), |count, &n| {
+                        count.checked_mul(n)
+                    })
+                    .ok_or(FunctionReadError::InvalidSampleTable("too many samples"))?;
+                let largest = 2.0_f64.powi(i32::from(bits)) - 1.0;
+                let mut reader = BitReader::new(stream);
+                let samples = (0..sample_count)
+                    .map(|_| {
+                        reader
+                            .read(bits)
+                            .and_then(|raw| (f64::from(raw) / largest).to_f32())
+                    })
+                    .collect::<Option<Vec<f32>>>()
+                    .ok_or(FunctionReadError::InvalidSampleTable(
+                        "the stream holds fewer samples than /Size requires",
+                    ))?;
+
+                Ok(Function {
+                    function_type,
+                    data: FunctionData::Sampled {
+                        domain,
+                        range,
+                        size,
+                        cubic,
+                        encode,
+                        decode,
+                        samples,
+                    },
+                })
+            }
             FunctionType::ExponentialInterpolation => {
                 let domain = dictionary
                     .get_or_err("Domain")?
@@ -394,13 +730,16 @@ impl Function {
                     .as_array_of::<f32, 2>()
                     .map_err(FunctionReadError::DomainParsingError)?;
 
+                // Stitching functions that refer to themselves would nest forever.
+                if depth >= MAX_STITCHING_DEPTH {
+                    return Err(FunctionReadError::NestingTooDeep);
+                }
                 let mut functions = Vec::new();
 
                 // Parse Functions array
                 let functions_arr = dictionary.get_or_err("Functions")?.try_array()?;
                 for obj in functions_arr.iter() {
-                    let dict = obj.try_dictionary()?;
-                    functions.push(Function::from_dictionary(dict, _objects, None)?);
+                    functions.push(Function::read(obj, objects, depth.saturating_add(1))?);
                 }
 
                 // Parse Bounds array
@@ -450,7 +789,7 @@ impl Function {
                     .as_vec_of::<f32>()
                     .map_err(FunctionReadError::DomainParsingError)?;
 
-                let stream = stream.ok_or(FunctionReadError::InvalidFunctionType)?;
+                let stream = stream.ok_or(FunctionReadError::MissingStream(function_type))?;
                 let a = String::from_utf8_lossy(stream);
                 let code = a.replace("{", " { ").replace("}", " } ");
                 Ok(Function {
@@ -464,7 +803,162 @@ impl Function {
                     },
                 })
             }
-            _ => Err(FunctionReadError::InvalidFunctionType),
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::{collections::BTreeMap, rc::Rc};
+
+    use pdf_object::indirect_object::IndirectObject;
+
+    use super::*;
+
+    fn dictionary(entries: &[(&str, ObjectVariant)]) -> Dictionary {
+        Dictionary::new(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), Box::new(value.clone())))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    fn numbers(values: &[f64]) -> ObjectVariant {
+        ObjectVariant::Array(values.iter().map(|&v| ObjectVariant::Real(v)).collect())
+    }
+
+    fn sampled(domain: &[f64], size: &[i64], samples: &[u8]) -> Function {
+        let dictionary = dictionary(&[
+            ("FunctionType", ObjectVariant::Integer(0)),
+            ("Domain", numbers(domain)),
+            ("Range", numbers(&[0.0, 1.0])),
+            (
+                "Size",
+                ObjectVariant::Array(size.iter().map(|&n| ObjectVariant::Integer(n)).collect()),
+            ),
+            ("BitsPerSample", ObjectVariant::Integer(8)),
+        ]);
+        Function::from_dictionary(&dictionary, &ObjectCollection::default(), Some(samples), 0)
+            .unwrap()
+    }
+
+    fn exponential(c0: f64, c1: f64) -> ObjectVariant {
+        ObjectVariant::Dictionary(Rc::new(dictionary(&[
+            ("FunctionType", ObjectVariant::Integer(2)),
+            ("Domain", numbers(&[0.0, 1.0])),
+            ("C0", numbers(&[c0])),
+            ("C1", numbers(&[c1])),
+            ("N", ObjectVariant::Integer(1)),
+        ])))
+    }
+
+    fn assert_close(actual: Vec<f32>, expected: f32) {
+        assert_eq!(actual.len(), 1);
+        assert!(
+            (actual[0] - expected).abs() < 1e-4,
+            "{actual:?} != {expected}"
+        );
+    }
+
+    #[test]
+    fn sampled_function_interpolates_between_samples() {
+        let function = sampled(&[0.0, 1.0], &[3], &[0, 255, 0]);
+        assert_close(function.interpolate(&[0.0]).unwrap(), 0.0);
+        assert_close(function.interpolate(&[0.25]).unwrap(), 0.5);
+        assert_close(function.interpolate(&[0.5]).unwrap(), 1.0);
+        assert_close(function.interpolate(&[0.75]).unwrap(), 0.5);
+        // Inputs are clipped to the domain.
+        assert_close(function.interpolate(&[2.0]).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn sampled_function_interpolates_over_two_inputs() {
+        // The first input varies fastest: (0, 0), (1, 0), (0, 1), (1, 1).
+        let function = sampled(&[0.0, 1.0, 0.0, 1.0], &[2, 2], &[0, 255, 255, 255]);
+        assert_close(function.interpolate(&[1.0, 0.0]).unwrap(), 1.0);
+        assert_close(function.interpolate(&[0.5, 0.0]).unwrap(), 0.5);
+        assert_close(function.interpolate(&[0.5, 0.5]).unwrap(), 0.75);
+    }
+
+    #[test]
+    fn sampled_function_requires_enough_samples() {
+        let dictionary = dictionary(&[
+            ("FunctionType", ObjectVariant::Integer(0)),
+            ("Domain", numbers(&[0.0, 1.0])),
+            ("Range", numbers(&[0.0, 1.0])),
+            ("Size", numbers(&[4.0])),
+            ("BitsPerSample", ObjectVariant::Integer(8)),
+        ]);
+        let result =
+            Function::from_dictionary(&dictionary, &ObjectCollection::default(), Some(&[0, 1]), 0);
+        assert!(matches!(
+            result,
+            Err(FunctionReadError::InvalidSampleTable(_))
+        ));
+    }
+
+    #[test]
+    fn sampled_function_inputs_are_limited() {
+        let dictionary = dictionary(&[
+            ("FunctionType", ObjectVariant::Integer(0)),
+            ("Domain", numbers(&[0.0, 1.0].repeat(17))),
+            ("Range", numbers(&[0.0, 1.0])),
+            ("Size", numbers(&[1.0; 17])),
+            ("BitsPerSample", ObjectVariant::Integer(8)),
+        ]);
+        let result =
+            Function::from_dictionary(&dictionary, &ObjectCollection::default(), Some(&[0]), 0);
+        assert!(matches!(
+            result,
+            Err(FunctionReadError::InvalidSampleTable(_))
+        ));
+    }
+
+    #[test]
+    fn stitching_function_encodes_each_subdomain() {
+        let dictionary = dictionary(&[
+            ("FunctionType", ObjectVariant::Integer(3)),
+            ("Domain", numbers(&[0.0, 1.0])),
+            (
+                "Functions",
+                ObjectVariant::Array(vec![exponential(0.0, 1.0), exponential(1.0, 0.0)]),
+            ),
+            ("Bounds", numbers(&[0.5])),
+            ("Encode", numbers(&[0.0, 1.0, 0.0, 1.0])),
+        ]);
+        let function =
+            Function::from_dictionary(&dictionary, &ObjectCollection::default(), None, 0).unwrap();
+        assert_close(function.interpolate(&[0.0]).unwrap(), 0.0);
+        assert_close(function.interpolate(&[0.25]).unwrap(), 0.5);
+        // A bound belongs to the subdomain that starts there.
+        assert_close(function.interpolate(&[0.5]).unwrap(), 1.0);
+        assert_close(function.interpolate(&[0.75]).unwrap(), 0.5);
+        assert_close(function.interpolate(&[1.0]).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn stitching_function_referring_to_itself_is_rejected() {
+        let stitching = dictionary(&[
+            ("FunctionType", ObjectVariant::Integer(3)),
+            ("Domain", numbers(&[0.0, 1.0])),
+            (
+                "Functions",
+                ObjectVariant::Array(vec![ObjectVariant::Reference(1)]),
+            ),
+            ("Bounds", numbers(&[])),
+            ("Encode", numbers(&[0.0, 1.0])),
+        ]);
+        let mut objects = ObjectCollection::default();
+        objects
+            .insert(ObjectVariant::IndirectObject(Rc::new(IndirectObject::new(
+                1,
+                0,
+                Some(ObjectVariant::Dictionary(Rc::new(stitching))),
+            ))))
+            .unwrap();
+        let result = Function::from_object(&ObjectVariant::Reference(1), &objects);
+        assert!(matches!(result, Err(FunctionReadError::NestingTooDeep)));
+    }
+}
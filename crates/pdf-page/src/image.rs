@@ -1,12 +1,18 @@
 use std::borrow::Cow;
 
+use num_traits::ToPrimitive;
 use pdf_object::{
-    dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
-    stream::StreamObject,
+    ObjectVariant, dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
+    stream::StreamObject, traits::FromDictionary,
 };
 use thiserror::Error;
 
-use crate::xobject::{XObject, XObjectError, XObjectReader};
+use crate::{
+    bit_reader::BitReader,
+    color_space::{ColorConversion, ColorSpace, ColorSpaceError, ColorSpaceReadError},
+    optional_content::{OptionalContent, OptionalContentError},
+    xobject::{XObject, XObjectError, XObjectReader},
+};
 
 #[derive(Debug, Error)]
 pub enum ImageXObjectError {
@@ -21,10 +27,35 @@ pub enum ImageXObjectError {
         #[from]
         source: Box<XObjectError>,
     },
+    #[error("Mask must be an array or an image mask, but it was not.")]
+    InvalidMask,
+    #[error("Error reading Image colour space: {0}")]
+    ColorSpaceError(#[from] ColorSpaceReadError),
+    #[error("Error reading Image /OC: {0}")]
+    OptionalContentError(#[from] OptionalContentError),
     #[error("Object error: {0}")]
     ObjectError(#[from] ObjectError),
 }
 
+/// The most samples an image may have, over all its components, for it to be
+/// decoded.
+const MAX_IMAGE_SAMPLES: usize = 1 << 28;
+
+/// Errors that can occur while decoding the samples of an image.
+#[derive(Debug, Error)]
+pub enum ImageDecodeError {
+    #[error("Image has no colour space")]
+    MissingColorSpace,
+    #[error("Unsupported BitsPerComponent {0}")]
+    UnsupportedBitsPerComponent(u32),
+    #[error("Image dimensions {width}x{height} are too large")]
+    TooLarge { width: u32, height: u32 },
+    #[error("Image data holds {found} bytes, expected {expected}")]
+    TruncatedData { expected: usize, found: usize },
+    #[error("Colour conversion error: {0}")]
+    ColorSpaceError(#[from] ColorSpaceError),
+}
+
 /// Represents the compression filter applied to an image's stream data.
 ///
 /// This corresponds to the `/Filter` entry in a PDF Image XObject's dictionary.
@@ -49,6 +80,17 @@ impl From<Cow<'_, str>> for ImageFilter {
     }
 }
 
+/// The `/Mask` entry of an image, which selects the parts of it that are painted.
+#[derive(Debug)]
+pub enum ImageMask {
+    /// Colour key masking: a `[min max]` range of raw sample values for each
+    /// component. Pixels whose samples all fall within the ranges are not painted.
+    ColorKey(Vec<u32>),
+    /// Stencil masking: an image mask whose painted samples mark the visible area.
+    /// It may have a different resolution than the image.
+    Stencil(Box<ImageXObject>),
+}
+
 /// Represents a PDF Image XObject, which is a self-contained raster image.
 ///
 /// An Image XObject is a type of external object (XObject) used to embed raster images
@@ -63,12 +105,29 @@ pub struct ImageXObject {
     /// The number of bits used to represent each color component.
     /// For example, 8 for a standard RGB image. Corresponds to the `/BitsPerComponent` entry.
     pub bits_per_component: u32,
+    /// The colour space the samples are in. Absent for image masks. Corresponds to
+    /// the `/ColorSpace` entry.
+    pub color_space: Option<ColorSpace>,
+    /// `[min max]` pairs mapping each component's raw samples onto colour values.
+    /// Corresponds to the `/Decode` entry.
+    pub decode: Option<Vec<f32>>,
+    /// Whether the image is a stencil mask painted with the current fill colour.
+    /// Corresponds to the `/ImageMask` entry.
+    pub image_mask: bool,
+    /// Colour key or stencil masking. Corresponds to the `/Mask` entry.
+    pub mask: Option<ImageMask>,
+    /// For a soft mask, the colour the parent image's samples were premultiplied
+    /// with, in the parent's colour space. Corresponds to the `/Matte` entry.
+    pub matte: Option<Vec<f32>>,
     /// The filter(s) used to decompress the image data, such as `DCTDecode` (JPEG)
     /// or `FlateDecode`. Corresponds to the `/Filter` entry.
     pub filter: Option<ImageFilter>,
     /// An optional soft mask, which is another `ImageXObject` used for transparency.
     /// Corresponds to the `/SMask` entry.
     pub smask: Option<Box<ImageXObject>>,
+    /// The optional content the image belongs to, from `/OC`. The image is not
+    /// drawn while that content is hidden.
+    pub optional_content: Option<OptionalContent>,
     /// The raw, potentially compressed, byte data of the image stream.
     pub data: Vec<u8>,
 }
@@ -84,9 +143,29 @@ impl XObjectReader for ImageXObject {
         // Extract required image properties from the dictionary.
         let width = dictionary.get_or_err("Width")?.as_number::<u32>()?;
         let height = dictionary.get_or_err("Height")?.as_number::<u32>()?;
-        let bits_per_component = dictionary
-            .get_or_err("BitsPerComponent")?
-            .as_number::<u32>()?;
+        let image_mask = dictionary
+            .get("ImageMask")
+            .map_or(Ok(false), |v| v.try_boolean())?;
+        // Image masks always have one bit per sample, and may leave it implicit.
+        let bits_per_component = match dictionary.get("BitsPerComponent") {
+            Some(bits) => bits.as_number::<u32>()?,
+            None if image_mask => 1,
+            None => dictionary
+                .get_or_err("BitsPerComponent")?
+                .as_number::<u32>()?,
+        };
+        let color_space = match dictionary.get("ColorSpace") {
+            Some(cs) if !image_mask => Some(ColorSpace::from_object(cs, objects)?),
+            _ => None,
+        };
+        let decode = dictionary
+            .get("Decode")
+            .map(|decode| read_numbers(decode, objects))
+            .transpose()?;
+        let matte = dictionary
+            .get("Matte")
+            .map(|matte| read_numbers(matte, objects))
+            .transpose()?;
 
         // Parse the optional `/Filter` entry, if present, and check for unsupported filters.
         let filter = dictionary
@@ -114,20 +193,448 @@ impl XObjectReader for ImageXObject {
                 })?;
             // Ensure the SMask is actually an image.
             match smask {
-                XObject::Image(img) => Some(Box::new(img)),
+                XObject::Image(img) => Some(img),
                 _ => return Err(ImageXObjectError::SMaskNotImage),
             }
         } else {
             None
         };
 
+        // Handle the optional `/Mask` entry: an array of colour key ranges, or a
+        // stencil image mask.
+        let mask = match dictionary.get("Mask").map(|m| objects.resolve_object(m)) {
+            Some(Ok(ObjectVariant::Stream(stream))) => {
+                let mask = ImageXObject::read_xobject(&stream.dictionary, &stream.data, objects)?;
+                if !mask.image_mask {
+                    return Err(ImageXObjectError::InvalidMask);
+                }
+                Some(ImageMask::Stencil(Box::new(mask)))
+            }
+            Some(Ok(ObjectVariant::Array(ranges))) => Some(ImageMask::ColorKey(
+                ranges
+                    .iter()
+                    .map(|v| v.as_number::<u32>())
+                    .collect::<Result<_, _>>()?,
+            )),
+            Some(Ok(_)) => return Err(ImageXObjectError::InvalidMask),
+            Some(Err(e)) => return Err(e.into()),
+            None => None,
+        };
+
+        let optional_content = OptionalContent::from_dictionary(dictionary, objects)?;
+
         Ok(Self {
             width,
             height,
             bits_per_component,
+            color_space,
+            decode,
+            image_mask,
+            mask,
+            matte,
             filter,
             smask,
+            optional_content,
             data: stream_data.to_vec(),
         })
     }
 }
+
+/// Reads an array of numbers, such as `/Decode` or `/Matte`.
+fn read_numbers(
+    object: &ObjectVariant,
+    objects: &ObjectCollection,
+) -> Result<Vec<f32>, ObjectError> {
+    objects
+        .resolve_object(object)?
+        .try_array()?
+        .iter()
+        .map(|v| v.as_number::<f32>())
+        .collect()
+}
+
+/// Converts a colour channel in `[0, 1]` to a byte.
+fn channel(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round().to_u8().unwrap_or(0)
+}
+
+impl ImageXObject {
+    /// Reads an inline image from its dictionary, with abbreviated keys already
+    /// expanded, and its data.
+    ///
+    /// Inline images cannot refer to other objects, so a `/ColorSpace` naming a
+    /// resource must be removed beforehand and resolved by the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required entry is missing or malformed.
+    pub fn from_inline(dictionary: &Dictionary, data: &[u8]) -> Result<Self, ImageXObjectError> {
+        Self::read_xobject(dictionary, data, &ObjectCollection::default())
+    }
+
+    /// Decodes the image into 8-bit RGBA pixels, not premultiplied, row by row
+    /// from the top.
+    ///
+    /// Samples of every bit depth are mapped through `/Decode` and converted from
+    /// the image's colour space to sRGB. The alpha channel comes from the soft
+    /// mask, whose `/Matte` colour is divided back out of the samples, or else
+    /// from the colour key or stencil `/Mask`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for image masks and images without a colour space, for
+    /// unsupported bit depths, or if colour conversion fails.
+    pub fn decode_rgba(&self, conversion: &ColorConversion) -> Result<Vec<u8>, ImageDecodeError> {
+        let space = self
+            .color_space
+            .as_ref()
+            .ok_or(ImageDecodeError::MissingColorSpace)?;
+        self.decode_rgba_in(space, conversion)
+    }
+
+    /// Like [`decode_rgba`](Self::decode_rgba), but with the samples in `space`
+    /// rather than in the image's own colour space, such as an inline image whose
+    /// `/ColorSpace` names a resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unsupported bit depths, or if colour conversion fails.
+    pub fn decode_rgba_in(
+        &self,
+        space: &ColorSpace,
+        conversion: &ColorConversion,
+    ) -> Result<Vec<u8>, ImageDecodeError> {
+        let components = space.component_count();
+        let samples = self.read_samples(components)?;
+        let ranges = self.decode_ranges(space)?;
+
+        let alpha = match (self.soft_or_stencil_alpha()?, &self.mask) {
+            (Some(alpha), _) => Some(alpha),
+            (None, Some(ImageMask::ColorKey(key))) => {
+                Some(color_key_alpha(&samples, components, key))
+            }
+            (None, _) => None,
+        };
+        let matte = self
+            .smask
+            .as_ref()
+            .and_then(|smask| smask.matte.as_deref())
+            .zip(alpha.as_deref());
+
+        let max = self.max_sample()?;
+        let decode = |raw: u32, [min, max_value]: [f32; 2]| {
+            // Computed in f64 so that 16-bit samples keep their precision.
+            let value =
+                f64::from(min) + f64::from(raw) * f64::from(max_value - min) / f64::from(max);
+            value.to_f32().unwrap_or(min)
+        };
+
+        let rgb = if components == 1 && max <= 255 && matte.is_none() {
+            // Few distinct samples: convert each possible one once.
+            let range = ranges.first().copied().unwrap_or([0.0, 1.0]);
+            let table = (0..=max)
+                .map(|raw| space.to_color(&[decode(raw, range)], conversion))
+                .collect::<Result<Vec<_>, _>>()?;
+            samples
+                .iter()
+                .flat_map(|&raw| {
+                    let color = table.get(raw.to_usize().unwrap_or(0)).copied();
+                    color.map_or([0; 3], |c| [channel(c.r), channel(c.g), channel(c.b)])
+                })
+                .collect()
+        } else {
+            let mut values: Vec<f32> = samples
+                .iter()
+                .zip(ranges.iter().cycle())
+                .map(|(&raw, &range)| decode(raw, range))
+                .collect();
+            if let Some((matte, alpha)) = matte {
+                unpremultiply(&mut values, components, matte, alpha);
+            }
+            convert_pixels(space, &values, conversion)?
+        };
+
+        let pixels = self.pixel_count()?;
+        let mut rgba = Vec::with_capacity(pixels.saturating_mul(4));
+        for (index, pixel) in rgb.chunks_exact(3).take(pixels).enumerate() {
+            rgba.extend_from_slice(pixel);
+            rgba.push(
+                alpha
+                    .as_ref()
+                    .and_then(|a| a.get(index))
+                    .copied()
+                    .unwrap_or(255),
+            );
+        }
+        Ok(rgba)
+    }
+
+    /// Decodes the opacity given by the image's soft mask or stencil `/Mask`, one
+    /// byte per pixel at the image's resolution, for images whose colour samples
+    /// are decoded elsewhere.
+    ///
+    /// Colour key masks depend on the samples and are only applied by
+    /// [`ImageXObject::decode_rgba`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mask has an unsupported bit depth or is too large.
+    pub fn decode_alpha(&self) -> Result<Option<Vec<u8>>, ImageDecodeError> {
+        self.soft_or_stencil_alpha()
+    }
+
+    /// Decodes an image mask into one byte per pixel: 255 where the mask is painted
+    /// and 0 elsewhere.
+    ///
+    /// A sample of 0 is painted, unless `/Decode` is `[1 0]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is too large.
+    pub fn decode_stencil(&self) -> Result<Vec<u8>, ImageDecodeError> {
+        let inverted = self
+            .decode
+            .as_deref()
+            .is_some_and(|decode| matches!(decode, [min, max, ..] if min > max));
+        Ok(self
+            .read_samples(1)?
+            .into_iter()
+            .map(|raw| if (raw == 0) != inverted { 255 } else { 0 })
+            .collect())
+    }
+
+    /// Returns the alpha of the soft mask, or else of a stencil mask, resampled to
+    /// the image's resolution.
+    fn soft_or_stencil_alpha(&self) -> Result<Option<Vec<u8>>, ImageDecodeError> {
+        let (mask, alpha) = match (&self.smask, &self.mask) {
+            (Some(smask), _) => (smask, smask.decode_gray()?),
+            (None, Some(ImageMask::Stencil(stencil))) => (stencil, stencil.decode_stencil()?),
+            _ => return Ok(None),
+        };
+        // The image itself may be decoded by a backend, as JPEG data is, so its
+        // size is checked here before the mask is resampled to it.
+        let pixel_count = self.pixel_count()?;
+        if pixel_count > MAX_IMAGE_SAMPLES {
+            return Err(ImageDecodeError::TooLarge {
+                width: self.width,
+                height: self.height,
+            });
+        }
+        Ok(Some(resample(
+            &alpha,
+            (mask.width, mask.height),
+            (self.width, self.height),
+            pixel_count,
+        )))
+    }
+
+    /// Decodes a single-component image, such as a soft mask, to one byte per pixel.
+    fn decode_gray(&self) -> Result<Vec<u8>, ImageDecodeError> {
+        let max = f32::from(u16::try_from(self.max_sample()?).unwrap_or(u16::MAX));
+        let [min, max_value] = match self.decode.as_deref() {
+            Some([min, max_value, ..]) => [*min, *max_value],
+            _ => [0.0, 1.0],
+        };
+        Ok(self
+            .read_samples(1)?
+            .into_iter()
+            .map(|raw| channel(min + raw.to_f32().unwrap_or(0.0) * (max_value - min) / max))
+            .collect())
+    }
+
+    /// Returns the decode range of each component: from `/Decode`, or else the
+    /// colour space's component ranges, with indexed images taking the raw index.
+    fn decode_ranges(&self, space: &ColorSpace) -> Result<Vec<[f32; 2]>, ImageDecodeError> {
+        let max = self.max_sample()?.to_f32().unwrap_or(1.0);
+        Ok((0..space.component_count())
+            .map(|index| {
+                let pair = index
+                    .checked_mul(2)
+                    .and_then(|start| self.decode.as_deref()?.get(start..start.checked_add(2)?));
+                match (pair, space) {
+                    (Some(&[min, max]), _) => [min, max],
+                    (_, ColorSpace::Indexed { .. }) => [0.0, max],
+                    _ => space.component_range(index),
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the largest raw sample value, `2^BitsPerComponent - 1`.
+    fn max_sample(&self) -> Result<u32, ImageDecodeError> {
+        match self.bits_per_component {
+            1 | 2 | 4 | 8 | 16 => Ok((1_u32 << self.bits_per_component).saturating_sub(1)),
+            bits => Err(ImageDecodeError::UnsupportedBitsPerComponent(bits)),
+        }
+    }
+
+    /// Returns the number of pixels in the image.
+    fn pixel_count(&self) -> Result<usize, ImageDecodeError> {
+        self.width
+            .to_usize()
+            .zip(self.height.to_usize())
+            .and_then(|(width, height)| width.checked_mul(height))
+            .ok_or(ImageDecodeError::TooLarge {
+                width: self.width,
+                height: self.height,
+            })
+    }
+
+    /// Reads the raw samples, `components` per pixel, row by row. Each row starts
+    /// on a byte boundary. Samples missing from truncated data read as 0, as long
+    /// as the data holds at least half of the image.
+    fn read_samples(&self, components: usize) -> Result<Vec<u32>, ImageDecodeError> {
+        let too_large = || ImageDecodeError::TooLarge {
+            width: self.width,
+            height: self.height,
+        };
+        let row_len = self
+            .width
+            .to_usize()
+            .and_then(|width| width.checked_mul(components))
+            .ok_or_else(too_large)?;
+        let count = self
+            .pixel_count()?
+            .checked_mul(components)
+            .filter(|&count| count <= MAX_IMAGE_SAMPLES)
+            .ok_or_else(too_large)?;
+        self.max_sample()?;
+
+        // The dimensions come from the dictionary, so they are checked against the
+        // data before that many samples are allocated.
+        let expected = row_len
+            .checked_mul(self.bits_per_component.to_usize().ok_or_else(too_large)?)
+            .map(|bits| bits.div_ceil(8))
+            .and_then(|row_bytes| row_bytes.checked_mul(self.height.to_usize()?))
+            .ok_or_else(too_large)?;
+        if self.data.len() < expected.div_ceil(2) {
+            return Err(ImageDecodeError::TruncatedData {
+                expected,
+                found: self.data.len(),
+            });
+        }
+
+        let mut samples: Vec<u32> = match self.bits_per_component {
+            8 => self
+                .data
+                .iter()
+                .take(count)
+                .map(|&b| u32::from(b))
+                .collect(),
+            16 => self
+                .data
+                .chunks_exact(2)
+                .take(count)
+                .map(|pair| u32::from(u16::from_be_bytes([pair[0], pair[1]])))
+                .collect(),
+            bits => {
+                let bits = bits.to_u8().unwrap_or(1);
+                let mut reader = BitReader::new(&self.data);
+                let mut samples = Vec::with_capacity(count);
+                for _ in 0..self.height {
+                    for _ in 0..row_len {
+                        samples.push(reader.read(bits).unwrap_or(0));
+                    }
+                    reader.align();
+                }
+                samples
+            }
+        };
+        samples.resize(count, 0);
+        Ok(samples)
+    }
+}
+
+/// Returns the alpha of colour key masking: 0 for pixels whose raw samples all
+/// fall within the key's `[min max]` ranges, 255 for the others.
+fn color_key_alpha(samples: &[u32], components: usize, key: &[u32]) -> Vec<u8> {
+    samples
+        .chunks_exact(components.max(1))
+        .map(|pixel| {
+            let masked = pixel.iter().enumerate().all(|(index, &raw)| {
+                let range = index
+                    .checked_mul(2)
+                    .and_then(|start| key.get(start..start.checked_add(2)?));
+                matches!(range, Some(&[min, max]) if (min..=max).contains(&raw))
+            });
+            if masked { 0 } else { 255 }
+        })
+        .collect()
+}
+
+/// Undoes the premultiplication of decoded colour values with a soft mask's
+/// `/Matte` colour: `c = m + (c' - m) / alpha`.
+fn unpremultiply(values: &mut [f32], components: usize, matte: &[f32], alpha: &[u8]) {
+    for (pixel, &alpha) in values.chunks_exact_mut(components.max(1)).zip(alpha) {
+        if alpha == 0 {
+            continue;
+        }
+        let alpha = f32::from(alpha) / 255.0;
+        for (value, &m) in pixel.iter_mut().zip(matte) {
+            *value = m + (*value - m) / alpha;
+        }
+    }
+}
+
+/// Converts decoded colour values, one run of the space's components per pixel,
+/// to 8-bit RGB.
+///
+/// When the space is colour managed the pixels go through the ICC profile in a
+/// single pass; otherwise each pixel is converted on its own.
+fn convert_pixels(
+    space: &ColorSpace,
+    values: &[f32],
+    conversion: &ColorConversion,
+) -> Result<Vec<u8>, ImageDecodeError> {
+    let components = space.component_count().max(1);
+    if let Some(profile) = space.managed_profile(conversion) {
+        let samples: Vec<u8> = values
+            .chunks_exact(components)
+            .flat_map(|pixel| {
+                pixel.iter().enumerate().map(|(index, &value)| {
+                    let [min, max] = space.component_range(index);
+                    channel((value - min) / (max - min))
+                })
+            })
+            .collect();
+        if let Some(rgb) = profile.transform_samples(&samples, conversion.rendering_intent) {
+            return Ok(rgb);
+        }
+    }
+
+    let mut rgb = Vec::with_capacity(values.len().saturating_mul(3));
+    for pixel in values.chunks_exact(components) {
+        let color = space.to_color(pixel, conversion)?;
+        rgb.extend_from_slice(&[channel(color.r), channel(color.g), channel(color.b)]);
+    }
+    Ok(rgb)
+}
+
+/// Resamples one byte per pixel from one resolution to another, picking the
+/// nearest source pixel. `pixel_count` is the number of pixels at the new resolution.
+fn resample(
+    values: &[u8],
+    (from_width, from_height): (u32, u32),
+    (width, height): (u32, u32),
+    pixel_count: usize,
+) -> Vec<u8> {
+    if (from_width, from_height) == (width, height) {
+        return values.to_vec();
+    }
+    let scale = |index: u32, from: u32, to: u32| {
+        u64::from(index)
+            .saturating_mul(u64::from(from))
+            .checked_div(u64::from(to))
+            .unwrap_or(0)
+    };
+    let mut out = Vec::with_capacity(pixel_count);
+    for y in 0..height {
+        let row = scale(y, from_height, height).saturating_mul(u64::from(from_width));
+        for x in 0..width {
+            let index = row.saturating_add(scale(x, from_width, width));
+            let value = index.to_usize().and_then(|index| values.get(index));
+            out.push(value.copied().unwrap_or(0));
+        }
+    }
+    out
+}
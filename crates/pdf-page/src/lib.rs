@@ -1,3 +1,6 @@
+pub mod annotation;
+mod annotation_appearance;
+mod bit_reader;
 pub mod color_space;
 pub mod content_stream;
 pub mod error;
@@ -6,10 +9,14 @@ pub mod form;
 pub mod function;
 pub mod image;
 pub mod matrix;
-pub mod media_box;
+pub mod mesh;
+pub mod optional_content;
+pub mod output_intent;
 pub mod page;
+pub mod page_box;
 pub mod pages;
 pub mod pattern;
 pub mod resources;
 pub mod shading;
+pub mod transparency_group;
 pub mod xobject;
@@ -0,0 +1,477 @@
+use num_traits::ToPrimitive;
+use pdf_object::{dictionary::Dictionary, error::ObjectError};
+use thiserror::Error;
+
+use crate::bit_reader::BitReader;
+
+/// Errors that can occur while decoding the vertex or patch data of a mesh shading.
+#[derive(Debug, Error)]
+pub enum MeshError {
+    #[error("Invalid value {value} for '{entry_name}'")]
+    InvalidBitDepth {
+        entry_name: &'static str,
+        value: i32,
+    },
+    #[error("/Decode must hold {expected} numbers, found {found}")]
+    InvalidDecodeLength { expected: usize, found: usize },
+    #[error("/VerticesPerRow must be at least 2, found {0}")]
+    InvalidVerticesPerRow(i32),
+    #[error("{0}")]
+    ObjectError(#[from] ObjectError),
+}
+
+/// A vertex of a triangle mesh, in shading space.
+#[derive(Debug, Clone)]
+pub struct MeshVertex {
+    pub x: f32,
+    pub y: f32,
+    /// The colour components at the vertex, or a single parametric value `t` when the
+    /// shading has a `/Function`.
+    pub values: Vec<f32>,
+}
+
+/// A tensor-product patch. Coons patches are converted to this form when decoded.
+#[derive(Debug, Clone)]
+pub struct Patch {
+    /// The 4 × 4 control points `p[i][j]`, where `i` follows the `u` parameter and
+    /// `j` the `v` parameter of the surface.
+    pub points: [[(f32, f32); 4]; 4],
+    /// The colour values at the corners `p00`, `p03`, `p33` and `p30`, in that order.
+    pub colors: [Vec<f32>; 4],
+}
+
+/// The packing parameters shared by all mesh shadings.
+struct MeshFormat {
+    bits_per_coordinate: u8,
+    bits_per_component: u8,
+    bits_per_flag: u8,
+    /// The `[min, max]` ranges of x, y and each colour value.
+    decode: Vec<[f32; 2]>,
+}
+
+impl MeshFormat {
+    /// Reads the packing parameters of a mesh shading.
+    ///
+    /// # Parameters
+    ///
+    /// - `value_count`: The number of colour values per vertex: 1 with a `/Function`,
+    ///   otherwise the number of components of the colour space.
+    /// - `with_flags`: Whether the vertices or patches carry edge flags.
+    fn read(
+        dictionary: &Dictionary,
+        value_count: usize,
+        with_flags: bool,
+    ) -> Result<Self, MeshError> {
+        let bits = |entry_name: &'static str, valid: &[i32]| -> Result<u8, MeshError> {
+            let value = dictionary.get_or_err(entry_name)?.as_number::<i32>()?;
+            valid
+                .contains(&value)
+                .then(|| value.to_u8())
+                .flatten()
+                .ok_or(MeshError::InvalidBitDepth { entry_name, value })
+        };
+        let bits_per_coordinate = bits("BitsPerCoordinate", &[1, 2, 4, 8, 12, 16, 24, 32])?;
+        let bits_per_component = bits("BitsPerComponent", &[1, 2, 4, 8, 12, 16])?;
+        let bits_per_flag = if with_flags {
+            bits("BitsPerFlag", &[2, 4, 8])?
+        } else {
+            0
+        };
+
+        let decode = dictionary.get_or_err("Decode")?.as_vec_of::<f32>()?;
+        let expected = value_count.saturating_add(2).saturating_mul(2);
+        if decode.len() < expected {
+            return Err(MeshError::InvalidDecodeLength {
+                expected,
+                found: decode.len(),
+            });
+        }
+        let decode = decode
+            .chunks_exact(2)
+            .take(value_count.saturating_add(2))
+            .map(|pair| [pair[0], pair[1]])
+            .collect();
+
+        Ok(Self {
+            bits_per_coordinate,
+            bits_per_component,
+            bits_per_flag,
+            decode,
+        })
+    }
+
+    fn read_point(&self, reader: &mut BitReader) -> Option<(f32, f32)> {
+        let x = reader.read_decoded(self.bits_per_coordinate, *self.decode.first()?)?;
+        let y = reader.read_decoded(self.bits_per_coordinate, *self.decode.get(1)?)?;
+        Some((x, y))
+    }
+
+    fn read_values(&self, reader: &mut BitReader) -> Option<Vec<f32>> {
+        self.decode
+            .iter()
+            .skip(2)
+            .map(|&range| reader.read_decoded(self.bits_per_component, range))
+            .collect()
+    }
+
+    fn read_vertex(&self, reader: &mut BitReader) -> Option<MeshVertex> {
+        let (x, y) = self.read_point(reader)?;
+        let values = self.read_values(reader)?;
+        Some(MeshVertex { x, y, values })
+    }
+}
+
+/// Decodes the vertices of a free-form triangle mesh (shading type 4) into triangles.
+///
+/// Each vertex carries an edge flag: 0 starts a new triangle, 1 forms a triangle
+/// with the last two vertices of the previous one, and 2 with its first and last
+/// vertices. Incomplete trailing data is ignored.
+pub(crate) fn read_free_form_triangles(
+    dictionary: &Dictionary,
+    data: &[u8],
+    value_count: usize,
+) -> Result<Vec<[MeshVertex; 3]>, MeshError> {
+    let format = MeshFormat::read(dictionary, value_count, true)?;
+    let mut reader = BitReader::new(data);
+    let mut triangles: Vec<[MeshVertex; 3]> = Vec::new();
+    let mut pending: Vec<MeshVertex> = Vec::new();
+
+    while let Some(flag) = reader.read(format.bits_per_flag) {
+        let Some(vertex) = format.read_vertex(&mut reader) else {
+            break;
+        };
+        reader.align();
+
+        let triangle = match (flag, triangles.last()) {
+            (1, Some([_, b, c])) if pending.is_empty() => [b.clone(), c.clone(), vertex],
+            (2, Some([a, _, c])) if pending.is_empty() => [a.clone(), c.clone(), vertex],
+            _ => {
+                pending.push(vertex);
+                let [a, b, c] = pending.as_slice() else {
+                    continue;
+                };
+                let triangle = [a.clone(), b.clone(), c.clone()];
+                pending.clear();
+                triangle
+            }
+        };
+        triangles.push(triangle);
+    }
+    Ok(triangles)
+}
+
+/// Decodes the vertices of a lattice-form triangle mesh (shading type 5) into
+/// triangles, two for each cell between adjacent rows.
+pub(crate) fn read_lattice_triangles(
+    dictionary: &Dictionary,
+    data: &[u8],
+    value_count: usize,
+) -> Result<Vec<[MeshVertex; 3]>, MeshError> {
+    let vertices_per_row = dictionary
+        .get_or_err("VerticesPerRow")?
+        .as_number::<i32>()?;
+    let row_length = vertices_per_row
+        .to_usize()
+        .filter(|&n| n >= 2)
+        .ok_or(MeshError::InvalidVerticesPerRow(vertices_per_row))?;
+    let format = MeshFormat::read(dictionary, value_count, false)?;
+    let mut reader = BitReader::new(data);
+
+    let mut vertices = Vec::new();
+    while let Some(vertex) = format.read_vertex(&mut reader) {
+        vertices.push(vertex);
+    }
+
+    let rows: Vec<&[MeshVertex]> = vertices.chunks_exact(row_length).collect();
+    let mut triangles = Vec::new();
+    for pair in rows.windows(2) {
+        let [top, bottom] = pair else {
+            continue;
+        };
+        for (top, bottom) in top.windows(2).zip(bottom.windows(2)) {
+            if let ([a, b], [c, d]) = (top, bottom) {
+                triangles.push([a.clone(), b.clone(), c.clone()]);
+                triangles.push([b.clone(), d.clone(), c.clone()]);
+            }
+        }
+    }
+    Ok(triangles)
+}
+
+/// The control points on the boundary of a patch, in the order the data lists them.
+const BOUNDARY: [(usize, usize); 12] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (1, 3),
+    (2, 3),
+    (3, 3),
+    (3, 2),
+    (3, 1),
+    (3, 0),
+    (2, 0),
+    (1, 0),
+];
+
+/// The interior control points of a tensor-product patch, in the order the data lists them.
+const INTERIOR: [(usize, usize); 4] = [(1, 1), (1, 2), (2, 2), (2, 1)];
+
+/// Decodes a Coons (shading type 6) or tensor-product (shading type 7) patch mesh.
+///
+/// Each patch carries an edge flag: 0 reads all of its points and colours, while
+/// 1, 2 and 3 reuse an edge of the previous patch, with its two corner colours, as
+/// the patch's first edge. Coons patches get the interior control points that
+/// reproduce their surface. Incomplete trailing data is ignored.
+pub(crate) fn read_patches(
+    dictionary: &Dictionary,
+    data: &[u8],
+    value_count: usize,
+    tensor: bool,
+) -> Result<Vec<Patch>, MeshError> {
+    let format = MeshFormat::read(dictionary, value_count, true)?;
+    let mut reader = BitReader::new(data);
+    let mut patches: Vec<Patch> = Vec::new();
+
+    while let Some(flag) = reader.read(format.bits_per_flag) {
+        let Some(patch) = read_patch(&format, &mut reader, flag, patches.last(), tensor) else {
+            break;
+        };
+        reader.align();
+        patches.push(patch);
+    }
+    Ok(patches)
+}
+
+fn read_patch(
+    format: &MeshFormat,
+    reader: &mut BitReader,
+    flag: u32,
+    previous: Option<&Patch>,
+    tensor: bool,
+) -> Option<Patch> {
+    let mut points = [[(0.0, 0.0); 4]; 4];
+    let mut colors: [Vec<f32>; 4] = Default::default();
+
+    // The edge of the previous patch that becomes this patch's first edge, with the
+    // indices of its boundary points and corner colours.
+    let shared = match (flag, previous) {
+        (1, Some(previous)) => Some((previous, [3, 4, 5, 6], [1, 2])),
+        (2, Some(previous)) => Some((previous, [6, 7, 8, 9], [2, 3])),
+        (3, Some(previous)) => Some((previous, [9, 10, 11, 0], [3, 0])),
+        _ => None,
+    };
+    let first_new_point = if let Some((previous, edge, corners)) = shared {
+        for (&(i, j), &k) in BOUNDARY.iter().zip(edge.iter()) {
+            let (pi, pj) = *BOUNDARY.get(k)?;
+            points[i][j] = previous.points[pi][pj];
+        }
+        colors[0] = previous.colors[corners[0]].clone();
+        colors[1] = previous.colors[corners[1]].clone();
+        4
+    } else {
+        0
+    };
+
+    for &(i, j) in BOUNDARY.iter().skip(first_new_point) {
+        points[i][j] = format.read_point(reader)?;
+    }
+    if tensor {
+        for &(i, j) in &INTERIOR {
+            points[i][j] = format.read_point(reader)?;
+        }
+    } else {
+        coons_interior(&mut points);
+    }
+    let first_new_color = if shared.is_some() { 2 } else { 0 };
+    for color in colors.iter_mut().skip(first_new_color) {
+        *color = format.read_values(reader)?;
+    }
+    Some(Patch { points, colors })
+}
+
+/// Sets the interior control points of a Coons patch from its boundary, as
+/// ISO 32000-1, 8.7.4.5.8 specifies.
+fn coons_interior(p: &mut [[(f32, f32); 4]; 4]) {
+    let combine = |terms: [(f32, (f32, f32)); 8]| {
+        let (x, y) = terms.iter().fold((0.0, 0.0), |(x, y), &(w, (px, py))| {
+            (x + w * px, y + w * py)
+        });
+        (x / 9.0, y / 9.0)
+    };
+    let interior = |corner: (f32, f32),
+                    near: [(f32, f32); 2],
+                    far: [(f32, f32); 2],
+                    mid: [(f32, f32); 2],
+                    opposite: (f32, f32)| {
+        combine([
+            (-4.0, corner),
+            (6.0, near[0]),
+            (6.0, near[1]),
+            (-2.0, far[0]),
+            (-2.0, far[1]),
+            (3.0, mid[0]),
+            (3.0, mid[1]),
+            (-1.0, opposite),
+        ])
+    };
+    p[1][1] = interior(
+        p[0][0],
+        [p[0][1], p[1][0]],
+        [p[0][3], p[3][0]],
+        [p[3][1], p[1][3]],
+        p[3][3],
+    );
+    p[1][2] = interior(
+        p[0][3],
+        [p[0][2], p[1][3]],
+        [p[0][0], p[3][3]],
+        [p[3][2], p[1][0]],
+        p[3][0],
+    );
+    p[2][1] = interior(
+        p[3][0],
+        [p[3][1], p[2][0]],
+        [p[3][3], p[0][0]],
+        [p[0][1], p[2][3]],
+        p[0][3],
+    );
+    p[2][2] = interior(
+        p[3][3],
+        [p[3][2], p[2][3]],
+        [p[3][0], p[0][3]],
+        [p[0][2], p[2][0]],
+        p[0][0],
+    );
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use pdf_object::ObjectVariant;
+
+    use super::*;
+
+    /// A mesh dictionary with the given bits per coordinate, component and flag,
+    /// coordinates decoded to [0, `max`] and one colour value decoded to [0, 1].
+    fn format(bits: [i64; 3], max: f64, extra: &[(&str, i64)]) -> Dictionary {
+        let names = ["BitsPerCoordinate", "BitsPerComponent", "BitsPerFlag"];
+        let mut entries: BTreeMap<_, _> = names
+            .into_iter()
+            .zip(bits)
+            .chain(extra.iter().copied())
+            .map(|(key, value)| (key.to_string(), Box::new(ObjectVariant::Integer(value))))
+            .collect();
+        let decode = [0.0, max, 0.0, max, 0.0, 1.0].map(ObjectVariant::Real);
+        entries.insert(
+            "Decode".to_string(),
+            Box::new(ObjectVariant::Array(decode.to_vec())),
+        );
+        Dictionary::new(entries)
+    }
+
+    fn position(vertex: &MeshVertex) -> (f32, f32) {
+        (vertex.x, vertex.y)
+    }
+
+    #[test]
+    fn free_form_flags_reuse_previous_vertices() {
+        #[rustfmt::skip]
+        let data = [
+            0, 0, 0, 0,
+            0, 10, 0, 255,
+            0, 0, 10, 0,
+            // Flag 1: with the last two vertices of the previous triangle.
+            1, 10, 10, 255,
+            // Flag 2: with the first and last vertices of the previous triangle.
+            2, 20, 20, 0,
+            // Incomplete trailing data.
+            0, 5,
+        ];
+        let dictionary = format([8, 8, 8], 255.0, &[]);
+        let triangles = read_free_form_triangles(&dictionary, &data, 1).unwrap();
+        let positions: Vec<_> = triangles
+            .iter()
+            .map(|triangle| triangle.each_ref().map(position))
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)],
+                [(10.0, 0.0), (0.0, 10.0), (10.0, 10.0)],
+                [(10.0, 0.0), (10.0, 10.0), (20.0, 20.0)],
+            ]
+        );
+        assert_eq!(triangles[0][1].values, [1.0]);
+    }
+
+    #[test]
+    fn free_form_vertices_are_bit_packed_and_byte_aligned() {
+        // Each vertex is a 2-bit flag, two 4-bit coordinates and a 4-bit value,
+        // padded to two bytes: 00 0011 0101 1111 00.
+        let data = [0x0d, 0x7c, 0x0d, 0x7c, 0x0d, 0x7c];
+        let dictionary = format([4, 4, 2], 15.0, &[]);
+        let triangles = read_free_form_triangles(&dictionary, &data, 1).unwrap();
+        assert_eq!(triangles.len(), 1);
+        assert_eq!(position(&triangles[0][2]), (3.0, 5.0));
+        assert_eq!(triangles[0][2].values, [1.0]);
+    }
+
+    #[test]
+    fn lattice_rows_form_two_triangles_per_cell() {
+        #[rustfmt::skip]
+        let data = [
+            0, 0, 0, 10, 0, 0, 20, 0, 0,
+            0, 10, 0, 10, 10, 0, 20, 10, 0,
+        ];
+        let dictionary = format([8, 8, 8], 255.0, &[("VerticesPerRow", 3)]);
+        let triangles = read_lattice_triangles(&dictionary, &data, 1).unwrap();
+        assert_eq!(triangles.len(), 4);
+        assert_eq!(
+            triangles[1].each_ref().map(position),
+            [(10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]
+        );
+
+        let dictionary = format([8, 8, 8], 255.0, &[("VerticesPerRow", 1)]);
+        let result = read_lattice_triangles(&dictionary, &data, 1);
+        assert!(matches!(result, Err(MeshError::InvalidVerticesPerRow(1))));
+    }
+
+    #[test]
+    fn coons_patches_share_edges_and_get_interior_points() {
+        // The boundary of a square whose control points lie on a 30-unit grid.
+        let grid = [0, 30, 60, 90];
+        let boundary: Vec<u8> = BOUNDARY
+            .iter()
+            .flat_map(|&(i, j)| [grid[i], grid[j]])
+            .collect();
+        let mut data = vec![0];
+        data.extend(&boundary);
+        data.extend([0, 85, 170, 255]);
+        // Flag 1 reuses the edge from p03 to p33 of the previous patch, and its colours.
+        data.push(1);
+        data.extend(&boundary[8..]);
+        data.extend([0, 255]);
+
+        let dictionary = format([8, 8, 8], 255.0, &[]);
+        let patches = read_patches(&dictionary, &data, 1, false).unwrap();
+        assert_eq!(patches.len(), 2);
+        let (first, second) = (&patches[0], &patches[1]);
+        assert_eq!(first.points[1][1], (30.0, 30.0));
+        assert_eq!(first.points[2][2], (60.0, 60.0));
+        assert_eq!(
+            second.points[0],
+            [
+                first.points[0][3],
+                first.points[1][3],
+                first.points[2][3],
+                first.points[3][3]
+            ]
+        );
+        assert_eq!(second.colors[0], first.colors[1]);
+        assert_eq!(second.colors[1], first.colors[2]);
+        assert_eq!(second.colors[3], [1.0]);
+    }
+}
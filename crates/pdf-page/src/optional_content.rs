@@ -0,0 +1,651 @@
+use std::collections::HashSet;
+
+use pdf_object::{
+    ObjectVariant, dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
+    traits::FromDictionary,
+};
+use thiserror::Error;
+
+/// How deeply `/VE` visibility expressions and `/Order` lists may nest. Deeper
+/// nesting can only come from reference cycles.
+const MAX_NESTING: u8 = 32;
+
+/// Errors that can occur while reading optional content dictionaries.
+#[derive(Debug, Error)]
+pub enum OptionalContentError {
+    #[error("Invalid visibility expression operator '{0}'")]
+    InvalidExpressionOperator(String),
+    #[error("Optional content is nested too deeply")]
+    TooDeeplyNested,
+    #[error("{0}")]
+    ObjectError(#[from] ObjectError),
+}
+
+/// How the visibility of an optional content membership dictionary follows
+/// from its groups. Corresponds to the `/P` entry.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum VisibilityPolicy {
+    /// Visible only if all of the groups are on.
+    AllOn,
+    /// Visible if any of the groups is on.
+    #[default]
+    AnyOn,
+    /// Visible if any of the groups is off.
+    AnyOff,
+    /// Visible only if all of the groups are off.
+    AllOff,
+}
+
+/// A `/VE` visibility expression, combining the states of groups.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VisibilityExpression {
+    /// The state of the group with this object number.
+    Group(i32),
+    And(Vec<VisibilityExpression>),
+    Or(Vec<VisibilityExpression>),
+    Not(Box<VisibilityExpression>),
+}
+
+/// The optional content a piece of content belongs to, from an `/OC` entry or a
+/// marked-content property list (ISO 32000-1, 8.11.2).
+///
+/// Groups are identified by the object numbers of their dictionaries.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionalContent {
+    /// An optional content group (`/Type /OCG`).
+    Group(i32),
+    /// An optional content membership dictionary (`/Type /OCMD`).
+    Membership {
+        /// The groups of `/OCGs`.
+        groups: Vec<i32>,
+        /// The `/P` policy applied to `groups`.
+        policy: VisibilityPolicy,
+        /// The `/VE` expression, which takes precedence over `groups` and `policy`.
+        expression: Option<VisibilityExpression>,
+    },
+}
+
+impl OptionalContent {
+    /// Reads the optional content group or membership dictionary `object` refers to.
+    ///
+    /// Returns `None` for other property lists, including objects that are not
+    /// dictionaries, and for groups that are not indirect objects and so cannot
+    /// be told apart.
+    pub fn from_object(
+        object: &ObjectVariant,
+        objects: &ObjectCollection,
+    ) -> Result<Option<Self>, OptionalContentError> {
+        let Ok(dictionary) = objects.resolve_dictionary(object) else {
+            return Ok(None);
+        };
+        match type_name(dictionary).as_deref() {
+            Some("OCG") => Ok(object.as_object_number().map(OptionalContent::Group)),
+            Some("OCMD") => {
+                let groups = match dictionary.get("OCGs").map(|g| objects.resolve_object(g)) {
+                    Some(Ok(ObjectVariant::Array(groups))) => groups
+                        .iter()
+                        .filter_map(ObjectVariant::as_object_number)
+                        .collect(),
+                    Some(Ok(_)) => dictionary
+                        .get("OCGs")
+                        .and_then(ObjectVariant::as_object_number)
+                        .into_iter()
+                        .collect(),
+                    Some(Err(e)) => return Err(e.into()),
+                    None => Vec::new(),
+                };
+                let policy = match dictionary.get("P").and_then(|p| p.as_str()) {
+                    Some(policy) => match policy.as_ref() {
+                        "AllOn" => VisibilityPolicy::AllOn,
+                        "AnyOff" => VisibilityPolicy::AnyOff,
+                        "AllOff" => VisibilityPolicy::AllOff,
+                        _ => VisibilityPolicy::AnyOn,
+                    },
+                    None => VisibilityPolicy::AnyOn,
+                };
+                let expression = match dictionary
+                    .get("VE")
+                    .map(|ve| read_expression(ve, objects, 0))
+                {
+                    Some(Ok(expression)) => Some(expression),
+                    // An expression that cannot be evaluated leaves the content
+                    // visible, as a membership dictionary without groups does.
+                    Some(Err(_)) => {
+                        return Ok(Some(OptionalContent::Membership {
+                            groups: Vec::new(),
+                            policy,
+                            expression: None,
+                        }));
+                    }
+                    None => None,
+                };
+                Ok(Some(OptionalContent::Membership {
+                    groups,
+                    policy,
+                    expression,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl FromDictionary for OptionalContent {
+    const KEY: &'static str = "OC";
+    type ResultType = Option<OptionalContent>;
+    type ErrorType = OptionalContentError;
+
+    /// Reads the `/OC` entry of a form or image XObject.
+    fn from_dictionary(
+        dictionary: &Dictionary,
+        objects: &ObjectCollection,
+    ) -> Result<Self::ResultType, Self::ErrorType> {
+        match dictionary.get(Self::KEY) {
+            Some(oc) => OptionalContent::from_object(oc, objects),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Returns the `/Type` of a dictionary, if it has one.
+fn type_name(dictionary: &Dictionary) -> Option<String> {
+    dictionary
+        .get("Type")
+        .and_then(|t| t.as_str().map(|t| t.into_owned()))
+}
+
+/// Reads a `/VE` visibility expression: a group, or an array of an operator name
+/// followed by its operands.
+fn read_expression(
+    object: &ObjectVariant,
+    objects: &ObjectCollection,
+    depth: u8,
+) -> Result<VisibilityExpression, OptionalContentError> {
+    if depth > MAX_NESTING {
+        return Err(OptionalContentError::TooDeeplyNested);
+    }
+    let resolved = objects.resolve_object(object)?;
+    let ObjectVariant::Array(items) = resolved else {
+        return Ok(VisibilityExpression::Group(object.try_object_number()?));
+    };
+
+    let Some((operator, operands)) = items.split_first() else {
+        return Err(OptionalContentError::InvalidExpressionOperator(
+            String::new(),
+        ));
+    };
+    let operands = operands
+        .iter()
+        .map(|operand| read_expression(operand, objects, depth.saturating_add(1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    match operator.try_str()?.as_ref() {
+        "And" => Ok(VisibilityExpression::And(operands)),
+        "Or" => Ok(VisibilityExpression::Or(operands)),
+        "Not" => match operands.into_iter().next() {
+            Some(operand) => Ok(VisibilityExpression::Not(Box::new(operand))),
+            None => Err(OptionalContentError::InvalidExpressionOperator(
+                "Not".to_string(),
+            )),
+        },
+        other => Err(OptionalContentError::InvalidExpressionOperator(
+            other.to_string(),
+        )),
+    }
+}
+
+/// An optional content group of the document, also known as a layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionalContentGroup {
+    /// The object number of the group's dictionary, which identifies it.
+    pub id: i32,
+    /// The `/Name` shown to users.
+    pub name: String,
+    /// Whether content in the group is drawn, initially from the default
+    /// configuration.
+    pub visible: bool,
+}
+
+/// An entry of the `/Order` array, which presents the groups as a tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderItem {
+    /// A group. A `List` following it holds the group's children.
+    Group(i32),
+    /// A nested list, with an optional text label as its first element.
+    List {
+        label: Option<String>,
+        items: Vec<OrderItem>,
+    },
+}
+
+/// The document's optional content, from the catalog's `/OCProperties`
+/// dictionary, with each group's visibility taken from the default
+/// configuration `/D`.
+#[derive(Debug, Clone, Default)]
+pub struct OptionalContentProperties {
+    /// All groups of the document, in the order of `/OCGs`.
+    pub groups: Vec<OptionalContentGroup>,
+    /// How a user interface should present the groups. Corresponds to `/Order`
+    /// in the default configuration.
+    pub order: Vec<OrderItem>,
+}
+
+impl FromDictionary for OptionalContentProperties {
+    const KEY: &'static str = "OCProperties";
+    type ResultType = Self;
+    type ErrorType = OptionalContentError;
+
+    fn from_dictionary(
+        dictionary: &Dictionary,
+        objects: &ObjectCollection,
+    ) -> Result<Self::ResultType, Self::ErrorType> {
+        let config = dictionary
+            .get("D")
+            .map(|d| objects.resolve_dictionary(d))
+            .transpose()?;
+        let group_set = |key: &str| -> Result<HashSet<i32>, ObjectError> {
+            match config.and_then(|config| config.get(key)) {
+                Some(list) => Ok(objects
+                    .resolve_object(list)?
+                    .try_array()?
+                    .iter()
+                    .filter_map(ObjectVariant::as_object_number)
+                    .collect()),
+                None => Ok(HashSet::new()),
+            }
+        };
+        let on = group_set("ON")?;
+        let off = group_set("OFF")?;
+        // `/Unchanged` only matters for alternate configurations; the default
+        // configuration treats it like `/ON`.
+        let base_visible = config
+            .and_then(|config| config.get("BaseState"))
+            .map(|state| state.try_str())
+            .transpose()?
+            .is_none_or(|state| state != "OFF");
+
+        let mut groups = Vec::new();
+        if let Some(ocgs) = dictionary.get("OCGs") {
+            for group in objects.resolve_object(ocgs)?.try_array()? {
+                // Entries that are not references to a group dictionary are skipped.
+                let Some(id) = group.as_object_number() else {
+                    continue;
+                };
+                let Ok(group) = objects.resolve_dictionary(group) else {
+                    continue;
+                };
+                let name = group
+                    .get("Name")
+                    .and_then(|name| name.as_str().map(|name| name.into_owned()))
+                    .unwrap_or_default();
+                let visible = if off.contains(&id) {
+                    false
+                } else {
+                    base_visible || on.contains(&id)
+                };
+                groups.push(OptionalContentGroup { id, name, visible });
+            }
+        }
+
+        // `/Order` only affects how groups are presented, so one that cannot be
+        // read, such as a cyclic one, is dropped.
+        let order = config
+            .and_then(|config| config.get("Order"))
+            .and_then(|order| {
+                let entries = objects.resolve_object(order).ok()?.as_array()?;
+                read_order(entries, objects, 0).ok()
+            })
+            .unwrap_or_default();
+
+        Ok(Self { groups, order })
+    }
+}
+
+/// Reads the entries of an `/Order` array or one of its nested lists.
+fn read_order(
+    entries: &[ObjectVariant],
+    objects: &ObjectCollection,
+    depth: u8,
+) -> Result<Vec<OrderItem>, OptionalContentError> {
+    if depth > MAX_NESTING {
+        return Err(OptionalContentError::TooDeeplyNested);
+    }
+    let mut items = Vec::new();
+    for entry in entries {
+        if let ObjectVariant::Array(list) = objects.resolve_object(entry)? {
+            let (label, rest) = match list.split_first() {
+                Some((
+                    label @ (ObjectVariant::LiteralString(_) | ObjectVariant::HexString(_)),
+                    rest,
+                )) => (label.as_str().map(|label| label.into_owned()), rest),
+                _ => (None, list.as_slice()),
+            };
+            items.push(OrderItem::List {
+                label,
+                items: read_order(rest, objects, depth.saturating_add(1))?,
+            });
+        } else if let Some(id) = entry.as_object_number() {
+            items.push(OrderItem::Group(id));
+        }
+    }
+    Ok(items)
+}
+
+impl OptionalContentProperties {
+    /// Returns whether the group with object number `id` is on. Groups the
+    /// document does not list are taken to be on.
+    pub fn is_group_visible(&self, id: i32) -> bool {
+        self.groups
+            .iter()
+            .find(|group| group.id == id)
+            .is_none_or(|group| group.visible)
+    }
+
+    /// Turns the group with object number `id` on or off.
+    ///
+    /// Returns `false` if the document has no such group.
+    pub fn set_group_visible(&mut self, id: i32, visible: bool) -> bool {
+        match self.groups.iter_mut().find(|group| group.id == id) {
+            Some(group) => {
+                group.visible = visible;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether content belonging to `content` is drawn.
+    pub fn is_visible(&self, content: &OptionalContent) -> bool {
+        match content {
+            OptionalContent::Group(id) => self.is_group_visible(*id),
+            OptionalContent::Membership {
+                expression: Some(expression),
+                ..
+            } => self.evaluate(expression),
+            OptionalContent::Membership { groups, policy, .. } => {
+                // A membership dictionary without groups has no effect.
+                if groups.is_empty() {
+                    return true;
+                }
+                let mut states = groups.iter().map(|&id| self.is_group_visible(id));
+                match policy {
+                    VisibilityPolicy::AllOn => states.all(|on| on),
+                    VisibilityPolicy::AnyOn => states.any(|on| on),
+                    VisibilityPolicy::AnyOff => states.any(|on| !on),
+                    VisibilityPolicy::AllOff => states.all(|on| !on),
+                }
+            }
+        }
+    }
+
+    fn evaluate(&self, expression: &VisibilityExpression) -> bool {
+        match expression {
+            VisibilityExpression::Group(id) => self.is_group_visible(*id),
+            VisibilityExpression::And(operands) => operands.iter().all(|e| self.evaluate(e)),
+            VisibilityExpression::Or(operands) => operands.iter().any(|e| self.evaluate(e)),
+            VisibilityExpression::Not(operand) => !self.evaluate(operand),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::{collections::BTreeMap, rc::Rc};
+
+    use pdf_object::indirect_object::IndirectObject;
+
+    use super::*;
+
+    fn dictionary(entries: &[(&str, ObjectVariant)]) -> Dictionary {
+        Dictionary::new(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), Box::new(value.clone())))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    fn name(name: &str) -> ObjectVariant {
+        ObjectVariant::Name(name.to_string())
+    }
+
+    fn references(ids: &[i32]) -> ObjectVariant {
+        ObjectVariant::Array(ids.iter().map(|&id| ObjectVariant::Reference(id)).collect())
+    }
+
+    /// A collection holding the groups `ids`, each named after its number.
+    fn groups(ids: &[i32]) -> ObjectCollection {
+        let mut objects = ObjectCollection::default();
+        for &id in ids {
+            let group = dictionary(&[
+                ("Type", name("OCG")),
+                ("Name", ObjectVariant::LiteralString(format!("Layer {id}"))),
+            ]);
+            let object =
+                IndirectObject::new(id, 0, Some(ObjectVariant::Dictionary(Rc::new(group))));
+            objects
+                .insert(ObjectVariant::IndirectObject(Rc::new(object)))
+                .unwrap();
+        }
+        objects
+    }
+
+    /// Properties where group 1 is on and group 2 is off.
+    fn one_on_two_off() -> OptionalContentProperties {
+        OptionalContentProperties {
+            groups: vec![
+                OptionalContentGroup {
+                    id: 1,
+                    name: String::new(),
+                    visible: true,
+                },
+                OptionalContentGroup {
+                    id: 2,
+                    name: String::new(),
+                    visible: false,
+                },
+            ],
+            order: Vec::new(),
+        }
+    }
+
+    fn membership(
+        entries: &[(&str, ObjectVariant)],
+        objects: &ObjectCollection,
+    ) -> OptionalContent {
+        let mut entries = entries.to_vec();
+        entries.push(("Type", name("OCMD")));
+        let object = ObjectVariant::Dictionary(Rc::new(dictionary(&entries)));
+        OptionalContent::from_object(&object, objects)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn membership_policies_combine_group_states() {
+        let properties = one_on_two_off();
+        let objects = groups(&[1, 2]);
+        let visible = |policy: &str, ids: &[i32]| {
+            let content = membership(&[("OCGs", references(ids)), ("P", name(policy))], &objects);
+            properties.is_visible(&content)
+        };
+
+        assert!(!visible("AllOn", &[1, 2]));
+        assert!(visible("AllOn", &[1]));
+        assert!(visible("AnyOn", &[1, 2]));
+        assert!(!visible("AnyOn", &[2]));
+        assert!(visible("AnyOff", &[1, 2]));
+        assert!(!visible("AnyOff", &[1]));
+        assert!(!visible("AllOff", &[1, 2]));
+        assert!(visible("AllOff", &[2]));
+        // `/AnyOn` is the default, and a membership without groups has no effect.
+        assert!(!visible("Unknown", &[2]));
+        assert!(visible("AllOff", &[]));
+    }
+
+    #[test]
+    fn visibility_expression_takes_precedence() {
+        let properties = one_on_two_off();
+        let objects = groups(&[1, 2]);
+        let visible = |expression: ObjectVariant| {
+            // `/OCGs` and `/P` alone would make the content visible.
+            let content = membership(&[("OCGs", references(&[1])), ("VE", expression)], &objects);
+            properties.is_visible(&content)
+        };
+        let operation = |operator: &str, operands: Vec<ObjectVariant>| {
+            let mut items = vec![name(operator)];
+            items.extend(operands);
+            ObjectVariant::Array(items)
+        };
+
+        assert!(!visible(operation(
+            "And",
+            vec![ObjectVariant::Reference(1), ObjectVariant::Reference(2)]
+        )));
+        assert!(visible(operation(
+            "Or",
+            vec![ObjectVariant::Reference(1), ObjectVariant::Reference(2)]
+        )));
+        assert!(visible(operation("Not", vec![ObjectVariant::Reference(2)])));
+        assert!(!visible(operation(
+            "Not",
+            vec![operation(
+                "Or",
+                vec![ObjectVariant::Reference(2), ObjectVariant::Reference(1)]
+            )]
+        )));
+    }
+
+    #[test]
+    fn unreadable_visibility_expression_is_visible() {
+        let properties = one_on_two_off();
+        let objects = groups(&[1, 2]);
+        for expression in [
+            ObjectVariant::Array(vec![name("Xor"), ObjectVariant::Reference(2)]),
+            ObjectVariant::Array(vec![name("Not")]),
+            ObjectVariant::Array(Vec::new()),
+        ] {
+            let content = membership(&[("OCGs", references(&[2])), ("VE", expression)], &objects);
+            assert!(properties.is_visible(&content));
+        }
+    }
+
+    #[test]
+    fn other_property_lists_are_not_optional_content() {
+        let objects = groups(&[1]);
+        let marked =
+            ObjectVariant::Dictionary(Rc::new(dictionary(&[("MCID", ObjectVariant::Integer(0))])));
+        let typed = ObjectVariant::Dictionary(Rc::new(dictionary(&[("Type", name("Other"))])));
+        for object in [
+            marked,
+            typed,
+            ObjectVariant::Integer(3),
+            ObjectVariant::Reference(9),
+        ] {
+            assert_eq!(
+                OptionalContent::from_object(&object, &objects).unwrap(),
+                None
+            );
+        }
+        assert_eq!(
+            OptionalContent::from_object(&ObjectVariant::Reference(1), &objects).unwrap(),
+            Some(OptionalContent::Group(1))
+        );
+    }
+
+    #[test]
+    fn default_configuration_sets_group_visibility() {
+        let objects = groups(&[1, 2, 3]);
+        let read = |config: &[(&str, ObjectVariant)]| {
+            let properties = dictionary(&[
+                ("OCGs", references(&[1, 2, 3])),
+                ("D", ObjectVariant::Dictionary(Rc::new(dictionary(config)))),
+            ]);
+            let properties =
+                OptionalContentProperties::from_dictionary(&properties, &objects).unwrap();
+            properties
+                .groups
+                .iter()
+                .map(|group| group.visible)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(read(&[]), [true, true, true]);
+        assert_eq!(read(&[("OFF", references(&[2]))]), [true, false, true]);
+        assert_eq!(
+            read(&[("BaseState", name("OFF")), ("ON", references(&[3]))]),
+            [false, false, true]
+        );
+        // `/OFF` wins over `/ON`, and `/Unchanged` is taken as on.
+        assert_eq!(
+            read(&[
+                ("BaseState", name("Unchanged")),
+                ("ON", references(&[1])),
+                ("OFF", references(&[1])),
+            ]),
+            [false, true, true]
+        );
+    }
+
+    #[test]
+    fn order_nests_labelled_lists() {
+        let objects = groups(&[1, 2, 3]);
+        let nested = ObjectVariant::Array(vec![
+            ObjectVariant::LiteralString("Labelled".to_string()),
+            ObjectVariant::Reference(2),
+            ObjectVariant::Array(vec![ObjectVariant::Reference(3)]),
+        ]);
+        let config = dictionary(&[(
+            "Order",
+            ObjectVariant::Array(vec![ObjectVariant::Reference(1), nested]),
+        )]);
+        let properties = dictionary(&[
+            ("OCGs", references(&[1, 2, 3])),
+            ("D", ObjectVariant::Dictionary(Rc::new(config))),
+        ]);
+
+        let properties = OptionalContentProperties::from_dictionary(&properties, &objects).unwrap();
+        assert_eq!(properties.groups.len(), 3);
+        assert_eq!(properties.groups[1].name, "Layer 2");
+        assert_eq!(
+            properties.order,
+            [
+                OrderItem::Group(1),
+                OrderItem::List {
+                    label: Some("Labelled".to_string()),
+                    items: vec![
+                        OrderItem::Group(2),
+                        OrderItem::List {
+                            label: None,
+                            items: vec![OrderItem::Group(3)],
+                        },
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unreadable_order_and_groups_are_dropped() {
+        let mut objects = groups(&[1]);
+        // An `/Order` list that contains itself.
+        let cyclic = IndirectObject::new(
+            5,
+            0,
+            Some(ObjectVariant::Array(vec![ObjectVariant::Reference(5)])),
+        );
+        objects
+            .insert(ObjectVariant::IndirectObject(Rc::new(cyclic)))
+            .unwrap();
+        let config = dictionary(&[("Order", ObjectVariant::Reference(5))]);
+        let properties = dictionary(&[
+            ("OCGs", references(&[1, 9])),
+            ("D", ObjectVariant::Dictionary(Rc::new(config))),
+        ]);
+
+        let properties = OptionalContentProperties::from_dictionary(&properties, &objects).unwrap();
+        assert_eq!(properties.groups.len(), 1);
+        assert_eq!(properties.groups[0].id, 1);
+        assert!(properties.order.is_empty());
+    }
+}
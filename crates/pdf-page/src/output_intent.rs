@@ -0,0 +1,53 @@
+use pdf_graphics::color_management::IccProfile;
+use pdf_object::{
+    dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
+    traits::FromDictionary,
+};
+use thiserror::Error;
+
+/// An entry of the document catalog's `/OutputIntents` array, describing the
+/// colour characteristics of the device the document was prepared for.
+#[derive(Debug)]
+pub struct OutputIntent {
+    /// The `/S` entry, such as `GTS_PDFX` or `GTS_PDFA1`.
+    pub subtype: String,
+    /// The `/OutputConditionIdentifier` entry, naming the intended printing condition.
+    pub output_condition_identifier: Option<String>,
+    /// The parsed `/DestOutputProfile`. A missing or unusable profile is `None`.
+    pub dest_output_profile: Option<IccProfile>,
+}
+
+/// Defines errors that can occur while reading an output intent dictionary.
+#[derive(Debug, Error)]
+pub enum OutputIntentError {
+    #[error("Error parsing OutputIntent: {0}")]
+    ObjectError(#[from] ObjectError),
+}
+
+impl FromDictionary for OutputIntent {
+    const KEY: &'static str = "OutputIntent";
+    type ResultType = Self;
+    type ErrorType = OutputIntentError;
+
+    fn from_dictionary(
+        dictionary: &Dictionary,
+        objects: &ObjectCollection,
+    ) -> Result<Self::ResultType, Self::ErrorType> {
+        let subtype = dictionary.get_or_err("S")?.try_str()?.into_owned();
+        let output_condition_identifier = dictionary
+            .get("OutputConditionIdentifier")
+            .and_then(|obj| obj.as_str().map(|s| s.into_owned()));
+
+        // The profile only refines colour conversion, so a broken one is ignored.
+        let dest_output_profile = dictionary
+            .get("DestOutputProfile")
+            .and_then(|obj| objects.resolve_stream(obj).ok())
+            .and_then(|stream| IccProfile::new(&stream.data).ok());
+
+        Ok(Self {
+            subtype,
+            output_condition_identifier,
+            dest_output_profile,
+        })
+    }
+}
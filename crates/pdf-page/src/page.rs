@@ -1,10 +1,16 @@
 use crate::{
-    content_stream::ContentStream, media_box::MediaBox, pages::PdfPagesError, resources::Resources,
+    annotation::Annotation, content_stream::ContentStream, page_box::PageBox, pages::PdfPagesError,
+    resources::Resources, transparency_group::TransparencyGroup,
 };
 use pdf_object::{
-    dictionary::Dictionary, object_collection::ObjectCollection, traits::FromDictionary,
+    ObjectVariant, dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
+    traits::FromDictionary,
 };
 
+/// How many `/Parent` links are followed when looking up an inherited attribute,
+/// which guards against cycles in malformed page trees.
+const MAX_TREE_DEPTH: usize = 64;
+
 /// Represents a single page in a PDF document.
 ///
 /// A page object is a dictionary that describes a single page of a document.
@@ -14,10 +20,33 @@ pub struct PdfPage {
     /// The contents of the page, which can be a single stream object or
     /// an array of streams.
     pub contents: Option<ContentStream>,
-    /// `/MediaBox` attribute which defines the page boundaries.
-    pub media_box: Option<MediaBox>,
+    /// `/MediaBox` attribute which defines the boundaries of the physical medium.
+    /// A US Letter page is assumed when neither the page nor its ancestors have one.
+    pub media_box: PageBox,
+    /// `/CropBox` attribute, the region the page is displayed in. Defaults to the
+    /// media box and is limited to it.
+    pub crop_box: PageBox,
+    /// `/BleedBox` attribute, the region to clip to in production. Defaults to the
+    /// crop box and is limited to the media box.
+    pub bleed_box: PageBox,
+    /// `/TrimBox` attribute, the intended dimensions of the finished page. Defaults
+    /// to the crop box and is limited to the media box.
+    pub trim_box: PageBox,
+    /// `/ArtBox` attribute, the extent of the page's meaningful content. Defaults
+    /// to the crop box and is limited to the media box.
+    pub art_box: PageBox,
+    /// `/Rotate` attribute, the clockwise rotation applied when the page is
+    /// displayed: 0, 90, 180 or 270 degrees.
+    pub rotate: u16,
+    /// `/UserUnit` attribute, the size of a default user space unit in multiples
+    /// of 1/72 inch.
+    pub user_unit: f32,
     /// `/Resources` attribute which defines the resources used by the page.
     pub resources: Option<Resources>,
+    /// `/Group` attribute, present when the page is a transparency group.
+    pub group: Option<TransparencyGroup>,
+    /// The annotations of the `/Annots` array, in drawing order.
+    pub annotations: Vec<Annotation>,
 }
 
 impl FromDictionary for PdfPage {
@@ -31,13 +60,106 @@ impl FromDictionary for PdfPage {
         objects: &ObjectCollection,
     ) -> Result<Self::ResultType, Self::ErrorType> {
         let contents = ContentStream::from_dictionary(dictionary, objects)?;
-        let media_box = MediaBox::from_dictionary(dictionary, objects)?;
+        let media_box = inherited(dictionary, objects, "MediaBox")?
+            .map(PageBox::from_object)
+            .transpose()?
+            .unwrap_or_default();
+        let crop_box = inherited(dictionary, objects, "CropBox")?
+            .map(PageBox::from_object)
+            .transpose()?
+            .map_or(media_box, |crop_box| crop_box.intersect(&media_box));
+        let production_box = |key: &str| -> Result<PageBox, PdfPagesError> {
+            Ok(dictionary
+                .get(key)
+                .map(|page_box| PageBox::from_object(objects.resolve_object(page_box)?))
+                .transpose()?
+                .map_or(crop_box, |page_box| page_box.intersect(&media_box)))
+        };
+        // Rotations that are not a multiple of 90 degrees are invalid and ignored.
+        let rotate = match inherited(dictionary, objects, "Rotate")?
+            .map(|rotate| rotate.as_number::<i32>())
+            .transpose()?
+            .unwrap_or(0)
+            .rem_euclid(360)
+        {
+            90 => 90,
+            180 => 180,
+            270 => 270,
+            _ => 0,
+        };
+        // A unit that is not a positive number falls back to the default of 1/72 inch.
+        let user_unit = dictionary
+            .get("UserUnit")
+            .and_then(|unit| unit.as_number::<f32>().ok())
+            .filter(|unit| unit.is_finite() && *unit > 0.0)
+            .unwrap_or(1.0);
+
         let resources = Resources::from_dictionary(dictionary, objects)?;
+        let group = TransparencyGroup::from_dictionary(dictionary, objects)?;
+
+        // Read the optional `/Annots` array. An annotation that cannot be read is
+        // skipped, as the page renders without it.
+        let mut annotations = Vec::new();
+        if let Some(Ok(annots)) = dictionary
+            .get("Annots")
+            .map(|annots| objects.resolve_object(annots))
+            && let Ok(annots) = annots.try_array()
+        {
+            for annot in annots {
+                if let Ok(annot) = objects.resolve_dictionary(annot)
+                    && let Ok(annotation) = Annotation::from_dictionary(annot, objects)
+                {
+                    annotations.push(annotation);
+                }
+            }
+        }
 
         Ok(Self {
             contents,
             media_box,
+            crop_box,
+            bleed_box: production_box("BleedBox")?,
+            trim_box: production_box("TrimBox")?,
+            art_box: production_box("ArtBox")?,
+            rotate,
+            user_unit,
             resources,
+            group,
+            annotations,
         })
     }
 }
+
+impl PdfPage {
+    /// Returns the width and height of the page as displayed: the crop box after
+    /// `/Rotate`, in units of 1/72 inch after `/UserUnit`.
+    pub fn display_size(&self) -> (f32, f32) {
+        let width = self.crop_box.width() * self.user_unit;
+        let height = self.crop_box.height() * self.user_unit;
+        if matches!(self.rotate, 90 | 270) {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+}
+
+/// Looks up an inheritable page attribute (ISO 32000-1, 7.7.3.4) on the page or,
+/// failing that, on its nearest ancestor in the page tree that has it.
+fn inherited<'a>(
+    dictionary: &'a Dictionary,
+    objects: &'a ObjectCollection,
+    key: &str,
+) -> Result<Option<&'a ObjectVariant>, ObjectError> {
+    let mut node = dictionary;
+    for _ in 0..MAX_TREE_DEPTH {
+        if let Some(value) = node.get(key) {
+            return objects.resolve_object(value).map(Some);
+        }
+        let Some(parent) = node.get("Parent") else {
+            break;
+        };
+        node = objects.resolve_dictionary(parent)?;
+    }
+    Ok(None)
+}
@@ -0,0 +1,73 @@
+use pdf_object::ObjectVariant;
+use pdf_object::error::ObjectError;
+use thiserror::Error;
+
+/// A page boundary rectangle, such as the `/MediaBox` or `/CropBox` of a page
+/// (ISO 32000-1, 14.11.2).
+///
+/// The rectangle is expressed in default user space units and normalised so that
+/// `left <= right` and `bottom <= top`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageBox {
+    /// The x-coordinate of the lower-left corner of the rectangle.
+    pub left: f32,
+    /// The y-coordinate of the lower-left corner of the rectangle.
+    pub bottom: f32,
+    /// The x-coordinate of the upper-right corner of the rectangle.
+    pub right: f32,
+    /// The y-coordinate of the upper-right corner of the rectangle.
+    pub top: f32,
+}
+
+impl Default for PageBox {
+    /// A US Letter page, used when a page has no `/MediaBox` at all.
+    fn default() -> Self {
+        PageBox::new(0.0, 0.0, 612.0, 792.0)
+    }
+}
+
+impl PageBox {
+    /// Creates a rectangle from two opposite corners, in any order.
+    pub fn new(x0: f32, y0: f32, x1: f32, y1: f32) -> Self {
+        PageBox {
+            left: x0.min(x1),
+            bottom: y0.min(y1),
+            right: x0.max(x1),
+            top: y0.max(y1),
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> f32 {
+        self.top - self.bottom
+    }
+
+    /// Returns the part of this rectangle that lies inside `other`, which is empty
+    /// (zero-sized at the nearest edge) if they do not overlap.
+    pub fn intersect(&self, other: &PageBox) -> PageBox {
+        let left = self.left.max(other.left);
+        let bottom = self.bottom.max(other.bottom);
+        PageBox {
+            left,
+            bottom,
+            right: self.right.min(other.right).max(left),
+            top: self.top.min(other.top).max(bottom),
+        }
+    }
+
+    /// Reads a rectangle from an array of four numbers, `[llx lly urx ury]`.
+    pub fn from_object(object: &ObjectVariant) -> Result<Self, PageBoxError> {
+        let [x0, y0, x1, y1] = object.as_array_of::<f32, 4>()?;
+        Ok(PageBox::new(x0, y0, x1, y1))
+    }
+}
+
+/// Defines errors that can occur while parsing a page boundary.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum PageBoxError {
+    #[error("Error parsing page box: {0}")]
+    ObjectError(#[from] ObjectError),
+}
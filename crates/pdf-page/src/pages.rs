@@ -1,6 +1,6 @@
 use crate::{
-    content_stream::ContentStreamReadError, media_box::MediaBoxError, page::PdfPage,
-    resources::ResourcesError,
+    content_stream::ContentStreamReadError, page::PdfPage, page_box::PageBoxError,
+    resources::ResourcesError, transparency_group::TransparencyGroupError,
 };
 use pdf_object::{
     dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
@@ -20,10 +20,12 @@ pub enum PdfPagesError {
     ObjectError(#[from] ObjectError),
     #[error("Failed to parse content stream for page: {0}")]
     ContentStreamParse(#[from] ContentStreamReadError),
-    #[error("Failed to parse media box for page: {0}")]
-    MediaBoxParse(#[from] MediaBoxError),
+    #[error("Failed to parse page box for page: {0}")]
+    PageBoxParse(#[from] PageBoxError),
     #[error("Failed to parse resources for page: {0}")]
     ResourcesParse(#[from] ResourcesError),
+    #[error("Failed to parse transparency group for page: {0}")]
+    GroupParse(#[from] TransparencyGroupError),
 }
 
 pub struct PdfPages {
@@ -215,10 +215,7 @@ impl Pattern {
             }
             Some(PatternType::Shading) => {
                 // Read the shading object that defines the gradient fill.
-                let shading = Shading::from_dictionary(
-                    dictionary.get_or_err("Shading")?.try_dictionary()?,
-                    objects,
-                )?;
+                let shading = Shading::from_object(dictionary.get_or_err("Shading")?, objects)?;
 
                 // Read an external graphics state dictionary to apply when painting the pattern.
                 let ext_g_state = dictionary
@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use pdf_font::font::{Font, FontError};
 use pdf_object::{
@@ -8,16 +11,33 @@ use pdf_object::{
 use thiserror::Error;
 
 use crate::{
+    color_space::{ColorSpace, ColorSpaceReadError},
     external_graphics_state::{ExternalGraphicsState, ExternalGraphicsStateError},
+    optional_content::{OptionalContent, OptionalContentError},
     pattern::{Pattern, PatternError},
+    shading::{Shading, ShadingError},
     xobject::{XObject, XObjectError, XObjectReader},
 };
 
+#[derive(Default)]
 pub struct Resources {
     pub fonts: HashMap<String, Font>,
+    /// Resource dictionaries of Type 3 fonts, keyed by the font's resource name.
+    /// Glyph procedures of a font without its own `/Resources` use the resources
+    /// of the content stream that shows the text. Fonts that name the same
+    /// resource dictionary share it.
+    pub font_resources: HashMap<String, Rc<Resources>>,
     pub external_graphics_states: HashMap<String, ExternalGraphicsState>,
     pub xobjects: HashMap<String, XObject>,
     pub patterns: HashMap<String, Pattern>,
+    /// Named colour spaces from `/ColorSpace`, including any `DefaultGray`,
+    /// `DefaultRGB` and `DefaultCMYK` overrides of the device spaces.
+    pub color_spaces: HashMap<String, ColorSpace>,
+    /// Named shadings from `/Shading`, painted by the `sh` operator.
+    pub shadings: HashMap<String, Shading>,
+    /// The optional content groups and membership dictionaries among the property
+    /// lists of `/Properties`, referred to by `BDC /OC` operators.
+    pub optional_content: HashMap<String, OptionalContent>,
 }
 
 /// Defines errors that can occur while reading Resources object.
@@ -31,6 +51,17 @@ pub enum ResourcesError {
     XObjectError(#[from] XObjectError),
     #[error("Pattern parsing error: {0}")]
     PatternError(#[from] PatternError),
+    #[error("Shading parsing error: {0}")]
+    ShadingError(#[from] ShadingError),
+    #[error("ColorSpace parsing error: {0}")]
+    ColorSpaceError(#[from] ColorSpaceReadError),
+    #[error("Optional content parsing error: {0}")]
+    OptionalContentError(#[from] OptionalContentError),
+    #[error("Error processing /Resources of font '{font_name}': {source}")]
+    FontResourcesError {
+        font_name: String,
+        source: Box<ResourcesError>,
+    },
     #[error("{0}")]
     ObjectError(#[from] ObjectError),
     #[error("Invalid type for entry '{entry_name}': expected {expected_type}, found {found_type}")]
@@ -41,6 +72,10 @@ pub enum ResourcesError {
     },
 }
 
+/// How deeply the `/Resources` of Type 3 fonts are followed into the resources of
+/// the fonts they use in turn. Deeper nesting can only come from reference cycles.
+const MAX_FONT_NESTING: u8 = 8;
+
 impl FromDictionary for Resources {
     const KEY: &'static str = "Resources";
     type ResultType = Option<Self>;
@@ -50,14 +85,87 @@ impl FromDictionary for Resources {
         dictionary: &Dictionary,
         objects: &ObjectCollection,
     ) -> Result<Self::ResultType, Self::ErrorType> {
-        let Some(resources) = dictionary.get(Self::KEY) else {
+        Self::read(dictionary, objects, 0, &mut FontResourcesCache::default())
+    }
+}
+
+/// The resource dictionaries of Type 3 fonts read while reading a `/Resources`
+/// dictionary, so that a dictionary named by many fonts is read only once.
+#[derive(Default)]
+struct FontResourcesCache {
+    /// Resource dictionaries that have been read, by object number.
+    read: HashMap<i32, Rc<Resources>>,
+    /// Object numbers of the resource dictionaries being read, which a font
+    /// naming one of them refers back to.
+    reading: HashSet<i32>,
+}
+
+impl Resources {
+    /// Reads the `/Resources` entry of `dictionary`.
+    ///
+    /// `depth` counts the Type 3 fonts whose resources are being read. Beyond
+    /// `MAX_FONT_NESTING`, the resources of further Type 3 fonts are left out, so
+    /// their glyphs use the resources of the content stream that shows them.
+    fn read(
+        dictionary: &Dictionary,
+        objects: &ObjectCollection,
+        depth: u8,
+        cache: &mut FontResourcesCache,
+    ) -> Result<Option<Self>, ResourcesError> {
+        let Some(entry) = dictionary.get(Self::KEY) else {
             return Ok(None);
         };
+        let id = entry.as_object_number();
+        if let Some(id) = id {
+            cache.reading.insert(id);
+        }
+        let resources = Self::read_entry(entry, objects, depth, cache);
+        if let Some(id) = id {
+            cache.reading.remove(&id);
+        }
+        resources.map(Some)
+    }
+
+    /// Reads the resources of the Type 3 font `font`, sharing those already read
+    /// for another font.
+    ///
+    /// Returns `None` if the font has no resources of its own, or if they are the
+    /// resources being read, which its glyphs then use anyway.
+    fn read_font_resources(
+        font: &Dictionary,
+        objects: &ObjectCollection,
+        depth: u8,
+        cache: &mut FontResourcesCache,
+    ) -> Result<Option<Rc<Self>>, ResourcesError> {
+        let id = font
+            .get(Self::KEY)
+            .and_then(ObjectVariant::as_object_number);
+        if let Some(id) = id {
+            if let Some(resources) = cache.read.get(&id) {
+                return Ok(Some(Rc::clone(resources)));
+            }
+            if cache.reading.contains(&id) {
+                return Ok(None);
+            }
+        }
+        let resources = Self::read(font, objects, depth, cache)?.map(Rc::new);
+        if let (Some(id), Some(resources)) = (id, &resources) {
+            cache.read.insert(id, Rc::clone(resources));
+        }
+        Ok(resources)
+    }
 
+    fn read_entry(
+        resources: &ObjectVariant,
+        objects: &ObjectCollection,
+        depth: u8,
+        cache: &mut FontResourcesCache,
+    ) -> Result<Self, ResourcesError> {
         // Resolve the actual `/Resources` dictionary.
         let resources = objects.resolve_dictionary(resources)?;
 
         let mut fonts = HashMap::new();
+        let mut font_resources = HashMap::new();
 
         // Process `/Font` entries.
         if let Some(font_dictionary) = resources
@@ -70,7 +178,26 @@ impl FromDictionary for Resources {
                 let font_dict = objects.resolve_dictionary(v)?;
 
                 // Parse the font and insert it into the fonts map.
-                fonts.insert(name.to_owned(), Font::from_dictionary(font_dict, objects)?);
+                let font = Font::from_dictionary(font_dict, objects)?;
+
+                // Type 3 glyph procedures may name their own resources.
+                if let Font::Type3(_) = font
+                    && depth < MAX_FONT_NESTING
+                    && let Some(resources) = Resources::read_font_resources(
+                        font_dict,
+                        objects,
+                        depth.saturating_add(1),
+                        cache,
+                    )
+                    .map_err(|err| ResourcesError::FontResourcesError {
+                        font_name: name.to_owned(),
+                        source: Box::new(err),
+                    })?
+                {
+                    font_resources.insert(name.to_owned(), resources);
+                }
+
+                fonts.insert(name.to_owned(), font);
             }
         }
 
@@ -130,6 +257,52 @@ impl FromDictionary for Resources {
             }
         }
 
+        let mut color_spaces = HashMap::new();
+
+        // Process `/ColorSpace` entries. A colour space that cannot be read is
+        // skipped, so only the content that selects it fails to render.
+        if let Some(cs) = resources
+            .get("ColorSpace")
+            .map(|d| objects.resolve_dictionary(d))
+            .transpose()?
+        {
+            for (name, v) in &cs.dictionary {
+                if let Ok(color_space) = ColorSpace::from_object(v, objects) {
+                    color_spaces.insert(name.to_owned(), color_space);
+                }
+            }
+        }
+
+        let mut shadings = HashMap::new();
+
+        // Process `/Shading` entries
+        if let Some(sh) = resources
+            .get("Shading")
+            .map(|d| objects.resolve_dictionary(d))
+            .transpose()?
+        {
+            for (name, v) in &sh.dictionary {
+                shadings.insert(name.to_owned(), Shading::from_object(v, objects)?);
+            }
+        }
+
+        let mut optional_content = HashMap::new();
+
+        // Process `/Properties` entries, keeping those that are optional content.
+        // Other property lists only matter to marked-content consumers, so an
+        // entry that is neither a group nor a membership dictionary is skipped.
+        if let Some(properties) = resources
+            .get("Properties")
+            .map(|d| objects.resolve_dictionary(d))
+            .transpose()?
+        {
+            for (name, v) in &properties.dictionary {
+                if let Some(content) = OptionalContent::from_object(v, objects)? {
+                    optional_content.insert(name.to_owned(), content);
+                }
+            }
+        }
+
         let mut xobjects = HashMap::new();
 
         // Process `/XObject` entries
@@ -151,11 +324,183 @@ impl FromDictionary for Resources {
             }
         }
 
-        Ok(Some(Self {
+        Ok(Self {
             fonts,
+            font_resources,
             external_graphics_states,
             xobjects,
             patterns,
-        }))
+            color_spaces,
+            shadings,
+            optional_content,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use pdf_object::indirect_object::IndirectObject;
+
+    use super::*;
+
+    fn dictionary(entries: Vec<(&str, ObjectVariant)>) -> Dictionary {
+        Dictionary::new(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), Box::new(value)))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    fn insert(objects: &mut ObjectCollection, id: i32, dictionary: Dictionary) {
+        let object =
+            IndirectObject::new(id, 0, Some(ObjectVariant::Dictionary(Rc::new(dictionary))));
+        objects
+            .insert(ObjectVariant::IndirectObject(Rc::new(object)))
+            .unwrap();
+    }
+
+    fn type3_font(resources: i32) -> Dictionary {
+        dictionary(vec![
+            ("Type", ObjectVariant::Name("Font".to_string())),
+            ("Subtype", ObjectVariant::Name("Type3".to_string())),
+            (
+                "FontMatrix",
+                ObjectVariant::Array(vec![
+                    ObjectVariant::Real(0.001),
+                    ObjectVariant::Integer(0),
+                    ObjectVariant::Integer(0),
+                    ObjectVariant::Real(0.001),
+                    ObjectVariant::Integer(0),
+                    ObjectVariant::Integer(0),
+                ]),
+            ),
+            (
+                "CharProcs",
+                ObjectVariant::Dictionary(Rc::new(dictionary(Vec::new()))),
+            ),
+            ("FirstChar", ObjectVariant::Integer(97)),
+            ("LastChar", ObjectVariant::Integer(97)),
+            (
+                "Widths",
+                ObjectVariant::Array(vec![ObjectVariant::Integer(500)]),
+            ),
+            ("Resources", ObjectVariant::Reference(resources)),
+        ])
+    }
+
+    /// A resource dictionary whose `/Font` names the fonts `ids`.
+    fn font_resources(ids: impl Iterator<Item = i32>) -> Dictionary {
+        let fonts = ids
+            .enumerate()
+            .map(|(i, id)| (format!("F{i}"), ObjectVariant::Reference(id)))
+            .collect::<Vec<_>>();
+        let fonts = fonts
+            .iter()
+            .map(|(name, font)| (name.as_str(), font.clone()))
+            .collect();
+        dictionary(vec![(
+            "Font",
+            ObjectVariant::Dictionary(Rc::new(dictionary(fonts))),
+        )])
+    }
+
+    #[test]
+    fn type3_fonts_naming_the_resources_being_read_are_not_followed() {
+        let mut objects = ObjectCollection::default();
+        insert(&mut objects, 1, font_resources(10..20));
+        for id in 10..20 {
+            insert(&mut objects, id, type3_font(1));
+        }
+        let page = dictionary(vec![("Resources", ObjectVariant::Reference(1))]);
+
+        let resources = Resources::from_dictionary(&page, &objects)
+            .unwrap()
+            .unwrap();
+        assert_eq!(resources.fonts.len(), 10);
+        assert!(resources.font_resources.is_empty());
+    }
+
+    #[test]
+    fn type3_fonts_share_resources_read_once() {
+        let mut objects = ObjectCollection::default();
+        // Every level names ten fonts whose resources are the next level, which
+        // would be read 10^depth times without sharing. Level n is object n * 100,
+        // with its fonts numbered after it.
+        let levels = (1..=i32::from(MAX_FONT_NESTING)).map(|level| level.wrapping_mul(100));
+        for level in levels.clone() {
+            let fonts = (1..=10).map(move |font| level.wrapping_add(font));
+            insert(&mut objects, level, font_resources(fonts.clone()));
+            for font in fonts {
+                insert(&mut objects, font, type3_font(level.wrapping_add(100)));
+            }
+        }
+        let last = i32::from(MAX_FONT_NESTING)
+            .wrapping_add(1)
+            .wrapping_mul(100);
+        insert(&mut objects, last, font_resources(std::iter::empty()));
+        let page = dictionary(vec![("Resources", ObjectVariant::Reference(100))]);
+
+        let resources = Resources::from_dictionary(&page, &objects)
+            .unwrap()
+            .unwrap();
+        let shared = resources.font_resources.get("F0").unwrap();
+        assert_eq!(resources.font_resources.len(), 10);
+        assert!(
+            resources
+                .font_resources
+                .values()
+                .all(|other| Rc::ptr_eq(shared, other))
+        );
+        assert_eq!(shared.fonts.len(), 10);
+    }
+
+    #[test]
+    fn unreadable_colour_spaces_are_skipped() {
+        let mut objects = ObjectCollection::default();
+        // An Indexed space whose base is itself.
+        let cyclic = ObjectVariant::Array(vec![
+            ObjectVariant::Name("Indexed".to_string()),
+            ObjectVariant::Reference(2),
+            ObjectVariant::Integer(0),
+            ObjectVariant::LiteralString("\0".to_string()),
+        ]);
+        objects
+            .insert(ObjectVariant::IndirectObject(Rc::new(IndirectObject::new(
+                2,
+                0,
+                Some(cyclic),
+            ))))
+            .unwrap();
+        let color_spaces = dictionary(vec![
+            ("Cyclic", ObjectVariant::Reference(2)),
+            ("Unknown", ObjectVariant::Name("DeviceUnknown".to_string())),
+            (
+                "Cmyk",
+                ObjectVariant::Array(vec![
+                    ObjectVariant::Name("CalCMYK".to_string()),
+                    ObjectVariant::Dictionary(Rc::new(dictionary(Vec::new()))),
+                ]),
+            ),
+        ]);
+        let page = dictionary(vec![(
+            "Resources",
+            ObjectVariant::Dictionary(Rc::new(dictionary(vec![(
+                "ColorSpace",
+                ObjectVariant::Dictionary(Rc::new(color_spaces)),
+            )]))),
+        )]);
+
+        let resources = Resources::from_dictionary(&page, &objects)
+            .unwrap()
+            .unwrap();
+        assert_eq!(resources.color_spaces.len(), 1);
+        assert!(matches!(
+            resources.color_spaces.get("Cmyk"),
+            Some(ColorSpace::DeviceCMYK)
+        ));
     }
 }
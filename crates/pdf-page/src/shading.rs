@@ -1,4 +1,4 @@
-use pdf_graphics::color::Color;
+use pdf_graphics::{color::Color, transform::Transform};
 use pdf_object::{
     ObjectVariant, dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
     traits::FromDictionary,
@@ -6,8 +6,10 @@ use pdf_object::{
 use thiserror::Error;
 
 use crate::{
-    color_space::ColorSpace,
+    color_space::{ColorConversion, ColorSpace, ColorSpaceReadError},
     function::{Function, FunctionReadError},
+    matrix::{Matrix, MatrixReadError},
+    mesh::{self, MeshError, MeshVertex, Patch},
 };
 
 /// Errors that can occur while parsing a Shading object.
@@ -27,6 +29,14 @@ pub enum ShadingError {
     },
     #[error("Error parsing Function: {0}")]
     FunctionReadError(#[from] FunctionReadError),
+    #[error("Error parsing ColorSpace: {0}")]
+    ColorSpaceReadError(#[from] ColorSpaceReadError),
+    #[error("Mesh shading of type {0} must be a stream")]
+    MissingMeshData(ShadingType),
+    #[error("Error decoding mesh data: {0}")]
+    MeshError(#[from] MeshError),
+    #[error("Error parsing /Matrix: {0}")]
+    MatrixReadError(#[from] MatrixReadError),
     #[error("Error parsing Dictionary: {0}")]
     ObjectError(#[from] ObjectError),
 }
@@ -80,7 +90,13 @@ pub struct ColorStops {
 }
 
 impl ColorStops {
-    pub fn from(function: &Function) -> Self {
+    /// Samples a 1-in shading function into evenly spaced stops, converting its
+    /// outputs through the shading's colour space with `conversion`.
+    pub fn from(
+        function: &Function,
+        color_space: &ColorSpace,
+        conversion: &ColorConversion,
+    ) -> Self {
         // Number of stops to sample
         const NUM_OF_STOPS: u16 = 16;
 
@@ -91,16 +107,12 @@ impl ColorStops {
         for i in 0..NUM_OF_STOPS {
             let t = f32::from(i) / f32::from(NUM_OF_STOPS);
             let x = domain[0] + t * (domain[1] - domain[0]);
-            // Evaluate function at x to get color components.
-            let color_components = function
-                .interpolate(x)
-                .unwrap_or_else(|_| vec![0.0, 0.0, 0.0]);
-            // Convert to Color.
-            let color = Color::from_rgb(
-                color_components.first().copied().unwrap_or(0.0),
-                color_components.get(1).copied().unwrap_or(0.0),
-                color_components.get(2).copied().unwrap_or(0.0),
-            );
+            // Evaluate function at x to get color components, and convert them to a Color.
+            let color = function
+                .interpolate(&[x])
+                .ok()
+                .and_then(|components| color_space.to_color(&components, conversion).ok())
+                .unwrap_or(Color::from_rgb(0.0, 0.0, 0.0));
             positions.push(t);
             colors.push(color);
         }
@@ -125,6 +137,8 @@ pub enum Shading {
         anti_alias: Option<bool>,
         /// The domain of the function(s).
         domain: Option<Vec<f32>>,
+        /// Maps the domain into the shading's target coordinate space.
+        matrix: Option<Transform>,
         /// A 2-in, n-out function or an array of n 2-in, 1-out functions
         /// that define the color at each point.
         functions: Vec<Function>,
@@ -140,8 +154,14 @@ pub enum Shading {
         /// A 1-in, n-out function that maps a parameter `t` (from 0.0 to 1.0)
         /// along the axis to a color.
         function: Function,
-        colors: Vec<Color>,
-        positions: Vec<f32>,
+        /// The values of the function's input at the start and end of the axis.
+        domain: [f32; 2],
+        /// Whether the shading extends beyond the start and the end of the axis.
+        extend: [bool; 2],
+        /// Color components of the background, painted outside the shading by pattern fills.
+        background: Option<Vec<f32>>,
+        /// A rectangle in shading space that clips the shading.
+        bbox: Option<[f32; 4]>,
     },
     /// A radial shading, where color transitions between two circles.
     Radial {
@@ -153,9 +173,156 @@ pub enum Shading {
         /// A 1-in, n-out function that maps a parameter `t` (from 0.0 to 1.0)
         /// between the circles to a color.
         function: Function,
-        colors: Vec<Color>,
-        positions: Vec<f32>,
+        /// The values of the function's input at the starting and ending circles.
+        domain: [f32; 2],
+        /// Whether the shading extends beyond the starting and the ending circles.
+        extend: [bool; 2],
+        /// Color components of the background, painted outside the shading by pattern fills.
+        background: Option<Vec<f32>>,
+        /// A rectangle in shading space that clips the shading.
+        bbox: Option<[f32; 4]>,
+    },
+    /// A free-form (type 4) or lattice-form (type 5) mesh of Gouraud-shaded
+    /// triangles, whose colours are interpolated between their vertices.
+    TriangleMesh {
+        /// The color space in which color values are expressed.
+        color_space: ColorSpace,
+        /// Color components of the background, painted outside the shading by pattern fills.
+        background: Option<Vec<f32>>,
+        /// A rectangle in shading space that clips the shading.
+        bbox: Option<[f32; 4]>,
+        /// A 1-in, n-out function or n 1-in, 1-out functions mapping the parametric
+        /// value of the vertices to a color. Empty when vertices carry color components.
+        functions: Vec<Function>,
+        /// The triangles, in painting order.
+        triangles: Vec<[MeshVertex; 3]>,
     },
+    /// A Coons (type 6) or tensor-product (type 7) patch mesh, whose colours are
+    /// interpolated between the corners of each patch.
+    PatchMesh {
+        /// The color space in which color values are expressed.
+        color_space: ColorSpace,
+        /// Color components of the background, painted outside the shading by pattern fills.
+        background: Option<Vec<f32>>,
+        /// A rectangle in shading space that clips the shading.
+        bbox: Option<[f32; 4]>,
+        /// A 1-in, n-out function or n 1-in, 1-out functions mapping the parametric
+        /// value of the corners to a color. Empty when corners carry color components.
+        functions: Vec<Function>,
+        /// The patches, in painting order.
+        patches: Vec<Patch>,
+    },
+}
+
+/// Reads a `/Function` entry: a single function, or an array of functions that each
+/// give one color component.
+fn read_functions(
+    object: &ObjectVariant,
+    objects: &ObjectCollection,
+) -> Result<Vec<Function>, ShadingError> {
+    match objects.resolve_object(object)? {
+        ObjectVariant::Array(array) => Ok(array
+            .iter()
+            .map(|value| Function::from_object(value, objects))
+            .collect::<Result<_, _>>()?),
+        _ => Ok(vec![Function::from_object(object, objects)?]),
+    }
+}
+
+/// Reads the optional `/Background` entry, a colour in the shading's colour space.
+fn read_background(dictionary: &Dictionary) -> Result<Option<Vec<f32>>, ShadingError> {
+    Ok(dictionary
+        .get("Background")
+        .map(|obj| obj.as_vec_of::<f32>())
+        .transpose()?)
+}
+
+/// Reads the optional `/BBox` entry, a clipping rectangle in shading space.
+fn read_bbox(dictionary: &Dictionary) -> Result<Option<[f32; 4]>, ShadingError> {
+    Ok(dictionary
+        .get("BBox")
+        .map(|obj| obj.as_array_of::<f32, 4>())
+        .transpose()?)
+}
+
+/// Reads the `/Domain` entry of an axial or radial shading, `[0 1]` by default.
+fn read_domain(dictionary: &Dictionary) -> Result<[f32; 2], ShadingError> {
+    Ok(dictionary
+        .get("Domain")
+        .map(|obj| obj.as_array_of::<f32, 2>())
+        .transpose()?
+        .unwrap_or([0.0, 1.0]))
+}
+
+/// Reads the `/Extend` entry of an axial or radial shading, `[false false]` by default.
+fn read_extend(dictionary: &Dictionary) -> Result<[bool; 2], ShadingError> {
+    let Some(extend) = dictionary.get("Extend") else {
+        return Ok([false, false]);
+    };
+    match extend.try_array()? {
+        [start, end] => Ok([start.try_boolean()?, end.try_boolean()?]),
+        values => Err(ObjectError::InvalidArrayLength {
+            expected: 2,
+            found: values.len(),
+        }
+        .into()),
+    }
+}
+
+impl Shading {
+    /// Reads a shading from an object that is, or refers to, a shading dictionary
+    /// or a shading stream.
+    pub fn from_object(
+        object: &ObjectVariant,
+        objects: &ObjectCollection,
+    ) -> Result<Shading, ShadingError> {
+        match objects.resolve_object(object)? {
+            ObjectVariant::Dictionary(dictionary) => Shading::read(dictionary, None, objects),
+            ObjectVariant::Stream(stream) => {
+                Shading::read(&stream.dictionary, Some(&stream.data), objects)
+            }
+            obj => Err(ShadingError::InvalidEntryType {
+                entry_name: "Shading",
+                expected_type: "Dictionary or Stream",
+                found_type: obj.name(),
+            }),
+        }
+    }
+
+    /// Returns the colour space in which the shading's colours are expressed.
+    pub fn color_space(&self) -> &ColorSpace {
+        match self {
+            Shading::FunctionBased { color_space, .. } => color_space
+                .as_ref()
+                .unwrap_or_else(|| ColorSpace::device_gray()),
+            Shading::Axial { color_space, .. }
+            | Shading::Radial { color_space, .. }
+            | Shading::TriangleMesh { color_space, .. }
+            | Shading::PatchMesh { color_space, .. } => color_space,
+        }
+    }
+
+    /// Returns the `/Background` colour components, if any.
+    pub fn background(&self) -> Option<&[f32]> {
+        match self {
+            Shading::FunctionBased { background, .. }
+            | Shading::Axial { background, .. }
+            | Shading::Radial { background, .. }
+            | Shading::TriangleMesh { background, .. }
+            | Shading::PatchMesh { background, .. } => background.as_deref(),
+        }
+    }
+
+    /// Returns the `/BBox` clipping rectangle, if any.
+    pub fn bbox(&self) -> Option<[f32; 4]> {
+        match self {
+            Shading::FunctionBased { bbox, .. }
+            | Shading::Axial { bbox, .. }
+            | Shading::Radial { bbox, .. }
+            | Shading::TriangleMesh { bbox, .. }
+            | Shading::PatchMesh { bbox, .. } => *bbox,
+        }
+    }
 }
 
 impl FromDictionary for Shading {
@@ -167,25 +334,31 @@ impl FromDictionary for Shading {
         dictionary: &Dictionary,
         objects: &ObjectCollection,
     ) -> Result<Self::ResultType, ShadingError> {
+        Shading::read(dictionary, None, objects)
+    }
+}
+
+impl Shading {
+    /// Reads a shading from its dictionary and, for mesh shadings, the decoded data
+    /// of its stream.
+    fn read(
+        dictionary: &Dictionary,
+        data: Option<&[u8]>,
+        objects: &ObjectCollection,
+    ) -> Result<Self, ShadingError> {
         // Extract the required `/ShadingType` entry.
         let shading_type = dictionary.get_or_err("ShadingType")?.as_number::<i32>()?;
 
         match ShadingType::from_i32(shading_type) {
             Some(ShadingType::FunctionBased) => {
                 // Read optional `/ColorSpace` entry, which defines the color space for the shading.
-                let color_space = dictionary.get("ColorSpace").map(ColorSpace::from);
-
-                // Read optional `/Background` entry, specifying a background color as an array of numbers.
-                let background = dictionary
-                    .get("Background")
-                    .map(|obj| obj.as_vec_of::<f32>())
+                let color_space = dictionary
+                    .get("ColorSpace")
+                    .map(|obj| ColorSpace::from_object(obj, objects))
                     .transpose()?;
 
-                // Read optional `/BBox` entry, which defines the bounding box for the shading.
-                let bbox = dictionary
-                    .get("BBox")
-                    .map(|obj| obj.as_array_of::<f32, 4>())
-                    .transpose()?;
+                let background = read_background(dictionary)?;
+                let bbox = read_bbox(dictionary)?;
 
                 // Read optional `/Domain` entry, specifying the valid input range for the function(s).
                 let domain = dictionary
@@ -193,60 +366,11 @@ impl FromDictionary for Shading {
                     .map(|obj| obj.as_vec_of::<f32>())
                     .transpose()?;
 
+                // Read optional `/Matrix` entry, mapping the domain into the target space.
+                let matrix = Matrix::from_dictionary(dictionary, objects)?;
+
                 // Read required `/Function` entry, which may be a single function or an array of functions.
-                let functions = match dictionary.get_or_err("Function")? {
-                    // If the `/Function` is an array, read each function object.
-                    obj if obj.is_array() => {
-                        let mut functions = Vec::new();
-                        if let Some(obj) = obj.as_array() {
-                            for value in obj.iter() {
-                                match objects.resolve_object(value)? {
-                                    ObjectVariant::Dictionary(dictionary) => {
-                                        functions.push(Function::from_dictionary(
-                                            dictionary, objects, None,
-                                        )?);
-                                    }
-                                    ObjectVariant::Stream(stream) => {
-                                        functions.push(Function::from_dictionary(
-                                            &stream.dictionary,
-                                            objects,
-                                            Some(&stream.data),
-                                        )?);
-                                    }
-                                    obj => {
-                                        return Err(ShadingError::InvalidEntryType {
-                                            entry_name: "Function",
-                                            expected_type: "Dictionary or Stream",
-                                            found_type: obj.name(),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                        functions
-                    }
-                    // If `/Function` is a single object, read it directly.
-                    obj => {
-                        let function = match objects.resolve_object(obj)? {
-                            ObjectVariant::Dictionary(dictionary) => {
-                                Function::from_dictionary(dictionary, objects, None)?
-                            }
-                            ObjectVariant::Stream(stream) => Function::from_dictionary(
-                                &stream.dictionary,
-                                objects,
-                                Some(&stream.data),
-                            )?,
-                            obj => {
-                                return Err(ShadingError::InvalidEntryType {
-                                    entry_name: "Function",
-                                    expected_type: "Dictionary or Stream",
-                                    found_type: obj.name(),
-                                });
-                            }
-                        };
-                        vec![function]
-                    }
-                };
+                let functions = read_functions(dictionary.get_or_err("Function")?, objects)?;
 
                 Ok(Shading::FunctionBased {
                     color_space,
@@ -254,6 +378,7 @@ impl FromDictionary for Shading {
                     bbox,
                     anti_alias: None,
                     domain,
+                    matrix,
                     functions,
                 })
             }
@@ -262,23 +387,20 @@ impl FromDictionary for Shading {
                 let coords = dictionary.get_or_err("Coords")?.as_array_of::<f32, 4>()?;
 
                 // Read required `/ColorSpace` entry.
-                let color_space = ColorSpace::from(dictionary.get_or_err("ColorSpace")?);
-
-                // Read required `/Function` entry as a dictionary.
-                let function = Function::from_dictionary(
-                    dictionary.get_or_err("Function")?.try_dictionary()?,
-                    objects,
-                    None,
-                )?;
+                let color_space =
+                    ColorSpace::from_object(dictionary.get_or_err("ColorSpace")?, objects)?;
 
-                let ColorStops { colors, positions } = ColorStops::from(&function);
+                // Read required `/Function` entry, a function dictionary or stream.
+                let function = Function::from_object(dictionary.get_or_err("Function")?, objects)?;
 
                 Ok(Shading::Axial {
                     color_space,
                     function,
                     coords,
-                    colors,
-                    positions,
+                    domain: read_domain(dictionary)?,
+                    extend: read_extend(dictionary)?,
+                    background: read_background(dictionary)?,
+                    bbox: read_bbox(dictionary)?,
                 })
             }
             Some(ShadingType::Radial) => {
@@ -286,25 +408,77 @@ impl FromDictionary for Shading {
                 let coords = dictionary.get_or_err("Coords")?.as_array_of::<f32, 6>()?;
 
                 // Read required `/ColorSpace` entry.
-                let color_space = ColorSpace::from(dictionary.get_or_err("ColorSpace")?);
+                let color_space =
+                    ColorSpace::from_object(dictionary.get_or_err("ColorSpace")?, objects)?;
 
-                // Read required `/Function` entry as a dictionary.
-                let function = Function::from_dictionary(
-                    dictionary.get_or_err("Function")?.try_dictionary()?,
-                    objects,
-                    None,
-                )?;
-
-                let ColorStops { colors, positions } = ColorStops::from(&function);
+                // Read required `/Function` entry, a function dictionary or stream.
+                let function = Function::from_object(dictionary.get_or_err("Function")?, objects)?;
 
                 Ok(Shading::Radial {
                     color_space,
                     function,
                     coords,
-                    colors,
-                    positions,
+                    domain: read_domain(dictionary)?,
+                    extend: read_extend(dictionary)?,
+                    background: read_background(dictionary)?,
+                    bbox: read_bbox(dictionary)?,
                 })
             }
+            Some(
+                shading_type @ (ShadingType::FreeFormTriangleMesh
+                | ShadingType::LatticeFormTriangleMesh
+                | ShadingType::CoonsPatchMesh
+                | ShadingType::TensorProductPatchMesh),
+            ) => {
+                let data = data.ok_or(ShadingError::MissingMeshData(shading_type))?;
+
+                // Read required `/ColorSpace` entry.
+                let color_space =
+                    ColorSpace::from_object(dictionary.get_or_err("ColorSpace")?, objects)?;
+
+                // Read optional `/Function` entry. With a function each vertex carries a
+                // single parametric value instead of color components.
+                let functions = dictionary
+                    .get("Function")
+                    .map(|obj| read_functions(obj, objects))
+                    .transpose()?
+                    .unwrap_or_default();
+                let value_count = if functions.is_empty() {
+                    color_space.component_count()
+                } else {
+                    1
+                };
+
+                let background = read_background(dictionary)?;
+                let bbox = read_bbox(dictionary)?;
+
+                match shading_type {
+                    ShadingType::FreeFormTriangleMesh | ShadingType::LatticeFormTriangleMesh => {
+                        let triangles = if shading_type == ShadingType::FreeFormTriangleMesh {
+                            mesh::read_free_form_triangles(dictionary, data, value_count)?
+                        } else {
+                            mesh::read_lattice_triangles(dictionary, data, value_count)?
+                        };
+                        Ok(Shading::TriangleMesh {
+                            color_space,
+                            background,
+                            bbox,
+                            functions,
+                            triangles,
+                        })
+                    }
+                    _ => {
+                        let tensor = shading_type == ShadingType::TensorProductPatchMesh;
+                        Ok(Shading::PatchMesh {
+                            color_space,
+                            background,
+                            bbox,
+                            functions,
+                            patches: mesh::read_patches(dictionary, data, value_count, tensor)?,
+                        })
+                    }
+                }
+            }
             // If the shading type is not recognized, return an error.
             _ => Err(ShadingError::InvalidShadingType(shading_type)),
         }
@@ -0,0 +1,69 @@
+use pdf_object::{
+    dictionary::Dictionary, error::ObjectError, object_collection::ObjectCollection,
+    traits::FromDictionary,
+};
+use thiserror::Error;
+
+use crate::color_space::{ColorSpace, ColorSpaceReadError};
+
+/// Errors that can occur while reading a `/Group` dictionary.
+#[derive(Debug, Error)]
+pub enum TransparencyGroupError {
+    #[error("Error parsing the group colour space /CS: {0}")]
+    ColorSpaceError(#[from] ColorSpaceReadError),
+    #[error("{0}")]
+    ObjectError(#[from] ObjectError),
+}
+
+/// The attributes of a transparency group, from the `/Group` entry of a form
+/// XObject or a page (ISO 32000-1, 11.6.6).
+pub struct TransparencyGroup {
+    /// The group colour space `/CS`, in which the group's objects are blended.
+    /// Renderers currently blend in RGB and do not use it.
+    pub color_space: Option<ColorSpace>,
+    /// `/I`: whether the group starts from a transparent backdrop rather than
+    /// from what lies beneath it.
+    pub isolated: bool,
+    /// `/K`: whether each object in the group is composited with the group's
+    /// initial backdrop rather than with the objects painted before it.
+    pub knockout: bool,
+}
+
+impl FromDictionary for TransparencyGroup {
+    const KEY: &'static str = "Group";
+    type ResultType = Option<TransparencyGroup>;
+    type ErrorType = TransparencyGroupError;
+
+    /// Reads the `/Group` entry of a form XObject or page dictionary.
+    ///
+    /// Returns `None` when there is no group, or when the group's subtype `/S` is
+    /// not `/Transparency`, the only group type PDF defines.
+    fn from_dictionary(
+        dictionary: &Dictionary,
+        objects: &ObjectCollection,
+    ) -> Result<Self::ResultType, Self::ErrorType> {
+        let Some(group) = dictionary.get("Group") else {
+            return Ok(None);
+        };
+        let group = objects.resolve_dictionary(group)?;
+        if group.get("S").map(|s| s.try_str()).transpose()?.as_deref() != Some("Transparency") {
+            return Ok(None);
+        }
+
+        let color_space = group
+            .get("CS")
+            .map(|cs| ColorSpace::from_object(cs, objects))
+            .transpose()?;
+        let flag = |key: &str| -> Result<bool, ObjectError> {
+            group
+                .get(key)
+                .map_or(Ok(false), |value| value.try_boolean())
+        };
+
+        Ok(Some(TransparencyGroup {
+            color_space,
+            isolated: flag("I")?,
+            knockout: flag("K")?,
+        }))
+    }
+}
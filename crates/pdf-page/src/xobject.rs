@@ -11,7 +11,7 @@ use thiserror::Error;
 /// self-contained graphical forms, or other types of external content.
 pub enum XObject {
     /// An image XObject, representing a raster image.
-    Image(ImageXObject),
+    Image(Box<ImageXObject>),
     /// A form XObject, which is a self-contained sequence of graphics objects
     /// that can be painted as a single unit.
     Form(Box<FormXObject>),
@@ -72,7 +72,7 @@ impl XObjectReader for XObject {
         match subtype.as_ref() {
             "Image" => {
                 let image_xobject = ImageXObject::read_xobject(dictionary, stream_data, objects)?;
-                Ok(XObject::Image(image_xobject))
+                Ok(XObject::Image(Box::new(image_xobject)))
             }
             "Form" => {
                 let form_xobject = FormXObject::read_xobject(dictionary, stream_data, objects)?;
@@ -0,0 +1,494 @@
+//! Stream filters (ISO 32000-1, 7.4).
+//!
+//! Decodes the general-purpose filters that turn encoded stream data back into
+//! its original bytes, together with the PNG and TIFF predictors that
+//! `/FlateDecode` and `/LZWDecode` may apply through `/DecodeParms` (7.4.4.4).
+//! Image-specific filters such as `/DCTDecode` are left to the image decoders.
+
+use std::io::Read;
+
+use flate2::bufread::ZlibDecoder;
+use pdf_object::dictionary::Dictionary;
+use thiserror::Error;
+
+/// Represents an error that can occur while decoding filtered stream data.
+#[derive(Debug, PartialEq, Error)]
+pub enum FilterError {
+    /// The filter is not one this module can decode.
+    #[error("Unsupported stream filter: {0}")]
+    Unsupported(String),
+    /// The data is not valid for the filter.
+    #[error("Invalid {filter} data: {reason}")]
+    InvalidData { filter: String, reason: String },
+    /// The `/Predictor` in the decode parameters is unknown or cannot be applied
+    /// to samples of the given size.
+    #[error("Unsupported predictor {predictor} for {bits}-bit components")]
+    UnsupportedPredictor { predictor: usize, bits: usize },
+    /// A row of PNG-predicted data starts with an unknown filter type.
+    #[error("Invalid PNG predictor row type {0}")]
+    InvalidPredictorRow(u8),
+    /// A predicted row is longer than all of the decoded data.
+    #[error("Predictor row of {row_length} bytes exceeds the {data_length} bytes of data")]
+    PredictorRowTooLong {
+        row_length: usize,
+        data_length: usize,
+    },
+}
+
+/// Returns whether [`decode`] can decode data encoded with `filter`.
+pub fn is_supported(filter: &str) -> bool {
+    matches!(
+        filter,
+        "FlateDecode" | "ASCIIHexDecode" | "ASCII85Decode" | "RunLengthDecode"
+    )
+}
+
+/// Decodes `data` encoded with the filter named `filter`, applying the
+/// predictor given in `params`, the filter's `/DecodeParms` dictionary.
+pub fn decode(
+    filter: &str,
+    data: &[u8],
+    params: Option<&Dictionary>,
+) -> Result<Vec<u8>, FilterError> {
+    let invalid = |reason: String| FilterError::InvalidData {
+        filter: filter.to_string(),
+        reason,
+    };
+    match filter {
+        "FlateDecode" => {
+            let mut decoded = Vec::new();
+            ZlibDecoder::new(data)
+                .read_to_end(&mut decoded)
+                .map_err(|e| invalid(e.to_string()))?;
+            Predictor::from_params(params).apply(decoded)
+        }
+        "ASCIIHexDecode" => decode_ascii_hex(data).map_err(invalid),
+        "ASCII85Decode" => decode_ascii85(data).map_err(invalid),
+        "RunLengthDecode" => Ok(decode_run_length(data)),
+        _ => Err(FilterError::Unsupported(filter.to_string())),
+    }
+}
+
+/// The predictor entries of a `/DecodeParms` dictionary (Table 8).
+struct Predictor {
+    predictor: usize,
+    colors: usize,
+    bits: usize,
+    columns: usize,
+}
+
+impl Predictor {
+    fn from_params(params: Option<&Dictionary>) -> Self {
+        let number = |key: &str, default: usize| {
+            params
+                .and_then(|params| params.get(key))
+                .and_then(|value| value.as_number::<usize>().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            predictor: number("Predictor", 1),
+            colors: number("Colors", 1).max(1),
+            bits: number("BitsPerComponent", 8),
+            columns: number("Columns", 1).max(1),
+        }
+    }
+
+    fn unsupported(&self) -> FilterError {
+        FilterError::UnsupportedPredictor {
+            predictor: self.predictor,
+            bits: self.bits,
+        }
+    }
+
+    /// The number of bytes in a row of samples, without the PNG row type byte.
+    fn row_length(&self) -> Option<usize> {
+        let bits = self
+            .colors
+            .checked_mul(self.bits)?
+            .checked_mul(self.columns)?;
+        Some(bits.div_ceil(8))
+    }
+
+    fn apply(&self, data: Vec<u8>) -> Result<Vec<u8>, FilterError> {
+        if self.predictor == 1 {
+            return Ok(data);
+        }
+        if !matches!(self.bits, 1 | 2 | 4 | 8 | 16) {
+            return Err(self.unsupported());
+        }
+        let row_length = self.row_length().ok_or_else(|| self.unsupported())?;
+        match self.predictor {
+            2 => self.apply_tiff(data, row_length),
+            10..=15 => {
+                // The left neighbour is the corresponding byte of the previous
+                // pixel, or the previous byte when a pixel is smaller than one.
+                let pixel = self.colors.saturating_mul(self.bits).div_ceil(8).max(1);
+                apply_png(&data, row_length, pixel)
+            }
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    /// Undoes TIFF predictor 2, where each sample is stored as the difference
+    /// from the same component of the pixel to its left.
+    fn apply_tiff(&self, mut data: Vec<u8>, row_length: usize) -> Result<Vec<u8>, FilterError> {
+        let samples = self.colors.saturating_mul(self.columns);
+        let mask = u16::MAX
+            .checked_shr(16u32.saturating_sub(u32::try_from(self.bits).unwrap_or(16)))
+            .unwrap_or(u16::MAX);
+        for row in data.chunks_mut(row_length) {
+            for index in self.colors..samples {
+                let left = read_sample(row, index.saturating_sub(self.colors), self.bits);
+                let value = read_sample(row, index, self.bits);
+                write_sample(row, index, self.bits, value.wrapping_add(left) & mask);
+            }
+        }
+        Ok(data)
+    }
+}
+
+/// Undoes the PNG predictors, where each row starts with a byte giving the
+/// filter type its bytes were encoded with.
+fn apply_png(data: &[u8], row_length: usize, pixel: usize) -> Result<Vec<u8>, FilterError> {
+    // The row length comes from `/Columns`, so it is checked against the data
+    // before a row of that size is allocated.
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+    if row_length > data.len() {
+        return Err(FilterError::PredictorRowTooLong {
+            row_length,
+            data_length: data.len(),
+        });
+    }
+    let mut decoded = Vec::with_capacity(data.len());
+    let mut previous = vec![0u8; row_length];
+    for row in data.chunks(row_length.saturating_add(1)) {
+        let Some((&kind, row)) = row.split_first() else {
+            break;
+        };
+        let mut current = row.to_vec();
+        for index in 0..current.len() {
+            let left_index = index.checked_sub(pixel);
+            let left = left_index
+                .and_then(|i| current.get(i))
+                .copied()
+                .unwrap_or(0);
+            let up = previous.get(index).copied().unwrap_or(0);
+            let up_left = left_index
+                .and_then(|i| previous.get(i))
+                .copied()
+                .unwrap_or(0);
+            let predicted = match kind {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => average(left, up),
+                4 => paeth(left, up, up_left),
+                other => return Err(FilterError::InvalidPredictorRow(other)),
+            };
+            if let Some(byte) = current.get_mut(index) {
+                *byte = byte.wrapping_add(predicted);
+            }
+        }
+        decoded.extend_from_slice(&current);
+        previous = current;
+    }
+    Ok(decoded)
+}
+
+fn average(left: u8, up: u8) -> u8 {
+    let sum = u16::from(left).wrapping_add(u16::from(up));
+    u8::try_from(sum.wrapping_shr(1)).unwrap_or(u8::MAX)
+}
+
+/// The Paeth predictor: whichever of left, up and upper-left is closest to
+/// `left + up - up_left`.
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let (a, b, c) = (i16::from(left), i16::from(up), i16::from(up_left));
+    let estimate = a.wrapping_add(b).wrapping_sub(c);
+    let (pa, pb, pc) = (
+        estimate.wrapping_sub(a).unsigned_abs(),
+        estimate.wrapping_sub(b).unsigned_abs(),
+        estimate.wrapping_sub(c).unsigned_abs(),
+    );
+    if pa <= pb && pa <= pc {
+        left
+    } else if pb <= pc {
+        up
+    } else {
+        up_left
+    }
+}
+
+/// Reads the sample at `index` of a row packed with `bits` bits per sample.
+fn read_sample(row: &[u8], index: usize, bits: usize) -> u16 {
+    if bits == 16 {
+        let start = index.saturating_mul(2);
+        let byte = |offset: usize| row.get(start.saturating_add(offset)).copied();
+        return u16::from_be_bytes([byte(0).unwrap_or(0), byte(1).unwrap_or(0)]);
+    }
+    let (byte, shift, mask) = sample_position(index, bits);
+    let value = row
+        .get(byte)
+        .copied()
+        .unwrap_or(0)
+        .checked_shr(shift)
+        .unwrap_or(0)
+        & mask;
+    u16::from(value)
+}
+
+/// Writes the sample at `index` of a row packed with `bits` bits per sample.
+fn write_sample(row: &mut [u8], index: usize, bits: usize, value: u16) {
+    if bits == 16 {
+        let start = index.saturating_mul(2);
+        let [high, low] = value.to_be_bytes();
+        if let Some(byte) = row.get_mut(start) {
+            *byte = high;
+        }
+        if let Some(byte) = row.get_mut(start.saturating_add(1)) {
+            *byte = low;
+        }
+        return;
+    }
+    let (byte, shift, mask) = sample_position(index, bits);
+    let value = u8::try_from(value).unwrap_or(u8::MAX) & mask;
+    if let Some(byte) = row.get_mut(byte) {
+        let cleared = *byte & !mask.checked_shl(shift).unwrap_or(0);
+        *byte = cleared | value.checked_shl(shift).unwrap_or(0);
+    }
+}
+
+/// Returns the byte holding the sample at `index`, the shift that brings it to
+/// the low bits and the mask for a sample of `bits` bits, where `bits` is at
+/// most 8.
+fn sample_position(index: usize, bits: usize) -> (usize, u32, u8) {
+    let bit = index.saturating_mul(bits);
+    let width = u32::try_from(bits).unwrap_or(8);
+    let offset = u32::try_from(bit % 8).unwrap_or(0);
+    let shift = 8u32.saturating_sub(width).saturating_sub(offset);
+    let mask = u8::MAX.checked_shr(8u32.saturating_sub(width)).unwrap_or(0);
+    (bit / 8, shift, mask)
+}
+
+const fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r' | b'\x0C' | b'\0')
+}
+
+fn decode_ascii_hex(data: &[u8]) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> = data
+        .iter()
+        .copied()
+        .take_while(|&b| b != b'>')
+        .filter(|&b| !is_whitespace(b))
+        .collect();
+    digits
+        .chunks(2)
+        .map(|pair| {
+            // A missing final digit is taken to be 0.
+            let pair = [
+                pair.first().copied().unwrap_or(b'0'),
+                pair.get(1).copied().unwrap_or(b'0'),
+            ];
+            std::str::from_utf8(&pair)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| format!("invalid hex digits {:?}", String::from_utf8_lossy(&pair)))
+        })
+        .collect()
+}
+
+fn decode_ascii85(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoded = Vec::new();
+    let mut group = [0u8; 5];
+    let mut count = 0usize;
+
+    for b in data.iter().copied() {
+        match b {
+            b'~' => break,
+            b'z' if count == 0 => decoded.extend_from_slice(&[0; 4]),
+            b'!'..=b'u' => {
+                if let Some(slot) = group.get_mut(count) {
+                    *slot = b.saturating_sub(b'!');
+                }
+                count = count.saturating_add(1);
+                if count == 5 {
+                    decoded.extend_from_slice(&ascii85_word(&group).to_be_bytes());
+                    count = 0;
+                }
+            }
+            b if is_whitespace(b) => {}
+            other => return Err(format!("invalid character {:?}", char::from(other))),
+        }
+    }
+
+    // A final partial group of n characters is padded with `u` and yields n - 1 bytes.
+    if count > 1 {
+        for slot in group.iter_mut().skip(count) {
+            *slot = 84;
+        }
+        let word = ascii85_word(&group).to_be_bytes();
+        decoded.extend_from_slice(word.get(..count.saturating_sub(1)).unwrap_or_default());
+    }
+    Ok(decoded)
+}
+
+fn ascii85_word(group: &[u8; 5]) -> u32 {
+    group.iter().fold(0u32, |word, &digit| {
+        word.wrapping_mul(85).wrapping_add(u32::from(digit))
+    })
+}
+
+fn decode_run_length(data: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::new();
+    let mut position = 0usize;
+    while let Some(&length) = data.get(position) {
+        position = position.saturating_add(1);
+        match length {
+            128 => break,
+            0..=127 => {
+                let end = position
+                    .saturating_add(usize::from(length))
+                    .saturating_add(1)
+                    .min(data.len());
+                decoded.extend_from_slice(data.get(position..end).unwrap_or_default());
+                position = end;
+            }
+            _ => {
+                let Some(&byte) = data.get(position) else {
+                    break;
+                };
+                position = position.saturating_add(1);
+                let count = 257usize.saturating_sub(usize::from(length));
+                decoded.extend(std::iter::repeat_n(byte, count));
+            }
+        }
+    }
+    decoded
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::{collections::BTreeMap, io::Write};
+
+    use flate2::{Compression, write::ZlibEncoder};
+    use pdf_object::ObjectVariant;
+
+    use super::*;
+
+    fn params(entries: &[(&str, i64)]) -> Dictionary {
+        Dictionary::new(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), Box::new(ObjectVariant::Integer(*value))))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    fn deflate(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_decode_ascii_hex() {
+        let decoded = decode("ASCIIHexDecode", b"48 65 6c6C 6>", None).unwrap();
+        assert_eq!(decoded, b"Hell`");
+        assert!(decode("ASCIIHexDecode", b"4G>", None).is_err());
+    }
+
+    #[test]
+    fn test_decode_ascii85() {
+        let decoded = decode("ASCII85Decode", b"87cURD]i,\"Ebo7~>", None).unwrap();
+        assert_eq!(decoded, b"Hello World");
+        assert_eq!(decode("ASCII85Decode", b"z~>", None).unwrap(), [0; 4]);
+    }
+
+    #[test]
+    fn test_decode_run_length() {
+        let decoded = decode(
+            "RunLengthDecode",
+            &[2, b'a', b'b', b'c', 254, b'x', 128],
+            None,
+        );
+        assert_eq!(decoded.unwrap(), b"abcxxx");
+    }
+
+    #[test]
+    fn test_decode_unsupported_filter() {
+        assert_eq!(
+            decode("DCTDecode", b"", None),
+            Err(FilterError::Unsupported("DCTDecode".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_flate_png_predictor() {
+        // Three rows of three bytes, predicted with Sub, Up and Paeth.
+        let encoded = deflate(&[1, 10, 5, 5, 2, 1, 1, 1, 4, 1, 1, 1]);
+        let params = params(&[("Predictor", 12), ("Columns", 3)]);
+        let decoded = decode("FlateDecode", &encoded, Some(&params)).unwrap();
+        assert_eq!(decoded, [10, 15, 20, 11, 16, 21, 12, 17, 22]);
+    }
+
+    #[test]
+    fn test_flate_png_predictor_average_uses_pixel_width() {
+        let encoded = deflate(&[0, 2, 4, 6, 8, 3, 1, 1, 4, 4]);
+        let params = params(&[("Predictor", 15), ("Colors", 2), ("Columns", 2)]);
+        let decoded = decode("FlateDecode", &encoded, Some(&params)).unwrap();
+        // The left neighbour of the third byte is the first, two bytes back.
+        assert_eq!(decoded, [2, 4, 6, 8, 2, 3, 8, 9]);
+    }
+
+    #[test]
+    fn test_flate_png_predictor_row_longer_than_data() {
+        let encoded = deflate(&[2, 1, 1, 1]);
+        let params = params(&[("Predictor", 12), ("Columns", 1 << 40)]);
+        assert_eq!(
+            decode("FlateDecode", &encoded, Some(&params)),
+            Err(FilterError::PredictorRowTooLong {
+                row_length: 1 << 40,
+                data_length: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_flate_png_predictor_invalid_row_type() {
+        let encoded = deflate(&[7, 1]);
+        let params = params(&[("Predictor", 10)]);
+        assert_eq!(
+            decode("FlateDecode", &encoded, Some(&params)),
+            Err(FilterError::InvalidPredictorRow(7))
+        );
+    }
+
+    #[test]
+    fn test_flate_tiff_predictor() {
+        let params8 = params(&[("Predictor", 2), ("Colors", 2), ("Columns", 3)]);
+        let encoded = deflate(&[1, 2, 1, 1, 255, 1]);
+        let decoded = decode("FlateDecode", &encoded, Some(&params8)).unwrap();
+        assert_eq!(decoded, [1, 2, 2, 3, 1, 4]);
+
+        // The 4-bit differences 1, 2, 15, 1 accumulate to 1, 3, 2, 3.
+        let params4 = params(&[("Predictor", 2), ("BitsPerComponent", 4), ("Columns", 4)]);
+        let encoded = deflate(&[0x12, 0xF1]);
+        let decoded = decode("FlateDecode", &encoded, Some(&params4)).unwrap();
+        assert_eq!(decoded, [0x13, 0x23]);
+    }
+
+    #[test]
+    fn test_flate_unsupported_predictor() {
+        let params = params(&[("Predictor", 3)]);
+        assert_eq!(
+            decode("FlateDecode", &deflate(b"data"), Some(&params)),
+            Err(FilterError::UnsupportedPredictor {
+                predictor: 3,
+                bits: 8
+            })
+        );
+    }
+}
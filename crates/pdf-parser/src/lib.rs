@@ -4,6 +4,7 @@ pub mod comment;
 mod cross_reference_table;
 mod dictionary;
 pub mod error;
+pub mod filter;
 pub mod header;
 mod hex_string;
 mod indirect_object;
@@ -1,11 +1,8 @@
-use std::io::Read;
-
-use flate2::bufread::ZlibDecoder;
 use pdf_object::{dictionary::Dictionary, error::ObjectError};
 use pdf_tokenizer::{PdfToken, error::TokenizerError};
 use thiserror::Error;
 
-use crate::{error::ParserError, parser::PdfParser, traits::StreamParser};
+use crate::{error::ParserError, filter, parser::PdfParser, traits::StreamParser};
 
 /// Represents an error that can occur while parsing an indirect object or an object reference.
 #[derive(Debug, PartialEq, Error)]
@@ -81,7 +78,8 @@ impl StreamParser for PdfParser<'_> {
     /// - It expects and consumes an EOL marker immediately after the raw stream data.
     /// - It consumes the `endstream` keyword and its trailing EOL.
     /// - If the `/Filter` entry in the dictionary is `/FlateDecode`, the raw stream
-    ///   data is decompressed using Zlib (DEFLATE).
+    ///   data is decompressed using Zlib (DEFLATE), and the predictor given in an
+    ///   inline `/DecodeParms` dictionary is undone.
     /// - **Current Limitation**: Only `/FlateDecode` is supported. If `/Filter` is
     ///   missing or specifies an unsupported filter, an error is returned.
     ///
@@ -132,14 +130,11 @@ impl StreamParser for PdfParser<'_> {
         // Check if the stream data is compressed using the FlateDecode (DEFLATE) algorithm.
         if let Some(decode) = decode {
             if decode == "FlateDecode" {
-                let mut d = ZlibDecoder::new(stream_data.as_slice());
-                let mut s = Vec::new();
-
-                if let Err(e) = d.read_to_end(&mut s) {
-                    return Err(StreamParsingError::DecompressionError(e.to_string()));
-                }
-
-                return Ok(s);
+                let params = dictionary
+                    .get("DecodeParms")
+                    .and_then(|params| params.as_dictionary());
+                return filter::decode(&decode, &stream_data, params.map(|p| p.as_ref()))
+                    .map_err(|e| StreamParsingError::DecompressionError(e.to_string()));
             } else if decode == "DCTDecode" {
                 return Ok(stream_data.to_vec());
             }
@@ -153,12 +148,59 @@ impl StreamParser for PdfParser<'_> {
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
-    use std::collections::BTreeMap;
+    use std::{collections::BTreeMap, io::Write, rc::Rc};
 
+    use flate2::{Compression, write::ZlibEncoder};
     use pdf_object::ObjectVariant;
 
     use super::*;
 
+    /// Wraps `data` in a stream body and parses it with `entries` added to its dictionary.
+    fn parse_flate_stream(data: &[u8], entries: Vec<(&str, ObjectVariant)>) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        let encoded = encoder.finish().unwrap();
+
+        let length = i64::try_from(encoded.len()).unwrap();
+        let dictionary = Dictionary::new(
+            [
+                ("Length", ObjectVariant::Integer(length)),
+                ("Filter", ObjectVariant::Name("FlateDecode".to_string())),
+            ]
+            .into_iter()
+            .chain(entries)
+            .map(|(key, value)| (key.to_string(), Box::new(value)))
+            .collect(),
+        );
+        let input = [b"stream\n".as_slice(), &encoded, b"\nendstream\n"].concat();
+        let mut parser = PdfParser::from(input.as_slice());
+        parser.parse_stream(&dictionary).unwrap()
+    }
+
+    #[test]
+    fn test_parse_stream_flate_decode() {
+        let decoded = parse_flate_stream(b"1 0 obj 2 0 obj", Vec::new());
+        assert_eq!(decoded, b"1 0 obj 2 0 obj");
+    }
+
+    #[test]
+    fn test_parse_stream_flate_decode_with_png_predictor() {
+        // Cross-reference streams are typically predicted row by row with
+        // /Predictor 12 and one column per byte of a /W [1 2 1] entry.
+        let params = Dictionary::new(
+            [("Predictor", 12), ("Columns", 4)]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), Box::new(ObjectVariant::Integer(value))))
+                .collect(),
+        );
+        let predicted = [2, 1, 0, 15, 0, 2, 0, 0, 16, 0];
+        let decoded = parse_flate_stream(
+            &predicted,
+            vec![("DecodeParms", ObjectVariant::Dictionary(Rc::new(params)))],
+        );
+        assert_eq!(decoded, [1, 0, 15, 0, 1, 0, 31, 0]);
+    }
+
     #[test]
     fn test_parse_stream_missing_stream_keyword() {
         let dictionary = Dictionary::new(
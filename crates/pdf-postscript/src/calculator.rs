@@ -1,4 +1,4 @@
-use crate::{operator::Operator, parser::parse_tokens};
+use crate::{operator::Operator, parser::parse_tokens, value::Value};
 use num_traits::ToPrimitive;
 use thiserror::Error;
 
@@ -31,19 +31,23 @@ pub enum CalcError {
     InvalidIntegerOperand { op: &'static str, value: f64 },
     #[error("operand for {op} must be a non-negative integer, got {value}")]
     NegativeIntegerOperand { op: &'static str, value: f64 },
+    #[error("operand of the wrong type for {op}")]
+    TypeCheck { op: &'static str },
+    #[error("result of {op} is undefined for its operands")]
+    UndefinedResult { op: &'static str },
 }
 
 // An explicit frame stack eliminates recursion for executing nested procedure blocks.
 struct Frame<'a> {
     ops: &'a [Operator],
     ip: usize,
-    stack: Vec<f64>,
+    stack: Vec<Value>,
 }
 
 impl<'a> Frame<'a> {
     /// Handles completion of a frame: propagates result to parent or returns if root.
     /// Returns Some(final_stack) if execution should return, or None to continue.
-    fn complete_frame(frames: &mut Vec<Frame<'a>>) -> Option<Vec<f64>> {
+    fn complete_frame(frames: &mut Vec<Frame<'a>>) -> Option<Vec<Value>> {
         let finished = frames.pop()?;
         if let Some(parent) = frames.last_mut() {
             parent.stack.clear();
@@ -57,24 +61,47 @@ impl<'a> Frame<'a> {
 
 impl Frame<'_> {
     /// Pushes a value onto the top of the stack.
-    fn push(&mut self, value: f64) -> Result<(), CalcError> {
-        if value.is_finite() {
-            self.stack.push(value);
-            Ok(())
-        } else {
-            Err(CalcError::ArithmeticOverflow { op: "push" })
+    fn push(&mut self, value: Value) -> Result<(), CalcError> {
+        match value {
+            Value::Real(real) if !real.is_finite() => {
+                Err(CalcError::ArithmeticOverflow { op: "push" })
+            }
+            _ => {
+                self.stack.push(value);
+                Ok(())
+            }
         }
     }
 
     /// Pops a value from the top of the stack.
     /// Returns an error if the stack is empty.
-    pub fn pop(&mut self) -> Result<f64, CalcError> {
+    pub fn pop(&mut self) -> Result<Value, CalcError> {
         self.stack.pop().ok_or(CalcError::StackUnderflow {
             needed: 1,
             found: 0,
         })
     }
 
+    /// Pops a number, returning a type error for a boolean.
+    fn pop_number(&mut self, op: &'static str) -> Result<f64, CalcError> {
+        self.pop()?.number(op)
+    }
+
+    /// Pops an integer operand.
+    fn pop_integer(&mut self, op: &'static str) -> Result<i32, CalcError> {
+        self.pop()?.integer(op)
+    }
+
+    /// Pops a non-negative integer operand, such as an element count.
+    fn pop_count(&mut self, op: &'static str) -> Result<usize, CalcError> {
+        let n = self.pop_integer(op)?;
+        n.to_usize()
+            .ok_or_else(|| CalcError::NegativeIntegerOperand {
+                op,
+                value: f64::from(n),
+            })
+    }
+
     /// Returns the number of elements in the stack.
     pub fn len(&self) -> usize {
         self.stack.len()
@@ -82,36 +109,108 @@ impl Frame<'_> {
 
     /// Returns the value at the top of the stack without removing it.
     /// Returns an error if the stack is empty.
-    pub fn back(&self) -> Result<f64, CalcError> {
+    pub fn back(&self) -> Result<Value, CalcError> {
         self.stack.last().copied().ok_or(CalcError::StackUnderflow {
             needed: 1,
             found: 0,
         })
     }
+
+    /// Applies `add`, `sub` or `mul`. Two integers give an integer unless the result
+    /// overflows, in which case it becomes a real, as in PostScript.
+    fn arithmetic(
+        &mut self,
+        op: &'static str,
+        int_op: fn(i32, i32) -> Option<i32>,
+        real_op: fn(f64, f64) -> f64,
+    ) -> Result<(), CalcError> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        let result = match (a, b) {
+            (Value::Integer(a), Value::Integer(b)) => int_op(a, b)
+                .map(Value::Integer)
+                .unwrap_or_else(|| Value::Real(real_op(f64::from(a), f64::from(b)))),
+            _ => Value::Real(real_op(a.number(op)?, b.number(op)?)),
+        };
+        self.push(result)
+    }
+
+    /// Applies a comparison between two numbers.
+    fn compare(&mut self, op: &'static str, cmp: fn(f64, f64) -> bool) -> Result<(), CalcError> {
+        let b = self.pop_number(op)?;
+        let a = self.pop_number(op)?;
+        self.push(Value::Boolean(cmp(a, b)))
+    }
+
+    /// Applies `and`, `or` or `xor`: logical for booleans and bitwise for integers.
+    /// Reals are taken as truth values, so function inputs can serve as flags.
+    fn logical(
+        &mut self,
+        int_op: fn(i32, i32) -> i32,
+        bool_op: fn(bool, bool) -> bool,
+    ) -> Result<(), CalcError> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        let result = match (a, b) {
+            (Value::Integer(a), Value::Integer(b)) => Value::Integer(int_op(a, b)),
+            _ => Value::Boolean(bool_op(a.truth(), b.truth())),
+        };
+        self.push(result)
+    }
+
+    /// Applies a rounding operator, which keeps integers unchanged.
+    fn round_with(&mut self, op: &'static str, round: fn(f64) -> f64) -> Result<(), CalcError> {
+        let result = match self.pop()? {
+            Value::Integer(int) => Value::Integer(int),
+            value => Value::Real(round(value.number(op)?)),
+        };
+        self.push(result)
+    }
+
+    /// Applies an operator whose result is always a real, failing when the result is
+    /// undefined (such as the logarithm of a negative number).
+    fn real_function(&mut self, op: &'static str, f: fn(f64) -> f64) -> Result<(), CalcError> {
+        let result = f(self.pop_number(op)?);
+        if result.is_nan() {
+            return Err(CalcError::UndefinedResult { op });
+        }
+        self.push(Value::Real(result))
+    }
+}
+
+/// Returns whether two operands are equal, comparing numbers by value regardless of
+/// their type. A boolean never equals a number.
+fn values_equal(a: Value, b: Value) -> bool {
+    match (a, b) {
+        (Value::Boolean(a), Value::Boolean(b)) => a == b,
+        (Value::Boolean(_), _) | (_, Value::Boolean(_)) => false,
+        (a, b) => a.to_f64() == b.to_f64(),
+    }
 }
 
 /// Executes a sequence of pre-parsed `Operator`s starting with `input_stack`.
 ///
-/// The interpreter uses a simple operand stack of `f64` values. Procedures
-/// (blocks for `if` / `ifelse`) are represented as nested `Vec<Operator>` and
-/// are executed recursively with a cloned snapshot of the current stack.
-///
-/// Returned is the final stack contents (bottom-to-top order) on success.
+/// The inputs enter the operand stack as reals. Operands keep their PostScript type
+/// (integer, real or boolean) while the program runs, and the final stack is
+/// returned as numbers in bottom-to-top order, with booleans as 1.0 and 0.0.
+/// Procedures (blocks for `if` / `ifelse`) are represented as nested
+/// `Vec<Operator>` and are executed with a cloned snapshot of the current stack.
 ///
-/// Errors include stack underflow, division by zero, square root of a negative
-/// number, and invalid counts for `roll` / `copy`.
+/// Errors include stack underflow, operands of the wrong type, division by zero,
+/// undefined results such as the square root or logarithm of a negative number,
+/// and invalid counts for `roll` / `copy` / `index`.
 pub fn execute(input_stack: &[f64], ops: &[Operator]) -> Result<Vec<f64>, CalcError> {
     let mut frames: Vec<Frame> = Vec::new();
     frames.push(Frame {
         ops,
         ip: 0,
-        stack: Vec::from(input_stack),
+        stack: input_stack.iter().copied().map(Value::Real).collect(),
     });
 
     while let Some(frame) = frames.last_mut() {
         if frame.ip >= frame.ops.len() {
             if let Some(final_stack) = Frame::complete_frame(&mut frames) {
-                return Ok(final_stack);
+                return Ok(final_stack.into_iter().map(Value::to_f64).collect());
             } else {
                 continue;
             }
@@ -124,28 +223,54 @@ pub fn execute(input_stack: &[f64], ops: &[Operator]) -> Result<Vec<f64>, CalcEr
             .checked_add(1)
             .ok_or(CalcError::ArithmeticOverflow { op: "ip_inc" })?;
         match op {
-            Operator::Add => {
-                let b = frame.pop()?;
-                let a = frame.pop()?;
-                frame.push(a + b)?;
-            }
-            Operator::Sub => {
-                let b = frame.pop()?;
-                let a = frame.pop()?;
-                frame.push(a - b)?;
-            }
-            Operator::Mul => {
-                let b = frame.pop()?;
-                let a = frame.pop()?;
-                frame.push(a * b)?;
-            }
+            Operator::Add => frame.arithmetic("add", i32::checked_add, |a, b| a + b)?,
+            Operator::Sub => frame.arithmetic("sub", i32::checked_sub, |a, b| a - b)?,
+            Operator::Mul => frame.arithmetic("mul", i32::checked_mul, |a, b| a * b)?,
             Operator::Div => {
-                let b = frame.pop()?;
+                let b = frame.pop_number("div")?;
                 if b == 0.0 {
                     return Err(CalcError::DivisionByZero);
                 }
-                let a = frame.pop()?;
-                frame.push(a / b)?;
+                let a = frame.pop_number("div")?;
+                frame.push(Value::Real(a / b))?;
+            }
+            Operator::Idiv => {
+                let b = frame.pop_integer("idiv")?;
+                if b == 0 {
+                    return Err(CalcError::DivisionByZero);
+                }
+                let a = frame.pop_integer("idiv")?;
+                let quotient = a
+                    .checked_div(b)
+                    .ok_or(CalcError::ArithmeticOverflow { op: "idiv" })?;
+                frame.push(Value::Integer(quotient))?;
+            }
+            Operator::Mod => {
+                let b = frame.pop_integer("mod")?;
+                if b == 0 {
+                    return Err(CalcError::DivisionByZero);
+                }
+                let a = frame.pop_integer("mod")?;
+                // Only i32::MIN mod -1 overflows, and its remainder is 0.
+                frame.push(Value::Integer(a.checked_rem(b).unwrap_or(0)))?;
+            }
+            Operator::Neg => {
+                let result = match frame.pop()? {
+                    Value::Integer(int) => int
+                        .checked_neg()
+                        .map_or_else(|| Value::Real(-f64::from(int)), Value::Integer),
+                    value => Value::Real(-value.number("neg")?),
+                };
+                frame.push(result)?;
+            }
+            Operator::Abs => {
+                let result = match frame.pop()? {
+                    Value::Integer(int) => int
+                        .checked_abs()
+                        .map_or_else(|| Value::Real(f64::from(int).abs()), Value::Integer),
+                    value => Value::Real(value.number("abs")?.abs()),
+                };
+                frame.push(result)?;
             }
             Operator::Dup => {
                 let a = frame.back()?;
@@ -163,50 +288,44 @@ pub fn execute(input_stack: &[f64], ops: &[Operator]) -> Result<Vec<f64>, CalcEr
             Operator::Eq => {
                 let b = frame.pop()?;
                 let a = frame.pop()?;
-                frame.push(if a == b { 1.0 } else { 0.0 })?;
+                frame.push(Value::Boolean(values_equal(a, b)))?;
             }
             Operator::Ne => {
                 let b = frame.pop()?;
                 let a = frame.pop()?;
-                frame.push(if a != b { 1.0 } else { 0.0 })?;
-            }
-            Operator::Gt => {
-                let b = frame.pop()?;
-                let a = frame.pop()?;
-                frame.push(if a > b { 1.0 } else { 0.0 })?;
-            }
-            Operator::Lt => {
-                let b = frame.pop()?;
-                let a = frame.pop()?;
-                frame.push(if a < b { 1.0 } else { 0.0 })?;
-            }
-            Operator::Ge => {
-                let b = frame.pop()?;
-                let a = frame.pop()?;
-                frame.push(if a >= b { 1.0 } else { 0.0 })?;
-            }
-            Operator::Le => {
-                let b = frame.pop()?;
-                let a = frame.pop()?;
-                frame.push(if a <= b { 1.0 } else { 0.0 })?;
-            }
-            Operator::And => {
-                let b = frame.pop()?;
-                let a = frame.pop()?;
-                frame.push(if a != 0.0 && b != 0.0 { 1.0 } else { 0.0 })?;
-            }
-            Operator::Or => {
-                let b = frame.pop()?;
-                let a = frame.pop()?;
-                frame.push(if a != 0.0 || b != 0.0 { 1.0 } else { 0.0 })?;
+                frame.push(Value::Boolean(!values_equal(a, b)))?;
             }
+            Operator::Gt => frame.compare("gt", |a, b| a > b)?,
+            Operator::Lt => frame.compare("lt", |a, b| a < b)?,
+            Operator::Ge => frame.compare("ge", |a, b| a >= b)?,
+            Operator::Le => frame.compare("le", |a, b| a <= b)?,
+            Operator::And => frame.logical(|a, b| a & b, |a, b| a && b)?,
+            Operator::Or => frame.logical(|a, b| a | b, |a, b| a || b)?,
+            Operator::Xor => frame.logical(|a, b| a ^ b, |a, b| a != b)?,
             Operator::Not => {
-                let a = frame.pop()?;
-                frame.push(if a == 0.0 { 1.0 } else { 0.0 })?;
+                let result = match frame.pop()? {
+                    Value::Integer(int) => Value::Integer(!int),
+                    value => Value::Boolean(!value.truth()),
+                };
+                frame.push(result)?;
+            }
+            Operator::Bitshift => {
+                let shift = frame.pop_integer("bitshift")?;
+                let int = frame.pop_integer("bitshift")?;
+                // Shifts are logical: the bits shifted in are zero in both directions.
+                let bits = u32::from_ne_bytes(int.to_ne_bytes());
+                let distance = shift.unsigned_abs();
+                let shifted = if shift >= 0 {
+                    bits.checked_shl(distance)
+                } else {
+                    bits.checked_shr(distance)
+                };
+                let result = i32::from_ne_bytes(shifted.unwrap_or(0).to_ne_bytes());
+                frame.push(Value::Integer(result))?;
             }
             Operator::If(block) => {
                 let cond = frame.pop()?;
-                if cond != 0.0 {
+                if cond.truth() {
                     // Push new frame with a cloned snapshot of current stack
                     let snapshot = frame.stack.clone();
                     frames.push(Frame {
@@ -218,7 +337,7 @@ pub fn execute(input_stack: &[f64], ops: &[Operator]) -> Result<Vec<f64>, CalcEr
             }
             Operator::IfElse(block1, block2) => {
                 let cond = frame.pop()?;
-                let chosen = if cond != 0.0 { block1 } else { block2 };
+                let chosen = if cond.truth() { block1 } else { block2 };
                 let snapshot = frame.stack.clone();
                 frames.push(Frame {
                     ops: chosen,
@@ -227,55 +346,32 @@ pub fn execute(input_stack: &[f64], ops: &[Operator]) -> Result<Vec<f64>, CalcEr
                 });
             }
             Operator::Copy => {
-                let n_val = frame.pop()?;
-                let n = n_val.to_usize().ok_or(CalcError::InvalidIntegerOperand {
-                    op: "copy",
-                    value: n_val,
-                })?;
-
+                let n = frame.pop_count("copy")?;
                 let len = frame.len();
                 let start = len
                     .checked_sub(n)
-                    .ok_or(CalcError::ArithmeticOverflow { op: "copy_index" })?;
-                let to_copy: Vec<f64> = frame.stack[start..].to_vec();
+                    .ok_or(CalcError::CopyCountTooLarge { n, size: len })?;
+                let to_copy: Vec<Value> = frame.stack[start..].to_vec();
                 for v in to_copy {
                     frame.push(v)?;
                 }
             }
-            Operator::Sqrt => {
-                let a = frame.pop()?;
-                if a < 0.0 {
-                    return Err(CalcError::NegativeSqrt);
-                }
-                frame.push(a.sqrt())?;
-            }
-            Operator::Mod => {
-                let b = frame.pop()?;
-                if b == 0.0 {
-                    return Err(CalcError::DivisionByZero);
-                }
-                let a = frame.pop()?;
-                frame.push(a % b)?;
-            }
-            Operator::Cvi => {
-                let a = frame.pop()?;
-                frame.push(a.trunc())?;
-            }
-            Operator::Abs => {
-                let a = frame.pop()?;
-                frame.push(a.abs())?;
+            Operator::Index => {
+                let n = frame.pop_count("index")?;
+                let len = frame.len();
+                let value = len
+                    .checked_sub(n)
+                    .and_then(|i| i.checked_sub(1))
+                    .and_then(|i| frame.stack.get(i).copied())
+                    .ok_or_else(|| CalcError::StackUnderflow {
+                        needed: n.saturating_add(1),
+                        found: len,
+                    })?;
+                frame.push(value)?;
             }
             Operator::Roll => {
-                let m_val = frame.pop()?;
-                let n_val = frame.pop()?;
-                let m = m_val.to_isize().ok_or(CalcError::NegativeIntegerOperand {
-                    op: "roll",
-                    value: m_val,
-                })?;
-                let n = n_val.to_usize().ok_or(CalcError::InvalidIntegerOperand {
-                    op: "roll",
-                    value: n_val,
-                })?;
+                let m = frame.pop_integer("roll")?;
+                let n = frame.pop_count("roll")?;
                 if n > frame.len() {
                     return Err(CalcError::RollCountTooLarge {
                         n,
@@ -290,10 +386,10 @@ pub fn execute(input_stack: &[f64], ops: &[Operator]) -> Result<Vec<f64>, CalcEr
                     .len()
                     .checked_sub(n)
                     .ok_or(CalcError::ArithmeticOverflow { op: "roll_index" })?;
-                let n_isize =
-                    isize::try_from(n).map_err(|_| CalcError::ArithmeticOverflow { op: "roll" })?;
+                let n_i64 =
+                    i64::try_from(n).map_err(|_| CalcError::ArithmeticOverflow { op: "roll" })?;
                 // Normalize m into [0, n) using `rem_euclid` to handle negatives & large |m|.
-                let m_norm = m.rem_euclid(n_isize);
+                let m_norm = i64::from(m).rem_euclid(n_i64);
                 let m_norm_usize = usize::try_from(m_norm)
                     .map_err(|_| CalcError::ArithmeticOverflow { op: "roll" })?;
                 if m_norm_usize != 0 {
@@ -301,11 +397,57 @@ pub fn execute(input_stack: &[f64], ops: &[Operator]) -> Result<Vec<f64>, CalcEr
                     frame.stack[start..].rotate_right(m_norm_usize);
                 }
             }
-            Operator::Truncate => {
-                let a = frame.pop()?;
-                frame.push(a.trunc())?;
+            Operator::Sqrt => {
+                let a = frame.pop_number("sqrt")?;
+                if a < 0.0 {
+                    return Err(CalcError::NegativeSqrt);
+                }
+                frame.push(Value::Real(a.sqrt()))?;
+            }
+            Operator::Cvi => {
+                let a = frame.pop_number("cvi")?.trunc();
+                let int = a
+                    .to_i32()
+                    .ok_or(CalcError::ArithmeticOverflow { op: "cvi" })?;
+                frame.push(Value::Integer(int))?;
+            }
+            Operator::Cvr => {
+                let a = frame.pop_number("cvr")?;
+                frame.push(Value::Real(a))?;
             }
-            Operator::Number(num) => frame.push(*num)?,
+            Operator::Truncate => frame.round_with("truncate", f64::trunc)?,
+            Operator::Floor => frame.round_with("floor", f64::floor)?,
+            Operator::Ceiling => frame.round_with("ceiling", f64::ceil)?,
+            Operator::Round => frame.round_with("round", |a| (a + 0.5).floor())?,
+            Operator::Sin => frame.real_function("sin", |a| a.to_radians().sin())?,
+            Operator::Cos => frame.real_function("cos", |a| a.to_radians().cos())?,
+            Operator::Ln => {
+                frame.real_function("ln", |a| if a > 0.0 { a.ln() } else { f64::NAN })?
+            }
+            Operator::Log => {
+                frame.real_function("log", |a| if a > 0.0 { a.log10() } else { f64::NAN })?
+            }
+            Operator::Atan => {
+                let den = frame.pop_number("atan")?;
+                let num = frame.pop_number("atan")?;
+                if num == 0.0 && den == 0.0 {
+                    return Err(CalcError::UndefinedResult { op: "atan" });
+                }
+                let angle = num.atan2(den).to_degrees();
+                frame.push(Value::Real(if angle < 0.0 { angle + 360.0 } else { angle }))?;
+            }
+            Operator::Exp => {
+                let exponent = frame.pop_number("exp")?;
+                let base = frame.pop_number("exp")?;
+                let result = base.powf(exponent);
+                if result.is_nan() {
+                    return Err(CalcError::UndefinedResult { op: "exp" });
+                }
+                frame.push(Value::Real(result))?;
+            }
+            Operator::Number(num) => frame.push(Value::Real(*num))?,
+            Operator::Integer(int) => frame.push(Value::Integer(*int))?,
+            Operator::Boolean(b) => frame.push(Value::Boolean(*b))?,
         }
     }
 
@@ -347,9 +489,9 @@ mod tests {
         assert_eq!(
             ops,
             vec![
-                Operator::Number(1.0),
+                Operator::Integer(1),
                 Operator::Number(2.5),
-                Operator::Number(-3.0)
+                Operator::Integer(-3)
             ]
         );
     }
@@ -361,8 +503,8 @@ mod tests {
         assert_eq!(
             ops,
             vec![Operator::If(vec![
-                Operator::Number(2.0),
-                Operator::Number(3.0),
+                Operator::Integer(2),
+                Operator::Integer(3),
                 Operator::Add
             ])]
         );
@@ -377,8 +519,8 @@ mod tests {
         assert_eq!(
             ops,
             vec![Operator::IfElse(
-                vec![Operator::Number(2.0), Operator::Number(3.0), Operator::Add],
-                vec![Operator::Number(4.0), Operator::Number(5.0), Operator::Add]
+                vec![Operator::Integer(2), Operator::Integer(3), Operator::Add],
+                vec![Operator::Integer(4), Operator::Integer(5), Operator::Add]
             )]
         );
     }
@@ -390,10 +532,10 @@ mod tests {
         assert_eq!(
             ops,
             vec![Operator::If(vec![
-                Operator::Number(1.0),
+                Operator::Integer(1),
                 Operator::If(vec![
-                    Operator::Number(2.0),
-                    Operator::Number(3.0),
+                    Operator::Integer(2),
+                    Operator::Integer(3),
                     Operator::Add
                 ])
             ])]
@@ -648,4 +790,135 @@ mod tests {
         let result = evaluate_postscript(&[0.0, 3.0], "mod").unwrap();
         assert_eq!(result, vec![0.0]);
     }
+
+    #[test]
+    fn test_parse_full_operator_set() {
+        let tokens = vec![
+            "idiv", "neg", "xor", "bitshift", "index", "cvr", "floor", "ceiling", "round", "sin",
+            "cos", "atan", "exp", "ln", "log", "true", "false",
+        ];
+        let ops = parse_tokens(&tokens).unwrap();
+        assert_eq!(
+            ops,
+            vec![
+                Operator::Idiv,
+                Operator::Neg,
+                Operator::Xor,
+                Operator::Bitshift,
+                Operator::Index,
+                Operator::Cvr,
+                Operator::Floor,
+                Operator::Ceiling,
+                Operator::Round,
+                Operator::Sin,
+                Operator::Cos,
+                Operator::Atan,
+                Operator::Exp,
+                Operator::Ln,
+                Operator::Log,
+                Operator::Boolean(true),
+                Operator::Boolean(false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_integer_arithmetic() {
+        let result = evaluate_postscript(&[], "7 2 idiv -7 2 idiv 7 2 div").unwrap();
+        assert_eq!(result, vec![3.0, -3.0, 3.5]);
+        let result = evaluate_postscript(&[], "2147483647 1 add").unwrap();
+        assert_eq!(result, vec![2147483648.0]);
+        let result = evaluate_postscript(&[], "7 0 idiv");
+        assert_eq!(result, Err(CalcError::DivisionByZero));
+        let result = evaluate_postscript(&[], "7.5 2 idiv");
+        assert!(matches!(
+            result,
+            Err(CalcError::InvalidIntegerOperand { op: "idiv", .. })
+        ));
+    }
+
+    #[test]
+    fn test_neg() {
+        let result = evaluate_postscript(&[2.5], "neg 3 neg").unwrap();
+        assert_eq!(result, vec![-2.5, -3.0]);
+    }
+
+    #[test]
+    fn test_rounding() {
+        let result =
+            evaluate_postscript(&[], "3.2 floor -3.2 floor 3.2 ceiling -3.2 ceiling").unwrap();
+        assert_eq!(result, vec![3.0, -4.0, 4.0, -3.0]);
+        let result = evaluate_postscript(&[], "2.5 round -2.5 round 6.49 round 5 round").unwrap();
+        assert_eq!(result, vec![3.0, -2.0, 6.0, 5.0]);
+    }
+
+    #[test]
+    fn test_cvr() {
+        let result = evaluate_postscript(&[], "3 cvr 2 div").unwrap();
+        assert_eq!(result, vec![1.5]);
+    }
+
+    #[test]
+    fn test_trigonometry() {
+        let result =
+            evaluate_postscript(&[], "90 sin 180 cos 0 1 atan 1 0 atan -1 0 atan").unwrap();
+        assert!((result[0] - 1.0).abs() < 1e-12);
+        assert!((result[1] + 1.0).abs() < 1e-12);
+        assert_eq!(&result[2..], &[0.0, 90.0, 270.0]);
+        let result = evaluate_postscript(&[], "0 0 atan");
+        assert_eq!(result, Err(CalcError::UndefinedResult { op: "atan" }));
+    }
+
+    #[test]
+    fn test_exp_and_logarithms() {
+        let result = evaluate_postscript(&[], "2 10 exp 100 log").unwrap();
+        assert_eq!(result, vec![1024.0, 2.0]);
+        let result = evaluate_postscript(&[], "0 ln");
+        assert_eq!(result, Err(CalcError::UndefinedResult { op: "ln" }));
+        let result = evaluate_postscript(&[], "-8 0.5 exp");
+        assert_eq!(result, Err(CalcError::UndefinedResult { op: "exp" }));
+    }
+
+    #[test]
+    fn test_bitwise_operators() {
+        let result = evaluate_postscript(&[], "12 10 and 12 10 or 12 10 xor 0 not").unwrap();
+        assert_eq!(result, vec![8.0, 14.0, 6.0, -1.0]);
+        let result =
+            evaluate_postscript(&[], "7 3 bitshift 142 -3 bitshift -1 -28 bitshift").unwrap();
+        assert_eq!(result, vec![56.0, 17.0, 15.0]);
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        let result = evaluate_postscript(&[], "true false xor true true xor false not").unwrap();
+        assert_eq!(result, vec![1.0, 0.0, 1.0]);
+        let result = evaluate_postscript(&[], "true { 1 } { 2 } ifelse false { 3 } if").unwrap();
+        assert_eq!(result, vec![1.0]);
+        let result = evaluate_postscript(&[], "1 1.0 eq true 1 eq").unwrap();
+        assert_eq!(result, vec![1.0, 0.0]);
+        let result = evaluate_postscript(&[], "true 1 gt");
+        assert_eq!(result, Err(CalcError::TypeCheck { op: "gt" }));
+    }
+
+    #[test]
+    fn test_index() {
+        let result = evaluate_postscript(&[1.0, 2.0, 3.0], "0 index 3 index").unwrap();
+        assert_eq!(result, vec![1.0, 2.0, 3.0, 3.0, 1.0]);
+        let result = evaluate_postscript(&[1.0], "1 index");
+        assert_eq!(
+            result,
+            Err(CalcError::StackUnderflow {
+                needed: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_type4_tint_transform() {
+        // A typical separation tint transform to CMYK.
+        let code = "{ dup 0.2 mul exch dup 0 mul exch dup 0.8 mul exch 0.1 mul }";
+        let result = evaluate_postscript(&[0.5], code).unwrap();
+        assert_eq!(result, vec![0.1, 0.0, 0.4, 0.05]);
+    }
 }
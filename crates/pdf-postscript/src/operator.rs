@@ -13,8 +13,12 @@ pub enum Operator {
     Sub,
     /// mul (a b -- a*b)
     Mul,
-    /// div (a b -- a/b)
+    /// div (a b -- a/b) always produces a real.
     Div,
+    /// idiv (a b -- a/b) integer quotient, truncated toward zero.
+    Idiv,
+    /// neg (x -- -x)
+    Neg,
     /// dup (x -- x x) duplicates the top stack element.
     Dup,
     /// exch (a b -- b a) swaps the top two elements.
@@ -39,26 +43,57 @@ pub enum Operator {
     Or,
     /// not (x -- !x) logical (if boolean) or bitwise complement (if integer-like).
     Not,
+    /// xor (x y -- x^y) logical (if both booleans) or bitwise (if integers) exclusive OR.
+    Xor,
+    /// bitshift (int shift -- int) shifts left for positive `shift`, right for negative.
+    Bitshift,
     /// copy (x1 .. xn n -- x1 .. xn x1 .. xn) duplicates the top `n` elements.
     Copy,
-    /// roll (x1 .. xn j n -- rotated) rotates the top `n` elements by `j` steps.
+    /// roll (x1 .. xn n j -- rotated) rotates the top `n` elements by `j` steps.
     Roll,
+    /// index (xn .. x0 n -- xn .. x0 xn) duplicates the element `n` places below the top.
+    Index,
     /// sqrt (x -- sqrt(x)) square root.
     Sqrt,
     /// abs (x -- |x|) absolute value.
     Abs,
     /// cvi (x -- int) convert to integer (truncate toward zero in PostScript).
     Cvi,
+    /// cvr (x -- real) convert to real.
+    Cvr,
     /// mod (a b -- a%b) remainder (same sign behavior as PostScript: sign of a).
     Mod,
-    /// truncate (x -- truncated) implementation-specific explicit truncation.
+    /// truncate (x -- truncated) rounds toward zero, keeping the operand's type.
     Truncate,
+    /// floor (x -- floor(x)) rounds toward negative infinity, keeping the operand's type.
+    Floor,
+    /// ceiling (x -- ceiling(x)) rounds toward positive infinity, keeping the operand's type.
+    Ceiling,
+    /// round (x -- round(x)) rounds to the nearest integer, halves upward, keeping the
+    /// operand's type.
+    Round,
+    /// sin (angle -- real) sine of an angle in degrees.
+    Sin,
+    /// cos (angle -- real) cosine of an angle in degrees.
+    Cos,
+    /// atan (num den -- angle) angle in degrees, in [0, 360), whose tangent is num/den.
+    Atan,
+    /// exp (base exponent -- real) raises `base` to the power `exponent`.
+    Exp,
+    /// ln (x -- real) natural logarithm.
+    Ln,
+    /// log (x -- real) base-10 logarithm.
+    Log,
     /// if (bool proc -- ) executes `proc` iff bool is true. Here stored as
     /// already-parsed operator sequence (executable array).
     If(Vec<Operator>),
     /// ifelse (bool proc_true proc_false -- ) executes one of the procedures
     /// depending on the boolean value.
     IfElse(Vec<Operator>, Vec<Operator>),
-    /// Numeric literal (integer or real). Kept as f64 for simplicity.
+    /// Real literal.
     Number(f64),
+    /// Integer literal.
+    Integer(i32),
+    /// Boolean literal (`true` / `false`).
+    Boolean(bool),
 }
@@ -32,6 +32,21 @@ impl ProgramBlock {
     }
 }
 
+/// Parses a numeric literal. Tokens without a fraction or exponent that fit in 32
+/// bits are integers; everything else, including integers too large for 32 bits, is
+/// a real.
+fn parse_number(token: &str) -> Result<Operator, CalcError> {
+    if let Ok(int) = token.parse::<i32>() {
+        return Ok(Operator::Integer(int));
+    }
+    token
+        .parse::<f64>()
+        .ok()
+        .filter(|num| num.is_finite())
+        .map(Operator::Number)
+        .ok_or_else(|| CalcError::InvalidNumber(token.to_string()))
+}
+
 /// Parse a linear slice of token strings into a flat `Vec<Operator>`.
 ///
 /// The grammar handled is intentionally small: numeric literals, the subset of
@@ -51,6 +66,8 @@ pub fn parse_tokens(tokens: &[&str]) -> Result<Vec<Operator>, CalcError> {
             "sub" => block_stack.push(Operator::Sub)?,
             "mul" => block_stack.push(Operator::Mul)?,
             "div" => block_stack.push(Operator::Div)?,
+            "idiv" => block_stack.push(Operator::Idiv)?,
+            "neg" => block_stack.push(Operator::Neg)?,
             "dup" => block_stack.push(Operator::Dup)?,
             "exch" => block_stack.push(Operator::Exch)?,
             "pop" => block_stack.push(Operator::Pop)?,
@@ -63,6 +80,8 @@ pub fn parse_tokens(tokens: &[&str]) -> Result<Vec<Operator>, CalcError> {
             "and" => block_stack.push(Operator::And)?,
             "or" => block_stack.push(Operator::Or)?,
             "not" => block_stack.push(Operator::Not)?,
+            "xor" => block_stack.push(Operator::Xor)?,
+            "bitshift" => block_stack.push(Operator::Bitshift)?,
             "if" => {
                 let block1 = block_stack.pop().ok_or(CalcError::MissingIfBlock)?;
                 block_stack.push(Operator::If(block1))?;
@@ -78,19 +97,25 @@ pub fn parse_tokens(tokens: &[&str]) -> Result<Vec<Operator>, CalcError> {
             "}" => {}
             "copy" => block_stack.push(Operator::Copy)?,
             "roll" => block_stack.push(Operator::Roll)?,
+            "index" => block_stack.push(Operator::Index)?,
             "sqrt" => block_stack.push(Operator::Sqrt)?,
             "cvi" => block_stack.push(Operator::Cvi)?,
+            "cvr" => block_stack.push(Operator::Cvr)?,
             "mod" => block_stack.push(Operator::Mod)?,
             "truncate" => block_stack.push(Operator::Truncate)?,
+            "floor" => block_stack.push(Operator::Floor)?,
+            "ceiling" => block_stack.push(Operator::Ceiling)?,
+            "round" => block_stack.push(Operator::Round)?,
             "abs" => block_stack.push(Operator::Abs)?,
-            "true" => block_stack.push(Operator::Number(1.0))?,
-            "false" => block_stack.push(Operator::Number(0.0))?,
-            t => {
-                let num = t
-                    .parse::<f64>()
-                    .map_err(|_| CalcError::InvalidNumber(t.to_string()))?;
-                block_stack.push(Operator::Number(num))?;
-            }
+            "sin" => block_stack.push(Operator::Sin)?,
+            "cos" => block_stack.push(Operator::Cos)?,
+            "atan" => block_stack.push(Operator::Atan)?,
+            "exp" => block_stack.push(Operator::Exp)?,
+            "ln" => block_stack.push(Operator::Ln)?,
+            "log" => block_stack.push(Operator::Log)?,
+            "true" => block_stack.push(Operator::Boolean(true))?,
+            "false" => block_stack.push(Operator::Boolean(false))?,
+            t => block_stack.push(parse_number(t)?)?,
         }
         i = i.checked_add(1).ok_or(CalcError::TokenIndexOverflow)?;
     }
@@ -0,0 +1,69 @@
+use num_traits::ToPrimitive;
+
+use crate::calculator::CalcError;
+
+/// An operand on the calculator's stack.
+///
+/// PostScript distinguishes integers, reals and booleans: arithmetic on two integers
+/// stays integral unless it overflows, `div` and the transcendental operators always
+/// produce reals, and the logical operators act bitwise on integers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Integer(i32),
+    Real(f64),
+    Boolean(bool),
+}
+
+impl Value {
+    /// Returns the numeric value of a number operand, or a type error for booleans.
+    pub fn number(self, op: &'static str) -> Result<f64, CalcError> {
+        match self {
+            Value::Integer(int) => Ok(f64::from(int)),
+            Value::Real(real) => Ok(real),
+            Value::Boolean(_) => Err(CalcError::TypeCheck { op }),
+        }
+    }
+
+    /// Returns the value of an operand that must be an integer.
+    ///
+    /// Reals without a fraction are accepted too, since function inputs always
+    /// arrive as reals.
+    pub fn integer(self, op: &'static str) -> Result<i32, CalcError> {
+        match self {
+            Value::Integer(int) => Ok(int),
+            Value::Real(real) if real.fract() == 0.0 => real
+                .to_i32()
+                .ok_or(CalcError::InvalidIntegerOperand { op, value: real }),
+            Value::Real(real) => Err(CalcError::InvalidIntegerOperand { op, value: real }),
+            Value::Boolean(_) => Err(CalcError::TypeCheck { op }),
+        }
+    }
+
+    /// Returns the truth value of an operand used as a condition.
+    ///
+    /// Numbers are accepted as well, non-zero being true, since function inputs
+    /// arrive as reals and older producers pass flags that way.
+    pub fn truth(self) -> bool {
+        match self {
+            Value::Boolean(b) => b,
+            Value::Integer(int) => int != 0,
+            Value::Real(real) => real != 0.0,
+        }
+    }
+
+    /// Converts the operand to the real value reported as a function output, with
+    /// booleans as 1.0 and 0.0.
+    pub fn to_f64(self) -> f64 {
+        match self {
+            Value::Integer(int) => f64::from(int),
+            Value::Real(real) => real,
+            Value::Boolean(b) => {
+                if b {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
@@ -19,6 +19,8 @@ pub enum PdfRendererError {
 pub struct PdfRenderer<'a, 'b, T> {
     document: &'b PdfDocument,
     canvas: &'a mut dyn CanvasBackend<ErrorType = T>,
+    color_management: bool,
+    render_annotations: bool,
 }
 
 impl<'a, 'b, T: std::error::Error> PdfRenderer<'a, 'b, T> {
@@ -31,7 +33,27 @@ impl<'a, 'b, T: std::error::Error> PdfRenderer<'a, 'b, T> {
         document: &'b PdfDocument,
         canvas: &'a mut dyn CanvasBackend<ErrorType = T>,
     ) -> Self {
-        Self { document, canvas }
+        Self {
+            document,
+            canvas,
+            color_management: true,
+            render_annotations: true,
+        }
+    }
+
+    /// Enables or disables ICC colour management, which is on by default.
+    ///
+    /// When disabled, ICC-based colours are converted through their alternate
+    /// colour spaces and the document's output intent is ignored, which is faster
+    /// but less accurate for print-oriented documents.
+    pub fn set_color_management(&mut self, enabled: bool) {
+        self.color_management = enabled;
+    }
+
+    /// Enables or disables drawing the appearance streams of annotations, such as
+    /// form fields, stamps and highlights, over the page content. On by default.
+    pub fn set_render_annotations(&mut self, enabled: bool) {
+        self.render_annotations = enabled;
     }
 
     /// Renders a page onto the canvas backend.
@@ -48,9 +70,12 @@ impl<'a, 'b, T: std::error::Error> PdfRenderer<'a, 'b, T> {
         let Some(p) = self.document.pages.get(page_index) else {
             return Err(PdfRendererError::PageNotFound(page_index));
         };
-        let mut canvas = PdfCanvas::new(self.canvas, p, None)?;
-        if let Some(cs) = &p.contents {
-            canvas.render_content_stream(&cs.operations, None, None)?;
+        let mut canvas = PdfCanvas::new(self.canvas, p, None)?
+            .with_color_management(self.color_management, self.document.output_profile())
+            .with_optional_content(self.document.optional_content.as_ref());
+        canvas.render_page()?;
+        if self.render_annotations {
+            canvas.render_annotations()?;
         }
         Ok(())
     }
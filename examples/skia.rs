@@ -72,7 +72,7 @@ use pdf_renderer::PdfRenderer;
 // 5. Run loop bootstrap (run)
 // Hardcoded constants for window size and input path were removed; path can be
 // given as first CLI argument. Fallback is a small embedded sample (if present) or error.
-// Initial window size derived from first page MediaBox when available.
+// Initial window size derived from the first page's displayed size.
 
 fn main() {
     let settings = AppSettings::from_env();
@@ -144,11 +144,8 @@ fn derive_initial_window_size(doc: &PdfDocument) -> (u32, u32) {
         Some(p) => p,
         None => return DEFAULT,
     };
-    if let Some(mb) = &page.media_box {
-        (mb.width().max(1), mb.height().max(1))
-    } else {
-        DEFAULT
-    }
+    let (width, height) = page.display_size();
+    (width.max(1.0) as u32, height.max(1.0) as u32)
 }
 
 fn create_window_and_context(el: &EventLoop<()>, doc: &PdfDocument) -> GlInitArtifacts {